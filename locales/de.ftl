@@ -0,0 +1,915 @@
+menu-file = Datei
+menu-file-new = Neues Projekt…
+menu-file-open = Öffnen…
+menu-file-save-as = Speichern unter…
+menu-file-import = Modell importieren…
+menu-file-import-dot = GraphViz-DOT importieren…
+menu-file-import-distribution = Systemverteilung importieren…
+menu-file-library = Komponentenbibliothek…
+menu-file-export-structure = Struktur exportieren
+menu-file-export-adjacency = Adjazenzmatrix (CSV)…
+menu-file-export-edge-list = Kantenliste (CSV)…
+menu-file-export-dot = Als GraphViz-DOT exportieren…
+menu-file-export-builder = Als Rust-Builder-Code exportieren…
+menu-file-export-distribution = Systemverteilung exportieren…
+menu-file-report = Bericht erstellen…
+menu-language = Sprache
+menu-view = Ansicht
+menu-view-legend = Legende der Markierungen
+menu-view-quick-add = Schnellzugriffsleiste
+menu-view-what-if = Was-wäre-wenn-Leiste
+menu-view-log = Protokoll
+menu-view-notifications = Benachrichtigungen
+menu-view-edge-values = Zuverlässigkeit an Verbindungen
+menu-view-weak-link = Schwachstelle
+menu-view-hotspots = Heatmap der Auswertungszeit
+menu-view-grid = Raster und Koordinaten
+menu-view-summary-card = Übersichtskarte
+menu-view-presentation = Präsentationsmodus (F5)
+quick-add-hint = Fügt den Knoten in der Mitte der Ansicht ein
+menu-arrange = Anordnen
+menu-arrange-align-left = Linke Kanten ausrichten
+menu-arrange-align-top = Obere Kanten ausrichten
+menu-arrange-align-center = Mitten ausrichten
+menu-arrange-distribute-horizontally = Horizontal verteilen
+menu-arrange-distribute-vertically = Vertikal verteilen
+menu-arrange-lock = Position der ausgewählten Knoten sperren
+menu-arrange-unlock = Position der ausgewählten Knoten freigeben
+menu-arrange-frame = Ausgewählte Knoten einrahmen
+menu-arrange-find-replace = Suchen und ersetzen…
+menu-analysis = Analyse
+menu-analysis-allocation = Zuverlässigkeitsaufteilung…
+menu-analysis-budget = Zuverlässigkeitsbudget…
+menu-analysis-goal-seek = Zielwertsuche…
+menu-analysis-sweep = Parameterstudie…
+menu-analysis-doe = Latin-Hypercube-Studie…
+menu-analysis-robustness = Robustheit gegen Datenunsicherheit…
+menu-analysis-simulation = Monte-Carlo-Simulation…
+menu-analysis-kaplan-meier = Kaplan-Meier-Schätzer…
+menu-analysis-field-data = Felddaten anpassen…
+menu-analysis-bayes = Bayes-Aktualisierung…
+menu-analysis-availability = Verfügbarkeit…
+menu-analysis-rocof = Ausfallintensität (ROCOF)…
+menu-analysis-age-replacement = Altersabhängiger Austausch…
+menu-analysis-calendar = Wartungskalender…
+menu-analysis-redundancy = Redundanzstufen…
+menu-analysis-event-tree = Ereignisbaum…
+menu-analysis-sil = Sicherheitsintegrität (SIL)…
+menu-analysis-fleet = Flottenausfälle…
+menu-analysis-competing-risks = Konkurrierende Ausfallarten…
+menu-analysis-criticality = Kritikalitätsrangfolge…
+menu-analysis-pareto = Pareto-Diagramm…
+menu-analysis-profile = Missionsprofil…
+menu-analysis-variables = Variablen…
+menu-analysis-scaling = Alle Parameter skalieren…
+menu-analysis-compare = Mit Basisversion vergleichen…
+menu-analysis-statistics = Modellstatistik…
+menu-analysis-feed = Live-Datenanbindung…
+menu-settings = Einstellungen…
+menu-help = Hilfe
+menu-help-examples = Beispiele
+
+plot-copy-data = Daten kopieren
+plot-show-availability = A(t) anzeigen
+plot-show-hazard = h(t) anzeigen
+plot-severity-hint = Zählt nur Ausfälle einer Schwereklasse; die übrigen Komponenten gelten als ausfallfrei.
+severity-all = Alle Ausfälle
+severity-safety-critical = Sicherheitskritisch
+severity-mission = Mission
+severity-convenience = Komfort
+plot-enlarge = Diagramm vergrößern
+plot-enlarged-title = Zuverlässigkeitsdiagramm
+plot-pop-out = Diagramm und Kennzahlen in einem eigenen Fenster zeigen, etwa für einen zweiten Bildschirm
+plot-popped-out = Das Diagramm wird in einem eigenen Fenster angezeigt.
+plot-export-csv = Als CSV exportieren…
+plot-approximate = (Näherung)
+plot-approximate-hint = Beim Ziehen eines Werts interpoliert; genau nach dem Loslassen.
+band-off = Kein Band
+band-90 = 90-%-Band
+band-95 = 95-%-Band
+mission-time = Missionszeit
+
+library-title = Komponentenbibliothek
+library-no-folder = Es ist keine Modellbibliothek eingerichtet. Setzen Sie library_paths in block.toml, um Komponentendaten zu teilen.
+library-reload = Neu laden
+library-save = Aktiven Knoten hinzufügen
+library-save-hint = Speichert die Werte des aktiven Knotens als Eintrag unter seinem Namen und verknüpft den Knoten damit.
+library-empty = In { $folder } gibt es noch keine Einträge.
+library-derived = { $count } abgeleitete Knoten
+library-insert = Einfügen
+library-up-to-date = Alle abgeleiteten Knoten stimmen mit dem Eintrag überein.
+library-review = { $count } Abweichungen prüfen
+library-node = Knoten
+library-field = Wert
+library-current = Im Modell
+library-entry = In der Bibliothek
+library-values = { $count } Werte
+library-push = { $count } Knoten aus der Bibliothek aktualisieren
+
+quantile-title = Quantilrechner
+quantile-probability = Ausfallwahrscheinlichkeit F
+quantile-time = Zeit t
+quantile-survived = Überlebt bis Alter T
+quantile-further = Für weitere t
+quantile-never-survives = Keine Einheit überlebt bis T
+
+validation-title = Prüfung ({ $count })
+validation-none = Keine Probleme gefunden.
+validation-unused-hint = Diese speisen keinen Serien-, Parallel-, Standby- oder Voting-Block, daher geht kein Systemergebnis auf sie ein:
+validation-unused = { $node } ist nicht angeschlossen
+validation-show = Auf der Arbeitsfläche zeigen
+
+checkpoints-title = Prüfzeitpunkte
+checkpoints-add = Prüfzeitpunkt hinzufügen
+regression-title = Gesperrte Ergebnisse
+regression-lock = Ergebnisse sperren
+regression-lock-hint = MTTF und R des aktiven Knotens zur Missionszeit und an den Prüfzeitpunkten im Projekt speichern und warnen, wenn spätere Änderungen sie verschieben.
+regression-unlock = Entsperren
+regression-none = Es sind keine Ergebnisse gesperrt.
+regression-tolerance = Toleranz
+regression-tolerance-hint = Die größte relative Änderung, die nicht als Abweichung gemeldet wird.
+regression-locked = { $figures } Kennwerte von { $node } sind gesperrt.
+regression-match = Die Ergebnisse stimmen mit den gesperrten Werten überein.
+regression-figure = Kennwert
+regression-locked-value = Gesperrt
+regression-now = Jetzt
+regression-node-deleted = Der gesperrte Knoten wurde gelöscht.
+regression-drift = Ergebnisse abgewichen
+regression-drift-hint = Einige gesperrte Ergebnisse haben sich über ihre Toleranz hinaus geändert; siehe Gesperrte Ergebnisse.
+quantile-beyond-horizon = Jenseits des Kurvenhorizonts
+equivalent-title = Äquivalente Verteilung
+equivalent-fit = Anpassen
+equivalent-failed = Die Kurve hat zu wenige Schritte zwischen R = 1 und R = 0 für eine Anpassung.
+equivalent-error = RMSE { $rmse } · größter Fehler { $max }
+equivalent-error-hint = Wie weit das angepasste R(t) über alle Zeitschritte von der Kurve abweicht.
+frequency-duration-title = Häufigkeit und Dauer
+frequency-duration-run = Ausfälle simulieren
+frequency-duration-frequency = Ausfallhäufigkeit
+frequency-duration-per-year = Ausfälle pro Jahr
+frequency-duration-mean = Mittlere Ausfalldauer
+frequency-duration-percentile = P{ $percent }-Ausfalldauer
+frequency-duration-unavailability = Nichtverfügbarkeit
+frequency-duration-outages = Aus { $count } Ausfällen, die innerhalb der Missionszeit endeten.
+frequency-duration-copy = Als TSV kopieren
+frequency-duration-no-horizon = Die Missionszeit muss größer als null sein.
+frequency-duration-hint = Das reparierbare System wird über die Missionszeit mit den Versuchen und dem Seed der Simulation simuliert. Ausfälle, die zur Missionszeit noch andauern, zählen zur Häufigkeit, aber nicht zu den Dauern.
+family-weibull = Weibull
+family-exponential = Exponentiell
+family-lognormal = Lognormal
+
+requirement = Anforderung
+requirement-at = bei
+requirement-pass = Erfüllt
+requirement-fail = Nicht erfüllt
+
+allocation-title = Zuverlässigkeitsaufteilung
+allocation-method = Methode
+allocation-equal = Gleichverteilung
+allocation-arinc = ARINC (proportional zur aktuellen Unzuverlässigkeit)
+allocation-feasibility = Nach Machbarkeit gewichtet
+allocation-no-system = Einen Knoten aktivieren, um seine Anforderung aufzuteilen.
+allocation-no-requirement = Die Anforderung aktivieren, um sie aufzuteilen.
+allocation-component = Komponente
+allocation-feasibility-rating = Machbarkeit (1–10)
+allocation-current = Aktuelles R
+allocation-target = Ziel-R
+allocation-adopt = Als Budget übernehmen
+allocation-adopt-hint = Diese Ziele mit dem Modell speichern, sodass jeder Knoten seine Reserve gegenüber ihnen zeigt, während sich der Entwurf ändert.
+budget-title = Zuverlässigkeitsbudget
+budget-empty = Noch kein Knoten hat ein Budget. Übernehmen Sie die Ziele einer Zuteilung, um sie zu verfolgen.
+budget-system-met = { $system } erfüllt die Anforderung: { $achieved } ≥ { $required }
+budget-system-missed = { $system } verfehlt die Anforderung: { $achieved } < { $required }
+budget-no-system = Setzen Sie einen Knoten aktiv und aktivieren Sie die Anforderung, um das System daran zu prüfen.
+budget-over = { $over } von { $total } Knoten überschreiten ihr Budget.
+budget-margin = Reserve
+budget-clear = Budget löschen
+
+goal-seek-title = Zielwertsuche
+goal-seek-component = Komponente
+goal-seek-parameter = Parameter
+goal-seek-target = Systemziel
+goal-seek-from-requirement = Aus Anforderung
+goal-seek-no-system = Zuerst den Systemknoten aktivieren.
+goal-seek-solve = Lösen
+goal-seek-solution = { $parameter } = { $value } ergibt R = { $reliability }.
+goal-seek-apply = Übernehmen
+goal-seek-no-solution = Kein Wert innerhalb von vier Größenordnungen um den aktuellen erreicht das Ziel.
+
+sweep-title = Parameterstudie
+sweep-range = Bereich
+sweep-steps = Schritte
+sweep-run = Studie starten
+sweep-metric-reliability = R(Missionszeit)
+sweep-metric-mttf = MTTF
+
+doe-title = Latin-Hypercube-Studie
+doe-add-factor = Parameter hinzufügen
+doe-samples = Stichproben
+doe-run = Studie starten
+doe-parallel-coordinates = Parallele Koordinaten
+doe-scatter = Streudiagramm
+
+robustness-title = Robustheit gegen Datenunsicherheit
+robustness-shape-tolerance = Toleranz der Form ±
+robustness-scale-tolerance = Toleranz der Skala ±
+robustness-trials = Durchläufe
+robustness-confidence = Geforderte Erfüllung
+robustness-confidence-hint = Der Anteil der Durchläufe, der die Anforderung erfüllen muss, damit der Entwurf als robust gilt.
+robustness-run = Durchläufe starten
+robustness-nominal = Nominelles R(t_m)
+robustness-mean = Mittleres R(t_m)
+robustness-share = { $share } % der Durchläufe erfüllen die Anforderung.
+robustness-robust = Robust: Die Anforderung bleibt trotz der Datenunsicherheit erfüllt.
+robustness-fragile = Fragil: Der Entwurf erfüllt die Anforderung nominell, verfehlt sie innerhalb der Toleranzen aber zu oft.
+robustness-not-met = Der Entwurf erfüllt die Anforderung nicht einmal nominell.
+robustness-no-requirement = Aktivieren Sie eine Anforderung, um die Robustheit zu beurteilen.
+robustness-hint = In jedem Durchlauf werden Form und Skala jeder Komponente gleichverteilt innerhalb ihrer Toleranz variiert.
+
+simulation-title = Monte-Carlo-Simulation
+simulation-settings = { $trials } Durchläufe, Startwert { $seed }
+simulation-run = Simulieren
+simulation-mean = Mittlere Systemlebensdauer: { $mean } { $unit }
+simulation-deviation = Abweichung von der analytischen Kurve: max. { $max }, Mittel { $mean } (Stichprobenfehler ≈ ±{ $error })
+simulation-export = Stichproben exportieren (CSV)…
+simulation-histogram = Histogramm
+simulation-survival = Überlebensfunktion
+simulation-bins = Klassen
+simulation-simulated = Simuliert
+simulation-analytic = Analytisch
+simulation-failure-times = Zeit bis zum Ausfall
+simulation-renewals = Erneuerungen
+simulation-renewals-horizon = Ausfälle je Komponente bis { $horizon } { $unit }
+simulation-maintenance = Instandhaltung
+maintenance-threshold = Austausch ab Alter
+maintenance-threshold-hint = Während das System für eine Reparatur ausfällt, werden funktionierende Komponenten ab diesem Alter ebenfalls getauscht.
+maintenance-corrective-cost = Kosten je Reparatur
+maintenance-preventive-cost = Kosten je vorzeitigem Austausch
+maintenance-downtime-cost = Kosten je Zeitschritt Ausfall
+maintenance-without = Nur Reparaturen
+maintenance-with = Opportunistisch
+maintenance-difference = Differenz
+maintenance-availability = Verfügbarkeit
+maintenance-repairs = Reparaturen
+maintenance-replacements = Vorzeitige Austausche
+maintenance-cost = Kosten
+maintenance-pays = Opportunistischer Austausch spart { $amount } je Mission.
+maintenance-does-not-pay = Opportunistischer Austausch kostet { $amount } mehr je Mission.
+maintenance-hint = Beide Strategien werden über die Missionszeit mit demselben Seed simuliert. Komponenten ohne MTTR werden nicht repariert.
+simulation-cancel = Anhalten
+simulation-convergence = Konvergenz
+simulation-progress = { $trials } von höchstens { $max } Durchläufen: R(t_m) = { $estimate } ± { $half_width }
+simulation-trials = Durchläufe
+simulation-interval = 95-%-Intervall
+shocks-title = Schocks ({ $count })
+shocks-default-name = Überlast
+shocks-rate = Ereignisse pro Schritt
+shocks-damage = Schädigung
+shocks-damage-hint = Der Anteil der verbleibenden Lebensdauer einer getroffenen Komponente, den jedes Ereignis wegnimmt.
+shocks-targets = Trifft { $count } Komponenten
+shocks-add = Schock hinzufügen
+shocks-remove = Schock entfernen
+shocks-hint = Ereignisse treten zufällig mit der angegebenen Rate auf, für alle getroffenen Komponenten gleich. Nur die Simulation der Zeit bis zum Ausfall berücksichtigt sie, daher weicht sie von der analytischen Kurve ab.
+
+km-title = Kaplan-Meier-Schätzer
+km-format = Eine Einheit pro Zeile: Zeit, dann 1 (Ausfall) oder 0 (Ausscheiden ohne Ausfall).
+km-import = Datei importieren…
+km-error-line = Zeile { $line } enthält keine Zeit mit Status.
+km-summary = { $failures } Ausfälle, { $suspensions } zensierte Einheiten
+km-estimate = Kaplan-Meier
+km-confidence = 95 %-Konfidenzband (Greenwood)
+km-attach-to = Empirischer Knoten
+km-attach = Kurve übernehmen
+km-horizon = bis Schritt
+km-horizon-hint = Nach der letzten Beobachtung hält die Schätzung ihren letzten Wert. Wählen Sie den Horizont der Blöcke, die der Knoten speist.
+km-no-empirical-nodes = Fügen Sie einen empirischen Knoten hinzu, um die Schätzung zu übernehmen.
+
+field-title = Feldausfallprotokoll
+field-format = Eine CSV-Datei mit den Spalten component, installed, failed und censored. Datumsangaben als JJJJ-MM-TT; jede Zeile enthält ein Ausfall- oder Zensierungsdatum.
+field-error-column = Die Kopfzeile enthält keine Spalte { $column }.
+field-error-line = Zeile { $line } enthält kein gültiges Installations- und Ausfall- oder Zensierungsdatum.
+field-name = Komponentenname
+field-units = Ausfälle / Einheiten
+field-node = Knoten
+field-apply-all = Alle übernehmen
+field-bootstrap-hint = Unter jedem Schätzwert steht sein 95-%-Konfidenzintervall aus einem parametrischen Bootstrap mit { $resamples } Stichproben. Beim Übernehmen einer Anpassung bleiben ihre 90-%-Intervalle für das Band im Plot erhalten.
+field-refresh = Datei erneut lesen alle
+field-tolerance = Anpassungen markieren, deren MTTF abweicht um mehr als
+field-auto-apply = Markierte Komponenten aktualisieren
+field-ratio = MTTF Feld / Modell
+field-ratio-hint = Die MTTF der Anpassung geteilt durch die der Knotenparameter. Werte außerhalb der Toleranz werden markiert.
+field-diverged = Die Felddaten von { $node } weichen von seinen Parametern ab.
+field-updated = { $node } wurde aus neuen Felddaten aktualisiert.
+
+bayes-title = Bayes-Aktualisierung
+bayes-prior-hint = Log-normale Prior-Verteilungen: der Median und die Streuung als Standardabweichung des Logarithmus. Beim Wählen einer Komponente werden ihre Parameter als Mediane übernommen.
+bayes-median = Median
+bayes-spread = Streuung (log)
+bayes-mean = Posterior-Mittelwert
+bayes-interval = 90-%-Glaubwürdigkeitsintervall
+bayes-apply = Posterior-Mittelwert übernehmen
+bayes-study = Intervalle an Studie übergeben
+bayes-study-hint = Öffnet die Latin-Hypercube-Studie mit Form und Skala über ihre Glaubwürdigkeitsintervalle variiert.
+
+profile-title = Missionsprofil
+profile-enabled = Ausfallraten der Komponenten mit dem Profil skalieren
+profile-hint = Die Abschnitte wiederholen sich der Reihe nach über den gesamten Auswertungszeitraum.
+profile-segment = Abschnitt
+profile-duration = Dauer
+profile-stress = Belastung
+profile-add-segment = Abschnitt hinzufügen
+
+variables-title = Variablen
+variables-hint = Komponentenparameter können als Ausdrücke dieser Variablen angegeben werden, etwa mission_hours / 2, unter „Ausdrücke“ an der Komponente.
+variables-name = Name
+variables-value = Wert
+variables-uses = Verwendet von
+variables-add = Variable hinzufügen
+variables-invalid-name = Namen beginnen mit einem Buchstaben oder Unterstrich, gefolgt von Buchstaben, Ziffern und Unterstrichen.
+variables-duplicate-name = Eine andere Variable hat diesen Namen; Ausdrücke verwenden die erste.
+variables-expression-hint = z. B. 2 * ambient_temp
+variables-out-of-range = { $value } liegt außerhalb des Bereichs für { $parameter }
+variables-syntax = „{ $text }“ ist nicht lesbar
+variables-unknown = Keine Variable namens { $name }
+variables-unclosed = Eine Klammer ist nicht geschlossen
+variables-incomplete = Der Ausdruck endet zu früh
+variables-not-finite = Der Ausdruck ergibt keine endliche Zahl
+
+scaling-title = Alle Parameter skalieren
+scaling-factor = Lebensdauer (Skalen)
+scaling-factor-hint = Multipliziert jede Skala, auch die weiterer Ausfallarten. 0,8 verkürzt die Lebensdauer jeder Komponente um 20 %.
+scaling-mttr-factor = Reparaturzeiten (MTTR)
+scaling-count = { $count } Komponenten werden skaliert
+scaling-skipped = { $count } ausgelassen: schreibgeschützt, spiegeln eine andere Komponente oder durch einen Ausdruck gegeben
+scaling-preview = System vorab berechnen
+scaling-system = R(t_m) des Systems: { $before } → { $after }
+scaling-apply = Skalieren
+scaling-undo = × { $factor } rückgängig machen (MTTR × { $mttr })
+
+availability-title = Verfügbarkeit
+availability-interval = Intervall
+availability-point = A(Missionszeit)
+availability-mean = Mittlere Verfügbarkeit { $from }–{ $to }
+availability-steady-state = Dauerverfügbarkeit
+availability-hint = Komponenten mit MTTR werden nach jedem Ausfall repariert, alle anderen nicht.
+
+rocof-title = Ausfallintensität
+rocof-total = Alle Komponenten
+rocof-per-component = Je Komponente
+rocof-cumulative = Kumuliert N(t)
+rocof-at-mission-time = w(Missionszeit) = { $rate } Ausfälle/{ $unit }
+rocof-expected-failures = Erwartete Ausfälle bis zur Missionszeit: { $failures }
+rocof-improving = Ausfälle werden seltener.
+rocof-deteriorating = Ausfälle werden häufiger.
+rocof-steady = Ausfälle treten mit gleichbleibender Rate auf.
+rocof-hint = Komponenten mit MTTR werden nach jedem Ausfall repariert, wie neu oder wie vorher; alle anderen fallen höchstens einmal aus.
+age-replacement-title = Altersabhängiger Austausch
+age-replacement-optimum = Austausch im Alter { $age } { $unit }: { $rate } je { $unit }
+age-replacement-saving = { $percent } % günstiger als Betrieb bis zum Ausfall.
+age-replacement-none = Betrieb bis zum Ausfall ist am günstigsten; vorbeugender Austausch lohnt sich nur bei Verschleiß, mit β > 1 und Ausfällen, die teurer als Austausche sind.
+age-replacement-axis = Kosten je { $unit }
+age-replacement-cost-rate = Kostenrate C(T)
+age-replacement-run-to-failure = Betrieb bis zum Ausfall
+age-replacement-optimal-age = Optimales Alter
+age-replacement-hint = Jede Einheit wird im Alter T oder bei ihrem Ausfall getauscht, je nachdem, was zuerst eintritt, über die Zeitschritte der Komponente.
+calendar-title = Wartungskalender
+calendar-start = Mission beginnt am
+calendar-bad-date = Datum als JJJJ-MM-TT eingeben.
+calendar-cycles = Zyklen haben kein Datum; wählen Sie in den Einstellungen eine Zeiteinheit, um den Kalender zu datieren.
+calendar-summary = { $events } Ereignisse über { $horizon } { $unit }
+calendar-inspection = Inspektion
+calendar-replacement = Vorbeugender Austausch
+calendar-expected-failure = Erwarteter Ausfall
+calendar-export-ical = iCal exportieren…
+calendar-export-csv = CSV exportieren…
+calendar-hint = Komponenten werden im kostenoptimalen Alter getauscht, erwartete Ausfälle folgen der langfristigen Ausfallrate unter dieser Strategie. Umfasst die Komponenten des aktiven Systems oder alle.
+redundancy-title = Redundanzstufen
+redundancy-block = Block
+redundancy-max-units = Bis zu
+redundancy-no-blocks = Fügen Sie einen Parallel- oder Standby-Block hinzu, um Redundanzstufen zu vergleichen.
+redundancy-run = Vergleichen
+redundancy-units = Einheiten
+redundancy-block-reliability = Block R(t_m)
+redundancy-system-reliability = System R(t_m)
+redundancy-gain = Gewinn
+redundancy-hint = Jede Einheit ist eine Kopie der Einheit an Eingang A des Blocks. Das System ist der aktive Knoten, wobei angenommen wird, dass der Block keine Knoten mit dem Rest teilt.
+
+event-tree-title = Ereignisbaum
+event-tree-frequency = Häufigkeit des auslösenden Ereignisses
+event-tree-per-year = pro Jahr
+event-tree-demand = Versagenswahrscheinlichkeit bei Anforderung
+event-tree-demand-mission-time = 1 − R zur Missionszeit
+event-tree-demand-average = Gemittelt über die Mission
+event-tree-stop-at-success = Sequenz an der ersten funktionierenden Schicht beenden
+event-tree-stop-at-success-hint = Spätere Schichten werden nicht mehr angefordert, sobald eine funktioniert, wie bei unabhängigen Schutzebenen. Abwählen, um an jeder Schicht zu verzweigen.
+event-tree-layers = Schutzschichten in der Reihenfolge ihrer Anforderung
+event-tree-add-layer = Schicht hinzufügen
+event-tree-build = Baum erstellen
+event-tree-unmitigated = Unbeherrschte Häufigkeit { $frequency } pro Jahr, eine Risikominderung um { $reduction }×
+event-tree-probability = Wahrscheinlichkeit
+event-tree-sequence-frequency = Häufigkeit pro Jahr
+event-tree-hint = ✔ die Schicht funktioniert, ✘ sie versagt. Die Schichten gelten als unabhängig; gemeinsame Knoten machen das Ergebnis zu optimistisch.
+
+sil-title = Sicherheitsintegrität
+sil-interval = Prüfintervall
+sil-coverage = Prüfabdeckung
+sil-coverage-hint = Der Anteil gefährlicher Ausfälle, den eine Wiederholungsprüfung aufdeckt. Der Rest bleibt die gesamte Lebensdauer verborgen.
+sil-lifetime = Lebensdauer (Missionszeit)
+sil-subsystems = Schutzteilsysteme
+sil-add = Teilsystem hinzufügen
+sil-run = Berechnen
+sil-subsystem = Teilsystem
+sil-low-demand = Niedrige Anforderungsrate
+sil-pfh = PFH [1/{ $unit }]
+sil-high-demand = Hohe Anforderungsrate
+sil-none = Kein SIL
+sil-cycles = Die Zeitschritte sind Zyklen, daher gibt es keine Rate pro Stunde.
+sil-hint = Bänder nach IEC 61508. Der SIL allein aus Ausfallkennwerten ist eine Obergrenze; architektonische Einschränkungen und systematische Eignung können ihn weiter begrenzen.
+weak-link-summary = Schwachstelle bei t = { $time }: { $members } (alle ausgefallen: { $probability })
+weak-link-none = Schwachstelle: Legen Sie einen aktiven Knoten fest, der ausfallen kann, um seinen dominanten Minimalschnitt zu verfolgen.
+canvas-pointer = Zeiger bei x { $x }, y { $y }
+canvas-node = { $node } bei x { $x }, y { $y }
+card-no-system = Setzen Sie den Systemknoten aktiv, um hier seine Ergebnisse zusammenzufassen.
+card-evaluated = Stand { $time }
+hotspots-summary = Auswertung dauert { $total }; am langsamsten: { $node } ({ $share } %)
+
+fleet-title = Flottenausfälle
+fleet-size = Flottengröße
+fleet-failures = Erwartete Ausfälle
+fleet-expected-failures = Erwartete Ausfälle in der Flotte bis zur Missionszeit: { $failures }
+fleet-hint = Für den aktiven Knoten, ohne Ersatz ausgefallener Einheiten. Für reparierte Komponenten siehe Ausfallintensität.
+
+statistics-title = Modellstatistik
+statistics-connections = Verbindungen
+statistics-depth = Maximale Tiefe
+statistics-subsystems = Getrennte Teilsysteme
+statistics-largest = Größtes Teilsystem
+statistics-largest-value = { $node } ({ $count } Knoten)
+statistics-cost = Auswertungsaufwand (Kurvenwerte)
+feed-title = Live-Datenanbindung
+feed-hint = Abonniert ein MQTT-Topic und schreibt eingehende Tag-Werte in Komponentenparameter. Eine Nachricht ist eine Zahl, benannt nach ihrem Topic, oder ein JSON-Objekt aus Tag-Namen und Zahlen.
+feed-host = Broker
+feed-topic = Topic
+feed-connect = Verbinden
+feed-disconnect = Trennen
+feed-received = { $count } Nachrichten empfangen
+feed-unsupported = Dieser Build enthält keinen MQTT-Client; für die Datenanbindung mit dem Feature mqtt bauen.
+feed-tag = Tag
+feed-parameter = Parameter
+feed-last = Letzter Wert
+feed-rejected = Der Wert liegt außerhalb des Bereichs dieses Parameters und wurde nicht übernommen.
+feed-add = Zuordnung hinzufügen
+feed-live = ● Live
+statistics-hint = Die Tiefe zählt die Knoten auf dem längsten Pfad entlang der Verbindungen. Der Auswertungsaufwand gilt für den aktiven Knoten und verdoppelt sich mit jedem Knoten, den mehrere Zweige teilen.
+statistics-selection = { $count } ausgewählt bei t_m: Serie R = { $series } · parallel R = { $parallel } · Σλ = { $rate } /{ $unit }
+statistics-selection-hint = Was die ausgewählten Komponenten in einem Serien- oder Parallelblock ergäben, und die Summe ihrer Ausfallraten zur Missionszeit.
+
+compare-title = Modelle vergleichen
+compare-baseline = Basisversion
+compare-with = Verglichen mit
+compare-current = Diesem Modell
+compare-choose = Datei wählen…
+compare-hint = Wählen Sie eine Basisversion, etwa den zuletzt eingecheckten Stand des Projekts. Knoten werden über ihre Identität zugeordnet; vergleichen Sie also Versionen desselben Projekts.
+compare-identical = Die Modelle sind gleich.
+compare-summary = { $added } hinzugefügt, { $removed } entfernt, { $changed } geändert; { $connected } Verbindungen hinzugefügt, { $disconnected } entfernt
+compare-connections = Verbindungen
+compare-label = Bezeichnung
+compare-old = vorher
+compare-new = nachher
+
+risks-title = Konkurrierende Ausfallarten
+risks-normalized = Anteil an den Ausfällen
+risks-single-mode = Diese Komponente hat nur eine Ausfallart; weitere lassen sich im Abschnitt Ausfallarten hinzufügen.
+
+criticality-title = Kritikalitätsrangfolge
+criticality-birnbaum = Birnbaum
+criticality-fussell-vesely = Fussell-Vesely
+criticality-index = Kritikalität
+criticality-export = Exportieren (CSV)…
+criticality-pop-out = Tabelle in einem eigenen Fenster zeigen, etwa für einen zweiten Bildschirm
+criticality-hint = Zur Missionszeit. Zum Sortieren auf eine Spaltenüberschrift klicken.
+
+pareto-title = Beiträge zur Unzuverlässigkeit
+pareto-share = Anteil an der Unzuverlässigkeit [%]
+pareto-contribution = Beitrag
+pareto-cumulative = Kumuliert
+pareto-threshold = Schwelle
+pareto-vital-few = { $count } von { $total } Komponenten erreichen sie
+pareto-hint = Zur Missionszeit, nach Kritikalität, auf 100 % normiert.
+
+report-title = Bericht
+report-default-title = Zuverlässigkeitsbericht
+report-document-title = Titel
+report-project = Projekt
+report-author = Autor
+report-revision = Revision
+report-logo = Logo (Pfad oder URL)
+report-sections = Abschnitte
+report-metrics = Systemkennwerte
+report-components = Komponenten
+report-notes = Anmerkungen
+report-system = System
+report-metric = Kennwert
+report-value = Wert
+report-audit = Berechnungseinstellungen
+report-setting = Einstellung
+report-export-html = HTML exportieren…
+report-export-latex = LaTeX-Tabellen exportieren…
+report-export-markdown = Markdown-Zusammenfassung exportieren…
+report-results-json = Ergebnisse als JSON…
+report-copy-markdown = Als Markdown kopieren
+report-saved = Gespeichert unter { $path }
+
+settings-title = Einstellungen
+settings-ui-scale = UI-Skalierung
+settings-reset = Zurücksetzen
+settings-theme = Farbschema
+settings-autosave = Intervall für automatisches Speichern
+settings-restore-session = Letzte Sitzung beim Start wieder öffnen
+settings-author = Ihr Name
+settings-author-hint = Wird an den von Ihnen gesperrten Abschnitten angezeigt, damit Mitarbeitende wissen, wen sie fragen können.
+settings-node-names = Neue Knoten benennen
+settings-node-names-hint = {"{"}type{"}"} ist die Knotenart, {"{"}parent{"}"} der Knoten, von dem aus verbunden wurde, und {"{"}n{"}"} oder {"{"}letter{"}"} nummeriert, etwa Pumpe-{"{"}n{"}"} oder Serie-{"{"}letter{"}"}.
+settings-time-unit = Zeiteinheit
+settings-number-format = Zahlen in Exporten
+settings-number-format-hint = Das Dezimaltrennzeichen und die Datumsreihenfolge von CSV-Dateien und Berichten. CSV-Dateien mit Dezimalkomma trennen ihre Felder mit Semikolons.
+settings-wire-style = Verbindungen
+settings-b-lives = Angezeigte B-Lebensdauern
+settings-precision = Kurvengenauigkeit
+precision-double = Doppelt (f64)
+precision-single = Einfach (f32), halber Speicher
+settings-fast-preview = Schnellvorschau beim Ziehen
+settings-fast-preview-hint = Bei großen Modellen werden Kurven beim Ziehen eines Werts aus jedem 32. Zeitschritt interpoliert und nach dem Loslassen genau ausgewertet.
+settings-disabled-nodes = Deaktivierte Knoten gelten als
+disabled-perfect = Perfekt
+disabled-absent = Nicht vorhanden
+settings-monte-carlo-samples = Monte-Carlo-Stichproben
+settings-monte-carlo-half-width = Abbruch bei R(t_m)-Genauigkeit
+settings-seed = Zufallsstartwert
+settings-new-seed = Neuer Startwert
+settings-record-metrics = Leistungsdaten aufzeichnen
+settings-record-metrics-hint = Schreibt jede Minute die Größe des Modells und Perzentile der Bild- und Auswertungszeiten nach { $path }. Die Datei bleibt auf diesem Rechner; hängen Sie sie an, wenn Sie ein langsames Modell melden.
+settings-default-parameters = Standardparameter für Komponenten
+settings-favorite-templates = Oben in der Knotensuche angeheftet
+
+unit-hours = Stunden
+unit-hours-symbol = h
+unit-days = Tage
+unit-days-symbol = d
+unit-years = Jahre
+unit-years-symbol = a
+unit-cycles = Zyklen
+unit-cycles-symbol = Zyklen
+
+wire-curved = Geschwungen
+wire-orthogonal = Rechtwinklig
+number-format-language = Wie die Sprache
+number-format-point = 1234.5 · 2024-03-31
+number-format-comma = 1234,5 · 31.03.2024
+
+type-reliability = Zuverlässigkeit
+type-parameters = Parameter
+type-text = Text
+type-dormancy = Ruhezustand
+type-human-action = Menschliche Handlung
+type-software-growth = Fehlerentdeckung
+type-one-shot = Erfolg bei Anforderung
+type-petri-net = Petri-Netz
+
+node-component = Komponente
+node-series = Reihe
+node-parallel = Parallel
+node-standby = Standby
+node-voting = Auswahl (k-aus-n)
+node-note = Notiz
+node-empirical = Empirisch
+node-empirical-steps = Kurve ({ $steps } Schritte)
+node-resample = Neu abtasten…
+node-resample-horizon = Bis Schritt
+node-resample-apply = Neu abtasten
+node-resample-hint = Schneidet die Kurve beim Schritt ab oder hält ihren letzten Wert bis dahin.
+node-human = Menschliche Handlung
+node-software = Software
+node-one-shot = Einweggerät
+node-petri-net = Petri-Netz
+node-set-active = Aktivieren
+node-active = Aktiv
+node-tag = Markierung
+node-tag-none = Keine Markierung
+node-tag-add = Markierung hinzufügen
+node-force = Erzwingen…
+node-force-none = Nicht erzwungen
+node-forced-failed = Ausgefallen erzwungen
+node-forced-working = Funktionsfähig erzwungen
+node-excluded = Ausgeschlossen
+node-enabled = Aktiv
+node-enabled-hint = Deaktivierte Knoten werden je nach Einstellung nicht ausgewertet, um ein System schrittweise aufzubauen.
+node-severity = Schwere eines Ausfalls
+node-coverage-hint = Fehlerabdeckung: die Wahrscheinlichkeit, dass der Ausfall einer Einheit erkannt und isoliert wird. Ein nicht abgedeckter Ausfall legt den Block trotz Redundanz lahm.
+node-votes-hint = Wie viele Mitglieder funktionieren müssen, damit der Block funktioniert.
+node-add-member = Mitglied hinzufügen
+node-remove-member = Letztes Mitglied entfernen
+node-copy-json = Als JSON kopieren
+node-lock = Position sperren
+mirror-none = Eigene Parameter
+mirror-of = Spiegelt { $master }
+mirror-hint = Die Parameter einer anderen Komponente übernehmen, für baugleiche Einheiten. Wenn Sie jene Komponente bearbeiten, wird diese mit aktualisiert.
+node-read-only = 🔒 { $owner }
+node-read-only-hint = In einem gesperrten Abschnitt; entsperren Sie ihn über die Titelleiste des Rahmens, um den Knoten zu bearbeiten.
+node-parameters-summary = β = { $shape } · η = { $scale }
+node-allocated-target = Ziel ≥ { $target }
+node-budget = Budget ≥ { $target }
+node-budget-hint = Die diesem Knoten zum Anforderungszeitpunkt zugeteilte Zuverlässigkeit und seine Reserve mit dem aktuellen Entwurf.
+node-horizon-mismatch = ⚠ Eingänge decken { $shortest } und { $longest } Schritte ab
+node-horizon-mismatch-hint = Der Block lässt sich erst auswerten, wenn seine Eingänge dieselben Zeitschritte abdecken. Die Verbindungen der kürzeren sind markiert.
+node-extend-horizon = Auf { $longest } erweitern
+port-same-direction = Ausgänge lassen sich nur mit Eingängen verbinden.
+port-same-node = Ein Knoten kann sich nicht selbst speisen.
+port-locked = In einem gesperrten Abschnitt.
+port-wrong-type = Erwartet { $expected }, die Verbindung führt aber { $carried }.
+port-cycle = Würde eine Schleife schließen, und Diagramme dürfen keine Zyklen enthalten.
+
+category-components = Komponenten
+category-structures = Strukturen
+category-analysis = Analyse
+category-data = Daten
+category-annotation = Anmerkungen
+
+frame-default-title = Gruppe
+frame-title = Titel
+frame-lock = Abschnitt sperren
+frame-lock-hint = Macht die Knoten im Rahmen schreibgeschützt, unter Ihrem Namen aus den Einstellungen, damit andere Bearbeiter des Projekts sie nicht verändern.
+frame-unlock = Abschnitt entsperren
+frame-locked-by = Gesperrt von { $owner }
+frame-locked-title = 🔒 { $title } ({ $owner })
+frame-anonymous = jemandem
+frame-delete = Rahmen entfernen (Knoten bleiben)
+
+rename-title = Suchen und ersetzen
+rename-find = Suchen
+rename-replace = Ersetzen durch
+rename-regex = Regulärer Ausdruck
+rename-match-case = Groß-/Kleinschreibung beachten
+rename-names = Knotennamen
+rename-notes = Notizen
+rename-selected-only = Nur ausgewählte Knoten
+rename-groups-hint = Gruppen des Ausdrucks werden mit $1 oder ${"{"}name{"}"} eingesetzt.
+rename-count = Ersetzungen: { $count }
+rename-locked = Treffer in gesperrten Abschnitten, unverändert: { $count }
+rename-more = … und { $count } weitere
+rename-replace-all = Alle ersetzen
+
+presentation-leave = Präsentation beenden
+presentation-hint = Nur lesen: Mit Strg+Ziehen verschieben, mit dem Mausrad zoomen. Esc oder F5 beendet.
+
+legend-title = Markierungen
+legend-empty = Noch sind keine Knoten markiert.
+
+what-if-title = Was wäre, wenn
+what-if-hint = Nicht angehakte Komponenten werden aus den Blöcken genommen, die sie speisen: Ein Serienblock hängt nicht mehr von ihnen ab, ein Parallelblock verliert sie als redundanten Pfad.
+what-if-empty = Das Diagramm enthält keine Komponenten.
+what-if-restore-all = Alle wiederherstellen ({ $count })
+
+note-hint = Notiz schreiben…
+dormancy-title = Reserve
+dormancy-hot = Heiß
+dormancy-warm = Warm
+dormancy-cold = Kalt
+dormancy-hint = Wie die Reserve an B altert, während die Hauptkomponente an A läuft: genauso schnell (heiß), mit einem Bruchteil der Rate (warm) oder gar nicht (kalt).
+dormancy-factor = Alterungsrate im Ruhezustand, bezogen auf den Betrieb
+human-hep = Nominale HEP
+human-hep-hint = Menschliche Fehlerwahrscheinlichkeit: die Wahrscheinlichkeit, dass die Handlung bei Bedarf misslingt.
+human-psfs = Leistungsbeeinflussende Faktoren ({ $count } gesetzt)
+human-psfs-hint = SPAR-H-Multiplikatoren der nominalen HEP: über 1 macht einen Fehler wahrscheinlicher, unter 1 unwahrscheinlicher. Bei drei oder mehr über 1 bleibt die angepasste HEP unter 1.
+human-adjusted = Angepasste HEP: { $hep }
+psf-available-time = Verfügbare Zeit
+psf-stress = Stress
+psf-complexity = Komplexität
+psf-experience = Erfahrung und Schulung
+psf-procedures = Verfahren
+psf-ergonomics = Ergonomie und Bedienoberfläche
+psf-fitness = Einsatzfähigkeit
+psf-work-processes = Arbeitsabläufe
+software-test-time = Testdauer
+software-test-time-hint = Wie lange getestet wurde, in Zeitschritten. Die Software wird an ihrem Ende freigegeben.
+software-discoveries = Entdeckungszeitpunkte der Fehler
+software-discoveries-hint = Wann jeder Fehler gefunden wurde, in Zeitschritten seit Testbeginn, einer pro Zeile
+software-fit = Goel-Okumoto: a = { $a }, b = { $b }, { $remaining } Fehler verbleiben bei Freigabe
+software-remaining = { $remaining } Fehler verbleiben
+software-error-line = Zeile { $line } ist kein Entdeckungszeitpunkt.
+software-error-no-data = Geben Sie an, wann jeder Fehler gefunden wurde.
+software-error-beyond-test = Entdeckungen müssen innerhalb der Testdauer liegen.
+software-error-no-growth = Die Entdeckungen werden im Test nicht seltener und zeigen daher kein Zuverlässigkeitswachstum.
+one-shot-success = Erfolg bei Anforderung
+one-shot-success-hint = Die Wahrscheinlichkeit, dass das Gerät bei seinem einmaligen Einsatz funktioniert.
+one-shot-time-steps-hint = Wie weit die eigene Kurve des Geräts reicht. Neben zeitabhängigen Blöcken gilt stattdessen deren Horizont.
+petri-summary = { $places } Stellen, { $transitions } Transitionen
+petri-works-while = Funktioniert, solange
+petri-trials = Durchläufe
+petri-trials-hint = Wie oft das Netz durchgespielt wird, um R(t) und A(t) zu schätzen. Mehr Durchläufe ergeben glattere Kurven, dauern aber nach jeder Änderung am Netz länger.
+petri-places = Stellen ({ $count })
+petri-place = Stelle
+petri-tokens = Marken
+petri-add-place = Stelle hinzufügen
+petri-transitions = Transitionen ({ $count })
+petri-transition = Transition
+petri-add-transition = Transition hinzufügen
+petri-remove-transition = Transition entfernen
+petri-takes = Nimmt
+petri-gives = Gibt
+petri-delay-exponential = Exponentiell
+petri-delay-deterministic = Feste Verzögerung
+petri-delay-weibull = Weibull
+petri-error-delay = die Verzögerung braucht positive, endliche Parameter.
+petri-error-no-place = Wählen Sie die Stelle, die angibt, ob das Teilsystem funktioniert.
+petri-error-no-trials = Das Netz braucht mindestens einen Durchlauf.
+petri-error-loop = Transitionen ohne Verzögerung feuern endlos im Kreis.
+petri-example-up = In Betrieb
+petri-example-down = Ausgefallen
+petri-example-crew = Team frei
+petri-example-repairing = In Reparatur
+petri-example-fail = Ausfall
+petri-example-start = Reparatur beginnen
+petri-example-repair = Reparatur
+
+example-series = Einfache Serienschaltung
+example-series-note = Ein Seriensystem funktioniert nur, solange jeder Block funktioniert, also R = R₁ · R₂ · R₃; der schwächste Block dominiert.
+example-pump = Pumpe
+example-motor = Motor
+example-controller = Steuerung
+example-voting = 2-aus-3-Auswahl
+example-voting-note = Die Sensoren stimmen ab: Je zwei funktionierende Sensoren halten das System in Betrieb. Jedes Paar ist ein Serienblock, die Paare sind parallel; da sich die Paare Sensoren teilen, bedingt die Auswertung auf jeden gemeinsamen Sensor, statt die Paare als unabhängig zu behandeln.
+example-sensor = Sensor
+example-standby = Notstromaggregat
+example-standby-note = Die Netzversorgung wird durch ein Aggregat hinter einem Umschalter abgesichert; alle drei werden instand gesetzt. Das Aggregat ist als heiße Reserve modelliert, die auch im Stillstand altert, was konservativ ist.
+example-mains = Netz
+example-switch = Umschalter
+example-generator = Aggregat
+example-bridge = Brückenschaltung
+example-bridge-note = Strecke 3 überbrückt zwei parallele Pfade, daher ist das Netz weder seriell noch parallel. Es besteht aus seinen vier minimalen Pfaden 1-4, 2-5, 1-3-5 und 2-3-4 in Parallelschaltung; auf die gemeinsamen Strecken wird bei der Auswertung bedingt.
+example-link = Strecke
+
+wizard-title = Neues Projekt
+wizard-mission-hint = Wie lange muss das System funktionieren, und in welcher Einheit wird die Zeit gezählt?
+wizard-target-hint = Welche Zuverlässigkeit muss das System am Ende der Mission erreichen?
+wizard-target-at = bei { $time } { $unit }
+wizard-components-hint = Wie viele Komponenten hat das System ungefähr? Sie beginnen in Serie, jede so bemessen, dass die Kette das Ziel gerade erfüllt; ordnen Sie sie von dort aus neu an.
+wizard-components = Komponenten
+wizard-replaces = Das Erstellen des Projekts ersetzt das aktuelle Diagramm.
+wizard-back = Zurück
+wizard-next = Weiter
+wizard-create = Erstellen
+wizard-component-label = Komponente { $number }
+wizard-system-label = System
+
+param-shape = Form β
+param-scale = Skala η
+param-scale-hint = Das Alter, bis zu dem 63,2 % der Einheiten ausgefallen sind.
+param-entry-mttf = MTTF
+param-entry-rate = Charakteristische Rate λ = 1/η
+param-entry-mttf-hint = MTTF = η Γ(1 + 1/β), also η = MTTF / Γ(1 + 1/β)
+param-entry-rate-hint = η = 1/λ
+param-time-steps = Zeitschritte
+param-duty-cycle = Einschaltdauer
+param-schedule = Betriebsplan
+param-schedule-hint = Nur in einem Fenster betreiben, das sich jede Periode wiederholt, sodass die Komponente nach Betriebszeit statt Kalenderzeit altert.
+schedule-every = alle
+schedule-from = ab
+schedule-operating = Betriebszeit je Periode
+schedule-period = Die Periode, mit der sich das Fenster wiederholt
+schedule-start = Wann das Fenster innerhalb jeder Periode öffnet
+param-mttr = MTTR
+param-mttr-hint = Mittlere Reparaturdauer. Null bedeutet, dass die Komponente nicht repariert wird.
+param-repair = Reparatur
+repair-renewal = Wie neu
+repair-minimal = Wie vorher (minimal)
+param-hidden-failures = Verdeckte Ausfälle
+param-hidden-failures-hint = Ausfälle bleiben unbemerkt, bis eine wiederkehrende Inspektion sie findet, etwa bei Sicherheitsventilen und anderen Schutzeinrichtungen. Ein gefundener Ausfall wird wie neu instand gesetzt.
+inspection-every = alle
+inspection-interval = Zeit zwischen Inspektionen
+inspection-effectiveness = Die Wahrscheinlichkeit, dass eine Inspektion einen verdeckten Ausfall findet
+param-environment-factor = Umgebungsfaktor π_E
+param-derating = Temperaturabhängigkeit
+param-temperature = Betriebstemperatur
+param-uncertainty = 90-%-Intervall
+param-uncertainty-clear = Intervall verwerfen, sodass der Plot für diese Komponente kein Band zeigt.
+param-failure-modes = Ausfallarten ({ $count })
+param-expressions = Ausdrücke ({ $count })
+mode-name = Ausfallart
+mode-primary = Primär
+mode-share = Anteil
+mode-share-hint = Anteil an der Ausfallwahrscheinlichkeit bis zur Missionszeit
+mode-add = Ausfallart hinzufügen
+
+derating-none = Keine
+derating-arrhenius = Arrhenius
+derating-curve = Derating-Kurve
+derating-activation-energy = Aktivierungsenergie
+derating-reference = Referenztemperatur
+derating-add-point = Punkt hinzufügen
+
+env-ground-benign = Boden, geschützt
+env-ground-fixed = Boden, stationär
+env-ground-mobile = Boden, mobil
+env-naval-sheltered = See, geschützt
+env-airborne-inhabited = Luftfahrt, bemannt
+env-airborne-uninhabited = Luftfahrt, unbemannt
+env-space-flight = Raumfahrt
+
+keyboard-hint = Umschalt+A: Knoten hinzufügen · N: nächster Knoten · C: verbinden · Eingabe: plotten · Entf: löschen · Strg+V: Knoten einfügen · Strg+P: Befehle · F5: präsentieren
+keyboard-select-one = Genau einen Knoten zum Verbinden auswählen.
+keyboard-connect-from = Verbindung von { $node }: Zielknoten auswählen und erneut C drücken, oder Esc zum Abbrechen.
+keyboard-connected = { $from } mit { $to } verbunden.
+keyboard-no-free-input = Der Zielknoten hat keinen freien passenden Eingang.
+keyboard-connect-cancelled = Verbindung abgebrochen.
+
+snippet-pasted = { $node } eingefügt.
+snippet-not-json = Die Zwischenablage enthält keinen JSON-Knoten.
+snippet-no-version = Dem Ausschnitt fehlt die Schemaversion; kopieren Sie den Knoten erneut mit „Als JSON kopieren“.
+snippet-unsupported-version = Der Ausschnitt verwendet Schemaversion { $version }, diese Version der Anwendung liest Version { $supported }.
+snippet-invalid = Der Ausschnitt ist kein gültiger Knoten: { $error }
+snippet-unknown-input = Der Ausschnitt setzt einen Eingang „{ $input }“, den diese Knotenart nicht hat.
+
+project-filter = Block-Projekt
+project-filter-json = Block-Projekt (JSON)
+project-filter-ron = Block-Projekt (RON)
+project-filter-yaml = Block-Projekt (YAML)
+project-filter-binary = Block-Projekt (binär)
+project-not-json = Die Datei ist kein gültiges JSON: { $error }
+project-not-ron = Die Datei ist kein gültiges RON: { $error }
+project-not-yaml = Die Datei ist kein gültiges YAML: { $error }
+project-not-text = Die Datei ist kein Text; binäre Projekte enden auf .blk.
+project-not-binary = Die Datei ist kein binäres Projekt.
+project-no-version = Die Datei hat keine Projektversion; vermutlich ist sie keine Projektdatei.
+project-unsupported-version = Das Projekt verwendet Dateiversion { $version }, diese Version der Anwendung liest Version { $supported }.
+project-newer-version = Das Projekt wurde mit einer neueren Version der Anwendung gespeichert (Dateiversion { $version }); diese Version liest bis Version { $supported }.
+project-migration-failed = Das Projekt konnte nicht von Dateiversion { $from } auf { $to } aktualisiert werden: { $error }
+project-ron-older = RON-Projekte werden nur mit Dateiversion { $supported } gelesen, dieses hat Version { $version }. Öffnen Sie es in der Version der Anwendung, mit der es gespeichert wurde, und speichern Sie es als JSON.
+project-binary-older = Binäre Projekte werden nur mit Dateiversion { $supported } gelesen, dieses hat Version { $version }. Öffnen Sie es in der Version der Anwendung, mit der es gespeichert wurde, und speichern Sie es als JSON.
+project-invalid = Die Projektdatei ist beschädigt oder unvollständig: { $error }
+project-invalid-field = { $field } im Projekt kann nicht gelesen werden: { $error }
+project-damaged = Die Projektdatei stimmt nicht mit ihrer Prüfsumme überein und ist womöglich abgeschnitten oder beschädigt. Prüfen Sie das Modell, bevor Sie die Datei überschreiben.
+dot-unterminated = Die DOT-Datei endet innerhalb eines Kommentars, Strings oder Graphen.
+dot-unexpected = Die DOT-Datei enthält ein unerwartetes „{ $character }“.
+dot-no-graph = Die Datei beginnt nicht mit einem DOT-Graphen.
+dot-edge-target = Eine Kante in der DOT-Datei zeigt auf keinen Knoten.
+dot-empty = Der DOT-Graph hat keine Knoten.
+distribution-invalid = Die Datei ist keine Systemverteilung: { $error }
+distribution-unsupported-version = Die Verteilung hat Version { $version }, lesbar ist nur Version { $supported }.
+distribution-no-active-node = Setzen Sie einen Knoten aktiv, um seine Verteilung zu exportieren.
+session-unreadable = Die letzte Sitzung konnte nicht wiederhergestellt werden und wurde beiseitegelegt: { $error }
+session-damaged = Die letzte Sitzung stimmt nicht mit ihrer Prüfsumme überein und ist womöglich unvollständig.
+
+config-invalid = { $file } konnte nicht gelesen werden, daher wurden seine Voreinstellungen ignoriert: { $error }
+config-invalid-value = Die Voreinstellung { $key } = "{ $value }" ist ungültig, daher wurden die konfigurierten Voreinstellungen ignoriert.
+
+log-title = Protokoll
+log-app-only = Nur diese Anwendung
+log-copy = Kopieren
+log-clear = Leeren
+log-unseen = ⚠ { $count } neue Warnungen
+
+toasts-title = Benachrichtigungen
+toasts-none = Noch keine Benachrichtigungen.
+toasts-clear = Leeren
+toast-saved = Projekt gespeichert.
+toast-imported = { $count } Knoten importiert.
+toast-distribution-imported = { $name } als empirischen Knoten hinzugefügt.
+toast-library-saved = { $name } in der Bibliothek gespeichert.
+toast-library-pushed = { $count } Knoten aus der Bibliothek aktualisiert.
+toast-simulation-done = Simulation nach { $trials } Durchläufen abgeschlossen.
+toast-simulation-failed = Simulation fehlgeschlagen: { $error }
+toast-results-exported = Ergebnisse exportiert.
+toast-results-drifted = Gesperrte Ergebnisse sind über ihre Toleranz hinaus abgewichen.
+
+journal-title = Ungespeicherte Änderungen wiederherstellen
+journal-message = Die Anwendung wurde beim letzten Mal nicht ordnungsgemäß beendet. { $edits } Änderungen seit dem letzten Speichern des Projekts können wiederhergestellt werden.
+journal-recover = Wiederherstellen
+journal-discard = Verwerfen
+
+palette-title = Befehlspalette
+palette-hint = Befehl eingeben…
+palette-no-match = Kein passender Befehl.
+palette-add-node = Knoten hinzufügen: { $node }
+palette-example = Beispiel öffnen: { $example }
+
+error-evaluation = Auswertung fehlgeschlagen
+health-not-finite = { $node }: R(t) ist ab t = { $time } undefiniert (NaN oder unendlich) und wird daher nicht dargestellt.
+health-increasing = { $node }: R(t) steigt bei t = { $time }, die Ausfallwahrscheinlichkeit sinkt also; die Kurve wird nicht dargestellt.
+health-parameters = Bitte { $parameters } prüfen.
+error-cycle = Das Diagramm enthält einen Zyklus.
+error-note = Notizen sind Anmerkungen und haben keine Zuverlässigkeit.
+error-empty-curve = Diesem Knoten wurde noch keine Kurve zugewiesen.
+error-chunked-measure = Nur die Zuverlässigkeit kann abschnittsweise ausgewertet werden.
+error-unconnected-input = Eingang { $input } ist nicht verbunden.
+error-expected-reliability = Zuverlässigkeitskurve erwartet.
+error-expected-parameters = Komponentenparameter erwartet.
+error-all-inputs-disabled = Alle Eingänge dieses Blocks sind deaktiviert.
+error-expected-dormancy = Ruhezustand einer Reserve erwartet.
+error-expected-human-action = Menschliche Handlung erwartet.
+error-expected-software-growth = Fehlerentdeckungsdaten erwartet.
+error-expected-one-shot = Einweggerät erwartet.
+error-expected-petri-net = Petri-Netz erwartet.
+error-too-few-members = Es werden mehr Stimmen benötigt, als die { $members } verbleibenden Mitglieder haben.
+error-not-component = Der Knoten ist keine Komponente.
+error-time-out-of-range = Zeitpunkt { $time } liegt außerhalb des ausgewerteten Horizonts.
+error-horizon-mismatch = Dieser Eingang endet bei Schritt { $shortest }, ein anderer reicht bis { $longest }. Erweitern Sie die kürzeren Kurven oder tasten Sie sie neu ab.
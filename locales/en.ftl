@@ -0,0 +1,915 @@
+menu-file = File
+menu-file-new = New project…
+menu-file-open = Open…
+menu-file-save-as = Save as…
+menu-file-import = Import model…
+menu-file-import-dot = Import GraphViz DOT…
+menu-file-import-distribution = Import a system distribution…
+menu-file-library = Component library…
+menu-file-export-structure = Export structure
+menu-file-export-adjacency = Adjacency matrix (CSV)…
+menu-file-export-edge-list = Edge list (CSV)…
+menu-file-export-dot = Export GraphViz DOT…
+menu-file-export-builder = Export as Rust builder code…
+menu-file-export-distribution = Export the system distribution…
+menu-file-report = Generate report…
+menu-language = Language
+menu-view = View
+menu-view-legend = Tag legend
+menu-view-quick-add = Quick-add toolbar
+menu-view-what-if = What-if panel
+menu-view-log = Log
+menu-view-notifications = Notifications
+menu-view-edge-values = Reliability on connections
+menu-view-weak-link = Weak link
+menu-view-hotspots = Evaluation time heatmap
+menu-view-grid = Grid and coordinates
+menu-view-summary-card = Summary card
+menu-view-presentation = Presentation mode (F5)
+quick-add-hint = Adds the node at the center of the view
+menu-arrange = Arrange
+menu-arrange-align-left = Align left edges
+menu-arrange-align-top = Align top edges
+menu-arrange-align-center = Align centers
+menu-arrange-distribute-horizontally = Distribute horizontally
+menu-arrange-distribute-vertically = Distribute vertically
+menu-arrange-lock = Lock positions of selected nodes
+menu-arrange-unlock = Unlock positions of selected nodes
+menu-arrange-frame = Frame selected nodes
+menu-arrange-find-replace = Find and replace…
+menu-analysis = Analysis
+menu-analysis-allocation = Reliability allocation…
+menu-analysis-budget = Reliability budget…
+menu-analysis-goal-seek = Goal seek…
+menu-analysis-sweep = Parameter sweep…
+menu-analysis-doe = Latin hypercube study…
+menu-analysis-robustness = Robustness to data uncertainty…
+menu-analysis-simulation = Monte Carlo simulation…
+menu-analysis-kaplan-meier = Kaplan-Meier estimate…
+menu-analysis-field-data = Fit field failure log…
+menu-analysis-bayes = Bayesian updating…
+menu-analysis-availability = Availability…
+menu-analysis-rocof = Failure intensity (ROCOF)…
+menu-analysis-age-replacement = Age replacement…
+menu-analysis-calendar = Maintenance calendar…
+menu-analysis-redundancy = Redundancy levels…
+menu-analysis-event-tree = Event tree…
+menu-analysis-sil = Safety integrity (SIL)…
+menu-analysis-fleet = Fleet failures…
+menu-analysis-competing-risks = Competing risks…
+menu-analysis-criticality = Criticality ranking…
+menu-analysis-pareto = Pareto chart…
+menu-analysis-profile = Mission profile…
+menu-analysis-variables = Variables…
+menu-analysis-scaling = Scale all parameters…
+menu-analysis-compare = Compare with baseline…
+menu-analysis-statistics = Model statistics…
+menu-analysis-feed = Live data feed…
+menu-settings = Settings…
+menu-help = Help
+menu-help-examples = Examples
+
+plot-copy-data = Copy data
+plot-show-availability = Show A(t)
+plot-show-hazard = Show h(t)
+plot-severity-hint = Counts only failures of one severity class, taking the other components as never failing.
+severity-all = All failures
+severity-safety-critical = Safety-critical
+severity-mission = Mission
+severity-convenience = Convenience
+plot-enlarge = Enlarge plot
+plot-enlarged-title = Reliability plot
+plot-pop-out = Show the plot and figures in a window of their own, for another monitor
+plot-popped-out = The plot is shown in its own window.
+plot-export-csv = Export CSV…
+plot-approximate = (approximate)
+plot-approximate-hint = Interpolated while a value is dragged; precise once it is let go.
+band-off = No band
+band-90 = 90% band
+band-95 = 95% band
+mission-time = Mission time
+
+library-title = Component library
+library-no-folder = No model library is configured. Set library_paths in block.toml to share component data.
+library-reload = Reload
+library-save = Add the active node
+library-save-hint = Saves the active node's values as an entry named after it, and links the node to it.
+library-empty = There are no entries in { $folder } yet.
+library-derived = { $count } derived nodes
+library-insert = Insert
+library-up-to-date = Every derived node matches the entry.
+library-review = Review { $count } differences
+library-node = Node
+library-field = Value
+library-current = In the model
+library-entry = In the library
+library-values = { $count } values
+library-push = Update { $count } nodes from the library
+
+quantile-title = Quantile calculator
+quantile-probability = Failure probability F
+quantile-time = Time t
+quantile-survived = Survived to age T
+quantile-further = For a further t
+quantile-never-survives = No unit survives to T
+
+validation-title = Validation ({ $count })
+validation-none = No problems found.
+validation-unused-hint = These feed no series, parallel, standby or voting block, so no system result includes them:
+validation-unused = { $node } is not connected
+validation-show = Show on the canvas
+
+checkpoints-title = Checkpoints
+checkpoints-add = Add checkpoint
+regression-title = Locked results
+regression-lock = Lock results
+regression-lock-hint = Store the active node's MTTF and R at the mission time and checkpoints with the project, and warn when later edits change them.
+regression-unlock = Unlock
+regression-none = No results are locked.
+regression-tolerance = Tolerance
+regression-tolerance-hint = The largest relative change that isn't reported as drift.
+regression-locked = { $figures } figures of { $node } are locked.
+regression-match = The results match the locked values.
+regression-figure = Figure
+regression-locked-value = Locked
+regression-now = Now
+regression-node-deleted = The locked node was deleted.
+regression-drift = Results drifted
+regression-drift-hint = Some locked results changed beyond their tolerance; see Locked results.
+quantile-beyond-horizon = Beyond the curve horizon
+equivalent-title = Equivalent distribution
+equivalent-fit = Fit
+equivalent-failed = The curve has too few steps between R = 1 and R = 0 to fit.
+equivalent-error = RMSE { $rmse } · largest error { $max }
+equivalent-error-hint = How far the fitted R(t) strays from the curve, over every time step.
+frequency-duration-title = Frequency and duration
+frequency-duration-run = Simulate outages
+frequency-duration-frequency = Outage frequency
+frequency-duration-per-year = Outages per year
+frequency-duration-mean = Mean outage duration
+frequency-duration-percentile = P{ $percent } outage duration
+frequency-duration-unavailability = Unavailability
+frequency-duration-outages = From { $count } outages that ended within the mission time.
+frequency-duration-copy = Copy as TSV
+frequency-duration-no-horizon = The mission time must be longer than zero.
+frequency-duration-hint = Repairable system simulated over the mission time with the simulation's trials and seed. Outages still going on at the mission time count towards the frequency but not the durations.
+family-weibull = Weibull
+family-exponential = Exponential
+family-lognormal = Lognormal
+
+requirement = Requirement
+requirement-at = at
+requirement-pass = Pass
+requirement-fail = Fail
+
+allocation-title = Reliability allocation
+allocation-method = Method
+allocation-equal = Equal apportionment
+allocation-arinc = ARINC (proportional to current unreliability)
+allocation-feasibility = Feasibility-weighted
+allocation-no-system = Set a node active to allocate its requirement.
+allocation-no-requirement = Enable the requirement to allocate it.
+allocation-component = Component
+allocation-feasibility-rating = Feasibility (1–10)
+allocation-current = Current R
+allocation-target = Target R
+allocation-adopt = Adopt as budget
+allocation-adopt-hint = Keep these targets with the model, so each node shows its margin against them as the design changes.
+budget-title = Reliability budget
+budget-empty = No node has a budget yet. Adopt the targets of an allocation to track them.
+budget-system-met = { $system } meets the requirement: { $achieved } ≥ { $required }
+budget-system-missed = { $system } misses the requirement: { $achieved } < { $required }
+budget-no-system = Set a node active and enable the requirement to check the system against it.
+budget-over = { $over } of { $total } nodes are over budget.
+budget-margin = Margin
+budget-clear = Clear budget
+
+goal-seek-title = Goal seek
+goal-seek-component = Component
+goal-seek-parameter = Parameter
+goal-seek-target = System target
+goal-seek-from-requirement = From requirement
+goal-seek-no-system = Set the system node active first.
+goal-seek-solve = Solve
+goal-seek-solution = { $parameter } = { $value } gives R = { $reliability }.
+goal-seek-apply = Apply
+goal-seek-no-solution = No value within four orders of magnitude of the current one reaches the target.
+
+sweep-title = Parameter sweep
+sweep-range = Range
+sweep-steps = Steps
+sweep-run = Run sweep
+sweep-metric-reliability = R(mission time)
+sweep-metric-mttf = MTTF
+
+doe-title = Latin hypercube study
+doe-add-factor = Add parameter
+doe-samples = Samples
+doe-run = Run study
+doe-parallel-coordinates = Parallel coordinates
+doe-scatter = Scatter
+
+robustness-title = Robustness to data uncertainty
+robustness-shape-tolerance = Shape tolerance ±
+robustness-scale-tolerance = Scale tolerance ±
+robustness-trials = Trials
+robustness-confidence = Required compliance
+robustness-confidence-hint = The share of trials that must meet the requirement for the design to count as robust.
+robustness-run = Run trials
+robustness-nominal = Nominal R(t_m)
+robustness-mean = Mean R(t_m)
+robustness-share = { $share } % of trials meet the requirement.
+robustness-robust = Robust: compliance holds across the data's uncertainty.
+robustness-fragile = Fragile: the design meets the requirement nominally, but too often fails it within the tolerances.
+robustness-not-met = The design doesn't meet the requirement even nominally.
+robustness-no-requirement = Enable a requirement to judge how robust compliance is.
+robustness-hint = Every component's shape and scale is varied uniformly within its tolerance in each trial.
+
+simulation-title = Monte Carlo simulation
+simulation-settings = { $trials } trials, seed { $seed }
+simulation-run = Simulate
+simulation-mean = Mean system time to failure: { $mean } { $unit }
+simulation-deviation = Deviation from the analytic curve: max { $max }, mean { $mean } (sampling error ≈ ±{ $error })
+simulation-export = Export samples (CSV)…
+simulation-histogram = Histogram
+simulation-survival = Survival
+simulation-bins = Bins
+simulation-simulated = Simulated
+simulation-analytic = Analytic
+simulation-failure-times = Time to failure
+simulation-renewals = Renewals
+simulation-renewals-horizon = Failures of each component up to { $horizon } { $unit }
+simulation-maintenance = Maintenance
+maintenance-threshold = Replace from age
+maintenance-threshold-hint = While the system is down for a repair, working components at least this old are replaced as well.
+maintenance-corrective-cost = Cost per repair
+maintenance-preventive-cost = Cost per early replacement
+maintenance-downtime-cost = Cost per time step down
+maintenance-without = Repairs only
+maintenance-with = Opportunistic
+maintenance-difference = Difference
+maintenance-availability = Availability
+maintenance-repairs = Repairs
+maintenance-replacements = Early replacements
+maintenance-cost = Cost
+maintenance-pays = Opportunistic replacement saves { $amount } per mission.
+maintenance-does-not-pay = Opportunistic replacement costs { $amount } more per mission.
+maintenance-hint = Both policies are simulated over the mission time from the same seed. Components with no MTTR aren't repaired.
+simulation-cancel = Stop
+simulation-convergence = Convergence
+simulation-progress = { $trials } of at most { $max } trials: R(t_m) = { $estimate } ± { $half_width }
+simulation-trials = Trials
+simulation-interval = 95% interval
+shocks-title = Shocks ({ $count })
+shocks-default-name = Overload
+shocks-rate = Events per step
+shocks-damage = Damage
+shocks-damage-hint = The fraction of a hit component's remaining life each event takes away.
+shocks-targets = Hits { $count } components
+shocks-add = Add shock
+shocks-remove = Remove shock
+shocks-hint = Events arrive at random at the given rate, the same for every component they hit. Only the time to failure simulation takes them into account, so it deviates from the analytic curve.
+
+km-title = Kaplan-Meier estimate
+km-format = One unit per line: time, then 1 (failure) or 0 (suspension).
+km-import = Import file…
+km-error-line = Line { $line } is not a time and status.
+km-summary = { $failures } failures, { $suspensions } suspensions
+km-estimate = Kaplan-Meier
+km-confidence = 95% confidence (Greenwood)
+km-attach-to = Empirical node
+km-attach = Attach curve
+km-horizon = up to step
+km-horizon-hint = Past the last observation the estimate holds its last value. Match the horizon of the blocks the node feeds.
+km-no-empirical-nodes = Add an Empirical node to attach the estimate to.
+
+field-title = Field failure log
+field-format = A CSV with the columns component, installed, failed and censored. Dates are YYYY-MM-DD; each row has a failed or a censored date.
+field-error-column = The header has no { $column } column.
+field-error-line = Line { $line } has no valid installation and failure or censoring date.
+field-name = Component name
+field-units = Failures / units
+field-node = Node
+field-apply-all = Apply all
+field-bootstrap-hint = Below each estimate is its 95% confidence interval from a parametric bootstrap of { $resamples } resamples. Applying a fit also keeps its 90% intervals for the plot's band.
+field-refresh = Read the file again every
+field-tolerance = Flag fits with an MTTF off by more than
+field-auto-apply = Update flagged components
+field-ratio = Field / model MTTF
+field-ratio-hint = The MTTF of the fit over that of the node's parameters. Values beyond the tolerance are flagged.
+field-diverged = Field data for { $node } has drifted from its parameters.
+field-updated = { $node } was updated from new field data.
+
+bayes-title = Bayesian updating
+bayes-prior-hint = Log-normal priors: the median, and the spread as the standard deviation of the logarithm. Picking a component takes its parameters as the medians.
+bayes-median = Median
+bayes-spread = Spread (log)
+bayes-mean = Posterior mean
+bayes-interval = 90% credible interval
+bayes-apply = Apply posterior mean
+bayes-study = Send intervals to study
+bayes-study-hint = Opens the Latin hypercube study with the shape and scale varied over their credible intervals.
+
+profile-title = Mission profile
+profile-enabled = Scale component hazards by the profile
+profile-hint = The segments repeat in order for the whole evaluation horizon.
+profile-segment = Segment
+profile-duration = Duration
+profile-stress = Stress
+profile-add-segment = Add segment
+
+variables-title = Variables
+variables-hint = Component parameters can be given as expressions of these, such as mission_hours / 2, under "Expressions" on the component.
+variables-name = Name
+variables-value = Value
+variables-uses = Used by
+variables-add = Add variable
+variables-invalid-name = Names start with a letter or underscore and go on with letters, digits and underscores.
+variables-duplicate-name = Another variable has this name; expressions use the first.
+variables-expression-hint = e.g. 2 * ambient_temp
+variables-out-of-range = { $value } is out of range for { $parameter }
+variables-syntax = Can't read "{ $text }"
+variables-unknown = No variable named { $name }
+variables-unclosed = A parenthesis isn't closed
+variables-incomplete = The expression ends too early
+variables-not-finite = The expression doesn't come to a finite number
+
+scaling-title = Scale all parameters
+scaling-factor = Life (scales)
+scaling-factor-hint = Multiplies every scale, including those of further failure modes. 0.8 makes every component's life 20% shorter.
+scaling-mttr-factor = Repair times (MTTR)
+scaling-count = { $count } components will be scaled
+scaling-skipped = { $count } left out: read-only, mirroring another component or given by an expression
+scaling-preview = Preview system
+scaling-system = R(t_m) of the system: { $before } → { $after }
+scaling-apply = Scale
+scaling-undo = Undo × { $factor } (MTTR × { $mttr })
+
+availability-title = Availability
+availability-interval = Interval
+availability-point = A(mission time)
+availability-mean = Mean availability { $from }–{ $to }
+availability-steady-state = Steady-state availability
+availability-hint = Components with an MTTR are repaired after each failure; others are not.
+
+rocof-title = Failure intensity
+rocof-total = All components
+rocof-per-component = Per component
+rocof-cumulative = Cumulative N(t)
+rocof-at-mission-time = w(mission time) = { $rate } failures/{ $unit }
+rocof-expected-failures = Expected failures by mission time: { $failures }
+rocof-improving = Failures are becoming less frequent.
+rocof-deteriorating = Failures are becoming more frequent.
+rocof-steady = Failures occur at a steady rate.
+rocof-hint = Components with an MTTR are repaired after each failure, as good as new or as bad as old; others fail at most once.
+age-replacement-title = Age replacement
+age-replacement-optimum = Replace at age { $age } { $unit }: { $rate } per { $unit }
+age-replacement-saving = { $percent } % cheaper than running to failure.
+age-replacement-none = Running to failure costs least; preventive replacement only pays for wear-out, with β > 1 and failures dearer than replacements.
+age-replacement-axis = Cost per { $unit }
+age-replacement-cost-rate = Cost rate C(T)
+age-replacement-run-to-failure = Run to failure
+age-replacement-optimal-age = Optimal age
+age-replacement-hint = Each unit is replaced at age T or when it fails, whichever comes first, over the component's time steps.
+calendar-title = Maintenance calendar
+calendar-start = Mission starts on
+calendar-bad-date = Enter the date as YYYY-MM-DD.
+calendar-cycles = Cycles aren't tied to dates; choose a time unit in the settings to date the calendar.
+calendar-summary = { $events } events over { $horizon } { $unit }
+calendar-inspection = Inspection
+calendar-replacement = Preventive replacement
+calendar-expected-failure = Expected failure
+calendar-export-ical = Export iCal…
+calendar-export-csv = Export CSV…
+calendar-hint = Components are replaced at their cost-optimal age, and expected failures are spaced at the long-run failure rate under that policy. Covers the active system's components, or all of them.
+redundancy-title = Redundancy levels
+redundancy-block = Block
+redundancy-max-units = Up to
+redundancy-no-blocks = Add a parallel or standby block to compare redundancy levels.
+redundancy-run = Compare
+redundancy-units = Units
+redundancy-block-reliability = Block R(t_m)
+redundancy-system-reliability = System R(t_m)
+redundancy-gain = Gain
+redundancy-hint = Each unit is a copy of the one on the block's input A. The system is the active node, assuming the block shares no nodes with the rest of it.
+
+event-tree-title = Event tree
+event-tree-frequency = Initiating event frequency
+event-tree-per-year = per year
+event-tree-demand = Probability of failure on demand
+event-tree-demand-mission-time = 1 − R at the mission time
+event-tree-demand-average = Averaged over the mission
+event-tree-stop-at-success = End a sequence at the first layer that works
+event-tree-stop-at-success-hint = Later layers aren't demanded once one works, as for independent protection layers. Untick to branch on every layer.
+event-tree-layers = Protective layers, in the order they are demanded
+event-tree-add-layer = Add layer
+event-tree-build = Build tree
+event-tree-unmitigated = Unmitigated frequency { $frequency } per year, a risk reduction of { $reduction }×
+event-tree-probability = Probability
+event-tree-sequence-frequency = Frequency per year
+event-tree-hint = ✔ the layer works, ✘ it fails. Layers are taken as independent; nodes shared between them make the result optimistic.
+
+sil-title = Safety integrity
+sil-interval = Proof test interval
+sil-coverage = Proof test coverage
+sil-coverage-hint = The fraction of dangerous failures a proof test reveals. The rest stay hidden for the whole lifetime.
+sil-lifetime = Lifetime (mission time)
+sil-subsystems = Protective subsystems
+sil-add = Add subsystem
+sil-run = Calculate
+sil-subsystem = Subsystem
+sil-low-demand = Low demand
+sil-pfh = PFH [1/{ $unit }]
+sil-high-demand = High demand
+sil-none = No SIL
+sil-cycles = Time steps are cycles, so there is no rate per hour.
+sil-hint = Bands as in IEC 61508. The SIL from failure figures alone is an upper bound; architectural constraints and systematic capability can limit it further.
+weak-link-summary = Weak link at t = { $time }: { $members } (all failed: { $probability })
+weak-link-none = Weak link: set an active node that can fail to trace its dominant cut set.
+canvas-pointer = Pointer at x { $x }, y { $y }
+canvas-node = { $node } at x { $x }, y { $y }
+card-no-system = Set the system node active to sum up its results here.
+card-evaluated = As of { $time }
+hotspots-summary = Evaluation takes { $total }; slowest: { $node } ({ $share } %)
+
+fleet-title = Fleet failures
+fleet-size = Fleet size
+fleet-failures = Expected failures
+fleet-expected-failures = Expected failures in the fleet by mission time: { $failures }
+fleet-hint = Of the active node, with failed units not replaced. For repaired components see Failure intensity.
+
+statistics-title = Model statistics
+statistics-connections = Connections
+statistics-depth = Maximum depth
+statistics-subsystems = Separate subsystems
+statistics-largest = Largest subsystem
+statistics-largest-value = { $node } ({ $count } nodes)
+statistics-cost = Evaluation cost (curve values)
+feed-title = Live data feed
+feed-hint = Subscribes to an MQTT topic and writes incoming tag values into component parameters. A message is a number, named by its topic, or a JSON object of tag names and numbers.
+feed-host = Broker
+feed-topic = Topic
+feed-connect = Connect
+feed-disconnect = Disconnect
+feed-received = { $count } messages received
+feed-unsupported = This build has no MQTT client; build with the mqtt feature to use the data feed.
+feed-tag = Tag
+feed-parameter = Parameter
+feed-last = Last value
+feed-rejected = The value is out of range for this parameter and wasn't applied.
+feed-add = Add mapping
+feed-live = ● Live
+statistics-hint = Depth counts the nodes on the longest path through the connections. The evaluation cost is that of the active node, doubling with each node shared by several branches.
+statistics-selection = { $count } selected at t_m: series R = { $series } · parallel R = { $parallel } · Σλ = { $rate } /{ $unit }
+statistics-selection-hint = What the selected components would give joined in a series or a parallel block, and the sum of their hazard rates at the mission time.
+
+compare-title = Compare models
+compare-baseline = Baseline
+compare-with = Compared with
+compare-current = This model
+compare-choose = Choose file…
+compare-hint = Choose a baseline, such as the version of the project last committed. Nodes are matched by their identity, so compare versions of the same project.
+compare-identical = The models are the same.
+compare-summary = { $added } added, { $removed } removed, { $changed } changed; { $connected } connections added, { $disconnected } removed
+compare-connections = Connections
+compare-label = Label
+compare-old = before
+compare-new = after
+
+risks-title = Competing risks
+risks-normalized = Share of failures
+risks-single-mode = This component has a single failure mode; add more in its Failure modes section.
+
+criticality-title = Criticality ranking
+criticality-birnbaum = Birnbaum
+criticality-fussell-vesely = Fussell-Vesely
+criticality-index = Criticality
+criticality-export = Export (CSV)…
+criticality-pop-out = Show the table in a window of its own, for another monitor
+criticality-hint = At the mission time. Click a column heading to sort by it.
+
+pareto-title = Unreliability contributions
+pareto-share = Share of unreliability [%]
+pareto-contribution = Contribution
+pareto-cumulative = Cumulative
+pareto-threshold = Threshold
+pareto-vital-few = { $count } of { $total } components reach it
+pareto-hint = At the mission time, by criticality importance, normalised to 100 %.
+
+report-title = Report
+report-default-title = Reliability report
+report-document-title = Title
+report-project = Project
+report-author = Author
+report-revision = Revision
+report-logo = Logo (path or URL)
+report-sections = Sections
+report-metrics = System metrics
+report-components = Components
+report-notes = Notes
+report-system = System
+report-metric = Metric
+report-value = Value
+report-audit = Calculation settings
+report-setting = Setting
+report-export-html = Export HTML…
+report-export-latex = Export LaTeX tables…
+report-export-markdown = Export Markdown summary…
+report-results-json = Results as JSON…
+report-copy-markdown = Copy as Markdown
+report-saved = Saved to { $path }
+
+settings-title = Settings
+settings-ui-scale = UI scale
+settings-reset = Reset
+settings-theme = Theme
+settings-autosave = Autosave interval
+settings-restore-session = Reopen the last session on startup
+settings-author = Your name
+settings-author-hint = Shown on the sections you lock, so collaborators know whom to ask.
+settings-node-names = Name new nodes
+settings-node-names-hint = {"{"}type{"}"} is the kind of node, {"{"}parent{"}"} the node it was connected from, and {"{"}n{"}"} or {"{"}letter{"}"} numbers it, as in Pump-{"{"}n{"}"} or Series-{"{"}letter{"}"}.
+settings-time-unit = Time unit
+settings-number-format = Numbers in exports
+settings-number-format-hint = The decimal separator and date order of CSV files and reports. CSV files with a decimal comma separate their fields with semicolons.
+settings-wire-style = Connections
+settings-b-lives = Reported B-lives
+settings-precision = Curve precision
+precision-double = Double (f64)
+precision-single = Single (f32), half the memory
+settings-fast-preview = Fast preview while dragging
+settings-fast-preview-hint = On large models, curves are interpolated from every 32nd time step while a value is being dragged, and evaluated precisely once it is let go.
+settings-disabled-nodes = Disabled nodes count as
+disabled-perfect = Perfect
+disabled-absent = Absent
+settings-monte-carlo-samples = Monte Carlo samples
+settings-monte-carlo-half-width = Stop at R(t_m) precision
+settings-seed = Random seed
+settings-new-seed = New seed
+settings-record-metrics = Record performance metrics
+settings-record-metrics-hint = Every minute, adds the model's size and percentiles of frame and evaluation times to { $path }. The file stays on this computer; attach it when reporting a slow model.
+settings-default-parameters = Default component parameters
+settings-favorite-templates = Pinned to the top of the node finder
+
+unit-hours = Hours
+unit-hours-symbol = h
+unit-days = Days
+unit-days-symbol = d
+unit-years = Years
+unit-years-symbol = y
+unit-cycles = Cycles
+unit-cycles-symbol = cycles
+
+wire-curved = Curved
+wire-orthogonal = Right-angled
+number-format-language = As the language
+number-format-point = 1234.5 · 2024-03-31
+number-format-comma = 1234,5 · 31.03.2024
+
+type-reliability = Reliability
+type-parameters = Parameters
+type-text = Text
+type-dormancy = Dormancy
+type-human-action = Human action
+type-software-growth = Defect discovery
+type-one-shot = Demand success
+type-petri-net = Petri net
+
+node-component = Component
+node-series = Series
+node-parallel = Parallel
+node-standby = Standby
+node-voting = Voting (k-out-of-n)
+node-note = Note
+node-empirical = Empirical
+node-empirical-steps = Curve ({ $steps } steps)
+node-resample = Resample…
+node-resample-horizon = Up to step
+node-resample-apply = Resample
+node-resample-hint = Cuts the curve off at the step, or holds its last value up to it.
+node-human = Human action
+node-software = Software
+node-one-shot = One-shot device
+node-petri-net = Petri net
+node-set-active = Set active
+node-active = Active
+node-tag = Tag
+node-tag-none = No tag
+node-tag-add = Add tag
+node-force = Force…
+node-force-none = Not forced
+node-forced-failed = Forced failed
+node-forced-working = Forced working
+node-excluded = Excluded
+node-enabled = Enabled
+node-enabled-hint = Disabled nodes are left out of evaluation, as set in the settings, for building up a system in stages.
+node-severity = Failure severity
+node-coverage-hint = Fault coverage: the probability that a unit's failure is detected and isolated. An uncovered failure brings the block down despite the redundancy.
+node-votes-hint = How many members must work for the block to work.
+node-add-member = Add a member
+node-remove-member = Remove the last member
+node-copy-json = Copy as JSON
+node-lock = Lock position
+mirror-none = Own parameters
+mirror-of = Mirrors { $master }
+mirror-hint = Share the parameters of another component, for identical installed units. Editing that component updates this one.
+node-read-only = 🔒 { $owner }
+node-read-only-hint = In a locked section; unlock it from the frame's title bar to edit the node.
+node-parameters-summary = β = { $shape } · η = { $scale }
+node-allocated-target = Target ≥ { $target }
+node-budget = Budget ≥ { $target }
+node-budget-hint = The reliability budgeted to this node at the requirement time, and its margin with the current design.
+node-horizon-mismatch = ⚠ Inputs cover { $shortest } and { $longest } steps
+node-horizon-mismatch-hint = The block can't be evaluated until its inputs cover the same time steps. The wires of the shorter ones are ringed.
+node-extend-horizon = Extend to { $longest }
+port-same-direction = Outputs connect to inputs only.
+port-same-node = A node can't feed itself.
+port-locked = In a locked section.
+port-wrong-type = Expects { $expected }, but the wire carries { $carried }.
+port-cycle = Would close a loop, and diagrams can't contain cycles.
+
+category-components = Components
+category-structures = Structures
+category-analysis = Analysis
+category-data = Data
+category-annotation = Annotation
+
+frame-default-title = Group
+frame-title = Title
+frame-lock = Lock section
+frame-lock-hint = Makes the nodes in the frame read-only, under your name from the settings, so others editing the project leave them alone.
+frame-unlock = Unlock section
+frame-locked-by = Locked by { $owner }
+frame-locked-title = 🔒 { $title } ({ $owner })
+frame-anonymous = someone
+frame-delete = Remove frame (keeps its nodes)
+
+rename-title = Find and replace
+rename-find = Find
+rename-replace = Replace with
+rename-regex = Regular expression
+rename-match-case = Match case
+rename-names = Node names
+rename-notes = Notes
+rename-selected-only = Selected nodes only
+rename-groups-hint = Refer to groups of the expression as $1 or ${"{"}name{"}"}.
+rename-count = Replacements: { $count }
+rename-locked = Matches in locked sections, left alone: { $count }
+rename-more = … and { $count } more
+rename-replace-all = Replace all
+
+presentation-leave = Leave presentation
+presentation-hint = Read-only: pan with Ctrl+drag, zoom with the scroll wheel. Esc or F5 leaves.
+
+legend-title = Tags
+legend-empty = No nodes are tagged yet.
+
+what-if-title = What-if
+what-if-hint = Unchecked components are left out of the blocks they feed: a series block no longer depends on them, and a parallel block loses them as a redundant path.
+what-if-empty = The diagram has no components.
+what-if-restore-all = Restore all ({ $count })
+
+note-hint = Write a note…
+dormancy-title = Spare
+dormancy-hot = Hot
+dormancy-warm = Warm
+dormancy-cold = Cold
+dormancy-hint = How the spare on B ages while the primary on A runs: as fast (hot), at a fraction of the rate (warm), or not at all (cold).
+dormancy-factor = Aging rate while dormant, relative to running
+human-hep = Nominal HEP
+human-hep-hint = Human error probability: the chance the action goes wrong when it is called for.
+human-psfs = Performance-shaping factors ({ $count } set)
+human-psfs-hint = SPAR-H multipliers of the nominal HEP: above 1 makes an error likelier, below 1 less likely. With three or more above 1, the adjusted HEP is kept below 1.
+human-adjusted = Adjusted HEP: { $hep }
+psf-available-time = Available time
+psf-stress = Stress
+psf-complexity = Complexity
+psf-experience = Experience and training
+psf-procedures = Procedures
+psf-ergonomics = Ergonomics and HMI
+psf-fitness = Fitness for duty
+psf-work-processes = Work processes
+software-test-time = Test duration
+software-test-time-hint = How long testing ran, in time steps. The software is released at its end.
+software-discoveries = Defect discovery times
+software-discoveries-hint = When each defect was found, in time steps since testing began, one per line
+software-fit = Goel-Okumoto: a = { $a }, b = { $b }, { $remaining } defects left at release
+software-remaining = { $remaining } defects left
+software-error-line = Line { $line } is not a discovery time.
+software-error-no-data = Enter when each defect was found.
+software-error-beyond-test = Discoveries must fall within the test duration.
+software-error-no-growth = The discoveries don't thin out over the test, so they show no reliability growth.
+one-shot-success = Success on demand
+one-shot-success-hint = The probability the device works when it is used, once.
+one-shot-time-steps-hint = How far the device's own curve runs. Next to time-based blocks it holds over their horizon instead.
+petri-summary = { $places } places, { $transitions } transitions
+petri-works-while = Works while
+petri-trials = Trials
+petri-trials-hint = How many times the net is played out to estimate R(t) and A(t). More trials give smoother curves but take longer whenever the net changes.
+petri-places = Places ({ $count })
+petri-place = Place
+petri-tokens = Tokens
+petri-add-place = Add place
+petri-transitions = Transitions ({ $count })
+petri-transition = Transition
+petri-add-transition = Add transition
+petri-remove-transition = Remove transition
+petri-takes = Takes
+petri-gives = Gives
+petri-delay-exponential = Exponential
+petri-delay-deterministic = Fixed delay
+petri-delay-weibull = Weibull
+petri-error-delay = the delay needs positive, finite parameters.
+petri-error-no-place = Choose the place that tells whether the subsystem works.
+petri-error-no-trials = The net needs at least one trial.
+petri-error-loop = Transitions without delay keep firing in a loop.
+petri-example-up = Working
+petri-example-down = Failed
+petri-example-crew = Crew free
+petri-example-repairing = Under repair
+petri-example-fail = Fail
+petri-example-start = Start repair
+petri-example-repair = Repair
+
+example-series = Simple series
+example-series-note = A series system works only while every block works, so R = R₁ · R₂ · R₃ and the weakest block dominates.
+example-pump = Pump
+example-motor = Motor
+example-controller = Controller
+example-voting = 2-out-of-3 voting
+example-voting-note = The sensors vote: any two working sensors keep the system up. Each pair is a series block and the pairs are in parallel; because the pairs share sensors, the evaluation conditions on each shared sensor instead of treating the pairs as independent.
+example-sensor = Sensor
+example-standby = Standby generator
+example-standby-note = Mains power is backed up by a generator behind a transfer switch; all three are repaired. The generator is modelled as a hot standby that ages while idle, which is conservative.
+example-mains = Mains
+example-switch = Transfer switch
+example-generator = Generator
+example-bridge = Bridge network
+example-bridge-note = Link 3 bridges two parallel paths, so the network is neither series nor parallel. It is built from its four minimal paths, 1-4, 2-5, 1-3-5 and 2-3-4, in parallel; the shared links are conditioned on during evaluation.
+example-link = Link
+
+wizard-title = New project
+wizard-mission-hint = How long must the system work, and in what unit is time counted?
+wizard-target-hint = What reliability must the system reach by the end of the mission?
+wizard-target-at = at { $time } { $unit }
+wizard-components-hint = Roughly how many components does the system have? They start out in series, each sized so the chain just meets the target; rearrange them from there.
+wizard-components = Components
+wizard-replaces = Creating the project replaces the current diagram.
+wizard-back = Back
+wizard-next = Next
+wizard-create = Create
+wizard-component-label = Component { $number }
+wizard-system-label = System
+
+param-shape = Shape β
+param-scale = Scale η
+param-scale-hint = The age by which 63.2% of units have failed.
+param-entry-mttf = MTTF
+param-entry-rate = Characteristic rate λ = 1/η
+param-entry-mttf-hint = MTTF = η Γ(1 + 1/β), so η = MTTF / Γ(1 + 1/β)
+param-entry-rate-hint = η = 1/λ
+param-time-steps = Time steps
+param-duty-cycle = Duty cycle
+param-schedule = Schedule
+param-schedule-hint = Operate only during a window that repeats every period, so the component ages by operating time rather than calendar time.
+schedule-every = every
+schedule-from = from
+schedule-operating = Operating time in each period
+schedule-period = The period the window repeats with
+schedule-start = When the window opens within each period
+param-mttr = MTTR
+param-mttr-hint = Mean time to repair. Zero means the component is not repaired.
+param-repair = Repair
+repair-renewal = As good as new
+repair-minimal = As bad as old (minimal)
+param-hidden-failures = Hidden failures
+param-hidden-failures-hint = Failures go unnoticed until a periodic inspection finds them, as with relief valves and other protective devices. A failure found is repaired as good as new.
+inspection-every = every
+inspection-interval = Time between inspections
+inspection-effectiveness = The probability that an inspection finds a hidden failure
+param-environment-factor = Environment factor π_E
+param-derating = Temperature derating
+param-temperature = Operating temperature
+param-uncertainty = 90% interval
+param-uncertainty-clear = Forget the interval, so the plot shows no band for this component.
+param-failure-modes = Failure modes ({ $count })
+param-expressions = Expressions ({ $count })
+mode-name = Mode
+mode-primary = Primary
+mode-share = Share
+mode-share-hint = Share of the probability of having failed by the mission time
+mode-add = Add failure mode
+
+derating-none = None
+derating-arrhenius = Arrhenius
+derating-curve = Derating curve
+derating-activation-energy = Activation energy
+derating-reference = Reference temperature
+derating-add-point = Add point
+
+env-ground-benign = Ground, benign
+env-ground-fixed = Ground, fixed
+env-ground-mobile = Ground, mobile
+env-naval-sheltered = Naval, sheltered
+env-airborne-inhabited = Airborne, inhabited
+env-airborne-uninhabited = Airborne, uninhabited
+env-space-flight = Space flight
+
+keyboard-hint = Shift+A: add node · N: next node · C: connect · Enter: plot · Del: delete · Ctrl+V: paste node · Ctrl+P: commands · F5: present
+keyboard-select-one = Select exactly one node to connect.
+keyboard-connect-from = Connecting from { $node }: select the target node and press C again, or Esc to cancel.
+keyboard-connected = Connected { $from } to { $to }.
+keyboard-no-free-input = The target node has no free compatible input.
+keyboard-connect-cancelled = Connection cancelled.
+
+snippet-pasted = Pasted { $node }.
+snippet-not-json = The clipboard does not hold a JSON node snippet.
+snippet-no-version = The snippet has no schema version; copy the node again with Copy as JSON.
+snippet-unsupported-version = The snippet uses schema version { $version }, but this version of the app reads version { $supported }.
+snippet-invalid = The snippet is not a valid node: { $error }
+snippet-unknown-input = The snippet sets an input "{ $input }" that this kind of node does not have.
+
+project-filter = Block project
+project-filter-json = Block project (JSON)
+project-filter-ron = Block project (RON)
+project-filter-yaml = Block project (YAML)
+project-filter-binary = Block project (binary)
+project-not-json = The file is not valid JSON: { $error }
+project-not-ron = The file is not valid RON: { $error }
+project-not-yaml = The file is not valid YAML: { $error }
+project-not-text = The file is not text; binary projects end in .blk.
+project-not-binary = The file is not a binary project.
+project-no-version = The file has no project version; it may not be a project file.
+project-unsupported-version = The project uses file version { $version }, but this version of the app reads version { $supported }.
+project-newer-version = The project was saved by a newer version of the app, with file version { $version }; this version reads up to { $supported }.
+project-migration-failed = The project could not be upgraded from file version { $from } to { $to }: { $error }
+project-ron-older = RON projects are only read at file version { $supported }, and this one has version { $version }. Open it in the version of the app that saved it and save it as JSON.
+project-binary-older = Binary projects are only read at file version { $supported }, and this one has version { $version }. Open it in the version of the app that saved it and save it as JSON.
+project-invalid = The project file is damaged or incomplete: { $error }
+project-invalid-field = The project's { $field } can't be read: { $error }
+project-damaged = The project file doesn't match its checksum, so it may have been cut short or damaged. Check the model before saving over the file.
+dot-unterminated = The DOT file ends inside a comment, string or graph.
+dot-unexpected = The DOT file contains an unexpected “{ $character }”.
+dot-no-graph = The file doesn't start with a DOT graph.
+dot-edge-target = An edge in the DOT file has no node to point to.
+dot-empty = The DOT graph has no nodes.
+distribution-invalid = The file isn't a system distribution: { $error }
+distribution-unsupported-version = The distribution has version { $version }, but only version { $supported } can be read.
+distribution-no-active-node = Set a node active to export its distribution.
+session-unreadable = The last session could not be restored and was kept aside: { $error }
+session-damaged = The last session doesn't match its checksum and may be incomplete.
+
+config-invalid = { $file } could not be read, so its defaults were ignored: { $error }
+config-invalid-value = The default { $key } = "{ $value }" is not valid, so the configured defaults were ignored.
+
+log-title = Log
+log-app-only = This app only
+log-copy = Copy
+log-clear = Clear
+log-unseen = ⚠ { $count } new warnings
+
+toasts-title = Notifications
+toasts-none = No notifications yet.
+toasts-clear = Clear
+toast-saved = Project saved.
+toast-imported = Imported { $count } nodes.
+toast-distribution-imported = Added { $name } as an empirical node.
+toast-library-saved = Saved { $name } to the library.
+toast-library-pushed = Updated { $count } nodes from the library.
+toast-simulation-done = Simulation finished after { $trials } trials.
+toast-simulation-failed = Simulation failed: { $error }
+toast-results-exported = Results exported.
+toast-results-drifted = Locked results drifted beyond their tolerance.
+
+journal-title = Recover unsaved edits
+journal-message = The app didn't close properly last time. { $edits } edits made since the project was last saved can be replayed.
+journal-recover = Recover
+journal-discard = Discard
+
+palette-title = Command palette
+palette-hint = Type a command…
+palette-no-match = No matching command.
+palette-add-node = Add node: { $node }
+palette-example = Open example: { $example }
+
+error-evaluation = Evaluation failed
+health-not-finite = { $node }: R(t) is undefined (NaN or infinite) from t = { $time }, so it isn't plotted.
+health-increasing = { $node }: R(t) rises at t = { $time }, so its failure probability falls and it isn't plotted.
+health-parameters = Check { $parameters }.
+error-cycle = The diagram contains a cycle.
+error-note = Notes are annotations and have no reliability.
+error-empty-curve = No curve has been attached to this node yet.
+error-chunked-measure = Only reliability can be evaluated in chunks.
+error-unconnected-input = Input { $input } is not connected.
+error-expected-reliability = Expected a reliability curve.
+error-expected-parameters = Expected component parameters.
+error-all-inputs-disabled = Every input of this block is disabled.
+error-expected-dormancy = Expected a standby dormancy.
+error-expected-human-action = Expected a human action.
+error-expected-software-growth = Expected defect discovery data.
+error-expected-one-shot = Expected a one-shot device.
+error-expected-petri-net = Expected a Petri net.
+error-too-few-members = More votes are needed than the { $members } members left.
+error-not-component = The node is not a component.
+error-time-out-of-range = Time { $time } is beyond the evaluated horizon.
+error-horizon-mismatch = This input ends at step { $shortest }, but another runs to { $longest }. Extend or resample the shorter curves.
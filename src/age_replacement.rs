@@ -0,0 +1,177 @@
+//! The cost-optimal age at which to replace a component before it fails.
+//!
+//! Replacing at age T or at failure, whichever comes first, costs on average
+//! C(T) = (C_p R(T) + C_f F(T)) / ∫₀ᵀ R(t) dt per unit time, the cost of a
+//! cycle over its mean length.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{HLine, Legend, Line, Plot, PlotPoints, VLine};
+
+use crate::eval;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, NodeTemplate};
+
+/// C(T) at every replacement age T from one time step up to the end of
+/// `curve`, indexed by T − 1.
+pub fn cost_rates(curve: &[f64], preventive: f64, corrective: f64) -> Vec<f64> {
+    let mut up = 0.0;
+    curve
+        .windows(2)
+        .map(|w| {
+            up += 0.5 * (w[0] + w[1]);
+            (preventive * w[1] + corrective * (1.0 - w[1])) / up
+        })
+        .collect()
+}
+
+/// The replacement age with the lowest cost rate, and that rate. `None` when
+/// the lowest is at the end of the curve, where running to failure is as good.
+pub fn optimum(rates: &[f64]) -> Option<(usize, f64)> {
+    let (i, rate) = rates
+        .iter()
+        .copied()
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+    (i + 1 < rates.len()).then_some((i + 1, rate))
+}
+
+pub struct AgeReplacement {
+    component: Option<NodeId>,
+    preventive: f64,
+    corrective: f64,
+}
+
+impl Default for AgeReplacement {
+    fn default() -> Self {
+        Self {
+            component: None,
+            preventive: 200.0,
+            corrective: 1_000.0,
+        }
+    }
+}
+
+impl AgeReplacement {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("age-replacement-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                if self.component.is_none() {
+                    self.component = state
+                        .active_node
+                        .filter(|node| graph[*node].user_data.template == NodeTemplate::Component);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr("goal-seek-component"));
+                    nodes::component_combo(
+                        ui,
+                        "age replacement component",
+                        graph,
+                        &mut self.component,
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("maintenance-preventive-cost"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.preventive)
+                            .speed(1.0)
+                            .clamp_range(0.0..=f64::MAX),
+                    );
+                    ui.label(tr("maintenance-corrective-cost"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.corrective)
+                            .speed(1.0)
+                            .clamp_range(0.0..=f64::MAX),
+                    );
+                });
+                let Some(parameters) = self
+                    .component
+                    .and_then(|node| nodes::component_parameters(graph, node))
+                else {
+                    return;
+                };
+                let curve = match eval::component_reliability(&parameters, &state.mission_profile) {
+                    Ok(curve) => curve,
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                        return;
+                    }
+                };
+                let rates = cost_rates(&curve, self.preventive, self.corrective);
+                let Some(run_to_failure) = rates.last().copied() else {
+                    return;
+                };
+                let unit = state.settings.time_unit.symbol();
+                let best = optimum(&rates);
+                match best {
+                    Some((age, rate)) => {
+                        ui.label(tr_with(
+                            "age-replacement-optimum",
+                            &[
+                                ("age", &age),
+                                ("unit", &unit),
+                                ("rate", &format!("{rate:.4}")),
+                            ],
+                        ));
+                        ui.label(tr_with(
+                            "age-replacement-saving",
+                            &[(
+                                "percent",
+                                &format!("{:.1}", 100.0 * (1.0 - rate / run_to_failure)),
+                            )],
+                        ));
+                    }
+                    None => {
+                        ui.label(tr("age-replacement-none"));
+                    }
+                }
+
+                let theme = state.settings.theme.catppuccin();
+                Plot::new("age replacement")
+                    .height(250.0)
+                    .include_y(0.0)
+                    .x_axis_label(format!("T [{unit}]"))
+                    .y_axis_label(tr_with("age-replacement-axis", &[("unit", &unit)]))
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        // The first steps cost far more than the rest, so the
+                        // curve starts where it has come down to twice the
+                        // run-to-failure rate.
+                        let start = rates
+                            .iter()
+                            .position(|rate| *rate <= 2.0 * run_to_failure)
+                            .unwrap_or(0);
+                        let points: PlotPoints = rates[start..]
+                            .iter()
+                            .enumerate()
+                            .map(|(i, rate)| [(start + i + 1) as f64, *rate])
+                            .collect();
+                        plot_ui.line(
+                            Line::new(points)
+                                .color(theme.blue)
+                                .name(tr("age-replacement-cost-rate")),
+                        );
+                        plot_ui.hline(
+                            HLine::new(run_to_failure)
+                                .color(theme.overlay1)
+                                .name(tr("age-replacement-run-to-failure")),
+                        );
+                        if let Some((age, _)) = best {
+                            plot_ui.vline(
+                                VLine::new(age as f64)
+                                    .color(theme.green)
+                                    .name(tr("age-replacement-optimal-age")),
+                            );
+                        }
+                    });
+                ui.label(tr("age-replacement-hint"));
+            });
+    }
+}
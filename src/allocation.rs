@@ -0,0 +1,297 @@
+//! Top-down apportionment of the system requirement to its components.
+//!
+//! Targets are pushed down the diagram from the system node. A series node
+//! splits its target R as R_i = R^w_i and a parallel node splits its target
+//! unreliability Q as Q_i = Q^v_i, with the weights summing to one. The
+//! methods differ only in how the weights are chosen.
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::eval::{evaluate_all, Options, OutputsCache};
+use crate::i18n::tr;
+use crate::nodes::{self, Graph, GraphState, NodeTemplate};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AllocationMethod {
+    /// Every branch gets the same share.
+    #[default]
+    Equal,
+    /// Shares proportional to each branch's current unreliability, the
+    /// generalization of ARINC's failure-rate weighting.
+    Arinc,
+    /// Shares proportional to the feasibility ratings of the components in
+    /// each branch: harder-to-improve components are asked for less.
+    Feasibility,
+}
+
+impl AllocationMethod {
+    pub const ALL: [AllocationMethod; 3] = [
+        AllocationMethod::Equal,
+        AllocationMethod::Arinc,
+        AllocationMethod::Feasibility,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            AllocationMethod::Equal => tr("allocation-equal"),
+            AllocationMethod::Arinc => tr("allocation-arinc"),
+            AllocationMethod::Feasibility => tr("allocation-feasibility"),
+        }
+    }
+}
+
+/// Allocates `target` reliability at `time` from `system` down to every
+/// component it depends on, weighing by the curves in `outputs` where the
+/// method asks for current reliabilities. Components reached through several
+/// paths keep the strictest target.
+pub fn allocate(
+    graph: &Graph,
+    outputs: &OutputsCache,
+    system: NodeId,
+    target: f64,
+    time: usize,
+    method: AllocationMethod,
+) -> HashMap<NodeId, f64> {
+    let mut allocator = Allocator {
+        graph,
+        outputs,
+        time,
+        method,
+        targets: HashMap::new(),
+        in_progress: HashSet::new(),
+    };
+    allocator.allocate(system, target);
+    allocator.targets
+}
+
+struct Allocator<'a> {
+    graph: &'a Graph,
+    outputs: &'a OutputsCache,
+    time: usize,
+    method: AllocationMethod,
+    targets: HashMap<NodeId, f64>,
+    in_progress: HashSet<NodeId>,
+}
+
+impl Allocator<'_> {
+    fn allocate(&mut self, node_id: NodeId, target: f64) {
+        if !self.in_progress.insert(node_id) {
+            return;
+        }
+        let strictest = self
+            .targets
+            .get(&node_id)
+            .map_or(target, |existing| existing.max(target));
+        self.targets.insert(node_id, strictest);
+
+        let node = &self.graph[node_id];
+        let children: Vec<NodeId> = node
+            .input_ids()
+            .filter_map(|input| self.graph.connection(input))
+            .map(|output| self.graph[output].node)
+            .collect();
+
+        match node.user_data.template {
+            // Needing every member is the strictest a voting block can be.
+            NodeTemplate::Series | NodeTemplate::Voting => {
+                let weights = self.weights(&children, false);
+                for (child, weight) in children.iter().zip(weights) {
+                    self.allocate(*child, strictest.powf(weight));
+                }
+            }
+            // A standby pair is held to the targets of the hot pair it is
+            // at least as reliable as.
+            NodeTemplate::Parallel | NodeTemplate::Standby => {
+                let weights = self.weights(&children, true);
+                for (child, weight) in children.iter().zip(weights) {
+                    self.allocate(*child, 1.0 - (1.0 - strictest).powf(weight));
+                }
+            }
+            NodeTemplate::Component
+            | NodeTemplate::Empirical
+            | NodeTemplate::Human
+            | NodeTemplate::Software
+            | NodeTemplate::OneShot
+            | NodeTemplate::PetriNet
+            | NodeTemplate::Note => {}
+        }
+        self.in_progress.remove(&node_id);
+    }
+
+    /// Normalized shares of the parent's budget for each child. For parallel
+    /// nodes a larger share means a stricter target, so feasibility weights
+    /// are inverted there.
+    fn weights(&self, children: &[NodeId], parallel: bool) -> Vec<f64> {
+        let raw: Vec<f64> = children
+            .iter()
+            .map(|child| match self.method {
+                AllocationMethod::Equal => 1.0,
+                AllocationMethod::Arinc => {
+                    let r = self.current_reliability(*child).unwrap_or(1.0);
+                    if parallel {
+                        -(1.0 - r).max(f64::MIN_POSITIVE).ln()
+                    } else {
+                        -r.max(f64::MIN_POSITIVE).ln()
+                    }
+                }
+                AllocationMethod::Feasibility => {
+                    let rating = self.feasibility(*child, &mut HashSet::new());
+                    if parallel {
+                        1.0 / rating
+                    } else {
+                        rating
+                    }
+                }
+            })
+            .collect();
+        let total: f64 = raw.iter().sum();
+        if total > 0.0 && total.is_finite() {
+            raw.iter().map(|w| w / total).collect()
+        } else {
+            vec![1.0 / children.len() as f64; children.len()]
+        }
+    }
+
+    fn current_reliability(&self, node_id: NodeId) -> Option<f64> {
+        let output = self.graph[node_id].get_output("reliability").ok()?;
+        let curve = self
+            .outputs
+            .get(&output)?
+            .clone()
+            .try_to_reliability()
+            .ok()?;
+        curve.get(self.time).copied()
+    }
+
+    /// The summed feasibility ratings of the components a node depends on.
+    fn feasibility(&self, node_id: NodeId, visited: &mut HashSet<NodeId>) -> f64 {
+        if !visited.insert(node_id) {
+            return 0.0;
+        }
+        let node = &self.graph[node_id];
+        if matches!(
+            node.user_data.template,
+            NodeTemplate::Component
+                | NodeTemplate::Empirical
+                | NodeTemplate::Human
+                | NodeTemplate::Software
+                | NodeTemplate::OneShot
+                | NodeTemplate::PetriNet
+        ) {
+            return node.user_data.feasibility;
+        }
+        node.input_ids()
+            .filter_map(|input| self.graph.connection(input))
+            .map(|output| self.feasibility(self.graph[output].node, visited))
+            .sum()
+    }
+}
+
+/// The allocation window: picks the method, runs the allocation against the
+/// requirement and lists the resulting component targets.
+pub fn window(ctx: &egui::Context, open: &mut bool, graph: &mut Graph, state: &mut GraphState) {
+    egui::Window::new(tr("allocation-title"))
+        .open(open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("allocation-method"));
+                egui::ComboBox::from_id_source("allocation method")
+                    .selected_text(state.allocation_method.name())
+                    .show_ui(ui, |ui| {
+                        for method in AllocationMethod::ALL {
+                            ui.selectable_value(
+                                &mut state.allocation_method,
+                                method,
+                                method.name(),
+                            );
+                        }
+                    });
+            });
+
+            let requirement = state.requirement;
+            let Some(system) = state.active_node else {
+                ui.label(tr("allocation-no-system"));
+                state.allocated_targets.clear();
+                return;
+            };
+            if !requirement.enabled {
+                ui.label(tr("allocation-no-requirement"));
+                state.allocated_targets.clear();
+                return;
+            }
+
+            let outputs = evaluate_all(graph, &Options::of(state));
+            state.allocated_targets = allocate(
+                graph,
+                &outputs,
+                system,
+                requirement.reliability,
+                requirement.time,
+                state.allocation_method,
+            );
+
+            let mut components: Vec<NodeId> = state
+                .allocated_targets
+                .keys()
+                .copied()
+                .filter(|node| graph[*node].user_data.template == NodeTemplate::Component)
+                .collect();
+            components.sort_by_key(|node| graph[*node].label.clone());
+
+            egui::Grid::new("allocation")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong(tr("allocation-component"));
+                    ui.strong("π_E")
+                        .on_hover_text(tr("param-environment-factor"));
+                    ui.strong(tr("allocation-feasibility-rating"));
+                    ui.strong(tr("allocation-current"));
+                    ui.strong(tr("allocation-target"));
+                    ui.end_row();
+
+                    for node in components {
+                        ui.label(&graph[node].label);
+                        match nodes::component_parameters(graph, node) {
+                            Some(parameters) => {
+                                ui.label(format!("{}", parameters.environment_factor))
+                            }
+                            None => ui.label("—"),
+                        };
+                        ui.add_enabled(
+                            state.allocation_method == AllocationMethod::Feasibility,
+                            egui::DragValue::new(&mut graph[node].user_data.feasibility)
+                                .speed(0.1)
+                                .clamp_range(1.0..=10.0),
+                        );
+                        let current = graph[node]
+                            .get_output("reliability")
+                            .ok()
+                            .and_then(|output| outputs.get(&output))
+                            .and_then(|value| value.clone().try_to_reliability().ok())
+                            .and_then(|curve| curve.get(requirement.time).copied());
+                        match current {
+                            Some(r) => ui.label(format!("{r:.6}")),
+                            None => ui.label("—"),
+                        };
+                        ui.label(format!("{:.6}", state.allocated_targets[&node]));
+                        ui.end_row();
+                    }
+                });
+            if ui
+                .button(tr("allocation-adopt"))
+                .on_hover_text(tr("allocation-adopt-hint"))
+                .clicked()
+            {
+                for (node, target) in &state.allocated_targets {
+                    graph[*node].user_data.target = Some(*target);
+                }
+            }
+        });
+    if !*open {
+        state.allocated_targets.clear();
+    }
+}
@@ -1,43 +1,1647 @@
-use eframe::{App, egui};
+use std::sync::Arc;
+use std::time::Instant;
 
-pub struct MyApp {}
+use eframe::egui::{self, Key, Modifiers};
+use eframe::App;
+use egui_node_graph2::{AnyParameterId, NodeFinder, NodeId, NodeResponse};
+
+use crate::age_replacement::AgeReplacement;
+use crate::allocation;
+use crate::arrange::Arrangement;
+use crate::audit::Audit;
+use crate::availability::Availability;
+use crate::bands::Bands;
+use crate::bayes::Bayes;
+use crate::budget;
+use crate::calendar::Calendar;
+use crate::canvas;
+use crate::card::SummaryCard;
+use crate::checkpoints;
+use crate::compare::Compare;
+use crate::competing_risks::CompetingRisks;
+use crate::config;
+use crate::criticality::Criticality;
+use crate::distribution;
+use crate::doe::Study;
+use crate::dot;
+use crate::equivalent::Equivalent;
+use crate::eval::{
+    self, evaluate_all_into, evaluate_curve, evaluate_node, AllCurves, OutputsCache,
+};
+use crate::event_tree::EventTree;
+use crate::examples::Example;
+use crate::feed::Feed;
+use crate::field_data::FieldData;
+use crate::fixture;
+use crate::fleet::Fleet;
+use crate::frames;
+use crate::frequency_duration::FrequencyDuration;
+use crate::goal_seek::GoalSeek;
+use crate::health;
+use crate::hotspots::{self, Hotspots};
+use crate::i18n::{self, tr, tr_with, Language};
+use crate::journal::Journal;
+use crate::kaplan_meier::KaplanMeier;
+use crate::keyboard::KeyboardEditing;
+use crate::library::Library;
+use crate::log::{self, LogPanel};
+use crate::metrics::Metrics;
+use crate::mirrors;
+use crate::nodes::{
+    self, EditorState, FinderTemplates, GraphState, NodeTemplate, Response, Severity, ValueType,
+};
+use crate::palette::{Command, Palette, Tool};
+use crate::pareto::Pareto;
+use crate::plot::{reliability_plot, EnlargedPlot, PoppedOutPlot};
+use crate::project;
+use crate::quantile::Quantile;
+use crate::redundancy::Redundancy;
+use crate::regression::Regression;
+use crate::rename::FindReplace;
+use crate::report::Report;
+use crate::results::Summary;
+use crate::robustness::Robustness;
+use crate::rocof::Rocof;
+use crate::scaling::Scaling;
+use crate::settings::{Settings, WireStyle};
+use crate::sil::Sil;
+use crate::simulate::MonteCarlo;
+use crate::statistics;
+use crate::sweep::Sweep;
+use crate::tags;
+use crate::toasts::{self, Toasts};
+use crate::topology::{self, StructureFormat};
+use crate::validation;
+use crate::variables;
+use crate::weak_link::{self, WeakLink};
+use crate::what_if;
+use crate::wires;
+use crate::wizard::Wizard;
+
+/// The window title, which also names the directory the app keeps its data in.
+pub const APP_NAME: &str = "Block: Reliability Block Programming";
+
+const LANGUAGE_KEY: &str = "language";
+const PERSISTENCE_KEY: &str = "egui_node_graph";
+/// The diagram being edited, saved as a project so that restoring it goes
+/// through the same checks and upgrades as opening a file.
+const SESSION_KEY: &str = "session";
+/// A session that couldn't be restored, kept so that it isn't overwritten.
+const UNREADABLE_SESSION_KEY: &str = "session_unreadable";
+
+/// How much larger the graph's text is drawn while presenting.
+const PRESENTATION_TEXT_SCALE: f32 = 1.4;
+/// The evaluation cost, in curve values, above which dragging a value
+/// evaluates the model coarsely.
+const FAST_PREVIEW_COST: usize = 1_000_000;
+/// The templates the quick-add toolbar has a button for.
+const QUICK_ADD: [NodeTemplate; 3] = [
+    NodeTemplate::Component,
+    NodeTemplate::Series,
+    NodeTemplate::Parallel,
+];
+
+pub struct MyApp {
+    state: EditorState,
+    user_state: GraphState,
+    keyboard: KeyboardEditing,
+    library: Library,
+    library_open: bool,
+    find_replace: FindReplace,
+    find_replace_open: bool,
+    palette: Palette,
+    /// Where the graph editor was drawn last frame, for placing new nodes.
+    graph_rect: egui::Rect,
+    /// The port of a wire dropped on empty canvas, joined to the node picked
+    /// in the node finder that opens there.
+    dropped_wire: Option<AnyParameterId>,
+    wizard: Wizard,
+    wizard_open: bool,
+    /// Why the last project file couldn't be opened, saved or imported.
+    file_error: Option<String>,
+    /// The saved session, if it couldn't be restored at startup.
+    unreadable_session: Option<String>,
+    settings_open: bool,
+    legend_open: bool,
+    quick_add_open: bool,
+    what_if_open: bool,
+    log_panel: LogPanel,
+    log_open: bool,
+    /// The evaluation problem last reported, so it is reported once.
+    logged_problem: Option<String>,
+    toasts: Toasts,
+    toasts_open: bool,
+    allocation_open: bool,
+    budget_open: bool,
+    goal_seek: GoalSeek,
+    goal_seek_open: bool,
+    sweep: Sweep,
+    sweep_open: bool,
+    doe: Study,
+    doe_open: bool,
+    robustness: Robustness,
+    robustness_open: bool,
+    monte_carlo: MonteCarlo,
+    monte_carlo_open: bool,
+    kaplan_meier: KaplanMeier,
+    kaplan_meier_open: bool,
+    field_data: FieldData,
+    field_data_open: bool,
+    bayes: Bayes,
+    bayes_open: bool,
+    availability: Availability,
+    availability_open: bool,
+    profile_open: bool,
+    variables_open: bool,
+    scaling: Scaling,
+    scaling_open: bool,
+    compare: Compare,
+    compare_open: bool,
+    statistics_open: bool,
+    feed: Feed,
+    feed_open: bool,
+    rocof: Rocof,
+    rocof_open: bool,
+    age_replacement: AgeReplacement,
+    age_replacement_open: bool,
+    calendar: Calendar,
+    calendar_open: bool,
+    redundancy: Redundancy,
+    redundancy_open: bool,
+    event_tree: EventTree,
+    event_tree_open: bool,
+    sil: Sil,
+    sil_open: bool,
+    fleet: Fleet,
+    fleet_open: bool,
+    competing_risks: CompetingRisks,
+    competing_risks_open: bool,
+    criticality: Criticality,
+    criticality_open: bool,
+    pareto: Pareto,
+    pareto_open: bool,
+    report: Report,
+    report_open: bool,
+    enlarged_plot: EnlargedPlot,
+    plot_enlarged: bool,
+    popped_out_plot: PoppedOutPlot,
+    /// Whether the plot is shown in a window of its own rather than above
+    /// the graph.
+    plot_popped_out: bool,
+    quantile: Quantile,
+    equivalent: Equivalent,
+    frequency_duration: FrequencyDuration,
+    regression: Regression,
+    /// The active node's curves and the edge values, kept from frame to frame
+    /// so evaluating them reuses the same buffers.
+    curves: OutputsCache,
+    all_curves: AllCurves,
+    /// The active node's weak link, refreshed every frame while it is shown.
+    weak_link: Option<WeakLink>,
+    /// Each node's evaluation time, measured every frame while the heatmap
+    /// is shown.
+    hotspots: Hotspots,
+    summary: Summary,
+    summary_card: SummaryCard,
+    /// The uncertainty band of the active node's curve.
+    bands: Bands,
+    /// Set when a project is opened, to size the panels as it was saved.
+    restore_panels: bool,
+    journal: Journal,
+    metrics: Metrics,
+}
 
 impl Default for MyApp {
     fn default() -> Self {
-        Self {}
+        Self {
+            state: EditorState::default(),
+            user_state: GraphState::default(),
+            keyboard: KeyboardEditing::default(),
+            library: Library::default(),
+            library_open: false,
+            find_replace: FindReplace::default(),
+            find_replace_open: false,
+            palette: Palette::default(),
+            graph_rect: egui::Rect::ZERO,
+            dropped_wire: None,
+            file_error: None,
+            unreadable_session: None,
+            wizard: Wizard::default(),
+            wizard_open: false,
+            settings_open: false,
+            legend_open: true,
+            quick_add_open: true,
+            what_if_open: false,
+            log_panel: LogPanel::default(),
+            log_open: false,
+            logged_problem: None,
+            toasts: Toasts::default(),
+            toasts_open: false,
+            allocation_open: false,
+            budget_open: false,
+            goal_seek: GoalSeek::default(),
+            goal_seek_open: false,
+            sweep: Sweep::default(),
+            sweep_open: false,
+            doe: Study::default(),
+            doe_open: false,
+            robustness: Robustness::default(),
+            robustness_open: false,
+            monte_carlo: MonteCarlo::default(),
+            monte_carlo_open: false,
+            kaplan_meier: KaplanMeier::default(),
+            kaplan_meier_open: false,
+            field_data: FieldData::default(),
+            field_data_open: false,
+            bayes: Bayes::default(),
+            bayes_open: false,
+            availability: Availability::default(),
+            availability_open: false,
+            profile_open: false,
+            variables_open: false,
+            scaling: Scaling::default(),
+            scaling_open: false,
+            compare: Compare::default(),
+            compare_open: false,
+            statistics_open: false,
+            feed: Feed::default(),
+            feed_open: false,
+            rocof: Rocof::default(),
+            rocof_open: false,
+            age_replacement: AgeReplacement::default(),
+            age_replacement_open: false,
+            calendar: Calendar::default(),
+            calendar_open: false,
+            redundancy: Redundancy::default(),
+            redundancy_open: false,
+            event_tree: EventTree::default(),
+            event_tree_open: false,
+            sil: Sil::default(),
+            sil_open: false,
+            fleet: Fleet::default(),
+            fleet_open: false,
+            competing_risks: CompetingRisks::default(),
+            competing_risks_open: false,
+            criticality: Criticality::default(),
+            criticality_open: false,
+            pareto: Pareto::default(),
+            pareto_open: false,
+            report: Report::default(),
+            report_open: false,
+            enlarged_plot: EnlargedPlot::default(),
+            plot_enlarged: false,
+            popped_out_plot: PoppedOutPlot::default(),
+            plot_popped_out: false,
+            quantile: Quantile::default(),
+            equivalent: Equivalent::default(),
+            frequency_duration: FrequencyDuration::default(),
+            regression: Regression::default(),
+            curves: OutputsCache::new(),
+            all_curves: AllCurves::default(),
+            weak_link: None,
+            hotspots: Hotspots::default(),
+            summary: Summary::default(),
+            summary_card: SummaryCard::default(),
+            bands: Bands::default(),
+            restore_panels: false,
+            journal: Journal::default(),
+            metrics: Metrics::default(),
+        }
+    }
+}
+
+impl MyApp {
+    /// The app as it was left, with random studies seeded by `seed` in place
+    /// of the saved setting if one is given.
+    pub fn new(cc: &eframe::CreationContext<'_>, seed: Option<u64>) -> Self {
+        if let Some(language) = cc
+            .storage
+            .and_then(|storage| storage.get_string(LANGUAGE_KEY))
+            .and_then(|tag| Language::from_tag(&tag))
+        {
+            i18n::set_language(language);
+        }
+        let mut settings: Settings = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, Settings::KEY))
+            .unwrap_or_else(|| config::get().settings());
+        settings.apply(&cc.egui_ctx);
+        if let Some(seed) = seed {
+            settings.seed = seed;
+        }
+        let session = cc.storage.filter(|_| settings.restore_session);
+        let mut file_error = config::error().map(str::to_owned);
+        let mut unreadable_session = None;
+        let (state, user_state) = match session.and_then(|storage| storage.get_string(SESSION_KEY))
+        {
+            Some(text) => match project::from_json(&text) {
+                Ok(project) => {
+                    file_error = project.damaged.then(|| tr("session-damaged"));
+                    (project.editor, project.state)
+                }
+                Err(err) => {
+                    file_error = Some(tr_with("session-unreadable", &[("error", &err)]));
+                    unreadable_session = Some(text);
+                    (Default::default(), config::get().graph_state())
+                }
+            },
+            // Sessions from before they were saved as projects.
+            None => (
+                session
+                    .and_then(|storage| eframe::get_value(storage, PERSISTENCE_KEY))
+                    .unwrap_or_default(),
+                session
+                    .and_then(|storage| eframe::get_value(storage, GraphState::KEY))
+                    .unwrap_or_else(|| config::get().graph_state()),
+            ),
+        };
+        Self {
+            state,
+            user_state: GraphState {
+                settings,
+                ..user_state
+            },
+            file_error,
+            unreadable_session,
+            journal: Journal::open(),
+            ..Default::default()
+        }
+    }
+}
+
+impl MyApp {
+    fn tool_open(&mut self, tool: Tool) -> &mut bool {
+        match tool {
+            Tool::Allocation => &mut self.allocation_open,
+            Tool::Budget => &mut self.budget_open,
+            Tool::GoalSeek => &mut self.goal_seek_open,
+            Tool::Sweep => &mut self.sweep_open,
+            Tool::Doe => &mut self.doe_open,
+            Tool::Robustness => &mut self.robustness_open,
+            Tool::Simulation => &mut self.monte_carlo_open,
+            Tool::KaplanMeier => &mut self.kaplan_meier_open,
+            Tool::FieldData => &mut self.field_data_open,
+            Tool::Bayes => &mut self.bayes_open,
+            Tool::Availability => &mut self.availability_open,
+            Tool::Rocof => &mut self.rocof_open,
+            Tool::AgeReplacement => &mut self.age_replacement_open,
+            Tool::Calendar => &mut self.calendar_open,
+            Tool::Redundancy => &mut self.redundancy_open,
+            Tool::EventTree => &mut self.event_tree_open,
+            Tool::Sil => &mut self.sil_open,
+            Tool::Fleet => &mut self.fleet_open,
+            Tool::CompetingRisks => &mut self.competing_risks_open,
+            Tool::Criticality => &mut self.criticality_open,
+            Tool::Pareto => &mut self.pareto_open,
+            Tool::Profile => &mut self.profile_open,
+            Tool::Variables => &mut self.variables_open,
+            Tool::Scaling => &mut self.scaling_open,
+            Tool::Compare => &mut self.compare_open,
+            Tool::Statistics => &mut self.statistics_open,
+            Tool::Feed => &mut self.feed_open,
+        }
+    }
+
+    /// Puts back the locked nodes the graph editor just moved by `delta`. It
+    /// moves the whole selection when a selected node is dragged, so dragging
+    /// a locked node keeps all of them in place, as does presenting. Nodes in
+    /// locked sections count as locked.
+    fn undo_locked_move(&mut self, dragged: NodeId, delta: egui::Vec2) {
+        let graph = &self.state.graph;
+        let locked = |node: NodeId| {
+            graph[node].user_data.locked || self.user_state.read_only.contains_key(&node)
+        };
+        let moved = if self.state.selected_nodes.contains(&dragged) {
+            self.state.selected_nodes.clone()
+        } else {
+            vec![dragged]
+        };
+        let dragged_locked = self.user_state.presenting || locked(dragged);
+        for node in moved {
+            if dragged_locked || locked(node) {
+                self.state.node_positions[node] -= delta;
+            }
+        }
+    }
+
+    /// Pans the graph editor to center `node`, unless it is already in view.
+    fn show_node(&mut self, node: NodeId) {
+        let Some(position) = self.state.node_positions.get(node).copied() else {
+            return;
+        };
+        let rect = self.user_state.layout.node_rect(node);
+        if rect.is_some_and(|rect| self.graph_rect.contains_rect(rect)) {
+            return;
+        }
+        let half_size = rect.map_or(egui::Vec2::ZERO, |rect| rect.size() / 2.0);
+        self.state.pan_zoom.pan = self.graph_rect.size() / 2.0 - position.to_vec2() - half_size;
+    }
+
+    /// Runs a command picked from a menu or the command palette.
+    fn run(&mut self, command: Command) {
+        match command {
+            Command::NewProject => self.wizard_open = true,
+            Command::OpenProject => match project::open() {
+                Ok(Some(project)) => {
+                    self.state = project.editor;
+                    self.user_state = GraphState {
+                        settings: std::mem::take(&mut self.user_state.settings),
+                        ..project.state
+                    };
+                    self.restore_panels = true;
+                    self.file_error = project.damaged.then(|| tr("project-damaged"));
+                    self.journal.saved();
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!("opening a project failed: {err}");
+                    toasts::error(err.to_string());
+                    self.file_error = Some(err.to_string());
+                }
+            },
+            Command::SaveProject => match project::save_as(&self.state, &self.user_state) {
+                Ok(saved) => {
+                    if saved {
+                        self.journal.saved();
+                        toasts::success(tr("toast-saved"));
+                    }
+                    self.file_error = None;
+                }
+                Err(err) => {
+                    tracing::warn!("saving the project failed: {err}");
+                    toasts::error(err.to_string());
+                    self.file_error = Some(err.to_string());
+                }
+            },
+            Command::ImportModel => match project::open() {
+                Ok(Some(project)) => {
+                    self.file_error = project.damaged.then(|| tr("project-damaged"));
+                    let imported = project::merge(&mut self.state, project.editor);
+                    tracing::info!("imported {} nodes", imported.len());
+                    toasts::success(tr_with("toast-imported", &[("count", &imported.len())]));
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!("importing a model failed: {err}");
+                    toasts::error(err.to_string());
+                    self.file_error = Some(err.to_string());
+                }
+            },
+            Command::ImportDot => match dot::open(&mut self.user_state) {
+                Ok(Some(editor)) => {
+                    let imported = project::merge(&mut self.state, editor);
+                    tracing::info!("imported {} nodes from DOT", imported.len());
+                    toasts::success(tr_with("toast-imported", &[("count", &imported.len())]));
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!("importing a DOT file failed: {err}");
+                    toasts::error(err.to_string());
+                    self.file_error = Some(err.to_string());
+                }
+            },
+            Command::ImportDistribution => match distribution::open() {
+                Ok(Some(distribution)) => {
+                    let node = distribution::add_node(
+                        &mut self.state,
+                        &mut self.user_state,
+                        &distribution,
+                        self.graph_rect.center(),
+                        self.graph_rect,
+                    );
+                    self.state.selected_nodes = vec![node];
+                    toasts::success(tr_with(
+                        "toast-distribution-imported",
+                        &[("name", &distribution.name)],
+                    ));
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!("importing a distribution failed: {err}");
+                    toasts::error(err.to_string());
+                    self.file_error = Some(err.to_string());
+                }
+            },
+            Command::ExportStructure(format) => {
+                if let Err(err) = topology::export(
+                    &self.state.graph,
+                    format,
+                    &Audit::new(&self.state.graph, &self.user_state),
+                ) {
+                    tracing::warn!("exporting the structure failed: {err}");
+                    toasts::error(err.to_string());
+                }
+            }
+            Command::ExportDot => {
+                let caption = self
+                    .user_state
+                    .show_summary_card
+                    .then(|| self.summary_card.lines());
+                if let Err(err) = dot::export(
+                    &self.state.graph,
+                    caption.as_deref(),
+                    &Audit::new(&self.state.graph, &self.user_state),
+                ) {
+                    tracing::warn!("exporting DOT failed: {err}");
+                    toasts::error(err.to_string());
+                }
+            }
+            Command::ExportBuilder => {
+                let audit = Audit::new(&self.state.graph, &self.user_state);
+                if let Err(err) = fixture::export(&self.state, &self.user_state, &audit) {
+                    tracing::warn!("exporting builder code failed: {err}");
+                    toasts::error(err.to_string());
+                }
+            }
+            Command::ExportDistribution => {
+                let Some(node) = self.user_state.active_node else {
+                    toasts::error(tr("distribution-no-active-node"));
+                    return;
+                };
+                let result = evaluate_curve(
+                    &self.state.graph,
+                    &eval::Options::of(&self.user_state),
+                    node,
+                    &mut OutputsCache::new(),
+                )
+                .map_err(anyhow::Error::from)
+                .and_then(|curve| {
+                    let mut distribution = distribution::Distribution::new(
+                        self.state.graph[node].label.clone(),
+                        &curve,
+                        self.user_state.settings.time_unit,
+                    );
+                    distribution.audit = Audit::new(&self.state.graph, &self.user_state).to_map();
+                    distribution::export(&distribution)
+                });
+                if let Err(err) = result {
+                    tracing::warn!("exporting the distribution failed: {err}");
+                    toasts::error(err.to_string());
+                }
+            }
+            Command::Library => self.library_open = true,
+            Command::FindReplace => self.find_replace_open = true,
+            Command::Report => self.report_open = true,
+            Command::AddNode(template) => {
+                let node = nodes::add_node_at(
+                    &mut self.state,
+                    &mut self.user_state,
+                    template,
+                    self.graph_rect.center(),
+                    self.graph_rect,
+                );
+                self.state.selected_nodes = vec![node];
+                self.user_state.settings.remember_template(template);
+            }
+            Command::Tool(tool) => *self.tool_open(tool) = true,
+            Command::ToggleLegend => self.legend_open = !self.legend_open,
+            Command::ToggleWhatIf => self.what_if_open = !self.what_if_open,
+            Command::ToggleLog => self.log_open = !self.log_open,
+            Command::ToggleNotifications => self.toasts_open = !self.toasts_open,
+            Command::ToggleEdgeValues => {
+                self.user_state.show_edge_values = !self.user_state.show_edge_values
+            }
+            Command::ToggleWeakLink => {
+                self.user_state.show_weak_link = !self.user_state.show_weak_link
+            }
+            Command::ToggleHotspots => {
+                self.user_state.show_hotspots = !self.user_state.show_hotspots
+            }
+            Command::ToggleGrid => self.user_state.show_grid = !self.user_state.show_grid,
+            Command::ToggleSummaryCard => {
+                self.user_state.show_summary_card = !self.user_state.show_summary_card
+            }
+            Command::TogglePresentation => self.user_state.presenting = !self.user_state.presenting,
+            Command::Arrange(arrangement) => arrangement.apply(&mut self.state, &self.user_state),
+            Command::LockSelection | Command::UnlockSelection => {
+                for node in &self.state.selected_nodes {
+                    self.state.graph[*node].user_data.locked = command == Command::LockSelection;
+                }
+            }
+            Command::FrameSelection => {
+                frames::frame_selection(&self.state, &mut self.user_state, self.graph_rect)
+            }
+            Command::Settings => self.settings_open = true,
+            Command::Example(example) => {
+                self.user_state = GraphState {
+                    settings: std::mem::take(&mut self.user_state.settings),
+                    ..Default::default()
+                };
+                self.state = example.build(&mut self.user_state);
+            }
+        }
     }
 }
 
 impl App for MyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        catppuccin_egui::set_theme(ctx, catppuccin_egui::MACCHIATO);
+        let started = Instant::now();
+        let settings = &mut self.user_state.settings;
+        catppuccin_egui::set_theme(ctx, settings.theme.catppuccin());
+        // egui's own Ctrl+Plus / Ctrl+Minus shortcuts change the zoom too.
+        settings.ui_scale = ctx.zoom_factor();
+        if std::mem::take(&mut self.restore_panels) {
+            self.user_state.panels.restore(ctx);
+        }
+
+        if let Some(node) = self.user_state.active_node {
+            if !self.state.graph.nodes.contains_key(node) {
+                self.user_state.active_node = None;
+            }
+        }
+        self.feed.apply(&mut self.state.graph);
+        self.user_state.variable_errors =
+            variables::apply(&mut self.state.graph, &self.user_state.variables);
+        mirrors::apply(&mut self.state.graph);
+        self.field_data
+            .poll(ctx, &mut self.state.graph, &self.user_state);
+        // The drag was found while the nodes were drawn last frame. Another
+        // frame is asked for, so the one after the drag ends is the precise one.
+        let dragging_value = std::mem::take(&mut self.user_state.dragging_value);
+        self.user_state.coarse = self.user_state.settings.fast_preview
+            && dragging_value
+            && self
+                .user_state
+                .active_node
+                .is_some_and(|node| eval::cost(&self.state.graph, node) > FAST_PREVIEW_COST);
+        if self.user_state.coarse {
+            ctx.request_repaint();
+        }
+        let options = eval::Options::of(&self.user_state);
+        self.curves.clear();
+        let evaluation = self.user_state.active_node.map(|node| {
+            let started = Instant::now();
+            let evaluation = evaluate_node(&self.state.graph, &options, node, &mut self.curves);
+            self.metrics.evaluation(started.elapsed());
+            evaluation
+        });
+        let curve = match &evaluation {
+            Some(Ok(value)) => value.clone().try_to_reliability().ok(),
+            _ => None,
+        };
+        let diagnosis = self
+            .user_state
+            .active_node
+            .zip(curve.as_deref())
+            .and_then(|(node, curve)| health::diagnose(&self.state.graph, &options, node, curve));
+        // A defective curve would plot as if it meant something.
+        let curve = curve.filter(|_| diagnosis.is_none());
+        self.regression
+            .check(&self.state.graph, &self.user_state, curve.as_deref());
+        self.summary.update(&self.state.graph, &self.user_state);
+        if self.user_state.show_summary_card {
+            self.summary_card.update(
+                self.summary
+                    .result()
+                    .and_then(|result| result.as_ref().ok())
+                    .filter(|_| curve.is_some()),
+            );
+        }
+        let mission_time = self.user_state.mission_time;
+        self.user_state.summarized = self.state.pan_zoom.zoom < nodes::SUMMARY_ZOOM;
+        // The overlays read all curves at once, which only change with the
+        // model, not from frame to frame.
+        let overlaid = self.user_state.show_edge_values
+            || self.user_state.summarized
+            || self.user_state.show_weak_link;
+        let all_curves =
+            overlaid.then(|| self.all_curves.update(&self.state.graph, &self.user_state));
+        if let Some(curves) = all_curves.filter(|_| self.user_state.show_edge_values) {
+            self.user_state.edge_values = curves
+                .iter()
+                .filter_map(|(output, value)| Some((*output, value_at(value, mission_time)?)))
+                .collect();
+        }
+        if let Some(curves) = all_curves.filter(|_| self.user_state.summarized) {
+            self.user_state.summaries = self
+                .state
+                .graph
+                .nodes
+                .iter()
+                .filter_map(|(node_id, node)| {
+                    let output = node.get_output("reliability").ok()?;
+                    Some((node_id, value_at(curves.get(&output)?, mission_time)?))
+                })
+                .collect();
+        }
+        self.weak_link = self
+            .user_state
+            .active_node
+            .zip(all_curves)
+            .filter(|_| self.user_state.show_weak_link)
+            .and_then(|(system, curves)| {
+                weak_link::trace(
+                    &self.state.graph,
+                    &options,
+                    curves,
+                    system,
+                    self.user_state.mission_time,
+                )
+            });
+        if self.user_state.show_hotspots {
+            let ((), timings) =
+                eval::timed(|| evaluate_all_into(&self.state.graph, &options, &mut self.curves));
+            self.hotspots.record(timings);
+        }
+        self.user_state.achieved =
+            budget::achieved(&self.state.graph, &self.user_state, &mut self.all_curves);
+
+        let mut command = None;
+        if self.user_state.presenting {
+            egui::TopBottomPanel::top("menu bar").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("presentation-leave")).clicked() {
+                        command = Some(Command::TogglePresentation);
+                    }
+                    ui.label(tr("presentation-hint"));
+                });
+            });
+        } else {
+            egui::TopBottomPanel::top("menu bar").show(ctx, |ui| {
+                let mut item = |ui: &mut egui::Ui, item: Command| {
+                    if ui.button(item.name()).clicked() {
+                        command = Some(item);
+                        ui.close_menu();
+                    }
+                };
+                egui::menu::bar(ui, |ui| {
+                    ui.menu_button(tr("menu-file"), |ui| {
+                        item(ui, Command::NewProject);
+                        item(ui, Command::OpenProject);
+                        item(ui, Command::SaveProject);
+                        item(ui, Command::ImportModel);
+                        item(ui, Command::ImportDot);
+                        item(ui, Command::ImportDistribution);
+                        item(ui, Command::Library);
+                        ui.menu_button(tr("menu-file-export-structure"), |ui| {
+                            for format in StructureFormat::ALL {
+                                item(ui, Command::ExportStructure(format));
+                            }
+                        });
+                        item(ui, Command::ExportDot);
+                        item(ui, Command::ExportBuilder);
+                        item(ui, Command::ExportDistribution);
+                        ui.separator();
+                        item(ui, Command::Report);
+                    });
+                    ui.menu_button(tr("menu-language"), |ui| {
+                        for language in Language::ALL {
+                            if ui
+                                .radio(i18n::language() == language, language.native_name())
+                                .clicked()
+                            {
+                                i18n::set_language(language);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    ui.menu_button(tr("menu-view"), |ui| {
+                        ui.checkbox(&mut self.legend_open, tr("menu-view-legend"));
+                        ui.checkbox(&mut self.quick_add_open, tr("menu-view-quick-add"));
+                        ui.checkbox(&mut self.what_if_open, tr("menu-view-what-if"));
+                        ui.checkbox(&mut self.log_open, tr("menu-view-log"));
+                        ui.checkbox(&mut self.toasts_open, tr("menu-view-notifications"));
+                        ui.checkbox(
+                            &mut self.user_state.show_edge_values,
+                            tr("menu-view-edge-values"),
+                        );
+                        ui.checkbox(
+                            &mut self.user_state.show_weak_link,
+                            tr("menu-view-weak-link"),
+                        );
+                        ui.checkbox(&mut self.user_state.show_hotspots, tr("menu-view-hotspots"));
+                        ui.checkbox(&mut self.user_state.show_grid, tr("menu-view-grid"));
+                        ui.checkbox(
+                            &mut self.user_state.show_summary_card,
+                            tr("menu-view-summary-card"),
+                        );
+                        ui.separator();
+                        item(ui, Command::TogglePresentation);
+                    });
+                    ui.menu_button(tr("menu-arrange"), |ui| {
+                        for arrangement in Arrangement::ALL {
+                            if arrangement == Arrangement::DistributeHorizontally {
+                                ui.separator();
+                            }
+                            item(ui, Command::Arrange(arrangement));
+                        }
+                        ui.separator();
+                        item(ui, Command::LockSelection);
+                        item(ui, Command::UnlockSelection);
+                        ui.separator();
+                        item(ui, Command::FrameSelection);
+                        item(ui, Command::FindReplace);
+                    });
+                    ui.menu_button(tr("menu-analysis"), |ui| {
+                        for tool in Tool::ALL {
+                            if matches!(tool, Tool::Profile | Tool::Compare) {
+                                ui.separator();
+                            }
+                            item(ui, Command::Tool(tool));
+                        }
+                    });
+                    if ui.button(tr("menu-settings")).clicked() {
+                        self.settings_open = true;
+                    }
+                    ui.menu_button(tr("menu-help"), |ui| {
+                        ui.menu_button(tr("menu-help-examples"), |ui| {
+                            for example in Example::ALL {
+                                item(ui, Command::Example(example));
+                            }
+                        });
+                    });
+                });
+            });
+            egui::TopBottomPanel::top("quick add").show_animated(ctx, self.quick_add_open, |ui| {
+                ui.horizontal(|ui| {
+                    for template in QUICK_ADD {
+                        if ui
+                            .button(format!("+ {}", template.name()))
+                            .on_hover_text(tr("quick-add-hint"))
+                            .clicked()
+                        {
+                            command = Some(Command::AddNode(template));
+                        }
+                    }
+                });
+            });
+        }
+        let presentation_key = ctx.input_mut(|i| {
+            i.consume_key(Modifiers::NONE, Key::F5)
+                || (self.user_state.presenting && i.consume_key(Modifiers::NONE, Key::Escape))
+        });
+        let command = command
+            .or_else(|| self.palette.show(ctx))
+            .or_else(|| presentation_key.then_some(Command::TogglePresentation));
+        if let Some(command) = command {
+            self.run(command);
+        }
+        self.wizard.window(
+            ctx,
+            &mut self.wizard_open,
+            &mut self.state,
+            &mut self.user_state,
+        );
+        self.user_state
+            .settings
+            .window(ctx, &mut self.settings_open);
+        self.user_state.mission_profile.window(
+            ctx,
+            &mut self.profile_open,
+            self.user_state.settings.time_unit,
+        );
+        variables::window(
+            ctx,
+            &mut self.variables_open,
+            &self.state.graph,
+            &mut self.user_state,
+        );
+        self.scaling.window(
+            ctx,
+            &mut self.scaling_open,
+            &mut self.state.graph,
+            &self.state.selected_nodes,
+            &self.user_state,
+        );
+        allocation::window(
+            ctx,
+            &mut self.allocation_open,
+            &mut self.state.graph,
+            &mut self.user_state,
+        );
+        self.library.window(
+            ctx,
+            &mut self.library_open,
+            &mut self.state,
+            &mut self.user_state,
+            self.graph_rect,
+        );
+        self.find_replace.window(
+            ctx,
+            &mut self.find_replace_open,
+            &mut self.state.graph,
+            &self.state.selected_nodes,
+            &self.user_state,
+        );
+        budget::window(
+            ctx,
+            &mut self.budget_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        self.goal_seek.window(
+            ctx,
+            &mut self.goal_seek_open,
+            &mut self.state.graph,
+            &mut self.user_state,
+        );
+        self.sweep.window(
+            ctx,
+            &mut self.sweep_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        self.doe.window(
+            ctx,
+            &mut self.doe_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        self.robustness.window(
+            ctx,
+            &mut self.robustness_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        self.monte_carlo.window(
+            ctx,
+            &mut self.monte_carlo_open,
+            &self.state.graph,
+            &mut self.user_state,
+        );
+        self.kaplan_meier.window(
+            ctx,
+            &mut self.kaplan_meier_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        self.field_data.window(
+            ctx,
+            &mut self.field_data_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        let ranges = self
+            .bayes
+            .window(ctx, &mut self.bayes_open, &mut self.state.graph);
+        if !ranges.is_empty() {
+            self.doe.set_factors(ranges);
+            self.doe_open = true;
+        }
+        self.availability.window(
+            ctx,
+            &mut self.availability_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.rocof.window(
+            ctx,
+            &mut self.rocof_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.age_replacement.window(
+            ctx,
+            &mut self.age_replacement_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.calendar.window(
+            ctx,
+            &mut self.calendar_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.redundancy.window(
+            ctx,
+            &mut self.redundancy_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.event_tree.window(
+            ctx,
+            &mut self.event_tree_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.sil
+            .window(ctx, &mut self.sil_open, &self.state.graph, &self.user_state);
+        self.fleet.window(
+            ctx,
+            &mut self.fleet_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.compare.window(
+            ctx,
+            &mut self.compare_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        statistics::window(
+            ctx,
+            &mut self.statistics_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.feed
+            .window(ctx, &mut self.feed_open, &self.state.graph);
+        self.competing_risks.window(
+            ctx,
+            &mut self.competing_risks_open,
+            &self.state.graph,
+            &self.user_state,
+        );
+        self.criticality.window(
+            ctx,
+            &mut self.criticality_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+        self.pareto.window(
+            ctx,
+            &mut self.pareto_open,
+            &mut self.state.graph,
+            &self.user_state,
+        );
+
+        self.report.window(
+            ctx,
+            &mut self.report_open,
+            &mut self.state.graph,
+            &mut self.user_state,
+        );
 
         egui::TopBottomPanel::top("vis panel")
             .resizable(true)
             .show(ctx, |ui| {
-                example_plot(ui);
+                let curve = curve.as_deref().unwrap_or_default();
+                let time_unit = self.user_state.settings.time_unit;
+                ui.horizontal(|ui| {
+                    ui.label(tr("mission-time"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.user_state.mission_time)
+                            .suffix(format!(" {}", time_unit.symbol())),
+                    );
+                    // A defective curve leaves no curve to sum up either.
+                    let summary = self
+                        .summary
+                        .result()
+                        .and_then(|result| result.as_ref().ok())
+                        .filter(|_| !curve.is_empty());
+                    if let Some(summary) = summary {
+                        ui.separator();
+                        ui.label(format!("R(t_m) = {:.6}", summary.reliability));
+                        if self.user_state.coarse {
+                            ui.weak(tr("plot-approximate"))
+                                .on_hover_text(tr("plot-approximate-hint"));
+                        }
+                        for (percent, life) in &summary.b_lives {
+                            let life = eval::format_b_life(*life);
+                            ui.label(format!("B{percent} = {life} {}", time_unit.symbol()));
+                        }
+                    }
+                    ui.separator();
+                    self.user_state.requirement.ui(ui, time_unit);
+                    let theme = self.user_state.settings.theme.catppuccin();
+                    match summary.and_then(|summary| summary.requirement_met) {
+                        Some(true) => {
+                            ui.colored_label(theme.green, tr("requirement-pass"));
+                        }
+                        Some(false) => {
+                            ui.colored_label(theme.red, tr("requirement-fail"));
+                        }
+                        None => {}
+                    }
+                    if self.regression.is_drifting() {
+                        ui.colored_label(theme.red, tr("regression-drift"))
+                            .on_hover_text(tr("regression-drift-hint"));
+                    }
+                    ui.separator();
+                    ui.checkbox(
+                        &mut self.user_state.show_availability,
+                        tr("plot-show-availability"),
+                    );
+                    if let Some(summary) = summary.filter(|_| self.user_state.show_availability) {
+                        ui.label(format!("A(t_m) = {:.6}", summary.availability));
+                    }
+                    ui.separator();
+                    ui.checkbox(&mut self.user_state.show_hazard, tr("plot-show-hazard"));
+                    ui.separator();
+                    let filter = &mut self.user_state.severity_filter;
+                    egui::ComboBox::from_id_source("severity filter")
+                        .selected_text(filter.map_or_else(|| tr("severity-all"), Severity::name))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(filter, None, tr("severity-all"));
+                            for severity in Severity::ALL {
+                                ui.selectable_value(filter, Some(severity), severity.name());
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("plot-severity-hint"));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("⛶").on_hover_text(tr("plot-enlarge")).clicked() {
+                            self.plot_enlarged = true;
+                        }
+                        ui.toggle_value(&mut self.plot_popped_out, "⧉")
+                            .on_hover_text(tr("plot-pop-out"));
+                    });
+                });
+                let unused =
+                    validation::unused_outputs(&self.state.graph, self.user_state.active_node);
+                egui::CollapsingHeader::new(validation::title(ui, unused.len()))
+                    .id_source("validation")
+                    .show(ui, |ui| {
+                        if let Some(node) = validation::ui(ui, &self.state.graph, &unused) {
+                            self.state.selected_nodes = vec![node];
+                            self.show_node(node);
+                        }
+                    });
+                egui::CollapsingHeader::new(tr("checkpoints-title"))
+                    .id_source("checkpoints")
+                    .show(ui, |ui| {
+                        checkpoints::ui(ui, &mut self.user_state, self.summary.result())
+                    });
+                egui::CollapsingHeader::new(tr("regression-title"))
+                    .id_source("regression")
+                    .show(ui, |ui| {
+                        self.regression
+                            .ui(ui, &self.state.graph, &mut self.user_state, curve)
+                    });
+                egui::CollapsingHeader::new(tr("quantile-title"))
+                    .id_source("quantile")
+                    .show(ui, |ui| self.quantile.ui(ui, curve, time_unit));
+                egui::CollapsingHeader::new(tr("equivalent-title"))
+                    .id_source("equivalent")
+                    .show(ui, |ui| self.equivalent.ui(ui, curve, time_unit));
+                egui::CollapsingHeader::new(tr("frequency-duration-title"))
+                    .id_source("frequency duration")
+                    .show(ui, |ui| {
+                        self.frequency_duration
+                            .ui(ui, &self.state.graph, &self.user_state)
+                    });
+                if let Some(diagnosis) = &diagnosis {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        diagnosis.message(&self.state.graph),
+                    );
+                }
+                // The summary holds A(t) already, so the plot doesn't solve its renewal
+                // equations again every frame.
+                let availability = self
+                    .summary
+                    .result()
+                    .filter(|_| self.user_state.show_availability)
+                    .and_then(|result| result.as_ref().ok())
+                    .map(|result| result.availability_curve.values.as_slice());
+                let band = self.user_state.active_node.and_then(|node| {
+                    self.bands
+                        .get(&self.state.graph, &self.user_state, node, curve)
+                });
+                if self.plot_popped_out {
+                    ui.weak(tr("plot-popped-out"));
+                } else {
+                    reliability_plot(
+                        ui,
+                        "reliability_plot",
+                        300.0,
+                        curve,
+                        availability,
+                        band,
+                        &self.user_state,
+                    );
+                }
+                self.popped_out_plot.show(
+                    ctx,
+                    &mut self.plot_popped_out,
+                    curve,
+                    availability,
+                    band,
+                    &self.state.graph,
+                    &self.user_state,
+                );
+                self.enlarged_plot.window(
+                    ctx,
+                    &mut self.plot_enlarged,
+                    curve,
+                    availability,
+                    band,
+                    &self.state.graph,
+                    &self.user_state,
+                );
             });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // TODO(Todd): Add RBD nodes
+        egui::TopBottomPanel::bottom("status bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("keyboard-hint"));
+                if let Some(status) = self.keyboard.status() {
+                    ui.separator();
+                    ui.label(status);
+                }
+                if let Some(selection) = statistics::selection(
+                    &self.state.graph,
+                    &self.user_state,
+                    &self.state.selected_nodes,
+                ) {
+                    ui.separator();
+                    let unit = self.user_state.settings.time_unit.symbol();
+                    ui.label(selection.label(&unit))
+                        .on_hover_text(tr("statistics-selection-hint"));
+                }
+                if let Some(err) = &self.file_error {
+                    ui.separator();
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                if self.feed.is_running() {
+                    ui.separator();
+                    if ui.button(tr("feed-live")).clicked() {
+                        self.feed_open = true;
+                    }
+                }
+                let unseen = log::unseen();
+                if unseen > 0 && !self.log_open {
+                    ui.separator();
+                    let warnings = tr_with("log-unseen", &[("count", &unseen)]);
+                    if ui
+                        .add(egui::Button::new(
+                            egui::RichText::new(warnings).color(ui.visuals().warn_fg_color),
+                        ))
+                        .clicked()
+                    {
+                        self.log_open = true;
+                    }
+                }
+            });
         });
+        self.log_panel.panel(ctx, self.log_open);
+
+        egui::SidePanel::right("legend")
+            .resizable(true)
+            .show_animated(ctx, self.legend_open, |ui| {
+                tags::legend(ui, &mut self.state.graph);
+            });
+
+        egui::SidePanel::left("what-if")
+            .resizable(true)
+            .show_animated(
+                ctx,
+                self.what_if_open && !self.user_state.presenting,
+                |ui| {
+                    what_if::panel(ui, &mut self.state.graph);
+                },
+            );
+        self.user_state.panels.record(ctx);
+
+        let graph_response = egui::CentralPanel::default()
+            .show(ctx, |ui| {
+                self.graph_rect = ui.max_rect();
+                let mut templates = self.user_state.settings.finder_order();
+                if let Some(from) = self.dropped_wire {
+                    if let Ok(typ) = self.state.graph.any_param_type(from).copied() {
+                        templates =
+                            nodes::compatible_templates(templates, &mut self.user_state, from, typ);
+                    }
+                }
+                let presenting = self.user_state.presenting;
+                self.user_state.read_only =
+                    frames::read_only_nodes(&self.state, &self.user_state.frames);
+                let responses = if presenting {
+                    Vec::new()
+                } else {
+                    self.keyboard
+                        .handle(ctx, &mut self.state, &mut self.user_state, ui.max_rect())
+                };
+                // Presenting leaves the connections as they were and opens no
+                // finder, whatever the editor made of this frame's drags.
+                let fixed =
+                    frames::read_only_connections(&self.state.graph, &self.user_state.read_only);
+                let connections = (presenting || !self.user_state.read_only.is_empty())
+                    .then(|| self.state.graph.connections.clone());
+                let style = self.state.pan_zoom.zoomed_style.clone();
+                if presenting {
+                    self.state.node_finder = None;
+                    self.state.pan_zoom.zoomed_style = presentation_style(&style);
+                }
+                let dragged = self.state.connection_in_progress;
+                let zoom = self.state.pan_zoom.zoom;
+                let theme = self.user_state.settings.theme.catppuccin();
+                if self.user_state.show_grid {
+                    canvas::paint(ui, &self.state, self.user_state.origin, &theme);
+                }
+                frames::paint(ui, &self.state, &self.user_state.frames);
+                self.user_state.layout = wires::Layout::new(zoom);
+                let response = self.state.draw_graph_editor(
+                    ui,
+                    FinderTemplates(templates),
+                    &mut self.user_state,
+                    responses,
+                );
+                // Nor can connections of locked sections be broken, unless
+                // the node at the other end was deleted.
+                let graph = &self.state.graph;
+                let connections = connections.filter(|_| {
+                    presenting
+                        || (fixed
+                            != frames::read_only_connections(graph, &self.user_state.read_only)
+                            && fixed.iter().all(|(input, output)| {
+                                graph.inputs.contains_key(*input)
+                                    && graph.outputs.contains_key(*output)
+                            }))
+                });
+                if let Some(connections) = connections {
+                    self.state.graph.connections = connections;
+                    self.state.connection_in_progress = None;
+                    self.state.node_finder = None;
+                    // Zooming rebuilds the style, which is then left alone.
+                    if self.state.pan_zoom.zoom == zoom {
+                        self.state.pan_zoom.zoomed_style = style;
+                    }
+                }
+                frames::follow_zoom(&mut self.user_state.frames, &self.state, zoom);
+                canvas::follow_zoom(&mut self.user_state.origin, &self.state, zoom);
+                canvas::follow_zoom(&mut self.user_state.summary_card, &self.state, zoom);
+                if !presenting {
+                    frames::interact(
+                        ui,
+                        &mut self.state,
+                        &mut self.user_state.frames,
+                        &self.user_state.settings.author,
+                    );
+                }
+                if self.user_state.settings.wire_style == WireStyle::Orthogonal {
+                    wires::paint(ui, &self.state, &self.user_state.layout);
+                }
+                dim_disabled(ui, &self.state, &self.user_state.layout);
+                wires::paint_drag_preview(ui, &self.state, &self.user_state);
+                if self.user_state.show_weak_link {
+                    weak_link::paint(
+                        ui,
+                        &self.state,
+                        &self.user_state.layout,
+                        self.weak_link.as_ref(),
+                        &self.user_state.settings.theme.catppuccin(),
+                    );
+                }
+                if self.user_state.show_hotspots {
+                    hotspots::paint(
+                        ui,
+                        &self.state,
+                        &self.user_state.layout,
+                        &self.hotspots,
+                        &self.user_state.settings.theme.catppuccin(),
+                    );
+                }
+                if self.user_state.show_summary_card {
+                    self.summary_card.show(
+                        ui,
+                        &self.state,
+                        &mut self.user_state.summary_card,
+                        &theme,
+                    );
+                }
+                if self.user_state.show_grid && !presenting {
+                    canvas::readout(ui, &self.state, self.user_state.origin, &theme);
+                }
+                outline_horizon_mismatches(ui, &self.state, &self.user_state.layout);
+                match (&evaluation, &diagnosis) {
+                    (Some(Err(err)), _) => outline_failure(
+                        ui,
+                        &self.state,
+                        &self.user_state.layout,
+                        err.node,
+                        err.input.as_deref(),
+                    ),
+                    (_, Some(diagnosis)) => outline_failure(
+                        ui,
+                        &self.state,
+                        &self.user_state.layout,
+                        diagnosis.node,
+                        None,
+                    ),
+                    _ => {}
+                }
+                let connected = response
+                    .node_responses
+                    .iter()
+                    .any(|response| matches!(response, NodeResponse::ConnectEventEnded { .. }));
+                let pointer = ctx.input(|i| i.pointer.clone());
+                if pointer.secondary_released() {
+                    // A right click opens the plain finder.
+                    self.dropped_wire = None;
+                }
+                if let Some((_, from)) = dragged {
+                    if self.state.connection_in_progress.is_none()
+                        && !connected
+                        && response.cursor_in_editor
+                    {
+                        if let Some(position) = pointer.interact_pos() {
+                            self.state.node_finder = Some(NodeFinder::new_at(position));
+                            self.dropped_wire = Some(from);
+                        }
+                    }
+                }
+                response
+            })
+            .inner;
+
+        for node_response in graph_response.node_responses {
+            match node_response {
+                NodeResponse::CreatedNode(node) => {
+                    let mut parent = None;
+                    if let Some(from) = self.dropped_wire.take() {
+                        parent = nodes::wire_parent(&self.state.graph, from);
+                        nodes::connect_dropped_wire(&mut self.state.graph, node, from);
+                    }
+                    self.state.selected_nodes = vec![node];
+                    let template = self.state.graph[node].user_data.template;
+                    self.state.graph[node].label = nodes::auto_name(
+                        &self.state.graph,
+                        template,
+                        &self.user_state.settings.node_names,
+                        parent.as_deref(),
+                    );
+                    self.user_state.settings.remember_template(template);
+                }
+                NodeResponse::MoveNode { node, drag_delta } => {
+                    self.undo_locked_move(node, drag_delta)
+                }
+                NodeResponse::User(user_event) => match user_event {
+                    Response::SetActiveNode(node) => self.user_state.active_node = Some(node),
+                    Response::ClearActiveNode => self.user_state.active_node = None,
+                    Response::SetTag(node, tag) => self.state.graph[node].user_data.tag = tag,
+                    Response::SetForced(node, forced) => {
+                        self.state.graph[node].user_data.forced = forced
+                    }
+                    Response::SetLocked(node, locked) => {
+                        self.state.graph[node].user_data.locked = locked
+                    }
+                    Response::SetEnabled(node, enabled) => {
+                        self.state.graph[node].user_data.enabled = enabled
+                    }
+                    Response::SetSeverity(node, severity) => {
+                        self.state.graph[node].user_data.severity = severity
+                    }
+                    Response::SetCoverage(node, coverage) => {
+                        self.state.graph[node].user_data.coverage = coverage
+                    }
+                    Response::SetVotes(node, votes) => {
+                        self.state.graph[node].user_data.votes = votes
+                    }
+                    Response::SetMirror(node, mirror) => {
+                        self.state.graph[node].user_data.mirror = mirror
+                    }
+                    Response::AddMember(node) => nodes::add_member(&mut self.state.graph, node),
+                    Response::RemoveMember(node) => {
+                        nodes::remove_member(&mut self.state.graph, node)
+                    }
+                    Response::ExtendHorizon(node, horizon) => {
+                        nodes::extend_horizon(&mut self.state.graph, node, horizon)
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        if self.state.node_finder.is_none() {
+            self.dropped_wire = None;
+        }
+
+        match self.journal.recovery_window(ctx) {
+            Ok(Some(project)) => {
+                self.state = project.editor;
+                self.user_state = GraphState {
+                    settings: std::mem::take(&mut self.user_state.settings),
+                    ..project.state
+                };
+                self.restore_panels = true;
+            }
+            Ok(None) => {}
+            Err(err) => {
+                toasts::error(err.to_string());
+                self.file_error = Some(err.to_string());
+            }
+        }
+        self.journal.record(&self.state, &self.user_state);
+
+        let problem = match &evaluation {
+            Some(Err(err)) => Some(format!(
+                "{}: {}: {err}",
+                tr("error-evaluation"),
+                self.state.graph[err.node].label
+            )),
+            _ => diagnosis
+                .as_ref()
+                .map(|diagnosis| diagnosis.message(&self.state.graph)),
+        };
+        // Evaluation runs every frame, so only changes are worth a line.
+        if problem != self.logged_problem {
+            if let Some(problem) = &problem {
+                tracing::warn!("{problem}");
+                toasts::error(problem.clone());
+            }
+            let failing = match &evaluation {
+                Some(Err(err)) => Some(err.node),
+                _ => diagnosis.as_ref().map(|diagnosis| diagnosis.node),
+            };
+            if let Some(node) = failing {
+                self.show_node(node);
+            }
+            self.logged_problem = problem;
+        }
+
+        let theme = self.user_state.settings.theme.catppuccin();
+        self.toasts.show(ctx, theme);
+        self.toasts
+            .history_window(ctx, &mut self.toasts_open, theme);
+
+        self.metrics.frame(started.elapsed());
+        self.metrics.record(
+            self.user_state.settings.record_metrics,
+            &self.state.graph,
+            &self.user_state,
+        );
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(LANGUAGE_KEY, i18n::language().tag().to_owned());
+        storage.set_string(SESSION_KEY, project::to_json(&self.state, &self.user_state));
+        if let Some(text) = &self.unreadable_session {
+            storage.set_string(UNREADABLE_SESSION_KEY, text.clone());
+        }
+        eframe::set_value(storage, Settings::KEY, &self.user_state.settings);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.journal.close();
+    }
+
+    fn auto_save_interval(&self) -> std::time::Duration {
+        self.user_state.settings.autosave_interval()
+    }
+}
+
+/// The reliability an evaluated output carries at `time`.
+fn value_at(value: &ValueType, time: usize) -> Option<f64> {
+    match value {
+        ValueType::Reliability { curve } => curve.get(time).copied(),
+        ValueType::CompactReliability { curve } => curve.get(time).map(|r| *r as f64),
+        _ => None,
+    }
+}
+
+/// Veils the nodes disabled for a staged build, so they read as dimmed.
+fn dim_disabled(ui: &egui::Ui, editor: &EditorState, layout: &wires::Layout) {
+    let veil = ui.visuals().extreme_bg_color.gamma_multiply(0.6);
+    for (node_id, node) in &editor.graph.nodes {
+        if node.user_data.enabled {
+            continue;
+        }
+        if let Some(rect) = layout.node_rect(node_id) {
+            ui.painter().rect_filled(rect, 6.0, veil);
+        }
     }
 }
 
-fn example_plot(ui: &mut egui::Ui) -> egui::Response {
-    use egui::plot::{Line, PlotPoints};
-    let n = 128;
-    let line_points: PlotPoints = (0..=n)
-        .map(|i| {
-            use std::f64::consts::TAU;
-            let x = egui::remap(i as f64, 0.0..=n as f64, -TAU..=TAU);
-            [x, x.sin()]
-        })
-        .collect();
-    let line = Line::new(line_points);
-    egui::plot::Plot::new("example_plot")
-        .height(300.0)
-        .data_aspect(1.0)
-        .show(ui, |plot_ui| plot_ui.line(line))
-        .response
+/// Outlines the node an evaluation failed at, and marks the input at fault.
+fn outline_failure(
+    ui: &egui::Ui,
+    editor: &EditorState,
+    layout: &wires::Layout,
+    node: NodeId,
+    input: Option<&str>,
+) {
+    let color = ui.visuals().error_fg_color;
+    let Some(rect) = layout.node_rect(node) else {
+        return;
+    };
+    let painter = ui.painter();
+    painter.rect_stroke(rect.expand(4.0), 6.0, egui::Stroke::new(2.5, color));
+    let port = input
+        .and_then(|input| editor.graph[node].get_input(input).ok())
+        .and_then(|input| layout.port_position(AnyParameterId::Input(input)));
+    if let Some(port) = port {
+        painter.circle_stroke(port, 9.0, egui::Stroke::new(2.5, color));
+    }
+}
+
+/// Rings both ends of every wire whose curve ends before the others feeding
+/// the same block, which can't be evaluated until they match.
+fn outline_horizon_mismatches(ui: &egui::Ui, editor: &EditorState, layout: &wires::Layout) {
+    let stroke = egui::Stroke::new(2.5, ui.visuals().warn_fg_color);
+    let painter = ui.painter();
+    for node_id in editor.graph.iter_nodes() {
+        let Some((_, _, short)) = eval::horizon_mismatch(&editor.graph, node_id) else {
+            continue;
+        };
+        for input in short {
+            let ends = [
+                Some(AnyParameterId::Input(input)),
+                editor.graph.connection(input).map(AnyParameterId::Output),
+            ];
+            for port in ends.into_iter().flatten() {
+                if let Some(position) = layout.port_position(port) {
+                    painter.circle_stroke(position, 9.0, stroke);
+                }
+            }
+        }
+    }
+}
+
+/// The graph editor's style with every text size enlarged, for projecting.
+fn presentation_style(style: &Arc<egui::Style>) -> Arc<egui::Style> {
+    let mut style = (**style).clone();
+    for font in style.text_styles.values_mut() {
+        font.size *= PRESENTATION_TEXT_SCALE;
+    }
+    Arc::new(style)
 }
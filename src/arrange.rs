@@ -0,0 +1,110 @@
+//! Aligning and evenly spacing the selected nodes, for tidy report layouts.
+use eframe::egui::{self, Rect};
+use egui_node_graph2::NodeId;
+
+use crate::i18n::tr;
+use crate::nodes::{EditorState, GraphState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arrangement {
+    AlignLeft,
+    AlignTop,
+    /// Lines the nodes' centers up on one vertical line.
+    AlignCenter,
+    /// Spaces the nodes so the gaps between them are all equal, keeping the
+    /// outermost two in place.
+    DistributeHorizontally,
+    DistributeVertically,
+}
+
+impl Arrangement {
+    pub const ALL: [Arrangement; 5] = [
+        Arrangement::AlignLeft,
+        Arrangement::AlignTop,
+        Arrangement::AlignCenter,
+        Arrangement::DistributeHorizontally,
+        Arrangement::DistributeVertically,
+    ];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Arrangement::AlignLeft => "menu-arrange-align-left",
+            Arrangement::AlignTop => "menu-arrange-align-top",
+            Arrangement::AlignCenter => "menu-arrange-align-center",
+            Arrangement::DistributeHorizontally => "menu-arrange-distribute-horizontally",
+            Arrangement::DistributeVertically => "menu-arrange-distribute-vertically",
+        })
+    }
+
+    /// Moves the selected nodes, using their sizes as drawn last frame.
+    /// Locked nodes and nodes in locked sections count towards the extent
+    /// but stay where they are.
+    pub fn apply(self, editor: &mut EditorState, user_state: &GraphState) {
+        let mut nodes: Vec<(NodeId, Rect)> = editor
+            .selected_nodes
+            .iter()
+            .filter_map(|node| {
+                let position = *editor.node_positions.get(*node)?;
+                let size = user_state
+                    .layout
+                    .node_rect(*node)
+                    .map_or(egui::Vec2::ZERO, |rect| rect.size());
+                Some((*node, Rect::from_min_size(position, size)))
+            })
+            .collect();
+        if nodes.len() < 2 {
+            return;
+        }
+        let bounds = nodes
+            .iter()
+            .map(|(_, rect)| *rect)
+            .reduce(|a, b| a.union(b))
+            .expect("at least two nodes");
+
+        let mut targets: Vec<(NodeId, egui::Pos2)> = Vec::new();
+        match self {
+            Arrangement::AlignLeft => {
+                for (node, rect) in &nodes {
+                    targets.push((*node, egui::pos2(bounds.left(), rect.top())));
+                }
+            }
+            Arrangement::AlignTop => {
+                for (node, rect) in &nodes {
+                    targets.push((*node, egui::pos2(rect.left(), bounds.top())));
+                }
+            }
+            Arrangement::AlignCenter => {
+                for (node, rect) in &nodes {
+                    let left = bounds.center().x - rect.width() / 2.0;
+                    targets.push((*node, egui::pos2(left, rect.top())));
+                }
+            }
+            Arrangement::DistributeHorizontally => {
+                nodes.sort_by(|(_, a), (_, b)| a.left().total_cmp(&b.left()));
+                let widths: f32 = nodes.iter().map(|(_, rect)| rect.width()).sum();
+                let gap = (bounds.width() - widths) / (nodes.len() - 1) as f32;
+                let mut left = bounds.left();
+                for (node, rect) in &nodes {
+                    targets.push((*node, egui::pos2(left, rect.top())));
+                    left += rect.width() + gap;
+                }
+            }
+            Arrangement::DistributeVertically => {
+                nodes.sort_by(|(_, a), (_, b)| a.top().total_cmp(&b.top()));
+                let heights: f32 = nodes.iter().map(|(_, rect)| rect.height()).sum();
+                let gap = (bounds.height() - heights) / (nodes.len() - 1) as f32;
+                let mut top = bounds.top();
+                for (node, rect) in &nodes {
+                    targets.push((*node, egui::pos2(rect.left(), top)));
+                    top += rect.height() + gap;
+                }
+            }
+        }
+
+        for (node, target) in targets {
+            if !editor.graph[node].user_data.locked && !user_state.read_only.contains_key(&node) {
+                editor.node_positions[node] = target;
+            }
+        }
+    }
+}
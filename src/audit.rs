@@ -0,0 +1,121 @@
+//! The settings a calculation ran with, written into every export and report
+//! so results can be traced back to the model that produced them and
+//! reproduced.
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use egui_node_graph2::NodeId;
+
+use crate::calendar;
+use crate::nodes::{Graph, GraphState};
+
+/// Named settings in the order they are written. The names are fixed, not
+/// translated, so that exports stay machine-readable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Audit {
+    pub entries: Vec<(&'static str, String)>,
+}
+
+impl Audit {
+    pub fn new(graph: &Graph, state: &GraphState) -> Self {
+        let settings = &state.settings;
+        let system = state
+            .active_node
+            .and_then(|node| graph.nodes.get(node))
+            .map_or_else(|| "—".to_owned(), |node| node.label.clone());
+        let severity = state
+            .severity_filter
+            .map_or_else(|| "All".to_owned(), |severity| format!("{severity:?}"));
+        let profile = if state.mission_profile.is_active() {
+            "On"
+        } else {
+            "Off"
+        };
+        Self {
+            entries: vec![
+                (
+                    "software",
+                    format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+                ),
+                ("exported", utc(SystemTime::now())),
+                ("model-crc32", format!("{:08x}", checksum(graph, state))),
+                ("system", system),
+                (
+                    "mission-time",
+                    format!("{} {}", state.mission_time, settings.time_unit.symbol()),
+                ),
+                ("time-step", format!("1 {}", settings.time_unit.symbol())),
+                ("precision", format!("{:?}", settings.precision)),
+                ("disabled-nodes", format!("{:?}", settings.disabled_nodes)),
+                ("severity-filter", severity),
+                ("mission-profile", profile.to_owned()),
+                ("shocks", state.shocks.len().to_string()),
+                (
+                    "monte-carlo-samples",
+                    settings.monte_carlo_samples.to_string(),
+                ),
+                (
+                    "monte-carlo-half-width",
+                    settings.monte_carlo_half_width.to_string(),
+                ),
+                ("seed", settings.seed.to_string()),
+            ],
+        }
+    }
+
+    /// Adds or replaces a setting particular to one export, such as the
+    /// trials a finished simulation actually ran.
+    pub fn with(mut self, key: &'static str, value: impl ToString) -> Self {
+        let value = value.to_string();
+        match self.entries.iter_mut().find(|(name, _)| *name == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+        self
+    }
+
+    /// One `key = value` line per setting, each after `prefix`, as the
+    /// comment header of a CSV or DOT file.
+    pub fn comments(&self, prefix: &str) -> String {
+        self.entries
+            .iter()
+            .map(|(key, value)| format!("{prefix}{key} = {value}\n"))
+            .collect()
+    }
+
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+}
+
+/// A CRC-32 of what evaluation depends on: the nodes, their values and
+/// connections, the mission profile and the nodes excluded for what-if
+/// analysis, which aren't saved, but not where nodes are drawn.
+pub fn checksum(graph: &Graph, state: &GraphState) -> u32 {
+    let excluded: Vec<NodeId> = graph
+        .iter_nodes()
+        .filter(|node_id| graph[*node_id].user_data.excluded)
+        .collect();
+    let model =
+        serde_json::to_string(&(graph, &state.mission_profile, excluded)).unwrap_or_default();
+    crc32fast::hash(model.as_bytes())
+}
+
+/// A time as `YYYY-MM-DD hh:mm:ss UTC`.
+pub fn utc(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let (days, seconds) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    let (year, month, day) = calendar::civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
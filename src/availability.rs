@@ -0,0 +1,154 @@
+//! Availability metrics of repairable systems.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::{evaluate_measure, Measure, Options, OutputsCache, Revision};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+
+/// Availability figures of the active node.
+pub struct Summary {
+    /// A(t) at the mission time.
+    pub point: f64,
+    /// The mean of A(t) over the interval.
+    pub mean: f64,
+    pub steady_state: f64,
+}
+
+/// The mean of `curve` over the time steps `from..=to`, or `None` if the
+/// curve doesn't reach `to`.
+pub fn interval_mean(curve: &[f64], from: usize, to: usize) -> Option<f64> {
+    let values = curve.get(from..=to)?;
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// A(t) of `system` and its steady-state availability.
+pub fn curves(graph: &Graph, options: &Options, system: NodeId) -> anyhow::Result<(Vec<f64>, f64)> {
+    let evaluate = |measure| {
+        evaluate_measure(graph, options, measure, system, &mut OutputsCache::new())?
+            .try_to_reliability()
+    };
+    Ok((
+        evaluate(Measure::Availability)?,
+        evaluate(Measure::SteadyStateAvailability)?[0],
+    ))
+}
+
+/// Reads the figures off A(t) and the steady-state availability.
+pub fn summarize(
+    curve: &[f64],
+    steady_state: f64,
+    mission_time: usize,
+    from: usize,
+    to: usize,
+) -> anyhow::Result<Summary> {
+    let out_of_range =
+        |time: usize| anyhow::anyhow!(tr_with("error-time-out-of-range", &[("time", &time)]));
+    Ok(Summary {
+        point: *curve
+            .get(mission_time)
+            .ok_or_else(|| out_of_range(mission_time))?,
+        mean: interval_mean(curve, from, to).ok_or_else(|| out_of_range(to))?,
+        steady_state,
+    })
+}
+
+impl Summary {
+    /// The figures as tab-separated name and value rows.
+    pub fn to_tsv(&self, from: usize, to: usize) -> String {
+        format!(
+            "{}\t{}\n{}\t{}\n{}\t{}\n",
+            tr("availability-point"),
+            self.point,
+            tr_with("availability-mean", &[("from", &from), ("to", &to)]),
+            self.mean,
+            tr("availability-steady-state"),
+            self.steady_state,
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct Availability {
+    from: usize,
+    /// The end of the interval; the mission time until edited.
+    to: Option<usize>,
+    key: Option<(Revision, Option<NodeId>)>,
+    /// A(t) and the steady-state availability of the active node, solved for
+    /// again only once the model changes rather than every frame.
+    curves: Option<Result<(Vec<f64>, f64), String>>,
+}
+
+impl Availability {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("availability-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                let symbol = state.settings.time_unit.symbol();
+                let mut to = self.to.unwrap_or(state.mission_time);
+                ui.horizontal(|ui| {
+                    ui.label(tr("availability-interval"));
+                    ui.add(egui::DragValue::new(&mut self.from).suffix(format!(" {symbol}")));
+                    ui.label("–");
+                    if ui
+                        .add(egui::DragValue::new(&mut to).suffix(format!(" {symbol}")))
+                        .changed()
+                    {
+                        self.to = Some(to);
+                    }
+                });
+                let from = self.from.min(to);
+
+                let key = (Revision::of(graph, state), state.active_node);
+                if self.key.as_ref() != Some(&key) {
+                    let result = state
+                        .active_node
+                        .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))
+                        .and_then(|system| curves(graph, &key.0.options, system));
+                    self.curves = Some(result.map_err(|err| err.to_string()));
+                    self.key = Some(key);
+                }
+                let summary = match &self.curves {
+                    Some(Ok((curve, steady_state))) => {
+                        summarize(curve, *steady_state, state.mission_time, from, to)
+                            .map_err(|err| err.to_string())
+                    }
+                    Some(Err(err)) => Err(err.clone()),
+                    None => return,
+                };
+                match summary {
+                    Ok(summary) => {
+                        egui::Grid::new("availability")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                ui.label(tr("availability-point"));
+                                ui.label(format!("{:.6}", summary.point));
+                                ui.end_row();
+                                ui.label(tr_with(
+                                    "availability-mean",
+                                    &[("from", &from), ("to", &to)],
+                                ));
+                                ui.label(format!("{:.6}", summary.mean));
+                                ui.end_row();
+                                ui.label(tr("availability-steady-state"));
+                                ui.label(format!("{:.6}", summary.steady_state));
+                                ui.end_row();
+                            });
+                        if ui.button(tr("plot-copy-data")).clicked() {
+                            ui.output_mut(|o| o.copied_text = summary.to_tsv(from, to));
+                        }
+                    }
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                }
+                ui.label(tr("availability-hint"));
+            });
+    }
+}
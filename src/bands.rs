@@ -0,0 +1,150 @@
+//! Bands around R(t) from the parameter uncertainty of components.
+//!
+//! Components with an `Uncertainty` have their shape and scale drawn
+//! log-normally, matching the stored 90% intervals, and the node is
+//! evaluated for each draw. The bands are the pointwise quantiles of the
+//! resulting curves.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use rand::distributions::Distribution;
+use statrs::distribution::Normal;
+
+use crate::eval::{evaluate_curve, Options, OutputsCache};
+use crate::i18n::tr;
+use crate::nodes::{self, Graph, GraphState, Uncertainty};
+
+/// Curves evaluated for a band.
+const DRAWS: usize = 200;
+/// z of the 95th percentile, relating a 90% interval to its log-normal.
+const Z_90: f64 = 1.644_854;
+
+/// Which band a plot shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Level {
+    Off,
+    #[default]
+    Ninety,
+    NinetyFive,
+}
+
+impl Level {
+    pub const ALL: [Level; 3] = [Level::Off, Level::Ninety, Level::NinetyFive];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Level::Off => "band-off",
+            Level::Ninety => "band-90",
+            Level::NinetyFive => "band-95",
+        })
+    }
+
+    /// The plot's choice, kept in egui's memory under its id.
+    pub fn of(ctx: &egui::Context, plot: &str) -> Level {
+        ctx.data(|d| d.get_temp(egui::Id::new((plot, "band"))))
+            .unwrap_or_default()
+    }
+
+    pub fn set(self, ctx: &egui::Context, plot: &str) {
+        ctx.data_mut(|d| d.insert_temp(egui::Id::new((plot, "band")), self));
+    }
+}
+
+/// The 90% and 95% bands of a node's curve.
+#[derive(Clone, Debug, Default)]
+pub struct Band {
+    pub ninety: (Vec<f64>, Vec<f64>),
+    pub ninety_five: (Vec<f64>, Vec<f64>),
+}
+
+impl Band {
+    /// The lower and upper curves at `level`.
+    pub fn at(&self, level: Level) -> Option<(&[f64], &[f64])> {
+        let (lower, upper) = match level {
+            Level::Off => return None,
+            Level::Ninety => &self.ninety,
+            Level::NinetyFive => &self.ninety_five,
+        };
+        Some((lower, upper))
+    }
+}
+
+/// The uncertain components upstream of `node_id`.
+fn uncertain(graph: &Graph, node_id: NodeId) -> Vec<(NodeId, Uncertainty)> {
+    nodes::upstream_components(graph, node_id)
+        .into_iter()
+        .filter_map(|node| Some((node, nodes::component_parameters(graph, node)?.uncertainty?)))
+        .collect()
+}
+
+/// Draws from the log-normal whose 5% and 95% quantiles are `interval`.
+fn draw(interval: (f64, f64), z: f64) -> f64 {
+    let (lo, hi) = (interval.0.ln(), interval.1.ln());
+    (0.5 * (lo + hi) + z * (hi - lo) / (2.0 * Z_90)).exp()
+}
+
+/// The bands of `node_id`'s curve, or `None` if no component upstream has
+/// an uncertainty.
+pub fn compute(graph: &Graph, state: &GraphState, node_id: NodeId) -> Option<Band> {
+    let uncertain = uncertain(graph, node_id);
+    if uncertain.is_empty() {
+        return None;
+    }
+    let normal = Normal::new(0.0, 1.0).expect("the standard normal is valid");
+    let mut rng = state.settings.rng();
+    let options = Options::of(state);
+    let mut graph = graph.clone();
+    let mut curves: Vec<Vec<f64>> = Vec::with_capacity(DRAWS);
+    for _ in 0..DRAWS {
+        for (node, uncertainty) in &uncertain {
+            if let Some(parameters) = nodes::component_parameters_mut(&mut graph, *node) {
+                parameters.shape = draw(uncertainty.shape, normal.sample(&mut rng));
+                parameters.scale = draw(uncertainty.scale, normal.sample(&mut rng));
+            }
+        }
+        let curve = evaluate_curve(&graph, &options, node_id, &mut OutputsCache::new()).ok()?;
+        curves.push(curve);
+    }
+
+    let length = curves.iter().map(Vec::len).min().unwrap_or(0);
+    let mut band = Band::default();
+    let mut column = vec![0.0; curves.len()];
+    for t in 0..length {
+        for (value, curve) in column.iter_mut().zip(&curves) {
+            *value = curve[t];
+        }
+        column.sort_by(f64::total_cmp);
+        let quantile = |q: f64| column[((column.len() - 1) as f64 * q).round() as usize];
+        band.ninety.0.push(quantile(0.05));
+        band.ninety.1.push(quantile(0.95));
+        band.ninety_five.0.push(quantile(0.025));
+        band.ninety_five.1.push(quantile(0.975));
+    }
+    Some(band)
+}
+
+type Key = (NodeId, Vec<f64>, Vec<(NodeId, Uncertainty)>);
+
+/// The band of the active node, computed again only when its curve or the
+/// uncertainties change, since it takes `DRAWS` evaluations.
+#[derive(Default)]
+pub struct Bands {
+    key: Option<Key>,
+    band: Option<Band>,
+}
+
+impl Bands {
+    pub fn get(
+        &mut self,
+        graph: &Graph,
+        state: &GraphState,
+        node_id: NodeId,
+        curve: &[f64],
+    ) -> Option<&Band> {
+        let key = (node_id, curve.to_vec(), uncertain(graph, node_id));
+        if self.key.as_ref() != Some(&key) {
+            self.band = compute(graph, state, node_id);
+            self.key = Some(key);
+        }
+        self.band.as_ref()
+    }
+}
@@ -0,0 +1,239 @@
+//! `block batch`: evaluates many project files without opening a window and
+//! writes their system metrics as one table, for tracking a portfolio of
+//! designs from a nightly job.
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use egui_node_graph2::NodeId;
+use serde::Serialize;
+
+use crate::eval::Options;
+use crate::nodes::{Graph, GraphState};
+use crate::project;
+use crate::results;
+use crate::settings::Settings;
+
+const USAGE: &str = "usage: block batch <pattern>... [--format csv|json] [--output <file>] \
+                     [--seed <u64>] [--watch]";
+
+/// The figures of one project's system.
+#[derive(Default, Serialize)]
+pub struct Metrics {
+    pub file: String,
+    pub system: String,
+    pub mission_time: usize,
+    pub time_unit: String,
+    pub reliability: Option<f64>,
+    pub availability: Option<f64>,
+    pub mttf: Option<f64>,
+    /// The Bx lives of the default settings, as (x, life).
+    pub b_lives: Vec<(f64, Option<f64>)>,
+    /// Whether the project's requirement is met, if it sets one.
+    pub requirement_met: Option<bool>,
+    /// The CRC-32 of the evaluated model, to tell which revision a row is of.
+    pub model_crc32: String,
+    /// The seed of the random parts of the evaluation, such as preventive
+    /// maintenance.
+    pub seed: u64,
+    /// Why the project couldn't be evaluated.
+    pub error: Option<String>,
+}
+
+/// Runs the command with the arguments after `batch`, returning the exit
+/// code: 0 if every project was evaluated, 1 if any failed, 2 for bad usage.
+/// With `--watch` it never returns, running again whenever a project changes.
+pub fn run(args: &[String]) -> i32 {
+    let mut patterns = Vec::new();
+    let mut json = false;
+    let mut output = None;
+    let mut seed = Settings::default().seed;
+    let mut watch = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => match args.next().map(String::as_str) {
+                Some("csv") => json = false,
+                Some("json") => json = true,
+                _ => return usage(),
+            },
+            "--output" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => return usage(),
+            },
+            "--seed" => match args.next().and_then(|seed| seed.parse().ok()) {
+                Some(value) => seed = value,
+                None => return usage(),
+            },
+            "--watch" => watch = true,
+            pattern => patterns.push(pattern),
+        }
+    }
+    if patterns.is_empty() {
+        return usage();
+    }
+    if let Some((pattern, err)) = patterns
+        .iter()
+        .find_map(|pattern| Some((pattern, glob::Pattern::new(pattern).err()?)))
+    {
+        eprintln!("{pattern}: {err}");
+        return 2;
+    }
+
+    // The table itself may match a pattern, and rewriting it mustn't count
+    // as a change.
+    let files = || {
+        let mut files: Vec<PathBuf> = patterns
+            .iter()
+            .filter_map(|pattern| glob::glob(pattern).ok())
+            .flat_map(|paths| paths.filter_map(Result::ok))
+            .filter(|path| Some(path) != output.as_ref())
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    };
+    let once = || write(&files(), json, output.as_deref(), seed);
+    if watch {
+        crate::watch::watch(files, once);
+    }
+    once()
+}
+
+/// Evaluates `files` and writes their table to `output`, or to stdout.
+fn write(files: &[PathBuf], json: bool, output: Option<&Path>, seed: u64) -> i32 {
+    let metrics: Vec<Metrics> = files.iter().map(|path| evaluate(path, seed)).collect();
+    let text = match json {
+        true => serde_json::to_string_pretty(&metrics).expect("metrics always serialize") + "\n",
+        false => to_csv(&metrics),
+    };
+    let written = match output {
+        Some(path) => std::fs::write(path, text),
+        None => std::io::stdout().write_all(text.as_bytes()),
+    };
+    if let Err(err) = written {
+        eprintln!("{err}");
+        return 1;
+    }
+    i32::from(metrics.iter().any(|metrics| metrics.error.is_some()))
+}
+
+fn usage() -> i32 {
+    eprintln!("{USAGE}");
+    2
+}
+
+/// Evaluates the project at `path` with random parts seeded by `seed`, its
+/// error kept in the row if it fails.
+pub fn evaluate(path: &Path, seed: u64) -> Metrics {
+    let file = path.display().to_string();
+    let result = project::read(path).and_then(|project| {
+        let mut graph = project.editor.graph;
+        let mut state = project.state;
+        state.active_node = system(&graph, &state);
+        state.settings.seed = seed;
+        results::evaluate(&mut graph, &state, &Options::of(&state))
+    });
+    match result {
+        Ok(result) => Metrics {
+            file,
+            system: result.system,
+            mission_time: result.mission_time,
+            time_unit: result.time_unit,
+            reliability: Some(result.reliability),
+            availability: Some(result.availability),
+            mttf: Some(result.mttf),
+            b_lives: result.b_lives,
+            requirement_met: result.requirement_met,
+            model_crc32: result.model_crc32,
+            seed,
+            error: None,
+        },
+        Err(err) => Metrics {
+            file,
+            seed,
+            error: Some(err.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// The node a project's metrics are of: the one it was saved with active,
+/// or else its only block whose output isn't connected anywhere.
+pub fn system(graph: &Graph, state: &GraphState) -> Option<NodeId> {
+    if let Some(node) = state
+        .active_node
+        .filter(|node| graph.nodes.contains_key(*node))
+    {
+        return Some(node);
+    }
+    let feeding: Vec<NodeId> = graph
+        .iter_connections()
+        .map(|(_, output)| graph[output].node)
+        .collect();
+    let mut sinks = graph.nodes.iter().filter(|(node_id, node)| {
+        node.user_data.template.evaluates() && !feeding.contains(node_id)
+    });
+    match (sinks.next(), sinks.next()) {
+        (Some((node, _)), None) => Some(node),
+        _ => None,
+    }
+}
+
+fn to_csv(metrics: &[Metrics]) -> String {
+    let b_lives: Vec<f64> = metrics
+        .iter()
+        .find(|metrics| metrics.error.is_none())
+        .map(|metrics| metrics.b_lives.iter().map(|(x, _)| *x).collect())
+        .unwrap_or_default();
+    let mut header = vec![
+        "file".to_owned(),
+        "system".to_owned(),
+        "mission_time".to_owned(),
+        "time_unit".to_owned(),
+        "reliability".to_owned(),
+        "availability".to_owned(),
+        "mttf".to_owned(),
+    ];
+    header.extend(b_lives.iter().map(|x| format!("B{x}")));
+    header.extend([
+        "requirement_met".to_owned(),
+        "model_crc32".to_owned(),
+        "seed".to_owned(),
+        "error".to_owned(),
+    ]);
+    let mut csv = header.join(",") + "\n";
+    let number = |value: Option<f64>| value.map_or_else(String::new, |value| value.to_string());
+    for row in metrics {
+        let mut fields = vec![
+            quote(&row.file),
+            quote(&row.system),
+            row.mission_time.to_string(),
+            quote(&row.time_unit),
+            number(row.reliability),
+            number(row.availability),
+            number(row.mttf),
+        ];
+        fields.extend(
+            (0..b_lives.len()).map(|i| number(row.b_lives.get(i).and_then(|(_, life)| *life))),
+        );
+        fields.push(
+            row.requirement_met
+                .map_or_else(String::new, |met| met.to_string()),
+        );
+        fields.push(row.model_crc32.clone());
+        fields.push(row.seed.to_string());
+        fields.push(quote(row.error.as_deref().unwrap_or_default()));
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// A CSV field, quoted if it holds a separator, quote or line break.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
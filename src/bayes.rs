@@ -0,0 +1,389 @@
+//! Bayesian updating of a component's Weibull parameters with field data.
+//!
+//! The shape and scale have independent log-normal priors, and the
+//! posterior is evaluated on a grid in log space, which suits the handful
+//! of failures typical of field data better than a maximum likelihood fit.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::i18n::{tr, tr_with};
+use crate::kaplan_meier::{self, Observation};
+use crate::nodes::{self, Graph, Parameter, Uncertainty};
+
+/// Grid points along each axis.
+const GRID: usize = 160;
+/// How many prior standard deviations the grid spans to either side.
+const SPAN: f64 = 4.0;
+/// The credible interval reported, as its lower and upper quantiles.
+const INTERVAL: (f64, f64) = (0.05, 0.95);
+
+/// A log-normal prior: the parameter's median, and the standard deviation of
+/// its logarithm.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Prior {
+    pub median: f64,
+    pub spread: f64,
+}
+
+/// A posterior mean with its credible interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    pub mean: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Posterior {
+    pub shape: Estimate,
+    pub scale: Estimate,
+}
+
+/// The log-likelihood of Weibull(`shape`, `scale`) for the observations.
+fn log_likelihood(observations: &[Observation], shape: f64, scale: f64) -> f64 {
+    observations
+        .iter()
+        .map(|o| {
+            let z = o.time / scale;
+            let survival = -z.powf(shape);
+            if o.failed {
+                shape.ln() - scale.ln() + (shape - 1.0) * z.ln() + survival
+            } else {
+                survival
+            }
+        })
+        .sum()
+}
+
+/// The grid values of ln(parameter) a prior spans.
+fn axis(prior: Prior) -> Vec<f64> {
+    let center = prior.median.ln();
+    (0..GRID)
+        .map(|i| center + prior.spread * SPAN * (2.0 * i as f64 / (GRID - 1) as f64 - 1.0))
+        .collect()
+}
+
+/// The mean and credible interval of a marginal given as probabilities over
+/// the log values `axis`.
+fn estimate(axis: &[f64], marginal: &[f64]) -> Estimate {
+    let mean = axis.iter().zip(marginal).map(|(x, p)| x.exp() * p).sum();
+    let quantile = |q: f64| {
+        let mut cumulative = 0.0;
+        for (x, p) in axis.iter().zip(marginal) {
+            cumulative += p;
+            if cumulative >= q {
+                return x.exp();
+            }
+        }
+        axis[axis.len() - 1].exp()
+    };
+    Estimate {
+        mean,
+        lower: quantile(INTERVAL.0),
+        upper: quantile(INTERVAL.1),
+    }
+}
+
+/// The posterior of the shape and scale given the priors and observations
+/// at positive times, or `None` if the priors aren't proper.
+pub fn update(shape: Prior, scale: Prior, observations: &[Observation]) -> Option<Posterior> {
+    let valid = |prior: Prior| prior.median > 0.0 && prior.spread > 0.0;
+    if !valid(shape) || !valid(scale) {
+        return None;
+    }
+    let observations: Vec<Observation> = observations
+        .iter()
+        .copied()
+        .filter(|o| o.time > 0.0)
+        .collect();
+    let (shapes, scales) = (axis(shape), axis(scale));
+    // Log-normal priors are normal in the log parameters the grid is even in.
+    let log_prior = |x: f64, prior: Prior| -0.5 * ((x - prior.median.ln()) / prior.spread).powi(2);
+    let mut log_posterior = vec![vec![0.0; GRID]; GRID];
+    for (i, ln_shape) in shapes.iter().enumerate() {
+        for (j, ln_scale) in scales.iter().enumerate() {
+            log_posterior[i][j] = log_prior(*ln_shape, shape)
+                + log_prior(*ln_scale, scale)
+                + log_likelihood(&observations, ln_shape.exp(), ln_scale.exp());
+        }
+    }
+    let peak = log_posterior
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|p| p.is_finite())
+        .fold(f64::NEG_INFINITY, f64::max);
+    if !peak.is_finite() {
+        return None;
+    }
+    let (mut shape_marginal, mut scale_marginal) = (vec![0.0; GRID], vec![0.0; GRID]);
+    let mut total = 0.0;
+    for (i, row) in log_posterior.iter().enumerate() {
+        for (j, p) in row.iter().enumerate() {
+            let p = if p.is_finite() { (p - peak).exp() } else { 0.0 };
+            shape_marginal[i] += p;
+            scale_marginal[j] += p;
+            total += p;
+        }
+    }
+    for p in shape_marginal.iter_mut().chain(&mut scale_marginal) {
+        *p /= total;
+    }
+    Some(Posterior {
+        shape: estimate(&shapes, &shape_marginal),
+        scale: estimate(&scales, &scale_marginal),
+    })
+}
+
+/// Parameters of a component with their credible intervals, for a
+/// Latin hypercube study to sample.
+pub type Ranges = Vec<(NodeId, Parameter, f64, f64)>;
+
+pub struct Bayes {
+    component: Option<NodeId>,
+    shape: Prior,
+    scale: Prior,
+    text: String,
+    observations: Vec<Observation>,
+    posterior: Option<Posterior>,
+    error: Option<String>,
+}
+
+impl Default for Bayes {
+    fn default() -> Self {
+        Self {
+            component: None,
+            shape: Prior {
+                median: 1.5,
+                spread: 0.5,
+            },
+            scale: Prior {
+                median: 10_000.0,
+                spread: 1.0,
+            },
+            text: String::new(),
+            observations: Vec::new(),
+            posterior: None,
+            error: None,
+        }
+    }
+}
+
+fn prior_row(ui: &mut egui::Ui, name: String, prior: &mut Prior) -> bool {
+    ui.label(name);
+    let speed = prior.median * 0.01;
+    let median = ui.add(
+        egui::DragValue::new(&mut prior.median)
+            .speed(speed)
+            .clamp_range(1e-6..=f64::MAX),
+    );
+    let spread = ui.add(
+        egui::DragValue::new(&mut prior.spread)
+            .speed(0.01)
+            .clamp_range(0.01..=5.0),
+    );
+    ui.end_row();
+    median.changed() || spread.changed()
+}
+
+impl Bayes {
+    fn update(&mut self) {
+        match kaplan_meier::parse(&self.text) {
+            Ok(observations) => {
+                self.posterior = update(self.shape, self.scale, &observations);
+                self.observations = observations;
+                self.error = None;
+            }
+            Err(err) => {
+                self.observations.clear();
+                self.posterior = None;
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Shows the window. Returns the posterior intervals when they are sent
+    /// to the Latin hypercube study.
+    pub fn window(&mut self, ctx: &egui::Context, open: &mut bool, graph: &mut Graph) -> Ranges {
+        let mut ranges = Vec::new();
+        egui::Window::new(tr("bayes-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                if self
+                    .component
+                    .is_some_and(|node| !graph.nodes.contains_key(node))
+                {
+                    self.component = None;
+                }
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label(tr("goal-seek-component"));
+                    let before = self.component;
+                    nodes::component_combo(ui, "bayes component", graph, &mut self.component);
+                    // The node's own parameters are the natural prior.
+                    if self.component != before {
+                        if let Some(parameters) = self
+                            .component
+                            .and_then(|node| nodes::component_parameters(graph, node))
+                        {
+                            self.shape.median = parameters.shape;
+                            self.scale.median = parameters.scale;
+                            changed = true;
+                        }
+                    }
+                });
+
+                ui.label(tr("bayes-prior-hint"));
+                egui::Grid::new("bayes priors")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.strong(tr("bayes-median"));
+                        ui.strong(tr("bayes-spread"));
+                        ui.end_row();
+                        changed |= prior_row(ui, tr("param-shape"), &mut self.shape);
+                        changed |= prior_row(ui, tr("param-scale"), &mut self.scale);
+                    });
+
+                ui.separator();
+                ui.label(tr("km-format"));
+                changed |= ui
+                    .add(
+                        egui::TextEdit::multiline(&mut self.text)
+                            .desired_rows(6)
+                            .code_editor(),
+                    )
+                    .changed();
+                if changed {
+                    self.update();
+                }
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                let failures = self.observations.iter().filter(|o| o.failed).count();
+                ui.label(tr_with(
+                    "km-summary",
+                    &[
+                        ("failures", &failures),
+                        ("suspensions", &(self.observations.len() - failures)),
+                    ],
+                ));
+
+                let Some(posterior) = self.posterior else {
+                    return;
+                };
+                ui.separator();
+                egui::Grid::new("bayes posterior")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.strong(tr("bayes-mean"));
+                        ui.strong(tr("bayes-interval"));
+                        ui.end_row();
+                        for (name, estimate) in [
+                            (tr("param-shape"), posterior.shape),
+                            (tr("param-scale"), posterior.scale),
+                        ] {
+                            ui.label(name);
+                            ui.label(format!("{:.4}", estimate.mean));
+                            ui.label(format!("{:.4} – {:.4}", estimate.lower, estimate.upper));
+                            ui.end_row();
+                        }
+                    });
+                let Some(component) = self.component else {
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    if ui.button(tr("bayes-apply")).clicked() {
+                        if let Some(parameters) = nodes::component_parameters_mut(graph, component)
+                        {
+                            parameters.shape = posterior.shape.mean;
+                            parameters.scale = posterior.scale.mean;
+                            parameters.uncertainty = Some(Uncertainty {
+                                shape: (posterior.shape.lower, posterior.shape.upper),
+                                scale: (posterior.scale.lower, posterior.scale.upper),
+                            });
+                        }
+                    }
+                    if ui
+                        .button(tr("bayes-study"))
+                        .on_hover_text(tr("bayes-study-hint"))
+                        .clicked()
+                    {
+                        ranges = vec![
+                            (
+                                component,
+                                Parameter::Shape,
+                                posterior.shape.lower,
+                                posterior.shape.upper,
+                            ),
+                            (
+                                component,
+                                Parameter::Scale,
+                                posterior.scale.lower,
+                                posterior.scale.upper,
+                            ),
+                        ];
+                    }
+                });
+            });
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Failures at the quantiles of Weibull(2, 100).
+    fn failures(count: usize) -> Vec<Observation> {
+        (0..count)
+            .map(|i| {
+                let p = (i as f64 + 0.5) / count as f64;
+                Observation {
+                    time: 100.0 * (-(1.0 - p).ln()).sqrt(),
+                    failed: true,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn data_outweighs_a_vague_prior() {
+        let prior = |median| Prior {
+            median,
+            spread: 1.0,
+        };
+        let posterior = update(prior(1.0), prior(300.0), &failures(200)).unwrap();
+        for (estimate, truth) in [(posterior.shape, 2.0), (posterior.scale, 100.0)] {
+            assert!(
+                estimate.lower < truth && truth < estimate.upper,
+                "{estimate:?}"
+            );
+            assert!((estimate.mean - truth).abs() < 0.1 * truth, "{estimate:?}");
+        }
+    }
+
+    #[test]
+    fn without_data_the_posterior_is_the_prior() {
+        let shape = Prior {
+            median: 1.5,
+            spread: 0.2,
+        };
+        let posterior = update(shape, shape, &[]).unwrap();
+        // The mean of a log-normal lies above its median.
+        let mean = 1.5 * (0.5 * 0.2_f64.powi(2)).exp();
+        assert!((posterior.shape.mean - mean).abs() < 1e-3, "{posterior:?}");
+        assert!(posterior.shape.lower < 1.5 && 1.5 < posterior.shape.upper);
+    }
+
+    #[test]
+    fn improper_priors_are_rejected() {
+        let flat = Prior {
+            median: 1.0,
+            spread: 0.0,
+        };
+        assert_eq!(update(flat, flat, &failures(10)), None);
+    }
+}
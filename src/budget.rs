@@ -0,0 +1,152 @@
+//! Reliability budgets: targets adopted from an allocation and kept with the
+//! model, so each node's margin can be tracked as its design changes.
+use std::collections::HashMap;
+
+use catppuccin_egui::Theme;
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::AllCurves;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState, ValueType};
+
+/// The nodes with a budget, sorted by label.
+pub fn budgeted(graph: &Graph) -> Vec<NodeId> {
+    let mut nodes: Vec<NodeId> = graph
+        .nodes
+        .iter()
+        .filter(|(_, node)| node.user_data.target.is_some())
+        .map(|(node_id, _)| node_id)
+        .collect();
+    nodes.sort_by_key(|node| graph[*node].label.clone());
+    nodes
+}
+
+/// R at the requirement time of each node with a budget and of the active
+/// node, or of none if no node has a budget, so models without budgets
+/// aren't evaluated in full.
+pub fn achieved(
+    graph: &Graph,
+    state: &GraphState,
+    all_curves: &mut AllCurves,
+) -> HashMap<NodeId, f64> {
+    let mut nodes = budgeted(graph);
+    if nodes.is_empty() {
+        return HashMap::new();
+    }
+    nodes.extend(state.active_node);
+    let curves = all_curves.update(graph, state);
+    let time = state.requirement.time;
+    nodes
+        .into_iter()
+        .filter_map(|node| {
+            let output = graph[node].get_output("reliability").ok()?;
+            let value = match curves.get(&output)? {
+                ValueType::Reliability { curve } => *curve.get(time)?,
+                ValueType::CompactReliability { curve } => *curve.get(time)? as f64,
+                _ => return None,
+            };
+            Some((node, value))
+        })
+        .collect()
+}
+
+/// Green for a budget that is met, red for one that isn't.
+pub fn margin_color(theme: &Theme, margin: f64) -> egui::Color32 {
+    if margin >= 0.0 {
+        theme.green
+    } else {
+        theme.red
+    }
+}
+
+/// Lists every budget with its margin, and whether the system requirement
+/// is met with the current designs.
+pub fn window(ctx: &egui::Context, open: &mut bool, graph: &mut Graph, state: &GraphState) {
+    egui::Window::new(tr("budget-title"))
+        .open(open)
+        .show(ctx, |ui| {
+            let theme = state.settings.theme.catppuccin();
+            let nodes = budgeted(graph);
+            if nodes.is_empty() {
+                ui.label(tr("budget-empty"));
+                return;
+            }
+            let requirement = state.requirement;
+            let system = state
+                .active_node
+                .and_then(|node| Some((node, *state.achieved.get(&node)?)));
+            match system {
+                Some((node, achieved)) if requirement.enabled => {
+                    let met = achieved >= requirement.reliability;
+                    ui.colored_label(
+                        margin_color(&theme, achieved - requirement.reliability),
+                        tr_with(
+                            if met {
+                                "budget-system-met"
+                            } else {
+                                "budget-system-missed"
+                            },
+                            &[
+                                ("system", &graph[node].label),
+                                ("achieved", &format!("{achieved:.6}")),
+                                ("required", &format!("{:.6}", requirement.reliability)),
+                            ],
+                        ),
+                    );
+                }
+                _ => {
+                    ui.label(tr("budget-no-system"));
+                }
+            }
+
+            let over = nodes
+                .iter()
+                .filter(|node| {
+                    let target = graph[**node].user_data.target.unwrap_or_default();
+                    state.achieved.get(node).is_some_and(|r| *r < target)
+                })
+                .count();
+            ui.label(tr_with(
+                "budget-over",
+                &[("over", &over), ("total", &nodes.len())],
+            ));
+
+            egui::Grid::new("budget")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong(tr("allocation-component"));
+                    ui.strong(tr("allocation-current"));
+                    ui.strong(tr("allocation-target"));
+                    ui.strong(tr("budget-margin"));
+                    ui.end_row();
+                    for node in &nodes {
+                        let target = graph[*node].user_data.target.unwrap_or_default();
+                        ui.label(&graph[*node].label);
+                        match state.achieved.get(node) {
+                            Some(achieved) => {
+                                ui.label(format!("{achieved:.6}"));
+                                ui.label(format!("{target:.6}"));
+                                let margin = achieved - target;
+                                ui.colored_label(
+                                    margin_color(&theme, margin),
+                                    format!("{margin:+.6}"),
+                                );
+                            }
+                            None => {
+                                ui.label("—");
+                                ui.label(format!("{target:.6}"));
+                                ui.label("—");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            if ui.button(tr("budget-clear")).clicked() {
+                for node in nodes {
+                    graph[node].user_data.target = None;
+                }
+            }
+        });
+}
@@ -0,0 +1,382 @@
+//! A projected maintenance calendar: the inspections and cost-optimal
+//! preventive replacements of each component over the mission, and when its
+//! corrective replacements can be expected, exported as iCalendar or CSV
+//! for planners' scheduling tools.
+//!
+//! The expected failures follow the long-run renewal rate F(T) / ∫₀ᵀ R(t) dt
+//! of a unit replaced at age T or at failure, with T the whole curve for
+//! components that run to failure.
+use std::time::SystemTime;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::age_replacement::{cost_rates, optimum};
+use crate::audit::Audit;
+use crate::eval;
+use crate::i18n::{tr, tr_with};
+use crate::locale;
+use crate::nodes::{self, Graph, GraphState};
+use crate::simulate::csv_field;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Activity {
+    Inspection,
+    Replacement,
+    ExpectedFailure,
+}
+
+impl Activity {
+    pub fn name(self) -> String {
+        tr(match self {
+            Activity::Inspection => "calendar-inspection",
+            Activity::Replacement => "calendar-replacement",
+            Activity::ExpectedFailure => "calendar-expected-failure",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    /// In time steps from the start of the mission.
+    pub time: f64,
+    pub component: String,
+    pub activity: Activity,
+}
+
+/// The events of the components `system` depends on, or of every
+/// component without one, up to the mission time and in time order.
+pub fn events(
+    graph: &Graph,
+    state: &GraphState,
+    system: Option<NodeId>,
+    preventive: f64,
+    corrective: f64,
+) -> anyhow::Result<Vec<Event>> {
+    let components = match system {
+        Some(system) => nodes::upstream_components(graph, system),
+        None => nodes::components(graph),
+    };
+    let horizon = state.mission_time as f64;
+    let mut events = Vec::new();
+    for node in components {
+        let Some(parameters) = nodes::component_parameters(graph, node) else {
+            continue;
+        };
+        let label = &graph[node].label;
+        let every = |interval: f64, activity: Activity, events: &mut Vec<Event>| {
+            if interval <= 0.0 {
+                return;
+            }
+            let mut time = interval;
+            while time <= horizon {
+                events.push(Event {
+                    time,
+                    component: label.clone(),
+                    activity,
+                });
+                time += interval;
+            }
+        };
+        if let Some(inspection) = parameters.inspection {
+            every(inspection.interval, Activity::Inspection, &mut events);
+        }
+        let curve = eval::component_reliability(&parameters, &state.mission_profile)?;
+        let rate = match optimum(&cost_rates(&curve, preventive, corrective)) {
+            Some((age, _)) => {
+                every(age as f64, Activity::Replacement, &mut events);
+                (1.0 - curve[age]) / eval::mttf(&curve[..=age])
+            }
+            None => 1.0 / eval::mttf(&curve),
+        };
+        if rate.is_finite() && rate > 0.0 {
+            every(1.0 / rate, Activity::ExpectedFailure, &mut events);
+        }
+    }
+    events.sort_by(|a, b| {
+        a.time
+            .total_cmp(&b.time)
+            .then_with(|| a.activity.cmp(&b.activity))
+            .then_with(|| a.component.cmp(&b.component))
+    });
+    Ok(events)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date, after Howard
+/// Hinnant's days_from_civil.
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date of a day since 1970-01-01, as (year, month, day), after Howard
+/// Hinnant's civil_from_days.
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (year_of_era + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Parses a `YYYY-MM-DD` date into days since 1970-01-01.
+pub fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    let days = days_from_civil(year, month, day);
+    // Rejects days past the end of their month, which would roll over.
+    (civil_from_days(days) == (year, month, day)).then_some(days)
+}
+
+/// Seconds since 1970-01-01 as (date, time) in the compact form of
+/// iCalendar, `YYYYMMDD` and `hhmmss`.
+fn ical_stamp(seconds: i64) -> (String, String) {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let seconds = seconds.rem_euclid(86_400);
+    (
+        format!("{year:04}{month:02}{day:02}"),
+        format!(
+            "{:02}{:02}{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+    )
+}
+
+/// The seconds since 1970-01-01 at which an event at `time` steps falls,
+/// for a mission starting at midnight on day `start`.
+fn event_seconds(start: i64, time: f64, hours_per_step: f64) -> i64 {
+    start * 86_400 + (time * hours_per_step * 3_600.0).round() as i64
+}
+
+/// Escapes text for an iCalendar property value.
+fn ical_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// The events as an iCalendar file, for a mission starting at midnight on
+/// day `start`.
+pub fn to_ical(events: &[Event], start: i64, hours_per_step: f64) -> String {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let (stamp_date, stamp_time) = ical_stamp(now);
+    let mut ical = format!(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//{}//{}//EN\r\n",
+        env!("CARGO_PKG_NAME"),
+        tr("calendar-title")
+    );
+    for (i, event) in events.iter().enumerate() {
+        let (date, time) = ical_stamp(event_seconds(start, event.time, hours_per_step));
+        ical.push_str(&format!(
+            "BEGIN:VEVENT\r\nUID:{i}-{date}{time}@{}\r\nDTSTAMP:{stamp_date}T{stamp_time}Z\r\n\
+             DTSTART:{date}T{time}\r\nSUMMARY:{}\r\nEND:VEVENT\r\n",
+            env!("CARGO_PKG_NAME"),
+            ical_text(&format!("{}: {}", event.activity.name(), event.component)),
+        ));
+    }
+    ical.push_str("END:VCALENDAR\r\n");
+    ical
+}
+
+/// The events as CSV, with their dates when steps are tied to time.
+pub fn to_csv(events: &[Event], start: Option<i64>, hours_per_step: Option<f64>) -> String {
+    let header = ["time", "date", "component", "activity"];
+    let mut csv = locale::row(&header.map(str::to_owned));
+    for event in events {
+        let date = match (start, hours_per_step) {
+            (Some(start), Some(hours)) => {
+                let seconds = event_seconds(start, event.time, hours);
+                let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+                locale::date(year, month, day)
+            }
+            _ => String::new(),
+        };
+        csv.push_str(&locale::row(&[
+            locale::number(event.time),
+            date,
+            csv_field(&event.component),
+            csv_field(&event.activity.name()),
+        ]));
+    }
+    csv
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    ICalendar,
+    Csv,
+}
+
+/// Asks for a file name and writes the calendar there. Returns whether it
+/// was written.
+fn export(contents: &str, format: Format) -> anyhow::Result<bool> {
+    let dialog = match format {
+        Format::ICalendar => rfd::FileDialog::new()
+            .add_filter("iCalendar", &["ics"])
+            .set_file_name("maintenance.ics"),
+        Format::Csv => rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("maintenance.csv"),
+    };
+    let Some(path) = dialog.save_file() else {
+        return Ok(false);
+    };
+    std::fs::write(path, contents)?;
+    Ok(true)
+}
+
+pub struct Calendar {
+    /// The first day of the mission, as `YYYY-MM-DD`.
+    start: String,
+    preventive: f64,
+    corrective: f64,
+    error: Option<String>,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        let today = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64
+            / 86_400;
+        let (year, month, day) = civil_from_days(today);
+        Self {
+            start: format!("{year:04}-{month:02}-{day:02}"),
+            preventive: 200.0,
+            corrective: 1_000.0,
+            error: None,
+        }
+    }
+}
+
+impl Calendar {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("calendar-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let hours_per_step = state.settings.time_unit.hours();
+                let start = parse_date(&self.start);
+                egui::Grid::new("calendar setup")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("calendar-start"));
+                        let edit = ui.add_enabled(
+                            hours_per_step.is_some(),
+                            egui::TextEdit::singleline(&mut self.start).desired_width(100.0),
+                        );
+                        if hours_per_step.is_none() {
+                            edit.on_disabled_hover_text(tr("calendar-cycles"));
+                        } else if start.is_none() {
+                            ui.colored_label(ui.visuals().error_fg_color, tr("calendar-bad-date"));
+                        }
+                        ui.end_row();
+                        ui.label(tr("maintenance-preventive-cost"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.preventive)
+                                .speed(1.0)
+                                .clamp_range(0.0..=f64::MAX),
+                        );
+                        ui.end_row();
+                        ui.label(tr("maintenance-corrective-cost"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.corrective)
+                                .speed(1.0)
+                                .clamp_range(0.0..=f64::MAX),
+                        );
+                        ui.end_row();
+                    });
+
+                let events = match events(
+                    graph,
+                    state,
+                    state.active_node,
+                    self.preventive,
+                    self.corrective,
+                ) {
+                    Ok(events) => events,
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                        return;
+                    }
+                };
+                let unit = state.settings.time_unit.symbol();
+                ui.label(tr_with(
+                    "calendar-summary",
+                    &[
+                        ("events", &events.len()),
+                        ("horizon", &state.mission_time),
+                        ("unit", &unit),
+                    ],
+                ));
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("calendar events")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for event in &events {
+                                    ui.label(format!("{:.0} {unit}", event.time));
+                                    ui.label(&event.component);
+                                    ui.label(event.activity.name());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.horizontal(|ui| {
+                    let dated = start.zip(hours_per_step);
+                    if ui
+                        .add_enabled(
+                            dated.is_some(),
+                            egui::Button::new(tr("calendar-export-ical")),
+                        )
+                        .clicked()
+                    {
+                        if let Some((start, hours)) = dated {
+                            self.error = export(&to_ical(&events, start, hours), Format::ICalendar)
+                                .err()
+                                .map(|err| err.to_string());
+                        }
+                    }
+                    if ui.button(tr("calendar-export-csv")).clicked() {
+                        let audit = Audit::new(graph, state)
+                            .with("calendar-start", &self.start)
+                            .with("preventive-cost", self.preventive)
+                            .with("corrective-cost", self.corrective);
+                        let csv = audit.comments("# ") + &to_csv(&events, start, hours_per_step);
+                        self.error = export(&csv, Format::Csv).err().map(|err| err.to_string());
+                    }
+                });
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                ui.label(tr("calendar-hint"));
+            });
+    }
+}
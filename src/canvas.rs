@@ -0,0 +1,127 @@
+//! The background of the graph editor: a grid with the origin marked, and a
+//! readout of coordinates, to help find the way around a large model, align
+//! nodes and tell others where something is.
+//!
+//! Canvas coordinates are distances from the origin at zoom 1. The origin is
+//! carried along when the editor zooms, as node positions are, so a node
+//! keeps its coordinates at any zoom.
+use catppuccin_egui::Theme;
+use eframe::egui::{self, Pos2, Stroke};
+
+use crate::frames;
+use crate::i18n::tr_with;
+use crate::nodes::EditorState;
+
+/// The spacing of the grid, at zoom 1.
+pub const GRID: f32 = 40.0;
+/// Every how many grid lines one is drawn stronger.
+const MAJOR: i64 = 5;
+/// Lines closer together on screen than this are left out.
+const MIN_SPACING: f32 = 8.0;
+
+/// Moves the origin along as the editor zooms from `zoom_before`.
+pub fn follow_zoom(origin: &mut Pos2, editor: &EditorState, zoom_before: f32) {
+    if editor.pan_zoom.zoom != zoom_before {
+        *origin = frames::zoomed(editor, zoom_before, *origin);
+    }
+}
+
+/// The canvas coordinates of `pos`, which is in the same coordinates as the
+/// editor's node positions.
+pub fn coordinates(editor: &EditorState, origin: Pos2, pos: Pos2) -> egui::Vec2 {
+    (pos - origin) / editor.pan_zoom.zoom
+}
+
+/// Paints the grid and the origin, before the graph editor so they lie
+/// behind the nodes.
+pub fn paint(ui: &egui::Ui, editor: &EditorState, origin: Pos2, theme: &Theme) {
+    let rect = ui.max_rect();
+    let painter = ui.painter();
+    let center = origin + editor.pan_zoom.pan + rect.min.to_vec2();
+    let mut spacing = GRID * editor.pan_zoom.zoom;
+    let mut every = MAJOR;
+    // Zoomed far out, only the major lines are left.
+    if spacing < MIN_SPACING {
+        spacing *= MAJOR as f32;
+        every = 1;
+    }
+    if spacing < MIN_SPACING {
+        return;
+    }
+    let stroke = |i: i64| {
+        if i == 0 {
+            Stroke::new(1.5, theme.overlay0)
+        } else if i % every == 0 {
+            Stroke::new(1.0, theme.surface1)
+        } else {
+            Stroke::new(1.0, theme.surface0)
+        }
+    };
+    let first = ((rect.left() - center.x) / spacing).ceil() as i64;
+    let last = ((rect.right() - center.x) / spacing).floor() as i64;
+    for i in first..=last {
+        let x = center.x + i as f32 * spacing;
+        painter.vline(x, rect.y_range(), stroke(i));
+    }
+    let first = ((rect.top() - center.y) / spacing).ceil() as i64;
+    let last = ((rect.bottom() - center.y) / spacing).floor() as i64;
+    for i in first..=last {
+        let y = center.y + i as f32 * spacing;
+        painter.hline(rect.x_range(), y, stroke(i));
+    }
+    if rect.contains(center) {
+        painter.circle(center, 5.0, theme.base, Stroke::new(2.0, theme.overlay1));
+        painter.text(
+            center + egui::vec2(8.0, 6.0),
+            egui::Align2::LEFT_TOP,
+            "0, 0",
+            egui::FontId::proportional(12.0),
+            theme.overlay1,
+        );
+    }
+}
+
+/// Says where the pointer is, and the selected node if just one is, in the
+/// bottom right corner of the canvas.
+pub fn readout(ui: &egui::Ui, editor: &EditorState, origin: Pos2, theme: &Theme) {
+    let rect = ui.max_rect();
+    let offset = editor.pan_zoom.pan + rect.min.to_vec2();
+    let mut lines = Vec::new();
+    if let [node] = editor.selected_nodes.as_slice() {
+        if let Some(position) = editor.node_positions.get(*node) {
+            let at = coordinates(editor, origin, *position);
+            lines.push(tr_with(
+                "canvas-node",
+                &[
+                    ("node", &editor.graph[*node].label),
+                    ("x", &format!("{:.0}", at.x)),
+                    ("y", &format!("{:.0}", at.y)),
+                ],
+            ));
+        }
+    }
+    let pointer = ui
+        .ctx()
+        .pointer_hover_pos()
+        .filter(|pos| rect.contains(*pos));
+    if let Some(pointer) = pointer {
+        let at = coordinates(editor, origin, pointer - offset);
+        lines.push(tr_with(
+            "canvas-pointer",
+            &[
+                ("x", &format!("{:.0}", at.x)),
+                ("y", &format!("{:.0}", at.y)),
+            ],
+        ));
+    }
+    if lines.is_empty() {
+        return;
+    }
+    ui.painter().text(
+        rect.right_bottom() + egui::vec2(-8.0, -8.0),
+        egui::Align2::RIGHT_BOTTOM,
+        lines.join("\n"),
+        egui::FontId::monospace(12.0),
+        theme.subtext0,
+    );
+}
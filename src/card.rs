@@ -0,0 +1,94 @@
+//! A card pinned to the canvas summing up the system's results, so they
+//! stay in view while editing and go along when the diagram is exported.
+//! It can be dragged anywhere, and moves with the canvas like a node.
+use std::time::SystemTime;
+
+use catppuccin_egui::Theme;
+use eframe::egui::{self, Pos2, Stroke};
+
+use crate::audit;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::EditorState;
+use crate::results::EvaluationResult;
+
+/// Where a new card is placed, in the editor's node coordinates.
+pub const DEFAULT_POSITION: Pos2 = Pos2::new(20.0, 20.0);
+
+const PADDING: f32 = 8.0;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Results {
+    system: String,
+    mttf: f64,
+    reliability: f64,
+    availability: Option<f64>,
+    unit: String,
+}
+
+#[derive(Default)]
+pub struct SummaryCard {
+    results: Option<Results>,
+    /// When the results last changed.
+    changed: Option<SystemTime>,
+}
+
+impl SummaryCard {
+    /// Takes in this frame's summary of the active node.
+    pub fn update(&mut self, summary: Option<&EvaluationResult>) {
+        let results = summary.map(|result| Results {
+            system: result.system.clone(),
+            mttf: result.mttf,
+            reliability: result.reliability,
+            availability: Some(result.availability),
+            unit: result.time_unit.clone(),
+        });
+        if results != self.results {
+            self.changed = results.as_ref().map(|_| SystemTime::now());
+            self.results = results;
+        }
+    }
+
+    /// The card's text, one line each, or a single line saying there is
+    /// nothing to sum up.
+    pub fn lines(&self) -> Vec<String> {
+        let Some(results) = &self.results else {
+            return vec![tr("card-no-system")];
+        };
+        let mut lines = vec![
+            results.system.clone(),
+            format!("MTTF = {:.1} {}", results.mttf, results.unit),
+            format!("R(t_m) = {:.6}", results.reliability),
+        ];
+        if let Some(availability) = results.availability {
+            lines.push(format!("A(t_m) = {availability:.6}"));
+        }
+        if let Some(changed) = self.changed {
+            lines.push(tr_with("card-evaluated", &[("time", &audit::utc(changed))]));
+        }
+        lines
+    }
+
+    /// Draws the card at `position`, over the nodes, and moves it when it is
+    /// dragged.
+    pub fn show(&self, ui: &egui::Ui, editor: &EditorState, position: &mut Pos2, theme: &Theme) {
+        let offset = editor.pan_zoom.pan + ui.max_rect().min.to_vec2();
+        let painter = ui.painter();
+        let lines = self.lines();
+        let galley = painter.layout_no_wrap(
+            lines.join("\n"),
+            egui::FontId::proportional(13.0),
+            theme.text,
+        );
+        let rect = egui::Rect::from_min_size(
+            *position + offset,
+            galley.size() + egui::vec2(2.0 * PADDING, 2.0 * PADDING),
+        );
+        let response = ui
+            .interact(rect, ui.id().with("summary card"), egui::Sense::drag())
+            .on_hover_cursor(egui::CursorIcon::Grab);
+        *position += response.drag_delta();
+        let rect = rect.translate(response.drag_delta());
+        painter.rect(rect, 6.0, theme.mantle, Stroke::new(1.0, theme.surface2));
+        painter.galley(rect.min + egui::vec2(PADDING, PADDING), galley, theme.text);
+    }
+}
@@ -0,0 +1,105 @@
+//! Results at several mission times at once, such as a month, a year and
+//! five years into service.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::eval::Options;
+use crate::i18n::tr;
+use crate::nodes::{Graph, GraphState};
+use crate::results::EvaluationResult;
+use crate::rocof;
+
+/// The figures of the active node at one checkpoint; `None` past the end of
+/// the curve.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub time: usize,
+    pub reliability: Option<f64>,
+    pub availability: Option<f64>,
+    /// The expected number of failures so far, counting repaired ones;
+    /// `None` also for systems with blocks that aren't components.
+    pub failures: Option<f64>,
+}
+
+/// Reads every checkpoint off the evaluated R(t) and A(t) of `system`.
+pub fn read(
+    graph: &Graph,
+    options: &Options,
+    system: NodeId,
+    times: &[usize],
+    reliability: &[f64],
+    availability: &[f64],
+) -> Vec<Checkpoint> {
+    let intensity = rocof::intensity(graph, options, system)
+        .map(|intensity| intensity.total)
+        .unwrap_or_default();
+    times
+        .iter()
+        .map(|&time| Checkpoint {
+            time,
+            reliability: reliability.get(time).copied(),
+            availability: availability.get(time).copied(),
+            failures: (time < intensity.len()).then(|| rocof::expected_failures(&intensity, time)),
+        })
+        .collect()
+}
+
+/// How a figure reads in the table.
+pub fn format(value: Option<f64>, precision: usize) -> String {
+    value.map_or_else(|| "—".to_owned(), |value| format!("{value:.precision$}"))
+}
+
+/// The editable list of checkpoints and the figures at each, as in the
+/// summary of the active node.
+pub fn ui(
+    ui: &mut egui::Ui,
+    state: &mut GraphState,
+    summary: Option<&Result<EvaluationResult, String>>,
+) {
+    let symbol = state.settings.time_unit.symbol();
+    let results = match summary {
+        Some(Ok(result)) => &result.checkpoints,
+        Some(Err(err)) => {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+            return;
+        }
+        None => {
+            ui.colored_label(ui.visuals().error_fg_color, tr("goal-seek-no-system"));
+            return;
+        }
+    };
+
+    let mut remove = None;
+    egui::Grid::new("checkpoints")
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("t");
+            ui.strong("R(t)");
+            ui.strong("A(t)");
+            ui.strong("N(t)");
+            ui.label("");
+            ui.end_row();
+            for (i, (time, result)) in state.checkpoints.iter_mut().zip(results).enumerate() {
+                ui.add(egui::DragValue::new(time).suffix(format!(" {symbol}")));
+                ui.label(format(result.reliability, 6));
+                ui.label(format(result.availability, 6));
+                ui.label(format(result.failures, 3));
+                if ui.small_button("🗑").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(i) = remove {
+        state.checkpoints.remove(i);
+    }
+    if ui.small_button(tr("checkpoints-add")).clicked() {
+        let next = state
+            .checkpoints
+            .last()
+            .map_or(state.mission_time, |t| t * 2);
+        state.checkpoints.push(next);
+    }
+}
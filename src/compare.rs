@@ -0,0 +1,316 @@
+//! Comparing a model with a baseline, such as the version last committed:
+//! which nodes were added, removed or changed, and how they are connected.
+use std::collections::{BTreeMap, BTreeSet};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde_json::Value;
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState, ValueType};
+use crate::project;
+
+/// A change to one node, as the path of what changed, its value in the
+/// baseline and its value now.
+pub type FieldChange = (String, String, String);
+
+pub struct NodeChange {
+    pub label: String,
+    pub fields: Vec<FieldChange>,
+}
+
+#[derive(Default)]
+pub struct ModelDiff {
+    /// The labels of the nodes only the current model has.
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<NodeChange>,
+    /// Connections as "from → to.input".
+    pub connections_added: Vec<String>,
+    pub connections_removed: Vec<String>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.connections_added.is_empty()
+            && self.connections_removed.is_empty()
+    }
+}
+
+/// Compares `current` with `baseline`. Nodes are matched by their IDs, which
+/// saving keeps, so this compares versions of the same project; a node whose
+/// block changed kind counts as removed and added.
+pub fn compare(baseline: &Graph, current: &Graph) -> ModelDiff {
+    let mut diff = ModelDiff::default();
+    let same = |node: NodeId| {
+        baseline.nodes.get(node).is_some_and(|old| {
+            current
+                .nodes
+                .get(node)
+                .is_some_and(|new| old.user_data.template == new.user_data.template)
+        })
+    };
+    for (node_id, node) in &current.nodes {
+        if !same(node_id) {
+            diff.added.push(node.label.clone());
+        }
+    }
+    for (node_id, node) in &baseline.nodes {
+        if !same(node_id) {
+            diff.removed.push(node.label.clone());
+            continue;
+        }
+        let fields = node_changes(baseline, current, node_id);
+        if !fields.is_empty() {
+            diff.changed.push(NodeChange {
+                label: current[node_id].label.clone(),
+                fields,
+            });
+        }
+    }
+
+    let old = connections(baseline);
+    let new = connections(current);
+    diff.connections_added = new.difference(&old).cloned().collect();
+    diff.connections_removed = old.difference(&new).cloned().collect();
+    diff
+}
+
+fn node_changes(baseline: &Graph, current: &Graph, node_id: NodeId) -> Vec<FieldChange> {
+    let (old, new) = (&baseline[node_id], &current[node_id]);
+    let mut fields = Vec::new();
+    if old.label != new.label {
+        fields.push((tr("compare-label"), old.label.clone(), new.label.clone()));
+    }
+    let data = |graph: &Graph| {
+        let mut leaves = BTreeMap::new();
+        let value = serde_json::to_value(&graph[node_id].user_data).unwrap_or_default();
+        flatten(String::new(), &value, &mut leaves);
+        leaves
+    };
+    changed_leaves(&data(baseline), &data(current), &mut fields);
+
+    for (name, input) in &new.inputs {
+        let Some(old_input) = old.get_input(name).ok() else {
+            continue;
+        };
+        match (&baseline[old_input].value, &current[*input].value) {
+            (
+                ValueType::Parameters { parameters: old },
+                ValueType::Parameters { parameters: new },
+            ) => {
+                let leaves = |parameters| {
+                    let mut leaves = BTreeMap::new();
+                    let value = serde_json::to_value(parameters).unwrap_or_default();
+                    flatten(String::new(), &value, &mut leaves);
+                    leaves
+                };
+                changed_leaves(&leaves(old), &leaves(new), &mut fields);
+            }
+            // Curves and other values are too long to list step by step.
+            (old, new) => {
+                let (old, new) = (
+                    serde_json::to_value(old).ok(),
+                    serde_json::to_value(new).ok(),
+                );
+                if old != new {
+                    fields.push((name.clone(), tr("compare-old"), tr("compare-new")));
+                }
+            }
+        }
+    }
+    fields
+}
+
+/// The leaves of a JSON value by dotted path, such as `modes.0.shape`.
+fn flatten(path: String, value: &Value, leaves: &mut BTreeMap<String, String>) {
+    let join = |key: &str| match path.is_empty() {
+        true => key.to_owned(),
+        false => format!("{path}.{key}"),
+    };
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten(join(key), value, leaves);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                flatten(join(&i.to_string()), value, leaves);
+            }
+        }
+        leaf => {
+            leaves.insert(path, leaf.to_string());
+        }
+    }
+}
+
+fn changed_leaves(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+    fields: &mut Vec<FieldChange>,
+) {
+    let missing = || "—".to_owned();
+    let paths: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for path in paths {
+        let (old, new) = (old.get(path), new.get(path));
+        if old != new {
+            fields.push((
+                path.clone(),
+                old.cloned().unwrap_or_else(missing),
+                new.cloned().unwrap_or_else(missing),
+            ));
+        }
+    }
+}
+
+fn connections(graph: &Graph) -> BTreeSet<String> {
+    graph
+        .iter_connections()
+        .map(|(input, output)| {
+            let to = &graph[graph[input].node];
+            let name = to
+                .inputs
+                .iter()
+                .find(|(_, id)| *id == input)
+                .map_or("", |(name, _)| name.as_str());
+            format!("{} → {}.{name}", graph[graph[output].node].label, to.label)
+        })
+        .collect()
+}
+
+/// One side of the comparison: its name and graph.
+type Side = Option<(String, Graph)>;
+
+#[derive(Default)]
+pub struct Compare {
+    baseline: Side,
+    /// The model compared with the baseline; the one being edited if `None`.
+    other: Side,
+    error: Option<String>,
+}
+
+impl Compare {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("compare-title"))
+            .open(open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("compare sides")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("compare-baseline"));
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("compare-choose")).clicked() {
+                                self.pick(true);
+                            }
+                            if let Some((name, _)) = &self.baseline {
+                                ui.label(name);
+                            }
+                        });
+                        ui.end_row();
+                        ui.label(tr("compare-with"));
+                        ui.horizontal(|ui| {
+                            if ui
+                                .radio(self.other.is_none(), tr("compare-current"))
+                                .clicked()
+                            {
+                                self.other = None;
+                            }
+                            if ui.button(tr("compare-choose")).clicked() {
+                                self.pick(false);
+                            }
+                            if let Some((name, _)) = &self.other {
+                                ui.label(name);
+                            }
+                        });
+                        ui.end_row();
+                    });
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                let Some((_, baseline)) = &self.baseline else {
+                    ui.label(tr("compare-hint"));
+                    return;
+                };
+                let current = self.other.as_ref().map_or(graph, |(_, graph)| graph);
+                let diff = compare(baseline, current);
+                ui.separator();
+                if diff.is_empty() {
+                    ui.label(tr("compare-identical"));
+                    return;
+                }
+                ui.label(tr_with(
+                    "compare-summary",
+                    &[
+                        ("added", &diff.added.len()),
+                        ("removed", &diff.removed.len()),
+                        ("changed", &diff.changed.len()),
+                        ("connected", &diff.connections_added.len()),
+                        ("disconnected", &diff.connections_removed.len()),
+                    ],
+                ));
+                let theme = state.settings.theme.catppuccin();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for label in &diff.added {
+                        ui.colored_label(theme.green, format!("+ {label}"));
+                    }
+                    for label in &diff.removed {
+                        ui.colored_label(theme.red, format!("− {label}"));
+                    }
+                    for change in &diff.changed {
+                        egui::CollapsingHeader::new(format!("~ {}", change.label))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                egui::Grid::new(("compare node", &change.label))
+                                    .num_columns(3)
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for (field, old, new) in &change.fields {
+                                            ui.label(field);
+                                            ui.colored_label(theme.red, old);
+                                            ui.colored_label(theme.green, new);
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    }
+                    if !diff.connections_added.is_empty() || !diff.connections_removed.is_empty() {
+                        ui.separator();
+                        ui.strong(tr("compare-connections"));
+                        for connection in &diff.connections_added {
+                            ui.colored_label(theme.green, format!("+ {connection}"));
+                        }
+                        for connection in &diff.connections_removed {
+                            ui.colored_label(theme.red, format!("− {connection}"));
+                        }
+                    }
+                });
+            });
+    }
+
+    fn pick(&mut self, baseline: bool) {
+        match project::open_named() {
+            Ok(Some((name, project))) => {
+                let side = Some((name, project.editor.graph));
+                if baseline {
+                    self.baseline = side;
+                } else {
+                    self.other = side;
+                }
+                self.error = None;
+            }
+            Ok(None) => {}
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+}
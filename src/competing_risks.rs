@@ -0,0 +1,113 @@
+//! Breakdown of a component's failure probability by failure mode.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Bar, BarChart, Legend, Plot};
+
+use crate::eval;
+use crate::i18n::tr;
+use crate::nodes::{self, Graph, GraphState, NodeTemplate};
+use crate::tags;
+
+/// The chart resamples the curves into at most this many stacked bars.
+const BARS: usize = 200;
+
+#[derive(Default)]
+pub struct CompetingRisks {
+    component: Option<NodeId>,
+    /// Shows each mode's fraction of the failures so far instead of its
+    /// probability.
+    normalized: bool,
+}
+
+impl CompetingRisks {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("risks-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                if self.component.is_none() {
+                    self.component = state
+                        .active_node
+                        .filter(|node| graph[*node].user_data.template == NodeTemplate::Component);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr("goal-seek-component"));
+                    nodes::component_combo(ui, "risks component", graph, &mut self.component);
+                    ui.checkbox(&mut self.normalized, tr("risks-normalized"));
+                });
+                let Some(parameters) = self
+                    .component
+                    .and_then(|node| nodes::component_parameters(graph, node))
+                else {
+                    return;
+                };
+                let curves =
+                    match eval::mode_failure_probabilities(&parameters, &state.mission_profile) {
+                        Ok(curves) => curves,
+                        Err(err) => {
+                            ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                            return;
+                        }
+                    };
+                if parameters.modes.is_empty() {
+                    ui.label(tr("risks-single-mode"));
+                }
+
+                let steps = parameters.time_steps;
+                let width = steps.div_ceil(BARS).max(1);
+                let times: Vec<usize> = (width..=steps).step_by(width).collect();
+                let totals: Vec<f64> = times
+                    .iter()
+                    .map(|t| curves.iter().map(|curve| curve[*t]).sum())
+                    .collect();
+                let theme = state.settings.theme.catppuccin();
+                let names = std::iter::once(tr("mode-primary"))
+                    .chain(parameters.modes.iter().map(|mode| mode.name.clone()));
+                let mut charts: Vec<BarChart> = Vec::new();
+                for (i, (curve, name)) in curves.iter().zip(names).enumerate() {
+                    let bars = times
+                        .iter()
+                        .zip(&totals)
+                        .map(|(t, total)| {
+                            let value = match self.normalized {
+                                true if *total > 0.0 => curve[*t] / total,
+                                true => 0.0,
+                                false => curve[*t],
+                            };
+                            Bar::new(*t as f64 - 0.5 * width as f64, value).width(width as f64)
+                        })
+                        .collect();
+                    let chart = BarChart::new(bars)
+                        .color(tags::next_color(&theme, i))
+                        .name(name);
+                    let below: Vec<&BarChart> = charts.iter().collect();
+                    let chart = chart.stack_on(&below);
+                    charts.push(chart);
+                }
+
+                let symbol = state.settings.time_unit.symbol();
+                Plot::new("competing risks")
+                    .height(250.0)
+                    .include_y(0.0)
+                    .include_y(1.0)
+                    .x_axis_label(format!("t [{symbol}]"))
+                    .y_axis_label(if self.normalized {
+                        tr("mode-share")
+                    } else {
+                        "F(t)".to_owned()
+                    })
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        for chart in charts {
+                            plot_ui.bar_chart(chart);
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,176 @@
+//! Site-wide defaults from a `block.toml` file and `BLOCK_*` environment
+//! variables, for teams that want every installation to start out alike.
+//! They only fill in what the user hasn't chosen: settings saved by the app
+//! itself still win.
+//!
+//! ```toml
+//! mission_time = 8760
+//! time_unit = "hours"
+//! theme = "latte"
+//! monte_carlo_samples = 20000
+//! library_paths = ["/srv/reliability/models"]
+//! ```
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::app::APP_NAME;
+use crate::i18n::tr_with;
+use crate::nodes::GraphState;
+use crate::settings::{Settings, Theme, TimeUnit};
+
+pub const FILE_NAME: &str = "block.toml";
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub mission_time: Option<usize>,
+    pub time_unit: Option<TimeUnit>,
+    pub theme: Option<Theme>,
+    pub monte_carlo_samples: Option<usize>,
+    /// Folders of shared models; project dialogs start in the first that exists.
+    pub library_paths: Vec<PathBuf>,
+}
+
+/// The file as written, with enum values still as text so they can be
+/// matched without regard to case.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct File {
+    mission_time: Option<usize>,
+    time_unit: Option<String>,
+    theme: Option<String>,
+    monte_carlo_samples: Option<usize>,
+    library_paths: Vec<PathBuf>,
+}
+
+static CONFIG: OnceLock<(Config, Option<String>)> = OnceLock::new();
+
+/// The defaults, read on first use.
+pub fn get() -> &'static Config {
+    &loaded().0
+}
+
+/// Why the defaults couldn't all be read, if they couldn't.
+pub fn error() -> Option<&'static str> {
+    loaded().1.as_deref()
+}
+
+fn loaded() -> &'static (Config, Option<String>) {
+    CONFIG.get_or_init(|| match load() {
+        Ok(config) => (config, None),
+        Err(err) => (Config::default(), Some(err.to_string())),
+    })
+}
+
+/// `block.toml` in the working directory, or else in the app's storage
+/// folder, overridden value by value by the environment.
+pub fn load() -> anyhow::Result<Config> {
+    let path = [
+        Some(PathBuf::from(FILE_NAME)),
+        eframe::storage_dir(APP_NAME).map(|dir| dir.join(FILE_NAME)),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|path| path.is_file());
+    let mut config = match &path {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)?;
+            let file: File = toml::from_str(&text).map_err(|err| {
+                anyhow::anyhow!(tr_with(
+                    "config-invalid",
+                    &[
+                        ("file", &path.display()),
+                        ("error", &err.message().to_owned())
+                    ]
+                ))
+            })?;
+            Config {
+                mission_time: file.mission_time,
+                time_unit: file
+                    .time_unit
+                    .map(|name| variant(&TimeUnit::ALL, "time_unit", &name))
+                    .transpose()?,
+                theme: file
+                    .theme
+                    .map(|name| variant(&Theme::ALL, "theme", &name))
+                    .transpose()?,
+                monte_carlo_samples: file.monte_carlo_samples,
+                library_paths: file.library_paths,
+            }
+        }
+        None => Config::default(),
+    };
+
+    let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    if let Some(value) = var("BLOCK_MISSION_TIME") {
+        config.mission_time = Some(number("BLOCK_MISSION_TIME", &value)?);
+    }
+    if let Some(value) = var("BLOCK_TIME_UNIT") {
+        config.time_unit = Some(variant(&TimeUnit::ALL, "BLOCK_TIME_UNIT", &value)?);
+    }
+    if let Some(value) = var("BLOCK_THEME") {
+        config.theme = Some(variant(&Theme::ALL, "BLOCK_THEME", &value)?);
+    }
+    if let Some(value) = var("BLOCK_MONTE_CARLO_SAMPLES") {
+        config.monte_carlo_samples = Some(number("BLOCK_MONTE_CARLO_SAMPLES", &value)?);
+    }
+    if let Some(value) = std::env::var_os("BLOCK_LIBRARY_PATH").filter(|value| !value.is_empty()) {
+        config.library_paths = std::env::split_paths(&value).collect();
+    }
+    Ok(config)
+}
+
+/// The variant of `all` whose name is `name`, in any case.
+fn variant<T: Copy + Debug>(all: &[T], key: &str, name: &str) -> anyhow::Result<T> {
+    all.iter()
+        .copied()
+        .find(|value| format!("{value:?}").eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| invalid_value(key, name))
+}
+
+fn number(key: &str, value: &str) -> anyhow::Result<usize> {
+    value.trim().parse().map_err(|_| invalid_value(key, value))
+}
+
+fn invalid_value(key: &str, value: &str) -> anyhow::Error {
+    anyhow::anyhow!(tr_with(
+        "config-invalid-value",
+        &[("key", &key), ("value", &value)]
+    ))
+}
+
+impl Config {
+    /// The settings of a user who hasn't changed any yet.
+    pub fn settings(&self) -> Settings {
+        let mut defaults = Settings::default();
+        // New components should reach the mission time.
+        if let Some(time) = self.mission_time {
+            defaults.default_parameters.time_steps =
+                defaults.default_parameters.time_steps.max(time);
+        }
+        Settings {
+            theme: self.theme.unwrap_or(defaults.theme),
+            time_unit: self.time_unit.unwrap_or(defaults.time_unit),
+            monte_carlo_samples: self
+                .monte_carlo_samples
+                .unwrap_or(defaults.monte_carlo_samples),
+            ..defaults
+        }
+    }
+
+    /// The project state of a new, empty project.
+    pub fn graph_state(&self) -> GraphState {
+        let defaults = GraphState::default();
+        GraphState {
+            mission_time: self.mission_time.unwrap_or(defaults.mission_time),
+            ..defaults
+        }
+    }
+
+    /// Where project dialogs start, if a library is configured.
+    pub fn library(&self) -> Option<&PathBuf> {
+        self.library_paths.iter().find(|path| path.is_dir())
+    }
+}
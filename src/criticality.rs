@@ -0,0 +1,254 @@
+//! Importance measures ranking components by how much they matter to the system.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::audit::Audit;
+use crate::eval::{reliability_at, Options};
+use crate::i18n::tr;
+use crate::locale;
+use crate::nodes::{self, Forced, Graph, GraphState};
+use crate::results::ImportanceTable;
+use crate::simulate::csv_field;
+use crate::viewport;
+
+/// One component's importance to the system at a given time.
+#[derive(Clone, Copy, Debug)]
+pub struct Row {
+    pub component: NodeId,
+    pub reliability: f64,
+    /// ∂R_s/∂R_i = R_s(i working) - R_s(i failed).
+    pub birnbaum: f64,
+    /// The fraction of system unreliability that goes away if the component
+    /// is made perfect.
+    pub fussell_vesely: f64,
+    /// Birnbaum weighted by the component's unreliability relative to the
+    /// system's: the probability that the component caused a system failure.
+    pub criticality: f64,
+}
+
+/// The importance of every component `system` depends on at `time`, found by
+/// forcing each one failed and working in turn. The graph is restored before
+/// returning.
+pub fn rank(
+    graph: &mut Graph,
+    options: &Options,
+    system: NodeId,
+    time: usize,
+) -> anyhow::Result<Vec<Row>> {
+    let system_reliability = reliability_at(graph, options, system, time)?;
+    let unreliability = 1.0 - system_reliability;
+    let mut rows = Vec::new();
+    for component in nodes::upstream_components(graph, system) {
+        let reliability = reliability_at(graph, options, component, time)?;
+        let original = graph[component].user_data.forced;
+        let mut system_with = |forced| {
+            graph[component].user_data.forced = Some(forced);
+            reliability_at(graph, options, system, time)
+        };
+        let working = system_with(Forced::Working);
+        let failed = system_with(Forced::Failed);
+        graph[component].user_data.forced = original;
+        let (working, failed) = (working?, failed?);
+
+        let birnbaum = working - failed;
+        let (fussell_vesely, criticality) = if unreliability > 0.0 {
+            (
+                (working - system_reliability) / unreliability,
+                birnbaum * (1.0 - reliability) / unreliability,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        rows.push(Row {
+            component,
+            reliability,
+            birnbaum,
+            fussell_vesely,
+            criticality,
+        });
+    }
+    Ok(rows)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Column {
+    Component,
+    Reliability,
+    Birnbaum,
+    FussellVesely,
+    #[default]
+    Criticality,
+}
+
+impl Column {
+    const ALL: [Column; 5] = [
+        Column::Component,
+        Column::Reliability,
+        Column::Birnbaum,
+        Column::FussellVesely,
+        Column::Criticality,
+    ];
+
+    fn name(self) -> String {
+        match self {
+            Column::Component => tr("goal-seek-component"),
+            Column::Reliability => "R(t_m)".to_owned(),
+            Column::Birnbaum => tr("criticality-birnbaum"),
+            Column::FussellVesely => tr("criticality-fussell-vesely"),
+            Column::Criticality => tr("criticality-index"),
+        }
+    }
+
+    fn value(self, row: &Row) -> f64 {
+        match self {
+            Column::Component => 0.0,
+            Column::Reliability => row.reliability,
+            Column::Birnbaum => row.birnbaum,
+            Column::FussellVesely => row.fussell_vesely,
+            Column::Criticality => row.criticality,
+        }
+    }
+}
+
+/// Formats the table as CSV, one row per component.
+pub fn to_csv(table: &ImportanceTable) -> String {
+    let header = [
+        "component",
+        "reliability",
+        "birnbaum",
+        "fussell_vesely",
+        "criticality",
+    ];
+    let mut csv = locale::row(&header.map(str::to_owned));
+    for row in &table.rows {
+        csv.push_str(&locale::row(&[
+            csv_field(&row.component),
+            locale::number(row.reliability),
+            locale::number(row.birnbaum),
+            locale::number(row.fussell_vesely),
+            locale::number(row.criticality),
+        ]));
+    }
+    csv
+}
+
+pub struct Criticality {
+    sort_by: Column,
+    descending: bool,
+    export_error: Option<String>,
+    /// Whether the table is shown in an OS window of its own.
+    popped_out: bool,
+}
+
+impl Default for Criticality {
+    fn default() -> Self {
+        Self {
+            sort_by: Column::default(),
+            descending: true,
+            export_error: None,
+            popped_out: false,
+        }
+    }
+}
+
+impl Criticality {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        if self.popped_out {
+            viewport::show(
+                ctx,
+                "criticality",
+                tr("criticality-title"),
+                [700.0, 500.0],
+                open,
+                |ui| self.contents(ui, graph, state),
+            );
+        } else {
+            egui::Window::new(tr("criticality-title"))
+                .open(open)
+                .show(ctx, |ui| self.contents(ui, graph, state));
+        }
+    }
+
+    fn contents(&mut self, ui: &mut egui::Ui, graph: &mut Graph, state: &GraphState) {
+        let result = state
+            .active_node
+            .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))
+            .and_then(|system| rank(graph, &Options::of(state), system, state.mission_time));
+        let mut rows = match result {
+            Ok(rows) => rows,
+            Err(err) => {
+                ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                return;
+            }
+        };
+        match self.sort_by {
+            Column::Component => rows.sort_by_key(|row| graph[row.component].label.clone()),
+            column => rows.sort_by(|a, b| column.value(a).total_cmp(&column.value(b))),
+        }
+        if self.descending {
+            rows.reverse();
+        }
+
+        egui::Grid::new("criticality")
+            .num_columns(Column::ALL.len())
+            .striped(true)
+            .show(ui, |ui| {
+                for column in Column::ALL {
+                    let mut text = column.name();
+                    if column == self.sort_by {
+                        text.push_str(if self.descending { " ⏷" } else { " ⏶" });
+                    }
+                    if ui.selectable_label(column == self.sort_by, text).clicked() {
+                        if column == self.sort_by {
+                            self.descending = !self.descending;
+                        } else {
+                            self.sort_by = column;
+                            self.descending = column != Column::Component;
+                        }
+                    }
+                }
+                ui.end_row();
+                for row in &rows {
+                    ui.label(&graph[row.component].label);
+                    for column in &Column::ALL[1..] {
+                        ui.label(format!("{:.4}", column.value(row)));
+                    }
+                    ui.end_row();
+                }
+            });
+
+        let table = ImportanceTable::new(graph, state.mission_time, &rows);
+        ui.horizontal(|ui| {
+            if ui.button(tr("plot-copy-data")).clicked() {
+                ui.output_mut(|o| o.copied_text = to_csv(&table));
+            }
+            if ui.button(tr("criticality-export")).clicked() {
+                self.export_error = export(&table, &Audit::new(graph, state)).err();
+            }
+            ui.toggle_value(&mut self.popped_out, "⧉")
+                .on_hover_text(tr("criticality-pop-out"));
+        });
+        if let Some(err) = &self.export_error {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+        ui.label(tr("criticality-hint"));
+    }
+}
+
+/// Asks for a file name and writes the table there, headed by `audit`.
+fn export(table: &ImportanceTable, audit: &Audit) -> Result<(), String> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .set_file_name("criticality.csv")
+        .save_file()
+    else {
+        return Ok(());
+    };
+    std::fs::write(path, audit.comments("# ") + &to_csv(table)).map_err(|err| err.to_string())
+}
@@ -0,0 +1,132 @@
+//! A system's time to failure saved as a distribution, so a whole model can
+//! be dropped into a higher-level one as a single component.
+//!
+//! The file holds F(t) at every time step, which imports exactly as an
+//! empirical node, and the least squares Weibull for tools that want a
+//! closed form.
+use std::collections::BTreeMap;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::equivalent::{self, Family};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, EditorState, GraphState, NodeTemplate, ValueType};
+use crate::settings::TimeUnit;
+
+const VERSION: u32 = 1;
+
+/// A Weibull fitted to a curve, and how far it strays from it.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Fit {
+    pub shape: f64,
+    pub scale: f64,
+    /// The root mean square difference in R(t) over the curve's steps.
+    pub rmse: f64,
+}
+
+/// The least squares Weibull through the curve's probability plot.
+pub fn fit(curve: &[f64]) -> Option<Fit> {
+    let fitted = equivalent::fit(Family::Weibull, curve)?;
+    Some(Fit {
+        shape: fitted.first,
+        scale: fitted.second,
+        rmse: fitted.rmse,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Distribution {
+    pub version: u32,
+    pub name: String,
+    /// The unit of one time step.
+    pub time_unit: TimeUnit,
+    /// F(t) = 1 − R(t) at each time step from zero.
+    pub cdf: Vec<f64>,
+    pub weibull: Option<Fit>,
+    /// The settings the curve was calculated with.
+    #[serde(default)]
+    pub audit: BTreeMap<String, String>,
+}
+
+impl Distribution {
+    pub fn new(name: String, curve: &[f64], time_unit: TimeUnit) -> Self {
+        Self {
+            version: VERSION,
+            name,
+            time_unit,
+            cdf: curve.iter().map(|r| 1.0 - r).collect(),
+            weibull: fit(curve),
+            audit: BTreeMap::new(),
+        }
+    }
+
+    pub fn reliability(&self) -> Vec<f64> {
+        self.cdf.iter().map(|f| 1.0 - f).collect()
+    }
+
+    fn from_json(text: &str) -> anyhow::Result<Self> {
+        let distribution: Self = serde_json::from_str(text)
+            .map_err(|err| anyhow::anyhow!(tr_with("distribution-invalid", &[("error", &err)])))?;
+        if distribution.version != VERSION {
+            anyhow::bail!(tr_with(
+                "distribution-unsupported-version",
+                &[("version", &distribution.version), ("supported", &VERSION)]
+            ));
+        }
+        if distribution.cdf.is_empty() {
+            anyhow::bail!(tr("error-empty-curve"));
+        }
+        Ok(distribution)
+    }
+}
+
+/// Asks where to save `distribution`. Returns whether it was saved.
+pub fn export(distribution: &Distribution) -> anyhow::Result<bool> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name(format!("{}.json", distribution.name))
+        .save_file()
+    else {
+        return Ok(false);
+    };
+    std::fs::write(path, serde_json::to_string_pretty(distribution)?)?;
+    Ok(true)
+}
+
+/// Asks for a distribution file and reads it.
+pub fn open() -> anyhow::Result<Option<Distribution>> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .pick_file()
+    else {
+        return Ok(None);
+    };
+    Distribution::from_json(&std::fs::read_to_string(path)?).map(Some)
+}
+
+/// Adds an empirical node carrying `distribution`'s curve at `position`.
+pub fn add_node(
+    editor: &mut EditorState,
+    user_state: &mut GraphState,
+    distribution: &Distribution,
+    position: egui::Pos2,
+    viewport: egui::Rect,
+) -> NodeId {
+    let node_id = nodes::add_node_at(
+        editor,
+        user_state,
+        NodeTemplate::Empirical,
+        position,
+        viewport,
+    );
+    let graph = &mut editor.graph;
+    graph[node_id].label = distribution.name.clone();
+    if let Ok(input) = graph[node_id].get_input("curve") {
+        graph[input].value = ValueType::Reliability {
+            curve: distribution.reliability(),
+        };
+    }
+    node_id
+}
@@ -0,0 +1,326 @@
+//! Latin hypercube studies over several uncertain component parameters.
+use std::collections::HashMap;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Line, Plot, PlotPoint, Points, Text};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::bayes::Ranges;
+use crate::eval::{evaluate_curve, mttf, Options, OutputsCache};
+use crate::i18n::tr;
+use crate::nodes::{self, Graph, GraphState, Parameter};
+
+/// A parameter varied uniformly between `low` and `high`.
+struct Factor {
+    component: Option<NodeId>,
+    parameter: Parameter,
+    low: f64,
+    high: f64,
+}
+
+impl Default for Factor {
+    fn default() -> Self {
+        Self {
+            component: None,
+            parameter: Parameter::default(),
+            low: 5_000.0,
+            high: 15_000.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum View {
+    #[default]
+    ParallelCoordinates,
+    Scatter,
+}
+
+pub struct Study {
+    factors: Vec<Factor>,
+    samples: usize,
+    view: View,
+    /// Columns of the scatter view.
+    x: usize,
+    y: usize,
+    /// Column names: one per factor, then R(mission) and MTTF.
+    columns: Vec<String>,
+    /// One row per sample, laid out like `columns`.
+    rows: Vec<Vec<f64>>,
+    /// The seed the rows were drawn with.
+    seed: u64,
+    error: Option<String>,
+}
+
+impl Default for Study {
+    fn default() -> Self {
+        Self {
+            factors: vec![Factor::default()],
+            samples: 100,
+            view: View::default(),
+            x: 0,
+            y: 0,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            seed: 0,
+            error: None,
+        }
+    }
+}
+
+/// Draws `samples` points from the unit hypercube of the given dimension so
+/// that each axis has exactly one point in each of `samples` equal strata.
+fn latin_hypercube(rng: &mut impl Rng, dimensions: usize, samples: usize) -> Vec<Vec<f64>> {
+    let mut points = vec![vec![0.0; dimensions]; samples];
+    let mut strata: Vec<usize> = (0..samples).collect();
+    for axis in 0..dimensions {
+        strata.shuffle(rng);
+        for (point, stratum) in points.iter_mut().zip(&strata) {
+            point[axis] = (*stratum as f64 + rng.gen::<f64>()) / samples as f64;
+        }
+    }
+    points
+}
+
+impl Study {
+    /// Replaces the factors with the given parameters and ranges, keeping
+    /// the rest of the study.
+    pub fn set_factors(&mut self, ranges: Ranges) {
+        self.factors = ranges
+            .into_iter()
+            .map(|(component, parameter, low, high)| Factor {
+                component: Some(component),
+                parameter,
+                low,
+                high,
+            })
+            .collect();
+        self.rows.clear();
+    }
+
+    /// Evaluates the system at each Latin hypercube sample, restoring the
+    /// components' parameters afterwards.
+    fn run(&mut self, graph: &mut Graph, system: NodeId, state: &GraphState) {
+        self.rows.clear();
+        self.error = None;
+        let factors: Vec<(NodeId, &Factor)> = self
+            .factors
+            .iter()
+            .filter_map(|factor| Some((factor.component?, factor)))
+            .collect();
+        self.columns = factors
+            .iter()
+            .map(|(node, factor)| format!("{}.{}", graph[*node].label, factor.parameter.name()))
+            .chain(["R(t_m)".to_owned(), "MTTF".to_owned()])
+            .collect();
+        let originals: HashMap<NodeId, nodes::NodeParameters> = factors
+            .iter()
+            .filter_map(|(node, _)| Some((*node, nodes::component_parameters(graph, *node)?)))
+            .collect();
+
+        self.seed = state.settings.seed;
+        let mut rng = state.settings.rng();
+        let options = Options::of(state);
+        for point in latin_hypercube(&mut rng, factors.len(), self.samples) {
+            let mut row: Vec<f64> = Vec::with_capacity(self.columns.len());
+            for ((node, factor), u) in factors.iter().zip(point) {
+                let value = factor.low + u * (factor.high - factor.low);
+                if let Some(parameters) = nodes::component_parameters_mut(graph, *node) {
+                    parameters.set(factor.parameter, value);
+                }
+                row.push(value);
+            }
+            match evaluate_curve(graph, &options, system, &mut OutputsCache::new()) {
+                Ok(curve) => {
+                    row.push(curve.get(state.mission_time).copied().unwrap_or(f64::NAN));
+                    row.push(mttf(&curve));
+                    self.rows.push(row);
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    self.rows.clear();
+                    break;
+                }
+            }
+        }
+
+        for (node, original) in originals {
+            if let Some(parameters) = nodes::component_parameters_mut(graph, node) {
+                *parameters = original;
+            }
+        }
+        self.x = 0;
+        self.y = self.columns.len().saturating_sub(2);
+    }
+
+    /// Formats the samples as tab-separated rows, headed by the seed they
+    /// were drawn with.
+    fn to_tsv(&self) -> String {
+        let mut tsv = format!("# seed = {}\n{}\n", self.seed, self.columns.join("\t"));
+        for row in &self.rows {
+            let row: Vec<String> = row.iter().map(f64::to_string).collect();
+            tsv.push_str(&row.join("\t"));
+            tsv.push('\n');
+        }
+        tsv
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("doe-title"))
+            .open(open)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                let mut remove = None;
+                egui::Grid::new("doe factors")
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        ui.strong(tr("goal-seek-component"));
+                        ui.strong(tr("goal-seek-parameter"));
+                        ui.strong(tr("sweep-range"));
+                        ui.end_row();
+                        for (i, factor) in self.factors.iter_mut().enumerate() {
+                            nodes::component_combo(
+                                ui,
+                                &format!("doe component {i}"),
+                                graph,
+                                &mut factor.component,
+                            );
+                            factor.parameter.combo(ui, &format!("doe parameter {i}"));
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut factor.low).speed(1.0));
+                                ui.label("–");
+                                ui.add(egui::DragValue::new(&mut factor.high).speed(1.0));
+                            });
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = remove {
+                    self.factors.remove(i);
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("doe-add-factor")).clicked() {
+                        self.factors.push(Factor::default());
+                    }
+                    ui.separator();
+                    ui.label(tr("doe-samples"));
+                    ui.add(egui::DragValue::new(&mut self.samples).clamp_range(2..=10_000));
+                });
+
+                let ready = state.active_node.is_some()
+                    && self.factors.iter().any(|factor| factor.component.is_some());
+                if state.active_node.is_none() {
+                    ui.label(tr("goal-seek-no-system"));
+                }
+                if ui
+                    .add_enabled(ready, egui::Button::new(tr("doe-run")))
+                    .clicked()
+                {
+                    if let Some(system) = state.active_node {
+                        self.run(graph, system, state);
+                    }
+                }
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                if self.rows.is_empty() {
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} {}", tr("settings-seed"), self.seed));
+                    if ui.button(tr("plot-copy-data")).clicked() {
+                        ui.output_mut(|o| o.copied_text = self.to_tsv());
+                    }
+                    ui.separator();
+                    ui.radio_value(
+                        &mut self.view,
+                        View::ParallelCoordinates,
+                        tr("doe-parallel-coordinates"),
+                    );
+                    ui.radio_value(&mut self.view, View::Scatter, tr("doe-scatter"));
+                    if self.view == View::Scatter {
+                        ui.separator();
+                        column_combo(ui, "doe x", &self.columns, &mut self.x);
+                        ui.label("→");
+                        column_combo(ui, "doe y", &self.columns, &mut self.y);
+                    }
+                });
+                let color = state.settings.theme.catppuccin().blue;
+                match self.view {
+                    View::ParallelCoordinates => {
+                        let ranges = column_ranges(&self.rows, self.columns.len());
+                        Plot::new("doe parallel coordinates")
+                            .height(300.0)
+                            .show_axes([false, true])
+                            .show(ui, |plot_ui| {
+                                for row in &self.rows {
+                                    let points: Vec<[f64; 2]> = row
+                                        .iter()
+                                        .zip(&ranges)
+                                        .enumerate()
+                                        .map(|(i, (value, (lo, hi)))| {
+                                            let span = if hi > lo { hi - lo } else { 1.0 };
+                                            [i as f64, (value - lo) / span]
+                                        })
+                                        .collect();
+                                    plot_ui
+                                        .line(Line::new(points).color(color.gamma_multiply(0.3)));
+                                }
+                                for (i, name) in self.columns.iter().enumerate() {
+                                    plot_ui.text(Text::new(PlotPoint::new(i as f64, 1.05), name));
+                                }
+                            });
+                    }
+                    View::Scatter => {
+                        let points: Vec<[f64; 2]> = self
+                            .rows
+                            .iter()
+                            .map(|row| [row[self.x], row[self.y]])
+                            .collect();
+                        Plot::new("doe scatter")
+                            .height(300.0)
+                            .x_axis_label(&self.columns[self.x])
+                            .y_axis_label(&self.columns[self.y])
+                            .show(ui, |plot_ui| {
+                                plot_ui.points(Points::new(points).radius(2.0).color(color));
+                            });
+                    }
+                }
+            });
+    }
+}
+
+fn column_combo(ui: &mut egui::Ui, id_source: &str, columns: &[String], selected: &mut usize) {
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(&columns[*selected])
+        .show_ui(ui, |ui| {
+            for (i, name) in columns.iter().enumerate() {
+                ui.selectable_value(selected, i, name);
+            }
+        });
+}
+
+/// The minimum and maximum of each column, for normalising the parallel
+/// coordinates.
+fn column_ranges(rows: &[Vec<f64>], columns: usize) -> Vec<(f64, f64)> {
+    (0..columns)
+        .map(|i| {
+            rows.iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), row| {
+                    (lo.min(row[i]), hi.max(row[i]))
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,457 @@
+//! GraphViz DOT files, for starting a model from a diagram drawn elsewhere
+//! and for laying one out with GraphViz's engines.
+//!
+//! Only the structure is read: statements naming nodes and edges, with
+//! their `label` and `type` attributes. Subgraphs are flattened and every
+//! other statement is skipped.
+use std::collections::HashMap;
+
+use egui_node_graph2::NodeId;
+
+use crate::audit::Audit;
+use crate::examples::Builder;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, EditorState, Graph, GraphState, NodeTemplate};
+use crate::topology;
+
+/// A DOT graph reduced to its nodes, in order of appearance, and edges.
+#[derive(Debug, Default, PartialEq)]
+pub struct Dot {
+    /// Each node's ID and attributes.
+    pub nodes: Vec<(String, HashMap<String, String>)>,
+    /// (from, to) by node ID.
+    pub edges: Vec<(String, String)>,
+}
+
+impl Dot {
+    fn node(&mut self, id: &str) -> usize {
+        match self.nodes.iter().position(|(node, _)| node == id) {
+            Some(index) => index,
+            None => {
+                self.nodes.push((id.to_owned(), HashMap::new()));
+                self.nodes.len() - 1
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Id(String),
+    Edge,
+    Punct(char),
+}
+
+fn tokenize(text: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '#' => {
+                // Preprocessor lines, treated as comments.
+                while chars.next_if(|c| *c != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => anyhow::bail!(tr("dot-unterminated")),
+                    }
+                }
+            }
+            '-' if matches!(chars.peek(), Some('>' | '-')) => {
+                chars.next();
+                tokens.push(Token::Edge);
+            }
+            '"' => {
+                let mut id = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some('"') => id.push('"'),
+                            Some('\n') => {}
+                            Some(c) => {
+                                id.push('\\');
+                                id.push(c);
+                            }
+                            None => anyhow::bail!(tr("dot-unterminated")),
+                        },
+                        Some('"') => break,
+                        Some(c) => id.push(c),
+                        None => anyhow::bail!(tr("dot-unterminated")),
+                    }
+                }
+                tokens.push(Token::Id(id));
+            }
+            '<' => {
+                // HTML labels: kept as the raw markup between the brackets.
+                let mut depth = 1;
+                let mut id = String::new();
+                while depth > 0 {
+                    let Some(c) = chars.next() else {
+                        anyhow::bail!(tr("dot-unterminated"));
+                    };
+                    match c {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth > 0 {
+                        id.push(c);
+                    }
+                }
+                tokens.push(Token::Id(id));
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' => tokens.push(Token::Punct(c)),
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    id.push(c);
+                }
+                tokens.push(Token::Id(id));
+            }
+            c => anyhow::bail!(tr_with("dot-unexpected", &[("character", &c)])),
+        }
+    }
+    Ok(tokens)
+}
+
+type Tokens = std::iter::Peekable<std::vec::IntoIter<Token>>;
+
+/// Reads the nodes and edges of a DOT graph.
+pub fn parse(text: &str) -> anyhow::Result<Dot> {
+    let mut tokens = tokenize(text)?.into_iter().peekable();
+    // The header, `[strict] (graph | digraph) [ID] {`.
+    loop {
+        match tokens.next() {
+            Some(Token::Punct('{')) => break,
+            Some(Token::Id(_)) => {}
+            _ => anyhow::bail!(tr("dot-no-graph")),
+        }
+    }
+    let mut dot = Dot::default();
+    let mut depth = 1;
+    while depth > 0 {
+        let Some(token) = tokens.next() else {
+            anyhow::bail!(tr("dot-unterminated"));
+        };
+        let first = match token {
+            Token::Punct('{') => {
+                depth += 1;
+                continue;
+            }
+            Token::Punct('}') => {
+                depth -= 1;
+                continue;
+            }
+            Token::Id(keyword) if keyword.eq_ignore_ascii_case("subgraph") => {
+                // Subgraphs are flattened; their braces are counted above.
+                tokens.next_if(|token| matches!(token, Token::Id(_)));
+                continue;
+            }
+            Token::Id(keyword)
+                if ["graph", "node", "edge"]
+                    .iter()
+                    .any(|k| keyword.eq_ignore_ascii_case(k)) =>
+            {
+                // Defaults carry no structure.
+                attributes(&mut tokens)?;
+                continue;
+            }
+            Token::Id(id) => {
+                if tokens.next_if_eq(&Token::Punct('=')).is_some() {
+                    // A graph attribute, `ID = ID`.
+                    tokens.next();
+                    continue;
+                }
+                skip_port(&mut tokens);
+                vec![id]
+            }
+            _ => continue,
+        };
+        // `a -> b -> { c d }`: each operand is one node or a group.
+        let mut operands = vec![first];
+        while tokens.next_if_eq(&Token::Edge).is_some() {
+            operands.push(operand(&mut tokens)?);
+        }
+        let attrs = attributes(&mut tokens)?;
+        for id in operands.iter().flatten() {
+            dot.node(id);
+        }
+        if let [node] = operands.as_slice() {
+            let index = dot.node(&node[0]);
+            dot.nodes[index].1.extend(attrs);
+        }
+        for pair in operands.windows(2) {
+            for from in &pair[0] {
+                for to in &pair[1] {
+                    dot.edges.push((from.clone(), to.clone()));
+                }
+            }
+        }
+    }
+    Ok(dot)
+}
+
+/// Skips a port, `:port[:compass]`, after a node ID.
+fn skip_port(tokens: &mut Tokens) {
+    while tokens.next_if_eq(&Token::Punct(':')).is_some() {
+        tokens.next();
+    }
+}
+
+/// The node ID, or IDs of a `{ ... }` group, after an edge operator.
+fn operand(tokens: &mut Tokens) -> anyhow::Result<Vec<String>> {
+    match tokens.next() {
+        Some(Token::Id(id)) => {
+            skip_port(tokens);
+            Ok(vec![id])
+        }
+        Some(Token::Punct('{')) => {
+            let mut ids = Vec::new();
+            loop {
+                match tokens.next() {
+                    Some(Token::Punct('}')) => return Ok(ids),
+                    Some(Token::Id(id)) if !id.eq_ignore_ascii_case("subgraph") => {
+                        skip_port(tokens);
+                        ids.push(id);
+                    }
+                    Some(_) => {}
+                    None => anyhow::bail!(tr("dot-unterminated")),
+                }
+            }
+        }
+        _ => anyhow::bail!(tr("dot-edge-target")),
+    }
+}
+
+/// The `name = value` pairs of the attribute lists that follow, if any:
+/// `[a=1, b=2][c=3]`.
+fn attributes(tokens: &mut Tokens) -> anyhow::Result<HashMap<String, String>> {
+    let mut attrs = HashMap::new();
+    while tokens.next_if_eq(&Token::Punct('[')).is_some() {
+        loop {
+            match tokens.next() {
+                Some(Token::Punct(']')) => break,
+                Some(Token::Id(name)) => {
+                    if tokens.next_if_eq(&Token::Punct('=')).is_some() {
+                        if let Some(Token::Id(value)) = tokens.next() {
+                            attrs.insert(name.to_lowercase(), value);
+                        }
+                    }
+                }
+                Some(_) => {}
+                None => anyhow::bail!(tr("dot-unterminated")),
+            }
+        }
+    }
+    Ok(attrs)
+}
+
+/// Builds the diagram a DOT graph describes, with edges pointing the way
+/// reliability flows. Nodes nothing points to become components with the
+/// default parameters. The others become series blocks, or parallel or
+/// standby ones given `type=parallel` or `type=standby`; more than two inputs are combined by a chain of
+/// blocks, the last taking the node's label.
+pub fn build(dot: &Dot, user_state: &mut GraphState) -> EditorState {
+    let index: HashMap<&str, usize> = dot
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _))| (id.as_str(), i))
+        .collect();
+    let mut inputs: Vec<Vec<usize>> = vec![Vec::new(); dot.nodes.len()];
+    for (from, to) in &dot.edges {
+        inputs[index[to.as_str()]].push(index[from.as_str()]);
+    }
+    // Columns by the longest path from a component, so edges point right.
+    let mut columns = vec![0; dot.nodes.len()];
+    for _ in 0..dot.nodes.len() {
+        let mut changed = false;
+        for (to, from) in inputs.iter().enumerate() {
+            let column = from
+                .iter()
+                .map(|from| columns[*from] + 1)
+                .max()
+                .unwrap_or(0);
+            if column > columns[to] {
+                columns[to] = column;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let mut model = Builder::new(user_state);
+    let mut rows: HashMap<i32, usize> = HashMap::new();
+    let mut next_row = |column: i32| {
+        let row = rows.entry(column).or_default();
+        *row += 1;
+        *row - 1
+    };
+    let label = |i: usize| {
+        let (id, attrs) = &dot.nodes[i];
+        attrs.get("label").cloned().unwrap_or_else(|| id.clone())
+    };
+    let template = |i: usize| match dot.nodes[i].1.get("type").map(|t| t.to_lowercase()) {
+        Some(t) if t == "parallel" => NodeTemplate::Parallel,
+        Some(t) if t == "standby" => NodeTemplate::Standby,
+        _ => NodeTemplate::Series,
+    };
+    // The node made for each DOT node, by index.
+    let mut built = Vec::with_capacity(dot.nodes.len());
+    for i in 0..dot.nodes.len() {
+        let column = 2 * columns[i] as i32;
+        let node = if inputs[i].is_empty() {
+            model.node(NodeTemplate::Component, label(i), column, next_row(column))
+        } else {
+            model.node(template(i), label(i), column, next_row(column))
+        };
+        built.push(node);
+    }
+    for (i, from) in inputs.iter().enumerate() {
+        let Some((last, first)) = from.split_last() else {
+            continue;
+        };
+        // Blocks take two inputs, so all but the last go into a chain of
+        // blocks of the same kind placed just before the node.
+        let column = 2 * columns[i] as i32 - 1;
+        let mut joined = None;
+        for from in first {
+            joined = Some(match joined {
+                None => built[*from],
+                Some(joined) => {
+                    let block =
+                        model.node(template(i), template(i).name(), column, next_row(column));
+                    model.connect(joined, block, "A");
+                    model.connect(built[*from], block, "B");
+                    block
+                }
+            });
+        }
+        match joined {
+            Some(joined) => {
+                model.connect(joined, built[i], "A");
+                model.connect(built[*last], built[i], "B");
+            }
+            // A single input is left for the other to be connected.
+            None => model.connect(built[*last], built[i], "A"),
+        }
+    }
+    model.finish()
+}
+
+/// Asks for a DOT file and builds the diagram it describes, or `None` if the
+/// dialog was cancelled.
+pub fn open(user_state: &mut GraphState) -> anyhow::Result<Option<EditorState>> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("GraphViz DOT", &["dot", "gv"])
+        .pick_file()
+    else {
+        return Ok(None);
+    };
+    let dot = parse(&std::fs::read_to_string(path)?)?;
+    if dot.nodes.is_empty() {
+        anyhow::bail!(tr("dot-empty"));
+    }
+    Ok(Some(build(&dot, user_state)))
+}
+
+/// A DOT string literal.
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// The label of a node in the exported graph: its name, then its kind, or
+/// for components the Weibull parameters and any MTTR.
+fn label(graph: &Graph, node_id: NodeId, name: &str) -> String {
+    let template = graph[node_id].user_data.template;
+    let details = match nodes::component_parameters(graph, node_id) {
+        Some(parameters) => {
+            let mut details = format!("β = {}, η = {}", parameters.shape, parameters.scale);
+            if parameters.mttr > 0.0 {
+                details.push_str(&format!("\nMTTR = {}", parameters.mttr));
+            }
+            details
+        }
+        None => template.name(),
+    };
+    format!("{name}\n{details}")
+}
+
+/// The diagram as a DOT digraph, with edges pointing the way reliability
+/// flows. Blocks carry a `type` attribute so that `parse` reads them back
+/// as the same kind. A `caption`, such as the summary card, labels the
+/// graph.
+pub fn encode(graph: &Graph, caption: Option<&[String]>) -> String {
+    let names = topology::names(graph);
+    let mut dot = String::from("digraph model {\n    rankdir=LR;\n");
+    if let Some(caption) = caption {
+        dot.push_str(&format!(
+            "    label={};\n    labelloc=t;\n    labeljust=l;\n",
+            quote(&caption.join("\n"))
+        ));
+    }
+    for (node_id, name) in &names {
+        let template = graph[*node_id].user_data.template;
+        let shape = match template {
+            NodeTemplate::Component
+            | NodeTemplate::Empirical
+            | NodeTemplate::Human
+            | NodeTemplate::Software
+            | NodeTemplate::OneShot
+            | NodeTemplate::PetriNet => "box",
+            _ => "ellipse",
+        };
+        let kind = match template {
+            NodeTemplate::Series => ", type=series",
+            NodeTemplate::Parallel => ", type=parallel",
+            NodeTemplate::Standby => ", type=standby",
+            _ => "",
+        };
+        dot.push_str(&format!(
+            "    {} [label={}, shape={shape}{kind}];\n",
+            quote(name),
+            quote(&label(graph, *node_id, name))
+        ));
+    }
+    let names: HashMap<NodeId, String> = names.into_iter().collect();
+    for (from, to, input) in topology::edges(graph) {
+        if let (Some(from), Some(to)) = (names.get(&from), names.get(&to)) {
+            dot.push_str(&format!(
+                "    {} -> {} [headlabel={}];\n",
+                quote(from),
+                quote(to),
+                quote(&input)
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Asks for a file name and writes the diagram there as DOT, headed by
+/// `audit` in comments. Returns whether it was written.
+pub fn export(graph: &Graph, caption: Option<&[String]>, audit: &Audit) -> anyhow::Result<bool> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("GraphViz DOT", &["dot", "gv"])
+        .set_file_name("model.dot")
+        .save_file()
+    else {
+        return Ok(false);
+    };
+    std::fs::write(path, audit.comments("// ") + &encode(graph, caption))?;
+    Ok(true)
+}
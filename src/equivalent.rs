@@ -0,0 +1,184 @@
+//! A single distribution fitted to the active node's curve, for summing up
+//! a complex diagram as one equivalent component.
+//!
+//! Each family is fitted by least squares on its probability plot, where
+//! its R(t) is a straight line, which needs no failure times and weighs the
+//! whole curve rather than only the steps it was sampled at most densely.
+use eframe::egui;
+use statrs::distribution::{ContinuousCDF, Normal};
+
+use crate::i18n::{tr, tr_with};
+use crate::settings::TimeUnit;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Family {
+    #[default]
+    Weibull,
+    Exponential,
+    Lognormal,
+}
+
+impl Family {
+    pub const ALL: [Family; 3] = [Family::Weibull, Family::Exponential, Family::Lognormal];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Family::Weibull => "family-weibull",
+            Family::Exponential => "family-exponential",
+            Family::Lognormal => "family-lognormal",
+        })
+    }
+}
+
+/// A fitted distribution: β and η for the Weibull, η = 1/λ for the
+/// exponential (with a unit shape), and μ and σ of ln t for the lognormal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fitted {
+    pub family: Family,
+    pub first: f64,
+    pub second: f64,
+    /// The root mean square and largest differences in R(t) over the curve.
+    pub rmse: f64,
+    pub max_error: f64,
+}
+
+impl Fitted {
+    pub fn reliability(&self, t: f64) -> f64 {
+        match self.family {
+            Family::Weibull | Family::Exponential => (-(t / self.second).powf(self.first)).exp(),
+            Family::Lognormal if t <= 0.0 => 1.0,
+            Family::Lognormal => 1.0 - standard_normal().cdf((t.ln() - self.first) / self.second),
+        }
+    }
+}
+
+fn standard_normal() -> Normal {
+    Normal::new(0.0, 1.0).expect("the standard normal is valid")
+}
+
+/// The slope and intercept of the least squares line through `points`.
+fn least_squares(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let slope = sxy / sxx;
+    (slope.is_finite() && slope > 0.0).then_some((slope, mean_y - slope * mean_x))
+}
+
+/// `family` fitted to `curve`, or `None` if fewer than two steps lie
+/// strictly between certain survival and certain failure.
+pub fn fit(family: Family, curve: &[f64]) -> Option<Fitted> {
+    let steps: Vec<(f64, f64)> = curve
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, r)| **r > 0.0 && **r < 1.0)
+        .map(|(t, r)| (t as f64, *r))
+        .collect();
+    if steps.len() < 2 {
+        return None;
+    }
+    let (first, second) = match family {
+        // ln(−ln R) = β ln t − β ln η
+        Family::Weibull => {
+            let points: Vec<(f64, f64)> = steps
+                .iter()
+                .map(|(t, r)| (t.ln(), (-r.ln()).ln()))
+                .collect();
+            let (shape, intercept) = least_squares(&points)?;
+            (shape, (-intercept / shape).exp())
+        }
+        // −ln R = t / η, a line through the origin.
+        Family::Exponential => {
+            let tt: f64 = steps.iter().map(|(t, _)| t * t).sum();
+            let ty: f64 = steps.iter().map(|(t, r)| t * -r.ln()).sum();
+            (1.0, tt / ty)
+        }
+        // Φ⁻¹(1 − R) = (ln t − μ) / σ
+        Family::Lognormal => {
+            let normal = standard_normal();
+            let points: Vec<(f64, f64)> = steps
+                .iter()
+                .map(|(t, r)| (t.ln(), normal.inverse_cdf(1.0 - r)))
+                .collect();
+            let (slope, intercept) = least_squares(&points)?;
+            (-intercept / slope, 1.0 / slope)
+        }
+    };
+    if !first.is_finite() || !second.is_finite() || second <= 0.0 {
+        return None;
+    }
+    let mut fitted = Fitted {
+        family,
+        first,
+        second,
+        rmse: 0.0,
+        max_error: 0.0,
+    };
+    let errors: Vec<f64> = curve
+        .iter()
+        .enumerate()
+        .map(|(t, r)| (r - fitted.reliability(t as f64)).abs())
+        .collect();
+    fitted.rmse = (errors.iter().map(|e| e * e).sum::<f64>() / errors.len() as f64).sqrt();
+    fitted.max_error = errors.iter().copied().fold(0.0, f64::max);
+    Some(fitted)
+}
+
+#[derive(Default)]
+pub struct Equivalent {
+    family: Family,
+    /// The last fit, or `None` after one failed.
+    fitted: Option<Option<Fitted>>,
+}
+
+impl Equivalent {
+    pub fn ui(&mut self, ui: &mut egui::Ui, curve: &[f64], time_unit: TimeUnit) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("equivalent family")
+                .selected_text(self.family.name())
+                .show_ui(ui, |ui| {
+                    for family in Family::ALL {
+                        ui.selectable_value(&mut self.family, family, family.name());
+                    }
+                });
+            if ui.button(tr("equivalent-fit")).clicked() {
+                self.fitted = Some(fit(self.family, curve));
+            }
+        });
+        let Some(fitted) = self.fitted else {
+            return;
+        };
+        let Some(fitted) = fitted else {
+            ui.colored_label(ui.visuals().warn_fg_color, tr("equivalent-failed"));
+            return;
+        };
+        let symbol = time_unit.symbol();
+        let parameters = match fitted.family {
+            Family::Weibull => format!(
+                "β = {:.4} · η = {:.1} {symbol}",
+                fitted.first, fitted.second
+            ),
+            Family::Exponential => format!(
+                "λ = {:.3e} /{symbol} · MTTF = {:.1} {symbol}",
+                1.0 / fitted.second,
+                fitted.second
+            ),
+            Family::Lognormal => format!("μ = {:.4} · σ = {:.4}", fitted.first, fitted.second),
+        };
+        ui.label(format!("{} · {parameters}", fitted.family.name()));
+        ui.label(tr_with(
+            "equivalent-error",
+            &[
+                ("rmse", &format!("{:.5}", fitted.rmse)),
+                ("max", &format!("{:.5}", fitted.max_error)),
+            ],
+        ))
+        .on_hover_text(tr("equivalent-error-hint"));
+    }
+}
@@ -0,0 +1,1877 @@
+//! Evaluation of reliability curves through the block diagram.
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use egui_node_graph2::{EguiGraphError, InputId, NodeId, OutputId};
+use statrs::distribution::Weibull;
+use statrs::StatsError;
+
+use crate::audit;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{
+    self, Dormancy, Graph, GraphState, Inspection, NodeData, NodeParameters, NodeTemplate, Repair,
+    Severity, ValueType,
+};
+use crate::profile::MissionProfile;
+use crate::settings::{DisabledNodes, Precision};
+
+/// The curves evaluated so far, by the output carrying them. Clearing keeps
+/// their buffers for the next evaluation, so a cache kept from frame to frame
+/// stops allocating once it has seen the whole diagram.
+#[derive(Default)]
+pub struct OutputsCache {
+    values: HashMap<OutputId, ValueType>,
+    /// Emptied curves, handed out again by [`OutputsCache::buffer`].
+    spare: Vec<Vec<f64>>,
+}
+
+impl OutputsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, output: &OutputId) -> Option<&ValueType> {
+        self.values.get(output)
+    }
+
+    pub fn insert(&mut self, output: OutputId, value: ValueType) {
+        if let Some(ValueType::Reliability { curve }) = self.values.insert(output, value) {
+            self.recycle(curve);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&OutputId, &ValueType)> {
+        self.values.iter()
+    }
+
+    /// Forgets every curve, keeping their buffers.
+    pub fn clear(&mut self) {
+        for (_, value) in self.values.drain() {
+            if let ValueType::Reliability { mut curve } = value {
+                curve.clear();
+                self.spare.push(curve);
+            }
+        }
+    }
+
+    /// An empty curve to fill, in a spare buffer if there is one.
+    fn buffer(&mut self) -> Vec<f64> {
+        self.spare.pop().unwrap_or_default()
+    }
+
+    fn recycle(&mut self, mut curve: Vec<f64>) {
+        curve.clear();
+        self.spare.push(curve);
+    }
+
+    /// The value on `output`, with a curve copied into a spare buffer.
+    fn copy(&mut self, output: &OutputId) -> Option<ValueType> {
+        Some(match self.values.get(output)? {
+            ValueType::Reliability { curve } => {
+                let mut copy = self.spare.pop().unwrap_or_default();
+                copy.extend_from_slice(curve);
+                ValueType::Reliability { curve: copy }
+            }
+            value => value.clone(),
+        })
+    }
+}
+
+/// Why a node couldn't be evaluated, and the node it happened at, so the
+/// editor can point at it.
+#[derive(Debug, thiserror::Error)]
+#[error("{problem}")]
+pub struct EvalError {
+    pub node: NodeId,
+    /// The input of `node` at fault, if the problem is with one.
+    pub input: Option<String>,
+    pub problem: Problem,
+}
+
+impl EvalError {
+    fn new(node: NodeId, problem: impl Into<Problem>) -> Self {
+        Self {
+            node,
+            input: None,
+            problem: problem.into(),
+        }
+    }
+
+    fn at_input(node: NodeId, input: &str, problem: impl Into<Problem>) -> Self {
+        Self {
+            node,
+            input: Some(input.to_owned()),
+            problem: problem.into(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Problem {
+    #[error("{}", tr("error-cycle"))]
+    Cycle,
+    #[error("{}", tr("error-chunked-measure"))]
+    ChunkedMeasure,
+    #[error("{}", tr("error-empty-curve"))]
+    EmptyCurve,
+    #[error("{}", tr("error-note"))]
+    Note,
+    #[error("{}", unconnected_input(.0))]
+    UnconnectedInput(String),
+    #[error("{}", tr("error-expected-reliability"))]
+    ExpectedReliability,
+    #[error("{}", tr("error-expected-parameters"))]
+    ExpectedParameters,
+    #[error("{}", tr("error-all-inputs-disabled"))]
+    AllInputsDisabled,
+    #[error("{}", tr("error-expected-dormancy"))]
+    ExpectedDormancy,
+    #[error("{}", tr("error-expected-human-action"))]
+    ExpectedHumanAction,
+    #[error("{}", tr("error-expected-software-growth"))]
+    ExpectedSoftwareGrowth,
+    #[error("{}", tr("error-expected-one-shot"))]
+    ExpectedOneShot,
+    #[error("{}", tr("error-expected-petri-net"))]
+    ExpectedPetriNet,
+    /// A Petri net that can't be simulated.
+    #[error("{0}")]
+    PetriNet(String),
+    /// Discovery data that doesn't fit a growth model.
+    #[error("{0}")]
+    Growth(String),
+    #[error("{}", too_few_members(.0))]
+    TooFewMembers(usize),
+    #[error("{}", time_out_of_range(.0))]
+    TimeOutOfRange(usize),
+    /// Inputs whose curves end at different time steps, as the shortest and
+    /// the longest horizon.
+    #[error("{}", horizons_differ(.0, .1))]
+    HorizonMismatch(usize, usize),
+    /// Shapes or scales that don't make a Weibull distribution.
+    #[error(transparent)]
+    Distribution(#[from] StatsError),
+    /// A node or input missing from the graph.
+    #[error(transparent)]
+    Graph(#[from] EguiGraphError),
+}
+
+fn unconnected_input(input: &str) -> String {
+    tr_with("error-unconnected-input", &[("input", &input)])
+}
+
+fn too_few_members(members: &usize) -> String {
+    tr_with("error-too-few-members", &[("members", members)])
+}
+
+fn time_out_of_range(time: &usize) -> String {
+    tr_with("error-time-out-of-range", &[("time", time)])
+}
+
+fn horizons_differ(shortest: &usize, longest: &usize) -> String {
+    tr_with(
+        "error-horizon-mismatch",
+        &[("shortest", shortest), ("longest", longest)],
+    )
+}
+
+/// The curve a node's output carries.
+fn reliability(value: ValueType) -> Result<Vec<f64>, Problem> {
+    match value {
+        ValueType::Reliability { curve } => Ok(curve),
+        ValueType::CompactReliability { curve } => Ok(curve.into_iter().map(f64::from).collect()),
+        _ => Err(Problem::ExpectedReliability),
+    }
+}
+
+/// What a diagram is evaluated under besides its own blocks: the mission
+/// profile and the calculation settings. The app, the command line and every
+/// tool pass the same options, so none of them depends on what was last
+/// chosen in another window.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    pub profile: MissionProfile,
+    /// How precisely curves are kept between blocks. Each block still
+    /// computes in f64; only what is kept between blocks is rounded.
+    pub precision: Precision,
+    /// Whether disabled nodes are taken as perfect or left out of the blocks
+    /// they feed.
+    pub disabled: DisabledNodes,
+    /// Only failures of this class count, with the other components taken
+    /// as never failing.
+    pub severity: Option<Severity>,
+    /// Whether components' R(t) is computed only every [`COARSE_STRIDE`]
+    /// time steps and linearly interpolated in between, for a fast preview
+    /// while a value is being dragged.
+    pub coarse: bool,
+}
+
+impl Options {
+    /// The options the diagram of `state` is evaluated with.
+    pub fn of(state: &GraphState) -> Self {
+        Self {
+            profile: state.mission_profile.clone(),
+            precision: state.settings.precision,
+            disabled: state.settings.disabled_nodes,
+            severity: state.severity_filter,
+            coarse: state.coarse,
+        }
+    }
+
+    pub fn disabled_absent(&self) -> bool {
+        self.disabled == DisabledNodes::Absent
+    }
+
+    /// What a disabled input contributes to a block: 1 if it is taken as
+    /// perfect, otherwise the block's identity, so it is as if it weren't
+    /// there.
+    fn disabled_reliability(&self, block: NodeTemplate) -> f64 {
+        match (self.disabled_absent(), block) {
+            (true, NodeTemplate::Parallel | NodeTemplate::Standby) => 0.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Whether a component's failures are left out by the severity filter,
+    /// so it is taken as never failing.
+    pub fn filtered_out(&self, node: &NodeData) -> bool {
+        node.template == NodeTemplate::Component
+            && self
+                .severity
+                .is_some_and(|severity| severity != node.severity)
+    }
+}
+
+/// Time steps between the points a component's hazard is computed at in the
+/// coarse mode, linearly interpolated in between.
+const COARSE_STRIDE: usize = 32;
+
+/// Time spent evaluating each node, recorded while [`timed`] runs.
+#[derive(Default)]
+struct Timings {
+    /// Each node's own time, without the inputs it evaluated on the way.
+    own: HashMap<NodeId, Duration>,
+    /// The time of the nodes evaluated within the one in progress.
+    nested: Duration,
+}
+
+thread_local! {
+    static TIMINGS: RefCell<Option<Timings>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, recording how long every node it evaluates takes on its own.
+/// A node evaluated more than once, as when [`factor`] conditions on shared
+/// nodes, adds up its times.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, HashMap<NodeId, Duration>) {
+    TIMINGS.with(|timings| *timings.borrow_mut() = Some(Timings::default()));
+    let value = f();
+    let timings = TIMINGS.with(|timings| timings.borrow_mut().take());
+    (
+        value,
+        timings.map(|timings| timings.own).unwrap_or_default(),
+    )
+}
+
+/// H(t) at every time step up to `horizon`, computed only every `stride`
+/// steps and at the horizon, and interpolated linearly in between.
+fn interpolated_hazards(parameters: &NodeParameters, horizon: usize, stride: usize) -> Vec<f64> {
+    let mut hazards = Vec::with_capacity(horizon + 1);
+    let mut start = 0;
+    let mut from = parameters.cumulative_hazard(0.0);
+    while start < horizon {
+        let end = (start + stride).min(horizon);
+        let to = parameters.cumulative_hazard(end as f64);
+        for t in start..end {
+            hazards.push(from + (to - from) * (t - start) as f64 / (end - start) as f64);
+        }
+        (start, from) = (end, to);
+    }
+    hazards.push(from);
+    hazards
+}
+
+/// What a curve flowing through the diagram measures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Measure {
+    /// R(t), the probability of no failure up to t.
+    #[default]
+    Reliability,
+    /// A(t), the probability of being up at t, with failed components repaired.
+    Availability,
+    /// The long-run fraction of time up, as a one-element curve.
+    SteadyStateAvailability,
+}
+
+/// Recursively evaluates all dependencies of this node, then evaluates the node itself.
+pub fn evaluate_node(
+    graph: &Graph,
+    options: &Options,
+    node_id: NodeId,
+    outputs_cache: &mut OutputsCache,
+) -> Result<ValueType, EvalError> {
+    evaluate_measure(graph, options, Measure::Reliability, node_id, outputs_cache)
+}
+
+/// Like [`evaluate_node`], but as a plain curve.
+pub fn evaluate_curve(
+    graph: &Graph,
+    options: &Options,
+    node_id: NodeId,
+    outputs_cache: &mut OutputsCache,
+) -> Result<Vec<f64>, EvalError> {
+    reliability(evaluate_node(graph, options, node_id, outputs_cache)?)
+        .map_err(|problem| EvalError::new(node_id, problem))
+}
+
+/// Like [`evaluate_node`], but for any measure. Series and parallel blocks
+/// combine availabilities like reliabilities, since components are repaired
+/// independently.
+pub fn evaluate_measure(
+    graph: &Graph,
+    options: &Options,
+    measure: Measure,
+    node_id: NodeId,
+    outputs_cache: &mut OutputsCache,
+) -> Result<ValueType, EvalError> {
+    evaluate_window(graph, options, measure, node_id, outputs_cache, None)
+}
+
+/// Like [`evaluate_measure`], but only over `window`'s time steps if given.
+fn evaluate_window(
+    graph: &Graph,
+    options: &Options,
+    measure: Measure,
+    node_id: NodeId,
+    outputs_cache: &mut OutputsCache,
+    window: Option<&Window>,
+) -> Result<ValueType, EvalError> {
+    let shared = shared_nodes(graph, node_id);
+    if shared.is_empty() || shared.len() > MAX_SHARED {
+        return Evaluator {
+            graph,
+            options,
+            measure,
+            outputs_cache,
+            in_progress: HashSet::new(),
+            assumed: &HashMap::new(),
+            window,
+        }
+        .evaluate(node_id);
+    }
+    let curve = factor(
+        graph,
+        options,
+        measure,
+        window,
+        node_id,
+        &shared,
+        &mut HashMap::new(),
+    )?;
+    let value = ValueType::Reliability { curve };
+    let output = graph[node_id]
+        .get_output("reliability")
+        .map_err(|err| EvalError::new(node_id, err))?;
+    outputs_cache.insert(output, value.clone());
+    Ok(value)
+}
+
+/// How many time steps [`evaluate_chunked`] evaluates at once.
+pub const CHUNK_STEPS: usize = 1 << 16;
+
+/// Where each component's cumulative hazard under the mission profile had
+/// got to, as (time, H), so the next chunk carries on from there.
+type Resume = RefCell<HashMap<NodeId, (f64, f64)>>;
+
+/// A range of time steps evaluated on its own.
+struct Window<'a> {
+    times: Range<usize>,
+    resume: &'a Resume,
+    /// Where the hazards stand at the end of `times`, for the next window.
+    next: &'a Resume,
+}
+
+/// Evaluates R(t) of `node_id` `chunk` time steps at a time, handing each
+/// piece to `visit` with the time step it starts at. Only one chunk of each
+/// node's R(t) is held at once; other measures can't be evaluated this way
+/// and fail with [`Problem::ChunkedMeasure`].
+pub fn evaluate_chunked(
+    graph: &Graph,
+    options: &Options,
+    node_id: NodeId,
+    chunk: usize,
+    mut visit: impl FnMut(usize, &[f64]),
+) -> Result<(), EvalError> {
+    let mut resume = Resume::default();
+    let mut start = 0;
+    loop {
+        let next = Resume::default();
+        let window = Window {
+            times: start..start + chunk,
+            resume: &resume,
+            next: &next,
+        };
+        let curve = evaluate_window(
+            graph,
+            options,
+            Measure::Reliability,
+            node_id,
+            &mut OutputsCache::new(),
+            Some(&window),
+        )
+        .and_then(|value| reliability(value).map_err(|problem| EvalError::new(node_id, problem)))?;
+        visit(start, &curve);
+        // Curves end at their horizon, which a short chunk has reached.
+        if curve.len() < chunk {
+            return Ok(());
+        }
+        resume = next;
+        start += chunk;
+    }
+}
+
+/// Above this many shared nodes, the 2ⁿ evaluations of [`factor`] get too
+/// slow to run every frame, and shared inputs are taken as independent.
+const MAX_SHARED: usize = 12;
+
+/// Roughly how many curve values evaluating `node_id` computes: every
+/// upstream curve's length, once for each combination of shared nodes that
+/// [`factor`] conditions on.
+pub fn cost(graph: &Graph, node_id: NodeId) -> usize {
+    let steps: usize = nodes::upstream(graph, node_id)
+        .into_iter()
+        .filter(|node| graph[*node].user_data.template.evaluates())
+        .filter_map(|node| {
+            let steps = horizon(graph, node)? + 1;
+            Some(match graph[node].user_data.template {
+                NodeTemplate::Standby => steps * steps / 2,
+                _ => steps,
+            })
+        })
+        .sum();
+    let shared = shared_nodes(graph, node_id).len();
+    if shared > MAX_SHARED {
+        return steps;
+    }
+    steps.saturating_mul(1 << shared)
+}
+
+/// The nodes upstream of `node_id` that feed more than one input on the way
+/// to it, upstream ones first. Forced nodes aren't random and are left out.
+fn shared_nodes(graph: &Graph, node_id: NodeId) -> Vec<NodeId> {
+    let upstream = nodes::upstream(graph, node_id);
+    let mut uses: HashMap<NodeId, usize> = HashMap::new();
+    for (input, output) in graph.iter_connections() {
+        if upstream.contains(&graph[input].node) {
+            *uses.entry(graph[output].node).or_default() += 1;
+        }
+    }
+    let mut shared: Vec<(usize, NodeId)> = uses
+        .into_iter()
+        .filter(|(node, uses)| {
+            let data = &graph[*node].user_data;
+            *uses > 1 && data.forced.is_none() && data.enabled
+        })
+        .map(|(node, _)| (nodes::upstream(graph, node).len(), node))
+        .collect();
+    // A node depends on everything its inputs depend on, so fewer upstream
+    // nodes puts it earlier.
+    shared.sort();
+    shared.into_iter().map(|(_, node)| node).collect()
+}
+
+/// Evaluates `node_id` by pivotal decomposition on the first shared node,
+/// R = R_i · R(i working) + (1 - R_i) · R(i failed), recursing on the rest.
+/// Series and parallel blocks assume independent inputs, which the inputs
+/// stop being once they share a node. Shared nodes are conditioned upstream
+/// first, so each one's own curve only depends on nodes already assumed.
+fn factor(
+    graph: &Graph,
+    options: &Options,
+    measure: Measure,
+    window: Option<&Window>,
+    node_id: NodeId,
+    shared: &[NodeId],
+    assumed: &mut HashMap<NodeId, f64>,
+) -> Result<Vec<f64>, EvalError> {
+    let evaluate = |node_id, assumed: &HashMap<NodeId, f64>| {
+        let value = Evaluator {
+            graph,
+            options,
+            measure,
+            outputs_cache: &mut OutputsCache::new(),
+            in_progress: HashSet::new(),
+            assumed,
+            window,
+        }
+        .evaluate(node_id)?;
+        reliability(value).map_err(|problem| EvalError::new(node_id, problem))
+    };
+    let Some((&pivot, rest)) = shared.split_first() else {
+        return evaluate(node_id, assumed);
+    };
+    let pivot_curve = evaluate(pivot, assumed)?;
+    assumed.insert(pivot, 1.0);
+    let working = factor(graph, options, measure, window, node_id, rest, assumed);
+    assumed.insert(pivot, 0.0);
+    let failed = factor(graph, options, measure, window, node_id, rest, assumed);
+    assumed.remove(&pivot);
+    let (working, failed) = (working?, failed?);
+    Ok(pivot_curve
+        .iter()
+        .zip(working.iter().zip(&failed))
+        .map(|(r, (working, failed))| r * working + (1.0 - r) * failed)
+        .collect())
+}
+
+/// The reliability of a node at a single time step.
+pub fn reliability_at(
+    graph: &Graph,
+    options: &Options,
+    node_id: NodeId,
+    time: usize,
+) -> Result<f64, EvalError> {
+    let curve = evaluate_curve(graph, options, node_id, &mut OutputsCache::new())?;
+    curve
+        .get(time)
+        .copied()
+        .ok_or_else(|| EvalError::new(node_id, Problem::TimeOutOfRange(time)))
+}
+
+/// Evaluates every node that produces a reliability curve. Nodes that fail to
+/// evaluate are left out of the cache.
+pub fn evaluate_all(graph: &Graph, options: &Options) -> OutputsCache {
+    let mut outputs_cache = OutputsCache::new();
+    evaluate_all_into(graph, options, &mut outputs_cache);
+    outputs_cache
+}
+
+/// Like [`evaluate_all`], but into an existing cache, which is cleared first
+/// and keeps its buffers.
+pub fn evaluate_all_into(graph: &Graph, options: &Options, outputs_cache: &mut OutputsCache) {
+    outputs_cache.clear();
+    for (node_id, node) in &graph.nodes {
+        if node.user_data.template.evaluates() {
+            let _ = evaluate_node(graph, options, node_id, outputs_cache);
+        }
+    }
+}
+
+/// What every evaluation of a diagram depends on, to tell when results kept
+/// from one frame to the next have to be made again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Revision {
+    model: u32,
+    pub options: Options,
+}
+
+impl Revision {
+    pub fn of(graph: &Graph, state: &GraphState) -> Self {
+        Self {
+            model: audit::checksum(graph, state),
+            options: Options::of(state),
+        }
+    }
+}
+
+/// The curves of every node, for what the canvas draws of all of them each
+/// frame, evaluated again only once the model or the options change.
+#[derive(Default)]
+pub struct AllCurves {
+    revision: Option<Revision>,
+    curves: OutputsCache,
+}
+
+impl AllCurves {
+    pub fn update(&mut self, graph: &Graph, state: &GraphState) -> &OutputsCache {
+        let revision = Revision::of(graph, state);
+        if self.revision.as_ref() != Some(&revision) {
+            evaluate_all_into(graph, &revision.options, &mut self.curves);
+            self.revision = Some(revision);
+        }
+        &self.curves
+    }
+}
+
+/// The last time step a node's curve covers, found without evaluating it.
+/// Series and parallel blocks cover only as far as their shortest input.
+pub fn horizon(graph: &Graph, node_id: NodeId) -> Option<usize> {
+    horizon_visiting(graph, node_id, &mut HashSet::new())
+}
+
+fn horizon_visiting(
+    graph: &Graph,
+    node_id: NodeId,
+    visited: &mut HashSet<NodeId>,
+) -> Option<usize> {
+    if !visited.insert(node_id) {
+        return None;
+    }
+    let node = &graph[node_id];
+    let horizon = match node.user_data.template {
+        NodeTemplate::Component => Some(nodes::component_parameters(graph, node_id)?.time_steps),
+        NodeTemplate::Empirical => {
+            let input_id = node.get_input("curve").ok()?;
+            let ValueType::Reliability { curve } = &graph[input_id].value else {
+                return None;
+            };
+            curve.len().checked_sub(1)
+        }
+        NodeTemplate::Human => Some(nodes::human_action(graph, node_id)?.time_steps),
+        NodeTemplate::Software => Some(nodes::software_growth(graph, node_id)?.time_steps),
+        // One-shot devices hold over whatever horizon the blocks next to them have.
+        NodeTemplate::OneShot => None,
+        NodeTemplate::PetriNet => Some(nodes::petri_net(graph, node_id)?.time_steps),
+        NodeTemplate::Series
+        | NodeTemplate::Parallel
+        | NodeTemplate::Standby
+        | NodeTemplate::Voting => node
+            .input_ids()
+            .filter_map(|input| graph.connection(input))
+            .filter_map(|output| horizon_visiting(graph, graph[output].node, visited))
+            .min(),
+        NodeTemplate::Note => None,
+    };
+    visited.remove(&node_id);
+    horizon
+}
+
+/// The horizons of the connected inputs of a block, as the shortest and the
+/// longest, and the inputs that fall short of the longest, when they
+/// disagree. Such a block fails to evaluate.
+pub fn horizon_mismatch(graph: &Graph, node_id: NodeId) -> Option<(usize, usize, Vec<InputId>)> {
+    let horizons: Vec<(InputId, usize)> = graph[node_id]
+        .input_ids()
+        .filter_map(|input| Some((input, graph.connection(input)?)))
+        .filter_map(|(input, output)| Some((input, horizon(graph, graph[output].node)?)))
+        .collect();
+    let shortest = horizons.iter().map(|(_, horizon)| *horizon).min()?;
+    let longest = horizons.iter().map(|(_, horizon)| *horizon).max()?;
+    let short = horizons
+        .into_iter()
+        .filter(|(_, horizon)| *horizon < longest)
+        .map(|(input, _)| input)
+        .collect();
+    (shortest != longest).then_some((shortest, longest, short))
+}
+
+struct Evaluator<'a> {
+    graph: &'a Graph,
+    options: &'a Options,
+    measure: Measure,
+    outputs_cache: &'a mut OutputsCache,
+    /// Nodes currently being evaluated further up the stack, used to reject cycles.
+    in_progress: HashSet<NodeId>,
+    /// Shared nodes taken as working (1) or failed (0) by [`factor`].
+    assumed: &'a HashMap<NodeId, f64>,
+    /// The time steps to evaluate, if not the whole horizon.
+    window: Option<&'a Window<'a>>,
+}
+
+impl Evaluator<'_> {
+    fn evaluate(&mut self, node_id: NodeId) -> Result<ValueType, EvalError> {
+        let outer = TIMINGS
+            .with(|timings| Some(std::mem::take(&mut timings.borrow_mut().as_mut()?.nested)));
+        let Some(outer) = outer else {
+            return self.evaluate_untimed(node_id);
+        };
+        let start = Instant::now();
+        let value = self.evaluate_untimed(node_id);
+        let elapsed = start.elapsed();
+        TIMINGS.with(|timings| {
+            if let Some(timings) = timings.borrow_mut().as_mut() {
+                let nested = std::mem::replace(&mut timings.nested, outer + elapsed);
+                *timings.own.entry(node_id).or_default() += elapsed.saturating_sub(nested);
+            }
+        });
+        value
+    }
+
+    fn evaluate_untimed(&mut self, node_id: NodeId) -> Result<ValueType, EvalError> {
+        if !self.in_progress.insert(node_id) {
+            return Err(EvalError::new(node_id, Problem::Cycle));
+        }
+
+        let node = &self.graph[node_id];
+        let fail = |problem: Problem| EvalError::new(node_id, problem);
+        let curve = match node.user_data.template {
+            NodeTemplate::Component => {
+                let parameters = match self.input_value(node_id, "parameters")? {
+                    ValueType::Parameters { parameters } => parameters,
+                    _ => {
+                        return Err(EvalError::at_input(
+                            node_id,
+                            "parameters",
+                            Problem::ExpectedParameters,
+                        ))
+                    }
+                };
+                match (self.measure, self.window) {
+                    (Measure::Reliability, Some(window)) => {
+                        windowed_reliability(&parameters, &self.options.profile, node_id, window)
+                    }
+                    (_, Some(_)) => Err(Problem::ChunkedMeasure),
+                    (Measure::Reliability, None) if self.options.coarse => {
+                        coarse_reliability(&parameters, &self.options.profile)
+                    }
+                    (Measure::Reliability, None) => {
+                        component_reliability(&parameters, &self.options.profile)
+                    }
+                    (Measure::Availability, None) => {
+                        component_availability(&parameters, &self.options.profile)
+                    }
+                    (Measure::SteadyStateAvailability, None) => {
+                        Ok(vec![steady_state_availability(&parameters)])
+                    }
+                }
+                .map_err(fail)?
+            }
+            NodeTemplate::Series => self.combine(node_id, |a, b| a * b)?,
+            NodeTemplate::Parallel => {
+                // Both units must be up, or the one down must have failed
+                // in a way the block detected and isolated.
+                let c = coverage(self.graph, node_id);
+                self.combine(node_id, move |a, b| {
+                    a * b + c * (a * (1.0 - b) + b * (1.0 - a))
+                })?
+            }
+            NodeTemplate::Standby => self.standby(node_id)?,
+            NodeTemplate::Voting => self.voting(node_id)?,
+            NodeTemplate::Empirical => {
+                let curve = reliability(self.input_value(node_id, "curve")?)
+                    .map_err(|problem| EvalError::at_input(node_id, "curve", problem))?;
+                if curve.is_empty() {
+                    return Err(EvalError::at_input(node_id, "curve", Problem::EmptyCurve));
+                }
+                let curve = match self.window {
+                    Some(window) => {
+                        let end = window.times.end.min(curve.len());
+                        curve[window.times.start.min(end)..end].to_vec()
+                    }
+                    None => curve,
+                };
+                // Field data carries no repair times, so it is taken as not repaired.
+                match self.measure {
+                    Measure::Reliability | Measure::Availability => curve,
+                    Measure::SteadyStateAvailability => vec![0.0],
+                }
+            }
+            NodeTemplate::Human => {
+                let action = match self.input_value(node_id, "action")? {
+                    ValueType::HumanAction { action } => action,
+                    _ => {
+                        return Err(EvalError::at_input(
+                            node_id,
+                            "action",
+                            Problem::ExpectedHumanAction,
+                        ))
+                    }
+                };
+                // The action is as likely to fail whenever it is called for.
+                let r = 1.0 - action.adjusted_hep();
+                let steps = match self.window {
+                    Some(window) => window
+                        .times
+                        .end
+                        .min(action.time_steps + 1)
+                        .saturating_sub(window.times.start),
+                    None => action.time_steps + 1,
+                };
+                match self.measure {
+                    Measure::Reliability | Measure::Availability => vec![r; steps],
+                    Measure::SteadyStateAvailability => vec![r],
+                }
+            }
+            NodeTemplate::Software => {
+                let growth = match self.input_value(node_id, "growth")? {
+                    ValueType::SoftwareGrowth { growth } => growth,
+                    _ => {
+                        return Err(EvalError::at_input(
+                            node_id,
+                            "growth",
+                            Problem::ExpectedSoftwareGrowth,
+                        ))
+                    }
+                };
+                let growth_error =
+                    |err| EvalError::at_input(node_id, "growth", Problem::Growth(err));
+                // Defects aren't fixed in the field, so it is taken as not
+                // repaired; in the long run only the defects left can fail it.
+                match (self.measure, self.window) {
+                    (Measure::SteadyStateAvailability, _) => {
+                        let model = growth.model().map_err(growth_error)?;
+                        vec![model.reliability(growth.test_time, f64::INFINITY)]
+                    }
+                    (_, Some(window)) => {
+                        let curve = growth.curve().map_err(growth_error)?;
+                        let end = window.times.end.min(curve.len());
+                        curve[window.times.start.min(end)..end].to_vec()
+                    }
+                    (_, None) => growth.curve().map_err(growth_error)?,
+                }
+            }
+            NodeTemplate::OneShot => {
+                let device = match self.input_value(node_id, "device")? {
+                    ValueType::OneShot { device } => device,
+                    _ => {
+                        return Err(EvalError::at_input(
+                            node_id,
+                            "device",
+                            Problem::ExpectedOneShot,
+                        ))
+                    }
+                };
+                let steps = match self.window {
+                    Some(window) => window
+                        .times
+                        .end
+                        .min(device.time_steps + 1)
+                        .saturating_sub(window.times.start),
+                    None => device.time_steps + 1,
+                };
+                match self.measure {
+                    Measure::Reliability | Measure::Availability => vec![device.success; steps],
+                    Measure::SteadyStateAvailability => vec![device.success],
+                }
+            }
+            NodeTemplate::PetriNet => {
+                let net = match self.input_value(node_id, "net")? {
+                    ValueType::PetriNet { net } => net,
+                    _ => {
+                        return Err(EvalError::at_input(
+                            node_id,
+                            "net",
+                            Problem::ExpectedPetriNet,
+                        ))
+                    }
+                };
+                let curves = net
+                    .curves()
+                    .map_err(|err| EvalError::at_input(node_id, "net", Problem::PetriNet(err)))?;
+                let curve = match self.measure {
+                    Measure::Reliability => curves.reliability,
+                    Measure::Availability => curves.availability,
+                    // The simulation runs only to the horizon, where A(t) has
+                    // settled if the net reaches a steady state.
+                    Measure::SteadyStateAvailability => {
+                        vec![curves.availability.last().copied().unwrap_or(0.0)]
+                    }
+                };
+                match self.window {
+                    Some(window) if self.measure != Measure::SteadyStateAvailability => {
+                        let end = window.times.end.min(curve.len());
+                        curve[window.times.start.min(end)..end].to_vec()
+                    }
+                    _ => curve,
+                }
+            }
+            NodeTemplate::Note => return Err(fail(Problem::Note)),
+        };
+
+        // Forced nodes keep their evaluated horizon, and still report errors.
+        let forced = match node.user_data.forced {
+            Some(forced) => Some(forced.value()),
+            None => self.options.filtered_out(&node.user_data).then_some(1.0),
+        };
+        let curve = match forced.or_else(|| self.assumed.get(&node_id).copied()) {
+            Some(value) => self.constant(value, curve),
+            None => curve,
+        };
+        let output_id = node
+            .get_output("reliability")
+            .map_err(|err| fail(err.into()))?;
+        let value = if self.options.precision == Precision::Single {
+            let compact = curve.iter().map(|r| *r as f32).collect();
+            self.outputs_cache.recycle(curve);
+            ValueType::CompactReliability { curve: compact }
+        } else {
+            ValueType::Reliability { curve }
+        };
+        self.outputs_cache.insert(output_id, value);
+        self.in_progress.remove(&node_id);
+        Ok(self.outputs_cache.copy(&output_id).expect("just inserted"))
+    }
+
+    /// Combines the curves on inputs A and B step by step, into a spare
+    /// buffer that theirs are then returned to. A disabled input isn't
+    /// evaluated, and stands for a constant over the other's horizon, as
+    /// does a one-shot device.
+    fn combine(
+        &mut self,
+        node_id: NodeId,
+        combine: impl Fn(f64, f64) -> f64,
+    ) -> Result<Vec<f64>, EvalError> {
+        let disabled = (
+            self.disabled_input(node_id, "A"),
+            self.disabled_input(node_id, "B"),
+        );
+        let mut curve = self.outputs_cache.buffer();
+        match disabled {
+            (None, None) => {
+                let mut a = self.input_reliability(node_id, "A")?;
+                let mut b = self.input_reliability(node_id, "B")?;
+                match (
+                    self.one_shot_input(node_id, "A"),
+                    self.one_shot_input(node_id, "B"),
+                ) {
+                    (true, false) => hold(&mut a, b.len()),
+                    (false, true) => hold(&mut b, a.len()),
+                    _ => {}
+                }
+                if let Err(err) = self.same_horizon(node_id, [("A", &a), ("B", &b)]) {
+                    self.outputs_cache.recycle(curve);
+                    return Err(err);
+                }
+                curve.extend(a.iter().zip(&b).map(|(a, b)| combine(*a, *b)));
+                self.outputs_cache.recycle(a);
+                self.outputs_cache.recycle(b);
+            }
+            (Some(a), None) => {
+                let b = self.input_reliability(node_id, "B")?;
+                curve.extend(b.iter().map(|b| combine(a, *b)));
+                self.outputs_cache.recycle(b);
+            }
+            (None, Some(b)) => {
+                let a = self.input_reliability(node_id, "A")?;
+                curve.extend(a.iter().map(|a| combine(*a, b)));
+                self.outputs_cache.recycle(a);
+            }
+            (Some(_), Some(_)) => {
+                self.outputs_cache.recycle(curve);
+                return Err(EvalError::new(node_id, Problem::AllInputsDisabled));
+            }
+        }
+        Ok(curve)
+    }
+
+    /// What a disabled node connected to input `name` contributes, or `None`
+    /// if the input isn't fed by one.
+    fn disabled_input(&self, node_id: NodeId, name: &str) -> Option<f64> {
+        let node = &self.graph[node_id];
+        let output_id = self.graph.connection(node.get_input(name).ok()?)?;
+        let source = &self.graph[self.graph[output_id].node];
+        (!source.user_data.enabled)
+            .then(|| self.options.disabled_reliability(node.user_data.template))
+    }
+
+    /// Whether input `name` is fed by a one-shot device.
+    fn one_shot_input(&self, node_id: NodeId, name: &str) -> bool {
+        let Ok(input_id) = self.graph[node_id].get_input(name) else {
+            return false;
+        };
+        self.graph.connection(input_id).is_some_and(|output_id| {
+            self.graph[self.graph[output_id].node].user_data.template == NodeTemplate::OneShot
+        })
+    }
+
+    /// A primary on input A backed by a spare on input B. Over a window, and
+    /// for repaired measures, the pair is taken as hot, which is conservative.
+    fn standby(&mut self, node_id: NodeId) -> Result<Vec<f64>, EvalError> {
+        let dormancy = match self.input_value(node_id, "dormancy")? {
+            ValueType::Dormancy { dormancy } => dormancy,
+            _ => {
+                return Err(EvalError::at_input(
+                    node_id,
+                    "dormancy",
+                    Problem::ExpectedDormancy,
+                ))
+            }
+        };
+        let c = coverage(self.graph, node_id);
+        let hot = self.window.is_some() || self.measure != Measure::Reliability;
+        // With a disabled input there is nothing to switch over to, and a
+        // one-shot device doesn't age while it waits.
+        let constant = ["A", "B"].iter().any(|input| {
+            self.disabled_input(node_id, input).is_some() || self.one_shot_input(node_id, input)
+        });
+        if hot || constant || dormancy == Dormancy::Hot {
+            return self.combine(node_id, move |a, b| a + c * (1.0 - a) * b);
+        }
+        let primary = self.input_reliability(node_id, "A")?;
+        let spare = self.input_reliability(node_id, "B")?;
+        self.same_horizon(node_id, [("A", &primary), ("B", &spare)])?;
+        let mut curve = self.outputs_cache.buffer();
+        curve.extend(standby(&primary, &spare, dormancy.aging(), c));
+        self.outputs_cache.recycle(primary);
+        self.outputs_cache.recycle(spare);
+        Ok(curve)
+    }
+
+    /// The members' curves, of which at least `votes` must work. Disabled
+    /// members are taken as working, or dropped if they count as absent,
+    /// and excluded ones are dropped.
+    fn voting(&mut self, node_id: NodeId) -> Result<Vec<f64>, EvalError> {
+        let node = &self.graph[node_id];
+        let votes = node.user_data.votes;
+        let names: Vec<String> = node.inputs.iter().map(|(name, _)| name.clone()).collect();
+        let mut members = Vec::with_capacity(names.len());
+        let mut member_names = Vec::with_capacity(names.len());
+        let mut one_shots = Vec::with_capacity(names.len());
+        let mut perfect = 0;
+        for name in &names {
+            let input_id = self.graph[node_id]
+                .get_input(name)
+                .map_err(|err| EvalError::at_input(node_id, name, err))?;
+            if let Some(output_id) = self.graph.connection(input_id) {
+                let source = &self.graph[self.graph[output_id].node].user_data;
+                if source.excluded || (!source.enabled && self.options.disabled_absent()) {
+                    continue;
+                }
+                if !source.enabled {
+                    perfect += 1;
+                    continue;
+                }
+            }
+            members.push(self.input_reliability(node_id, name)?);
+            member_names.push(name.as_str());
+            one_shots.push(self.one_shot_input(node_id, name));
+        }
+        let horizon = members
+            .iter()
+            .zip(&one_shots)
+            .filter(|(_, one_shot)| !**one_shot)
+            .map(|(member, _)| member.len())
+            .min();
+        if let Some(horizon) = horizon {
+            for (member, _) in members
+                .iter_mut()
+                .zip(&one_shots)
+                .filter(|(_, one_shot)| **one_shot)
+            {
+                hold(member, horizon);
+            }
+        }
+        self.same_horizon(node_id, member_names.into_iter().zip(&members))?;
+        if members.is_empty() {
+            return Err(EvalError::new(node_id, Problem::AllInputsDisabled));
+        }
+        if votes > members.len() + perfect {
+            return Err(EvalError::new(
+                node_id,
+                Problem::TooFewMembers(members.len() + perfect),
+            ));
+        }
+        let mut curve = self.outputs_cache.buffer();
+        curve.extend(voting(
+            &members,
+            votes.saturating_sub(perfect),
+            coverage(self.graph, node_id),
+        ));
+        for member in members {
+            self.outputs_cache.recycle(member);
+        }
+        Ok(curve)
+    }
+
+    /// Fails at the input with the shortest of `curves` unless they all end
+    /// at the same time step. Blocks don't cut their inputs down to the
+    /// shortest, which would quietly end the result early.
+    fn same_horizon<'c>(
+        &self,
+        node_id: NodeId,
+        curves: impl IntoIterator<Item = (&'c str, &'c Vec<f64>)>,
+    ) -> Result<(), EvalError> {
+        let start = self.window.map_or(0, |window| window.times.start);
+        let horizons: Vec<(&str, usize)> = curves
+            .into_iter()
+            .map(|(name, curve)| (name, (start + curve.len()).saturating_sub(1)))
+            .collect();
+        let Some(&(name, shortest)) = horizons.iter().min_by_key(|(_, horizon)| *horizon) else {
+            return Ok(());
+        };
+        let longest = horizons
+            .iter()
+            .map(|(_, horizon)| *horizon)
+            .max()
+            .unwrap_or(shortest);
+        if shortest == longest {
+            return Ok(());
+        }
+        Err(EvalError::at_input(
+            node_id,
+            name,
+            Problem::HorizonMismatch(shortest, longest),
+        ))
+    }
+
+    /// `value` at every step of `curve`, which is given back to the cache.
+    fn constant(&mut self, value: f64, curve: Vec<f64>) -> Vec<f64> {
+        let mut constant = self.outputs_cache.buffer();
+        constant.resize(curve.len(), value);
+        self.outputs_cache.recycle(curve);
+        constant
+    }
+
+    /// The value flowing into an input: the connected output if there is one,
+    /// otherwise the input's inline constant.
+    fn input_value(&mut self, node_id: NodeId, name: &str) -> Result<ValueType, EvalError> {
+        let input_id = self.graph[node_id]
+            .get_input(name)
+            .map_err(|err| EvalError::at_input(node_id, name, err))?;
+
+        if let Some(output_id) = self.graph.connection(input_id) {
+            if let Some(value) = self.outputs_cache.copy(&output_id) {
+                return Ok(value);
+            }
+            let source = self.graph[output_id].node;
+            self.evaluate(source)
+        } else {
+            Ok(self.graph[input_id].value.clone())
+        }
+    }
+
+    fn input_reliability(&mut self, node_id: NodeId, name: &str) -> Result<Vec<f64>, EvalError> {
+        let input_id = self.graph[node_id]
+            .get_input(name)
+            .map_err(|err| EvalError::at_input(node_id, name, err))?;
+        let Some(output_id) = self.graph.connection(input_id) else {
+            return Err(EvalError::at_input(
+                node_id,
+                name,
+                Problem::UnconnectedInput(name.to_owned()),
+            ));
+        };
+        let curve = reliability(self.input_value(node_id, name)?)
+            .map_err(|problem| EvalError::at_input(node_id, name, problem))?;
+        // An excluded input is taken out of the block: a series block no
+        // longer depends on it, and a parallel block loses the redundancy.
+        if self.graph[self.graph[output_id].node].user_data.excluded {
+            let identity = match self.graph[node_id].user_data.template {
+                NodeTemplate::Parallel | NodeTemplate::Standby => 0.0,
+                _ => 1.0,
+            };
+            return Ok(self.constant(identity, curve));
+        }
+        Ok(curve)
+    }
+}
+
+/// The probability that a failure inside a redundant block is detected and
+/// isolated. With fewer than two inputs left enabled and not excluded there
+/// is no redundancy to defeat, so failures count as covered.
+pub fn coverage(graph: &Graph, node_id: NodeId) -> f64 {
+    let node = &graph[node_id];
+    let live = node
+        .input_ids()
+        .filter_map(|input| graph.connection(input))
+        .filter(|output| {
+            let source = &graph[graph[*output].node].user_data;
+            source.enabled && !source.excluded
+        })
+        .count();
+    if live >= 2 {
+        node.user_data.coverage
+    } else {
+        1.0
+    }
+}
+
+/// Holds the first value of a one-shot device's `curve` over `horizon` steps.
+fn hold(curve: &mut Vec<f64>, horizon: usize) {
+    let value = curve.first().copied().unwrap_or(1.0);
+    curve.clear();
+    curve.resize(horizon, value);
+}
+
+/// R(t) of a block that works while at least `votes` of `members` do, with
+/// each failure caught with probability `coverage`, and the first one that
+/// isn't taking the block down.
+///
+/// The number of working members follows a Poisson binomial distribution,
+/// which is built up one member at a time in O(n²) per step. Unlike
+/// inclusion–exclusion over the 2ⁿ sets of members, this stays exact and
+/// cheap for any number of members, so there is no size at which
+/// simulation has to take over. The members' curves must cover the same
+/// horizon.
+pub fn voting(members: &[Vec<f64>], votes: usize, coverage: f64) -> Vec<f64> {
+    let horizon = members.first().map_or(0, Vec::len);
+    assert!(
+        members.iter().all(|member| member.len() == horizon),
+        "voting members cover different horizons"
+    );
+    // P(j members work and every failure so far was covered).
+    let mut working = vec![0.0; members.len() + 1];
+    (0..horizon)
+        .map(|t| {
+            working.fill(0.0);
+            working[0] = 1.0;
+            for (n, member) in members.iter().enumerate() {
+                let r = member[t];
+                for j in (0..=n + 1).rev() {
+                    let up = if j > 0 { working[j - 1] * r } else { 0.0 };
+                    working[j] = working[j] * (1.0 - r) * coverage + up;
+                }
+            }
+            working[votes..].iter().sum()
+        })
+        .collect()
+}
+
+/// R(t) of a primary backed by a spare that ages at `aging` times its
+/// active rate while it waits. The spare takes over at the age it reached,
+/// when the switch catches the failure, which it does with probability
+/// `coverage`. It then survives to t if its own curve does at that age plus
+/// the time since the switch:
+///
+/// R(t) = R_A(t) + c Σ_{k ≤ t} (R_A(k − 1) − R_A(k)) · R_B(aging · k + t − k)
+///
+/// This costs the square of the horizon, against linear for the other blocks.
+pub fn standby(primary: &[f64], spare: &[f64], aging: f64, coverage: f64) -> Vec<f64> {
+    let horizon = primary.len().min(spare.len());
+    // The spare's curve between steps, held at its last value beyond them.
+    let spare_at = |age: f64| {
+        let below = age.floor() as usize;
+        if below + 1 >= horizon {
+            return spare[horizon - 1];
+        }
+        let fraction = age - below as f64;
+        spare[below] + (spare[below + 1] - spare[below]) * fraction
+    };
+    (0..horizon)
+        .map(|t| {
+            let switched: f64 = (1..=t)
+                .map(|k| {
+                    (primary[k - 1] - primary[k]) * spare_at(aging * k as f64 + (t - k) as f64)
+                })
+                .sum();
+            primary[t] + coverage * switched
+        })
+        .collect()
+}
+
+/// H(t) of a Weibull component at each time step, both ends included, with
+/// the hazard scaled by the mission profile.
+pub fn component_hazards(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+) -> Result<Vec<f64>, Problem> {
+    // Rejects shapes and scales that don't make a distribution.
+    for (shape, scale) in parameters.all_modes() {
+        Weibull::new(shape, parameters.mode_effective_scale(shape, scale))?;
+    }
+    if !profile.is_active() {
+        return Ok(parameters.cumulative_hazards(0..parameters.time_steps + 1));
+    }
+    Ok(profile.cumulative_hazards(|t| parameters.cumulative_hazard(t), parameters.time_steps))
+}
+
+/// [`component_reliability`] with the hazard computed only every
+/// [`COARSE_STRIDE`] time steps. Under a mission profile it is computed at
+/// every step, as the profile's phases needn't line up with the stride.
+fn coarse_reliability(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+) -> Result<Vec<f64>, Problem> {
+    if profile.is_active() {
+        return component_reliability(parameters, profile);
+    }
+    for (shape, scale) in parameters.all_modes() {
+        Weibull::new(shape, parameters.mode_effective_scale(shape, scale))?;
+    }
+    Ok(
+        interpolated_hazards(parameters, parameters.time_steps, COARSE_STRIDE)
+            .into_iter()
+            .map(|hazard| (-hazard).exp())
+            .collect(),
+    )
+}
+
+/// R(t) of a Weibull component over `window`'s time steps, up to its horizon.
+fn windowed_reliability(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+    node_id: NodeId,
+    window: &Window,
+) -> Result<Vec<f64>, Problem> {
+    for (shape, scale) in parameters.all_modes() {
+        Weibull::new(shape, parameters.mode_effective_scale(shape, scale))?;
+    }
+    let end = window.times.end.min(parameters.time_steps + 1);
+    let times = window.times.start.min(end)..end;
+    let hazards = if profile.is_active() {
+        let from = window.resume.borrow().get(&node_id).copied();
+        let (hazards, to) = profile.cumulative_hazards_from(
+            |t| parameters.cumulative_hazard(t),
+            from.unwrap_or((0.0, 0.0)),
+            times,
+        );
+        window.next.borrow_mut().insert(node_id, to);
+        hazards
+    } else {
+        parameters.cumulative_hazards(times)
+    };
+    Ok(hazards.into_iter().map(|hazard| (-hazard).exp()).collect())
+}
+
+/// For each failure mode, the primary one first, the probability of having
+/// failed from that mode by each time step: ∫ h_i(u) R(u) du, accumulated
+/// step by step. They add up to 1 - R(t).
+pub fn mode_failure_probabilities(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+) -> Result<Vec<Vec<f64>>, Problem> {
+    let total = component_hazards(parameters, profile)?;
+    let reliability: Vec<f64> = total.iter().map(|hazard| (-hazard).exp()).collect();
+    Ok(parameters
+        .all_modes()
+        .map(|(shape, scale)| {
+            let hazards = profile.cumulative_hazards(
+                |t| parameters.mode_cumulative_hazard(shape, scale, t),
+                parameters.time_steps,
+            );
+            let mut failed = 0.0;
+            let mut curve = vec![0.0];
+            for t in 1..hazards.len() {
+                // This mode's share of the step's failures, by its share of
+                // the hazard over the step.
+                let step = total[t] - total[t - 1];
+                if step > 0.0 {
+                    failed += (reliability[t - 1] - reliability[t]) * (hazards[t] - hazards[t - 1])
+                        / step;
+                }
+                curve.push(failed);
+            }
+            curve
+        })
+        .collect())
+}
+
+/// R(t) = exp(-H(t)) of a Weibull component at each time step.
+pub fn component_reliability(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+) -> Result<Vec<f64>, Problem> {
+    Ok(component_hazards(parameters, profile)?
+        .into_iter()
+        .map(|hazard| (-hazard).exp())
+        .collect())
+}
+
+/// The hazard rate h(t) = -d ln R / dt of any reliability curve, over each
+/// time step. It is infinite from the step where R(t) reaches zero.
+pub fn hazard_rate(reliability: &[f64]) -> Vec<f64> {
+    let hazards: Vec<f64> = reliability.iter().map(|r| -r.ln()).collect();
+    hazard_rates(&hazards)
+}
+
+/// The hazard rate h(t) over each time step, from differences of H(t).
+fn hazard_rates(hazards: &[f64]) -> Vec<f64> {
+    let mut rates: Vec<f64> = hazards.windows(2).map(|w| w[1] - w[0]).collect();
+    rates.insert(0, rates.first().copied().unwrap_or_default());
+    rates
+}
+
+/// Point availability is computed on a grid of at most this many steps, as
+/// the renewal equations are quadratic in its length.
+const AVAILABILITY_GRID: usize = 1_000;
+
+/// The alternating renewal process of a repaired component, on a grid of
+/// `step` time steps per bin.
+struct Renewal {
+    step: usize,
+    r: Vec<f64>,
+    /// The probability of a unit failing within each bin of its life.
+    failure: Vec<f64>,
+    /// The probability that some repair completes within each bin.
+    renewal: Vec<f64>,
+}
+
+impl Renewal {
+    /// `None` for components that aren't repaired.
+    fn new(parameters: &NodeParameters, reliability: &[f64]) -> Option<Self> {
+        if parameters.mttr <= 0.0 || reliability.len() < 2 {
+            return None;
+        }
+        let horizon = reliability.len() - 1;
+        let step = horizon.div_ceil(AVAILABILITY_GRID);
+        let bins = horizon / step;
+        let r: Vec<f64> = (0..=bins).map(|k| reliability[k * step]).collect();
+        // Probability of failing, of finishing a repair, and of completing a
+        // whole up-down cycle within each bin.
+        let failure: Vec<f64> = (0..=bins)
+            .map(|k| if k == 0 { 0.0 } else { r[k - 1] - r[k] })
+            .collect();
+        let rate = step as f64 / parameters.mttr;
+        let repair: Vec<f64> = (0..=bins)
+            .map(|k| {
+                if k == 0 {
+                    0.0
+                } else {
+                    (-rate * (k - 1) as f64).exp() - (-rate * k as f64).exp()
+                }
+            })
+            .collect();
+        // A failure in bin j followed by a repair in bin i completes on
+        // average at the end of bin j + i - 1.
+        let cycle: Vec<f64> = (0..=bins)
+            .map(|k| (1..=k).map(|j| failure[j] * repair[k - j + 1]).sum())
+            .collect();
+        let mut renewal = vec![0.0; bins + 1];
+        for k in 1..=bins {
+            renewal[k] = cycle[k] + (1..k).map(|j| cycle[j] * renewal[k - j]).sum::<f64>();
+        }
+        Some(Renewal {
+            step,
+            r,
+            failure,
+            renewal,
+        })
+    }
+
+    fn interpolate(&self, grid: &[f64], horizon: usize) -> Vec<f64> {
+        interpolate(grid, self.step, horizon)
+    }
+}
+
+/// Back to one value per time step of `horizon` from a grid of `step` time
+/// steps per bin, interpolating between grid points.
+fn interpolate(grid: &[f64], step: usize, horizon: usize) -> Vec<f64> {
+    (0..=horizon)
+        .map(|t| {
+            let (k, offset) = (t / step, t % step);
+            match grid.get(k + 1) {
+                Some(next) => {
+                    let weight = offset as f64 / step as f64;
+                    grid[k] * (1.0 - weight) + next * weight
+                }
+                None => grid[k.min(grid.len() - 1)],
+            }
+        })
+        .collect()
+}
+
+/// A(t) of a component whose failures stay hidden until an inspection finds
+/// them. Each bin ages the working units, indexed by their age in bins,
+/// moves failures into a hidden pool, takes what inspections find from it
+/// into repair, and renews the repairs that finish.
+fn hidden_availability(
+    parameters: &NodeParameters,
+    inspection: Inspection,
+    reliability: &[f64],
+) -> Vec<f64> {
+    let horizon = reliability.len() - 1;
+    if horizon == 0 {
+        return reliability.to_vec();
+    }
+    let step = horizon.div_ceil(AVAILABILITY_GRID);
+    let bins = horizon / step;
+    let r: Vec<f64> = (0..=bins).map(|k| reliability[k * step]).collect();
+    let finished = if parameters.mttr > 0.0 {
+        1.0 - (-(step as f64) / parameters.mttr).exp()
+    } else {
+        1.0
+    };
+    let mut up = vec![0.0; bins + 1];
+    up[0] = 1.0;
+    let (mut hidden, mut repairing) = (0.0, 0.0);
+    let mut availability = vec![1.0];
+    for k in 1..=bins {
+        for age in (0..k).rev() {
+            let survival = if r[age] > 0.0 {
+                r[age + 1] / r[age]
+            } else {
+                0.0
+            };
+            hidden += up[age] * (1.0 - survival);
+            up[age + 1] = up[age] * survival;
+        }
+        let renewed = repairing * finished;
+        repairing -= renewed;
+        up[0] = renewed;
+        let inspections = ((k * step) as f64 / inspection.interval).floor()
+            - (((k - 1) * step) as f64 / inspection.interval).floor();
+        if inspections > 0.0 {
+            let found = hidden * (1.0 - (1.0 - inspection.effectiveness).powf(inspections));
+            hidden -= found;
+            if parameters.mttr > 0.0 {
+                repairing += found;
+            } else {
+                up[0] += found;
+            }
+        }
+        availability.push(up.iter().sum());
+    }
+    interpolate(&availability, step, horizon)
+}
+
+/// A(t) of a component repaired after each failure, with exponentially
+/// distributed repairs of mean MTTR. Renewals follow the discrete alternating
+/// renewal equations. Minimal repairs leave the hazard as it was, so A(t) is
+/// taken as the quasi-steady 1 / (1 + h(t)·MTTR), which holds while h(t)
+/// changes slowly over a repair. Components with no MTTR are never repaired,
+/// so A(t) = R(t). Inspected components are renewed once a failure is found,
+/// whatever their repair.
+pub fn component_availability(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+) -> Result<Vec<f64>, Problem> {
+    let hazards = component_hazards(parameters, profile)?;
+    if let Some(inspection) = parameters.inspection {
+        let reliability: Vec<f64> = hazards.iter().map(|hazard| (-hazard).exp()).collect();
+        return Ok(hidden_availability(parameters, inspection, &reliability));
+    }
+    if parameters.mttr > 0.0 && parameters.repair == Repair::Minimal {
+        return Ok(hazard_rates(&hazards)
+            .into_iter()
+            .map(|rate| 1.0 / (1.0 + rate * parameters.mttr))
+            .collect());
+    }
+    let reliability: Vec<f64> = hazards.iter().map(|hazard| (-hazard).exp()).collect();
+    let Some(renewal) = Renewal::new(parameters, &reliability) else {
+        return Ok(reliability);
+    };
+    let r = &renewal.r;
+    // Renewals are spread over their bin, so the time since one is taken as
+    // half a bin longer than the bin count.
+    let availability: Vec<f64> = (0..r.len())
+        .map(|k| {
+            r[k] + (1..=k)
+                .map(|j| renewal.renewal[j] * 0.5 * (r[k - j] + r[k - j + 1]))
+                .sum::<f64>()
+        })
+        .collect();
+    Ok(renewal.interpolate(&availability, reliability.len() - 1))
+}
+
+/// The rate of occurrence of failures w(t), in failures per time step. After
+/// a renewal it is the density of the first failure plus that of every
+/// failure after a repair; under minimal repair it is h(t) while up, h(t)·A(t).
+/// Components with no MTTR fail at most once, so w(t) is their failure density.
+pub fn component_failure_intensity(
+    parameters: &NodeParameters,
+    profile: &MissionProfile,
+) -> Result<Vec<f64>, Problem> {
+    let hazards = component_hazards(parameters, profile)?;
+    if parameters.mttr > 0.0 && parameters.repair == Repair::Minimal {
+        return Ok(hazard_rates(&hazards)
+            .into_iter()
+            .map(|rate| rate / (1.0 + rate * parameters.mttr))
+            .collect());
+    }
+    let reliability: Vec<f64> = hazards.iter().map(|hazard| (-hazard).exp()).collect();
+    let Some(renewal) = Renewal::new(parameters, &reliability) else {
+        let mut density: Vec<f64> = reliability.windows(2).map(|w| w[0] - w[1]).collect();
+        density.insert(0, density.first().copied().unwrap_or_default());
+        return Ok(density);
+    };
+    let failure = &renewal.failure;
+    let mut intensity: Vec<f64> = (0..failure.len())
+        .map(|k| {
+            let failures = failure[k]
+                + (1..k)
+                    .map(|j| renewal.renewal[j] * failure[k - j])
+                    .sum::<f64>();
+            failures / renewal.step as f64
+        })
+        .collect();
+    // Each grid point holds the failures of the bin ending there, so t = 0
+    // takes the first bin's rate.
+    intensity[0] = intensity[1];
+    Ok(renewal.interpolate(&intensity, reliability.len() - 1))
+}
+
+/// The long-run fraction of time up, ignoring the mission profile: MTTF /
+/// (MTTF + MTTR) under renewal, or zero for components that aren't repaired.
+/// Under minimal repair the hazard keeps growing if any mode has β > 1, so
+/// the unit is eventually always down, and keeps falling if all have β < 1.
+/// Hidden failures add the mean time until an inspection finds them.
+pub fn steady_state_availability(parameters: &NodeParameters) -> f64 {
+    if let Some(inspection) = parameters.inspection {
+        let mttf = parameters.mean_time_to_failure();
+        return mttf / (mttf + inspection.mean_hidden_time() + parameters.mttr);
+    }
+    if parameters.mttr <= 0.0 {
+        return 0.0;
+    }
+    match parameters.repair {
+        Repair::Minimal if parameters.all_modes().any(|(shape, _)| shape > 1.0) => 0.0,
+        Repair::Minimal if parameters.all_modes().all(|(shape, _)| shape < 1.0) => 1.0,
+        _ => {
+            let mttf = parameters.mean_time_to_failure();
+            mttf / (mttf + parameters.mttr)
+        }
+    }
+}
+
+/// The B-life, the time by which `percent` % of units have failed,
+/// interpolated between time steps; `None` if `curve` never falls that far.
+pub fn b_life(curve: &[f64], percent: f64) -> Option<f64> {
+    let target = 1.0 - percent / 100.0;
+    if *curve.first()? <= target {
+        return Some(0.0);
+    }
+    let step = curve.windows(2).position(|w| w[1] <= target)?;
+    let (before, after) = (curve[step], curve[step + 1]);
+    Some(step as f64 + (before - target) / (before - after))
+}
+
+/// How a B-life reads in tables and labels.
+pub fn format_b_life(life: Option<f64>) -> String {
+    life.map_or_else(|| "—".to_owned(), |life| format!("{life:.1}"))
+}
+
+/// The mean time to failure, ∫R(t)dt by the trapezoidal rule. Curves that
+/// haven't decayed to zero by their last step are truncated there.
+pub fn mttf(curve: &[f64]) -> f64 {
+    curve.windows(2).map(|w| 0.5 * (w[0] + w[1])).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::Builder;
+
+    fn repairable(shape: f64, scale: f64, mttr: f64) -> NodeParameters {
+        NodeParameters {
+            shape,
+            scale,
+            mttr,
+            time_steps: 2_000,
+            ..NodeParameters::default()
+        }
+    }
+
+    #[test]
+    fn availability_settles_at_mttf_over_mttf_and_mttr() {
+        for (shape, mttf) in [
+            (1.0, 100.0),
+            (2.0, 100.0 * statrs::function::gamma::gamma(1.5)),
+        ] {
+            let availability =
+                component_availability(&repairable(shape, 100.0, 10.0), &MissionProfile::default())
+                    .unwrap();
+            let settled = availability.last().unwrap();
+            let expected = mttf / (mttf + 10.0);
+            assert!(
+                (settled - expected).abs() < 5e-3,
+                "A(∞) = {settled} for β = {shape}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn minimal_repair_of_a_constant_hazard_is_at_its_steady_state() {
+        // A memoryless unit can't tell a minimal repair from a renewal, and
+        // its hazard never changes, so A(t) = μ / (λ + μ) throughout.
+        let parameters = NodeParameters {
+            repair: Repair::Minimal,
+            ..repairable(1.0, 100.0, 10.0)
+        };
+        let availability = component_availability(&parameters, &MissionProfile::default()).unwrap();
+        let expected = 100.0 / (100.0 + 10.0);
+        for (step, a) in availability.iter().enumerate() {
+            assert!(
+                (a - expected).abs() < 1e-9,
+                "A = {a} at step {step}, expected {expected}"
+            );
+        }
+    }
+
+    fn inspected(mttf: f64, mttr: f64, interval: f64, effectiveness: f64) -> NodeParameters {
+        NodeParameters {
+            inspection: Some(Inspection {
+                interval,
+                effectiveness,
+            }),
+            time_steps: 1_000,
+            ..repairable(1.0, mttf, mttr)
+        }
+    }
+
+    #[test]
+    fn hidden_failures_average_half_an_interval_of_unavailability() {
+        // λτ/2 for a rarely failing unit renewed at once when found.
+        let (mttf, interval) = (10_000.0, 100.0);
+        let availability = component_availability(
+            &inspected(mttf, 0.0, interval, 1.0),
+            &MissionProfile::default(),
+        )
+        .unwrap();
+        let unavailability =
+            availability.iter().map(|a| 1.0 - a).sum::<f64>() / availability.len() as f64;
+        let expected = interval / mttf / 2.0;
+        assert!(
+            (unavailability - expected).abs() < 0.05 * expected,
+            "mean unavailability {unavailability}, expected λτ/2 = {expected}"
+        );
+    }
+
+    #[test]
+    fn inspected_availability_settles_at_mttf_over_mttf_and_mttr() {
+        // Each renewal cycle is up for the MTTF, then down while the failure
+        // stays hidden and while it is repaired. Inspected every step, the
+        // failure is hidden for only half a step.
+        for (interval, effectiveness) in [(1.0, 1.0), (20.0, 0.8)] {
+            let parameters = inspected(100.0, 5.0, interval, effectiveness);
+            let availability =
+                component_availability(&parameters, &MissionProfile::default()).unwrap();
+            // A(t) saws up and down between inspections, so it is averaged
+            // over the later ones.
+            let settled = availability[500..].iter().sum::<f64>() / 501.0;
+            let hidden = parameters.inspection.unwrap().mean_hidden_time();
+            let expected = 100.0 / (100.0 + hidden + 5.0);
+            assert!(
+                (settled - expected).abs() < 1e-3,
+                "A(∞) = {settled} inspected every {interval} at {effectiveness}, \
+                 expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn voting_on_identical_members_is_binomial() {
+        let member = vec![1.0, 0.95, 0.7, 0.4, 0.1, 0.0];
+        let binomial = |n: u64, k: u64, r: f64| {
+            (k..=n)
+                .map(|j| {
+                    statrs::function::factorial::binomial(n, j)
+                        * r.powi(j as i32)
+                        * (1.0 - r).powi((n - j) as i32)
+                })
+                .sum::<f64>()
+        };
+        for (n, k) in [(3, 2), (4, 2), (5, 5), (6, 1)] {
+            let curve = voting(&vec![member.clone(); n], k, 1.0);
+            for (r, voted) in member.iter().zip(&curve) {
+                let expected = binomial(n as u64, k as u64, *r);
+                assert!(
+                    (voted - expected).abs() < 1e-12,
+                    "{k}-of-{n} at R = {r}: {voted}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_join_up_to_the_whole_curve() {
+        let mut state = GraphState::default();
+        let mut model = Builder::new(&mut state);
+        let parameters = |shape, scale| NodeParameters {
+            shape,
+            scale,
+            time_steps: 1_000,
+            ..NodeParameters::default()
+        };
+        let pump = model.component("Pump".to_owned(), parameters(1.5, 800.0), 0, 0);
+        let valve = model.component("Valve".to_owned(), parameters(0.7, 3_000.0), 0, 1);
+        let system = model.block(NodeTemplate::Series, [pump, valve], 1, 0);
+        let graph = model.finish().graph;
+
+        let options = Options::default();
+        let whole = evaluate_curve(&graph, &options, system, &mut OutputsCache::new()).unwrap();
+        let mut chunked = Vec::new();
+        evaluate_chunked(&graph, &options, system, 64, |start, chunk| {
+            assert_eq!(start, chunked.len());
+            chunked.extend_from_slice(chunk);
+        })
+        .unwrap();
+        assert_eq!(chunked.len(), whole.len());
+        for (t, (a, b)) in chunked.iter().zip(&whole).enumerate() {
+            assert!((a - b).abs() < 1e-12, "R({t}) = {a} in chunks, {b} whole");
+        }
+    }
+
+    #[test]
+    fn excluding_a_node_invalidates_all_curves() {
+        let mut state = GraphState::default();
+        let mut model = Builder::new(&mut state);
+        let pump = model.component("Pump".to_owned(), NodeParameters::default(), 0, 0);
+        let valve = model.component("Valve".to_owned(), NodeParameters::default(), 0, 1);
+        let system = model.block(NodeTemplate::Series, [pump, valve], 1, 0);
+        let mut graph = model.finish().graph;
+        let output = graph[system].get_output("reliability").unwrap();
+
+        let mut all_curves = AllCurves::default();
+        let mut system_curve = |graph: &Graph| match all_curves.update(graph, &state).get(&output) {
+            Some(ValueType::Reliability { curve }) => curve.clone(),
+            _ => panic!("the system wasn't evaluated"),
+        };
+        let before = system_curve(&graph);
+        graph[valve].user_data.excluded = true;
+        let after = system_curve(&graph);
+        let pump = component_reliability(&NodeParameters::default(), &MissionProfile::default());
+        assert_ne!(before, after);
+        assert_eq!(after, pump.unwrap());
+    }
+
+    #[test]
+    fn coarse_curves_follow_the_precise_ones() {
+        let mut state = GraphState::default();
+        let mut model = Builder::new(&mut state);
+        let parameters = model.parameters(2.5, 5_000.0, 0.0);
+        let pump = model.component("Pump".to_owned(), parameters, 0, 0);
+        let graph = model.finish().graph;
+
+        let precise = evaluate_curve(&graph, &Options::default(), pump, &mut OutputsCache::new());
+        let coarse = Options {
+            coarse: true,
+            ..Options::default()
+        };
+        let coarse = evaluate_curve(&graph, &coarse, pump, &mut OutputsCache::new());
+        let (precise, coarse) = (precise.unwrap(), coarse.unwrap());
+        assert_eq!(coarse.len(), precise.len());
+        assert_ne!(coarse, precise);
+        for (t, (a, b)) in coarse.iter().zip(&precise).enumerate() {
+            assert!((a - b).abs() < 1e-3, "R({t}) = {a} coarsely, {b} precisely");
+        }
+    }
+
+    /// A unit of constant hazard `1 / mttf` backed by a cold, perfectly
+    /// switched spare of the same kind, over twice the MTTF.
+    fn cold_standby(state: &mut GraphState, mttf: f64) -> (Graph, NodeId) {
+        let mut model = Builder::new(state);
+        let parameters = NodeParameters {
+            time_steps: 2 * mttf as usize,
+            ..model.parameters(1.0, mttf, 0.0)
+        };
+        let pump = model.component("Pump".to_owned(), parameters.clone(), 0, 0);
+        let spare = model.component("Spare".to_owned(), parameters, 0, 1);
+        let system = model.block(NodeTemplate::Standby, [pump, spare], 1, 0);
+        model.set_value(
+            system,
+            "dormancy",
+            ValueType::Dormancy {
+                dormancy: Dormancy::Cold,
+            },
+        );
+        model.data(system).coverage = 1.0;
+        (model.finish().graph, system)
+    }
+
+    #[test]
+    fn cold_standby_follows_the_erlang_closed_form() {
+        let mut state = GraphState::default();
+        let (graph, system) = cold_standby(&mut state, 1_000.0);
+        let curve = evaluate_curve(
+            &graph,
+            &Options::default(),
+            system,
+            &mut OutputsCache::new(),
+        )
+        .unwrap();
+        // The pair lives for the sum of two exponential lives:
+        // R(t) = e^{-λt} (1 + λt).
+        for (t, r) in curve.iter().enumerate() {
+            let lt = t as f64 / 1_000.0;
+            let expected = (-lt).exp() * (1.0 + lt);
+            assert!(
+                (r - expected).abs() < 1e-3,
+                "R({t}) = {r}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn component_curves_include_both_ends() {
+        let parameters = NodeParameters {
+            time_steps: 10,
+            ..NodeParameters::default()
+        };
+        let curve = component_reliability(&parameters, &MissionProfile::default()).unwrap();
+        assert_eq!(curve.len(), parameters.time_steps + 1);
+        assert_eq!(curve[0], 1.0);
+        let end = (-(10.0 / parameters.scale).powf(parameters.shape)).exp();
+        assert!((curve[10] - end).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,319 @@
+//! Event trees: the sequences that can follow an initiating event as each
+//! protective layer, modelled by a node of the diagram, works or fails when
+//! demanded.
+//!
+//! A layer fails on demand with probability 1 − R at the mission time, or
+//! with its average over the mission, as for layers that are proof tested
+//! at that interval.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::{evaluate_curve, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+
+/// The most layers a tree is built for, as a full tree doubles with each.
+pub const MAX_LAYERS: usize = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Demand {
+    AtMissionTime,
+    /// The average over the mission, the usual PFD_avg of safety functions.
+    #[default]
+    Average,
+}
+
+impl Demand {
+    pub const ALL: [Demand; 2] = [Demand::AtMissionTime, Demand::Average];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Demand::AtMissionTime => "event-tree-demand-mission-time",
+            Demand::Average => "event-tree-demand-average",
+        })
+    }
+}
+
+/// One path through the tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sequence {
+    /// Whether each layer worked, or `None` where it wasn't demanded.
+    pub outcomes: Vec<Option<bool>>,
+    pub probability: f64,
+    /// The initiating event's frequency times the sequence's probability.
+    pub frequency: f64,
+}
+
+impl Sequence {
+    /// Whether every layer failed, so the event's consequence is unmitigated.
+    pub fn unmitigated(&self) -> bool {
+        self.outcomes.iter().all(|outcome| *outcome == Some(false))
+    }
+}
+
+/// The probability of failure on demand of a layer with reliability `curve`.
+pub fn pfd(curve: &[f64], time: usize, demand: Demand) -> f64 {
+    let time = time.min(curve.len().saturating_sub(1));
+    match demand {
+        Demand::AtMissionTime => 1.0 - curve.get(time).copied().unwrap_or(1.0),
+        Demand::Average if time == 0 => 1.0 - curve.first().copied().unwrap_or(1.0),
+        Demand::Average => {
+            let up: f64 = curve[..=time].windows(2).map(|w| 0.5 * (w[0] + w[1])).sum();
+            1.0 - up / time as f64
+        }
+    }
+}
+
+/// The sequences following an event of `frequency` through layers that fail
+/// with `pfds`, success branches first. With `stop_at_success`, a layer that
+/// works ends its sequence, as the later layers are then never demanded.
+pub fn sequences(frequency: f64, pfds: &[f64], stop_at_success: bool) -> Vec<Sequence> {
+    let mut sequences = Vec::new();
+    let mut outcomes = Vec::with_capacity(pfds.len());
+    branch(
+        frequency,
+        pfds,
+        stop_at_success,
+        1.0,
+        &mut outcomes,
+        &mut sequences,
+    );
+    sequences
+}
+
+fn branch(
+    frequency: f64,
+    pfds: &[f64],
+    stop_at_success: bool,
+    probability: f64,
+    outcomes: &mut Vec<Option<bool>>,
+    sequences: &mut Vec<Sequence>,
+) {
+    let layer = outcomes.len();
+    let Some(pfd) = pfds.get(layer) else {
+        sequences.push(Sequence {
+            outcomes: outcomes.clone(),
+            probability,
+            frequency: frequency * probability,
+        });
+        return;
+    };
+    for (worked, p) in [(true, 1.0 - pfd), (false, *pfd)] {
+        outcomes.push(Some(worked));
+        if worked && stop_at_success {
+            let mut ended = outcomes.clone();
+            ended.resize(pfds.len(), None);
+            sequences.push(Sequence {
+                outcomes: ended,
+                probability: probability * p,
+                frequency: frequency * probability * p,
+            });
+        } else {
+            branch(
+                frequency,
+                pfds,
+                stop_at_success,
+                probability * p,
+                outcomes,
+                sequences,
+            );
+        }
+        outcomes.pop();
+    }
+}
+
+/// A built tree: each layer's node and PFD, and the sequences.
+struct Tree {
+    layers: Vec<(NodeId, f64)>,
+    sequences: Vec<Sequence>,
+}
+
+pub struct EventTree {
+    /// Initiating events per year.
+    frequency: f64,
+    layers: Vec<Option<NodeId>>,
+    demand: Demand,
+    stop_at_success: bool,
+    result: Option<Result<Tree, String>>,
+}
+
+impl Default for EventTree {
+    fn default() -> Self {
+        Self {
+            frequency: 0.1,
+            layers: vec![None],
+            demand: Demand::default(),
+            stop_at_success: true,
+            result: None,
+        }
+    }
+}
+
+impl EventTree {
+    fn build(&self, graph: &Graph, state: &GraphState) -> anyhow::Result<Tree> {
+        let options = Options::of(state);
+        let mut curves = OutputsCache::new();
+        let layers = self
+            .layers
+            .iter()
+            .flatten()
+            .map(|node| {
+                let curve = evaluate_curve(graph, &options, *node, &mut curves)?;
+                Ok((*node, pfd(&curve, state.mission_time, self.demand)))
+            })
+            .collect::<anyhow::Result<Vec<(NodeId, f64)>>>()?;
+        let pfds: Vec<f64> = layers.iter().map(|(_, pfd)| *pfd).collect();
+        Ok(Tree {
+            sequences: sequences(self.frequency, &pfds, self.stop_at_success),
+            layers,
+        })
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("event-tree-title"))
+            .open(open)
+            .default_width(550.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("event tree setup")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("event-tree-frequency"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.frequency)
+                                .speed(0.01)
+                                .clamp_range(0.0..=f64::MAX)
+                                .suffix(format!(" {}", tr("event-tree-per-year"))),
+                        );
+                        ui.end_row();
+                        ui.label(tr("event-tree-demand"));
+                        egui::ComboBox::from_id_source("event tree demand")
+                            .selected_text(self.demand.name())
+                            .show_ui(ui, |ui| {
+                                for demand in Demand::ALL {
+                                    ui.selectable_value(&mut self.demand, demand, demand.name());
+                                }
+                            });
+                        ui.end_row();
+                    });
+                ui.checkbox(&mut self.stop_at_success, tr("event-tree-stop-at-success"))
+                    .on_hover_text(tr("event-tree-stop-at-success-hint"));
+
+                ui.strong(tr("event-tree-layers"));
+                let candidates: Vec<NodeId> = graph
+                    .nodes
+                    .iter()
+                    .filter(|(_, node)| node.user_data.template.evaluates())
+                    .map(|(node_id, _)| node_id)
+                    .collect();
+                let mut removed = None;
+                for (i, layer) in self.layers.iter_mut().enumerate() {
+                    if layer.is_some_and(|node| !candidates.contains(&node)) {
+                        *layer = None;
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}.", i + 1));
+                        egui::ComboBox::from_id_source(("event tree layer", i))
+                            .selected_text(
+                                layer
+                                    .map(|node| graph[node].label.clone())
+                                    .unwrap_or_default(),
+                            )
+                            .show_ui(ui, |ui| {
+                                for node in &candidates {
+                                    ui.selectable_value(layer, Some(*node), &graph[*node].label);
+                                }
+                            });
+                        if ui.small_button("🗑").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = removed {
+                    self.layers.remove(i);
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            self.layers.len() < MAX_LAYERS,
+                            egui::Button::new(tr("event-tree-add-layer")),
+                        )
+                        .clicked()
+                    {
+                        self.layers.push(None);
+                    }
+                    let ready = !self.layers.is_empty() && self.layers.iter().all(Option::is_some);
+                    if ui
+                        .add_enabled(ready, egui::Button::new(tr("event-tree-build")))
+                        .clicked()
+                    {
+                        self.result = Some(self.build(graph, state).map_err(|err| err.to_string()));
+                    }
+                });
+
+                let tree = match &self.result {
+                    Some(Ok(tree)) => tree,
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                        return;
+                    }
+                    None => return,
+                };
+                let unmitigated: f64 = tree
+                    .sequences
+                    .iter()
+                    .filter(|sequence| sequence.unmitigated())
+                    .map(|sequence| sequence.frequency)
+                    .sum();
+                let all_fail: f64 = tree.layers.iter().map(|(_, pfd)| pfd).product();
+                ui.label(tr_with(
+                    "event-tree-unmitigated",
+                    &[
+                        ("frequency", &format!("{unmitigated:.3e}")),
+                        ("reduction", &format!("{:.1}", 1.0 / all_fail)),
+                    ],
+                ));
+                ui.separator();
+                let theme = state.settings.theme.catppuccin();
+                egui::ScrollArea::both().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("event tree")
+                        .num_columns(tree.layers.len() + 3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("#");
+                            for (layer, pfd) in &tree.layers {
+                                let label = graph.nodes.get(*layer).map_or("", |node| &node.label);
+                                ui.strong(label).on_hover_text(format!("PFD = {pfd:.3e}"));
+                            }
+                            ui.strong(tr("event-tree-probability"));
+                            ui.strong(tr("event-tree-sequence-frequency"));
+                            ui.end_row();
+                            for (i, sequence) in tree.sequences.iter().enumerate() {
+                                ui.label((i + 1).to_string());
+                                for outcome in &sequence.outcomes {
+                                    match outcome {
+                                        Some(true) => ui.colored_label(theme.green, "✔"),
+                                        Some(false) => ui.colored_label(theme.red, "✘"),
+                                        None => ui.label(""),
+                                    };
+                                }
+                                ui.label(format!("{:.3e}", sequence.probability));
+                                let frequency = format!("{:.3e}", sequence.frequency);
+                                if sequence.unmitigated() {
+                                    ui.colored_label(theme.red, frequency);
+                                } else {
+                                    ui.label(frequency);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                ui.label(tr("event-tree-hint"));
+            });
+    }
+}
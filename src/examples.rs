@@ -0,0 +1,267 @@
+//! Small example models showing common structures, opened from the Help menu.
+use eframe::egui;
+use egui_node_graph2::{NodeId, NodeTemplateTrait};
+
+use crate::i18n::tr;
+use crate::nodes::{
+    self, EditorState, GraphState, NodeData, NodeParameters, NodeTemplate, ValueType,
+};
+
+/// Horizontal and vertical distance between the nodes of an example.
+pub const COLUMN: f32 = 260.0;
+pub const ROW: f32 = 190.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Example {
+    Series,
+    TwoOutOfThree,
+    StandbyGenerator,
+    Bridge,
+}
+
+impl Example {
+    pub const ALL: [Example; 4] = [
+        Example::Series,
+        Example::TwoOutOfThree,
+        Example::StandbyGenerator,
+        Example::Bridge,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            Example::Series => tr("example-series"),
+            Example::TwoOutOfThree => tr("example-voting"),
+            Example::StandbyGenerator => tr("example-standby"),
+            Example::Bridge => tr("example-bridge"),
+        }
+    }
+
+    fn description(self) -> String {
+        match self {
+            Example::Series => tr("example-series-note"),
+            Example::TwoOutOfThree => tr("example-voting-note"),
+            Example::StandbyGenerator => tr("example-standby-note"),
+            Example::Bridge => tr("example-bridge-note"),
+        }
+    }
+
+    /// The example's graph, with its system node made active in `user_state`.
+    pub fn build(self, user_state: &mut GraphState) -> EditorState {
+        let mut model = Builder::new(user_state);
+        let system = match self {
+            Example::Series => {
+                let pump = model.component(
+                    tr("example-pump"),
+                    model.parameters(1.5, 20_000.0, 0.0),
+                    0,
+                    0,
+                );
+                let motor = model.component(
+                    tr("example-motor"),
+                    model.parameters(1.2, 40_000.0, 0.0),
+                    0,
+                    1,
+                );
+                let controller = model.component(
+                    tr("example-controller"),
+                    model.parameters(1.0, 80_000.0, 0.0),
+                    0,
+                    2,
+                );
+                let drive = model.block(NodeTemplate::Series, [pump, motor], 1, 0);
+                model.block(NodeTemplate::Series, [drive, controller], 2, 1)
+            }
+            Example::TwoOutOfThree => {
+                let sensors =
+                    ["A", "B", "C"].map(|name| format!("{} {name}", tr("example-sensor")));
+                let [a, b, c] = [0, 1, 2].map(|row| {
+                    model.component(
+                        sensors[row].clone(),
+                        model.parameters(1.0, 30_000.0, 0.0),
+                        0,
+                        row,
+                    )
+                });
+                let ab = model.block(NodeTemplate::Series, [a, b], 1, 0);
+                let ac = model.block(NodeTemplate::Series, [a, c], 1, 1);
+                let bc = model.block(NodeTemplate::Series, [b, c], 1, 2);
+                let any = model.block(NodeTemplate::Parallel, [ab, ac], 2, 0);
+                model.block(NodeTemplate::Parallel, [any, bc], 3, 1)
+            }
+            Example::StandbyGenerator => {
+                let mains = model.component(
+                    tr("example-mains"),
+                    model.parameters(1.0, 2_000.0, 4.0),
+                    0,
+                    0,
+                );
+                let switch = model.component(
+                    tr("example-switch"),
+                    model.parameters(1.0, 100_000.0, 8.0),
+                    0,
+                    1,
+                );
+                let generator = model.component(
+                    tr("example-generator"),
+                    model.parameters(1.3, 5_000.0, 24.0),
+                    0,
+                    2,
+                );
+                let backup = model.block(NodeTemplate::Series, [switch, generator], 1, 2);
+                // Over a year a repaired supply is judged by its availability.
+                model.user_state.show_availability = true;
+                model.block(NodeTemplate::Parallel, [mains, backup], 2, 1)
+            }
+            Example::Bridge => {
+                let [one, two, three, four, five] = [0, 1, 2, 3, 4].map(|i| {
+                    let label = format!("{} {}", tr("example-link"), i + 1);
+                    model.component(label, model.parameters(1.0, 25_000.0, 0.0), 0, i)
+                });
+                // The four minimal paths across the bridge: 1-4, 2-5, 1-3-5 and 2-3-4.
+                let path_14 = model.block(NodeTemplate::Series, [one, four], 1, 0);
+                let path_25 = model.block(NodeTemplate::Series, [two, five], 1, 1);
+                let link_13 = model.block(NodeTemplate::Series, [one, three], 1, 2);
+                let link_23 = model.block(NodeTemplate::Series, [two, three], 1, 3);
+                let path_135 = model.block(NodeTemplate::Series, [link_13, five], 2, 2);
+                let path_234 = model.block(NodeTemplate::Series, [link_23, four], 2, 3);
+                let direct = model.block(NodeTemplate::Parallel, [path_14, path_25], 3, 0);
+                let crossing = model.block(NodeTemplate::Parallel, [path_135, path_234], 3, 3);
+                model.block(NodeTemplate::Parallel, [direct, crossing], 4, 1)
+            }
+        };
+        model.note(self.description(), -1, 0);
+        model.user_state.active_node = Some(system);
+        model.finish()
+    }
+}
+
+/// Lays out a new graph on a grid of columns and rows.
+pub struct Builder<'a> {
+    editor: EditorState,
+    pub user_state: &'a mut GraphState,
+}
+
+impl<'a> Builder<'a> {
+    pub fn new(user_state: &'a mut GraphState) -> Self {
+        Self {
+            editor: EditorState::default(),
+            user_state,
+        }
+    }
+
+    pub fn finish(self) -> EditorState {
+        self.editor
+    }
+
+    pub fn node(
+        &mut self,
+        template: NodeTemplate,
+        label: String,
+        column: i32,
+        row: usize,
+    ) -> NodeId {
+        let user_state = &mut *self.user_state;
+        let node_id =
+            self.editor
+                .graph
+                .add_node(label, template.user_data(user_state), |graph, node_id| {
+                    template.build_node(graph, user_state, node_id)
+                });
+        self.editor.node_positions.insert(
+            node_id,
+            egui::pos2(COLUMN * column as f32, ROW * row as f32),
+        );
+        self.editor.node_order.push(node_id);
+        node_id
+    }
+
+    /// The default parameters with the given Weibull distribution and MTTR.
+    pub fn parameters(&self, shape: f64, scale: f64, mttr: f64) -> NodeParameters {
+        NodeParameters {
+            shape,
+            scale,
+            mttr,
+            ..self.user_state.settings.default_parameters.clone()
+        }
+    }
+
+    pub fn component(
+        &mut self,
+        label: String,
+        parameters: NodeParameters,
+        column: i32,
+        row: usize,
+    ) -> NodeId {
+        let node_id = self.node(NodeTemplate::Component, label, column, row);
+        if let Some(inline) = nodes::component_parameters_mut(&mut self.editor.graph, node_id) {
+            *inline = parameters;
+        }
+        node_id
+    }
+
+    pub fn rename(&mut self, node_id: NodeId, label: String) {
+        self.editor.graph[node_id].label = label;
+    }
+
+    /// A series or parallel block fed by `inputs`.
+    pub fn block(
+        &mut self,
+        template: NodeTemplate,
+        inputs: [NodeId; 2],
+        column: i32,
+        row: usize,
+    ) -> NodeId {
+        let label = template.node_graph_label(self.user_state);
+        let node_id = self.node(template, label, column, row);
+        for (input, name) in inputs.into_iter().zip(["A", "B"]) {
+            self.connect(input, node_id, name);
+        }
+        node_id
+    }
+
+    /// Feeds the reliability of `from` into the input `name` of block `to`.
+    pub fn connect(&mut self, from: NodeId, to: NodeId, name: &str) {
+        let graph = &mut self.editor.graph;
+        let output = graph[from]
+            .get_output("reliability")
+            .expect("blocks have outputs");
+        let input = graph[to].get_input(name).expect("blocks have two inputs");
+        graph.add_connection(output, input);
+    }
+
+    /// Sets the constant input `name` of `node_id` to `value`.
+    pub fn set_value(&mut self, node_id: NodeId, name: &str, value: ValueType) {
+        let graph = &mut self.editor.graph;
+        if let Ok(input) = graph[node_id].get_input(name) {
+            graph[input].value = value;
+        }
+    }
+
+    /// The node's settings, such as a voting block's votes.
+    pub fn data(&mut self, node_id: NodeId) -> &mut NodeData {
+        &mut self.editor.graph[node_id].user_data
+    }
+
+    /// Adds or removes members of a voting block until it has `count`.
+    pub fn members(&mut self, node_id: NodeId, count: usize) {
+        let graph = &mut self.editor.graph;
+        while graph[node_id].inputs.len() < count {
+            nodes::add_member(graph, node_id);
+        }
+        while graph[node_id].inputs.len() > count {
+            let before = graph[node_id].inputs.len();
+            nodes::remove_member(graph, node_id);
+            if graph[node_id].inputs.len() == before {
+                break;
+            }
+        }
+    }
+
+    pub fn note(&mut self, text: String, column: i32, row: usize) {
+        let node_id = self.node(NodeTemplate::Note, tr("node-note"), column, row);
+        let graph = &mut self.editor.graph;
+        if let Ok(input) = graph[node_id].get_input("text") {
+            graph[input].value = ValueType::Text { text };
+        }
+    }
+}
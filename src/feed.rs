@@ -0,0 +1,341 @@
+//! Live parameter updates from an MQTT broker, for watching a model follow
+//! measurements from the plant as they come in.
+//!
+//! Each message on the subscribed topic is either a number, read as the
+//! value of the tag named by its topic, or a JSON object of tag names and
+//! numbers. Mapped tags overwrite a component parameter, and the model is
+//! evaluated again on the next frame. OPC UA servers can take part through
+//! one of the usual OPC UA to MQTT bridges.
+//!
+//! The client is only built with the `mqtt` feature.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, NodeParameters};
+
+/// The component parameters a tag can set.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Field {
+    Shape,
+    #[default]
+    Scale,
+    DutyCycle,
+    EnvironmentFactor,
+    Temperature,
+    Mttr,
+}
+
+impl Field {
+    pub const ALL: [Field; 6] = [
+        Field::Shape,
+        Field::Scale,
+        Field::DutyCycle,
+        Field::EnvironmentFactor,
+        Field::Temperature,
+        Field::Mttr,
+    ];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Field::Shape => "param-shape",
+            Field::Scale => "param-scale",
+            Field::DutyCycle => "param-duty-cycle",
+            Field::EnvironmentFactor => "param-environment-factor",
+            Field::Temperature => "param-temperature",
+            Field::Mttr => "param-mttr",
+        })
+    }
+
+    /// Sets the field, or returns `false` if `value` is outside its range.
+    fn set(self, parameters: &mut NodeParameters, value: f64) -> bool {
+        let (field, valid) = match self {
+            Field::Shape => (&mut parameters.shape, value > 0.0),
+            Field::Scale => (&mut parameters.scale, value > 0.0),
+            Field::DutyCycle => (&mut parameters.duty_cycle, (0.0..=1.0).contains(&value)),
+            Field::EnvironmentFactor => (&mut parameters.environment_factor, value > 0.0),
+            Field::Temperature => (&mut parameters.temperature, value.is_finite()),
+            Field::Mttr => (&mut parameters.mttr, value >= 0.0),
+        };
+        if valid {
+            *field = value;
+        }
+        valid
+    }
+}
+
+/// A tag whose values go to a parameter of a component.
+#[derive(Clone, Debug, Default)]
+pub struct Mapping {
+    pub tag: String,
+    pub component: Option<NodeId>,
+    pub field: Field,
+    /// The last value received, and whether it was applied.
+    pub last: Option<(f64, bool)>,
+}
+
+/// What the subscription thread hands over to the app.
+#[derive(Default)]
+struct Shared {
+    /// Tag values received since the app last took them.
+    values: Vec<(String, f64)>,
+    received: usize,
+    /// Set when the connection ends, with the reason if it failed.
+    ended: Option<Option<String>>,
+}
+
+/// A subscription running on a background thread.
+#[cfg_attr(not(feature = "mqtt"), allow(dead_code))]
+struct Running {
+    shared: Arc<Mutex<Shared>>,
+    stop: Arc<AtomicBool>,
+    #[cfg(feature = "mqtt")]
+    client: rumqttc::Client,
+}
+
+impl Running {
+    #[cfg(feature = "mqtt")]
+    fn start(ctx: &egui::Context, host: &str, port: u16, topic: &str) -> anyhow::Result<Self> {
+        use rumqttc::{Event, MqttOptions, Packet, QoS};
+
+        let id = format!("block-{}", std::process::id());
+        let mut options = MqttOptions::new(id, host, port);
+        options.set_keep_alive(std::time::Duration::from_secs(5));
+        let (client, mut connection) = rumqttc::Client::new(options, 16);
+        client.subscribe(topic, QoS::AtMostOnce)?;
+
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let running = Self {
+            shared: shared.clone(),
+            stop: stop.clone(),
+            client,
+        };
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let mut error = None;
+            for notification in connection.iter() {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                match notification {
+                    Ok(Event::Incoming(Packet::Publish(publish))) => {
+                        let values = decode(&publish.topic, &publish.payload);
+                        let mut shared = shared.lock().unwrap();
+                        shared.received += 1;
+                        shared.values.extend(values);
+                        ctx.request_repaint();
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        error = Some(err.to_string());
+                        break;
+                    }
+                }
+            }
+            shared.lock().unwrap().ended = Some(error);
+            ctx.request_repaint();
+        });
+        Ok(running)
+    }
+
+    #[cfg(not(feature = "mqtt"))]
+    fn start(_ctx: &egui::Context, _host: &str, _port: u16, _topic: &str) -> anyhow::Result<Self> {
+        anyhow::bail!(tr("feed-unsupported"))
+    }
+
+    fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        #[cfg(feature = "mqtt")]
+        let _ = self.client.disconnect();
+    }
+}
+
+/// The tag values in one message on `topic`.
+pub fn decode(topic: &str, payload: &[u8]) -> Vec<(String, f64)> {
+    let Ok(text) = std::str::from_utf8(payload) else {
+        return Vec::new();
+    };
+    if let Ok(value) = text.trim().parse::<f64>() {
+        return vec![(topic.to_owned(), value)];
+    }
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(serde_json::Value::Object(object)) => object
+            .into_iter()
+            .filter_map(|(tag, value)| Some((tag, value.as_f64()?)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub struct Feed {
+    host: String,
+    port: u16,
+    topic: String,
+    pub mappings: Vec<Mapping>,
+    running: Option<Running>,
+    received: usize,
+    /// Why the last connection couldn't be made or ended.
+    error: Option<String>,
+}
+
+impl Default for Feed {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_owned(),
+            port: 1883,
+            topic: "plant/#".to_owned(),
+            mappings: Vec::new(),
+            running: None,
+            received: 0,
+            error: None,
+        }
+    }
+}
+
+impl Feed {
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Writes the values received since the last call into the mapped
+    /// parameters and returns how many were applied. Called every frame,
+    /// whether or not the window is open.
+    pub fn apply(&mut self, graph: &mut Graph) -> usize {
+        let Some(running) = &self.running else {
+            return 0;
+        };
+        let (values, ended) = {
+            let mut shared = running.shared.lock().unwrap();
+            self.received = shared.received;
+            (std::mem::take(&mut shared.values), shared.ended.take())
+        };
+        let mut applied = 0;
+        for (tag, value) in values {
+            for mapping in self.mappings.iter_mut().filter(|m| m.tag == tag) {
+                let parameters = mapping
+                    .component
+                    .and_then(|node| nodes::component_parameters_mut(graph, node));
+                let set = parameters.is_some_and(|parameters| mapping.field.set(parameters, value));
+                mapping.last = Some((value, set));
+                applied += set as usize;
+            }
+        }
+        if let Some(error) = ended {
+            if let Some(error) = &error {
+                tracing::warn!("the data feed disconnected: {error}");
+            }
+            self.error = error;
+            self.running = None;
+        }
+        applied
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context, open: &mut bool, graph: &Graph) {
+        egui::Window::new(tr("feed-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                ui.label(tr("feed-hint"));
+                let running = self.running.is_some();
+                ui.add_enabled_ui(!running, |ui| {
+                    egui::Grid::new("feed broker")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label(tr("feed-host"));
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(&mut self.host);
+                                ui.add(egui::DragValue::new(&mut self.port).prefix(":"));
+                            });
+                            ui.end_row();
+                            ui.label(tr("feed-topic"));
+                            ui.text_edit_singleline(&mut self.topic);
+                            ui.end_row();
+                        });
+                });
+                ui.horizontal(|ui| {
+                    if running {
+                        if ui.button(tr("feed-disconnect")).clicked() {
+                            if let Some(running) = self.running.take() {
+                                running.stop();
+                            }
+                        }
+                        ui.label(tr_with("feed-received", &[("count", &self.received)]));
+                    } else if ui.button(tr("feed-connect")).clicked() {
+                        match Running::start(ctx, &self.host, self.port, &self.topic) {
+                            Ok(running) => {
+                                tracing::info!("subscribed to {} on {}", self.topic, self.host);
+                                self.running = Some(running);
+                                self.received = 0;
+                                self.error = None;
+                            }
+                            Err(err) => self.error = Some(err.to_string()),
+                        }
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.separator();
+                let mut removed = None;
+                egui::Grid::new("feed mappings")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("feed-tag"));
+                        ui.strong(tr("goal-seek-component"));
+                        ui.strong(tr("feed-parameter"));
+                        ui.strong(tr("feed-last"));
+                        ui.end_row();
+                        for (i, mapping) in self.mappings.iter_mut().enumerate() {
+                            if mapping
+                                .component
+                                .is_some_and(|node| !graph.nodes.contains_key(node))
+                            {
+                                mapping.component = None;
+                            }
+                            ui.add(
+                                egui::TextEdit::singleline(&mut mapping.tag).desired_width(140.0),
+                            );
+                            nodes::component_combo(
+                                ui,
+                                &format!("feed component {i}"),
+                                graph,
+                                &mut mapping.component,
+                            );
+                            egui::ComboBox::from_id_source(format!("feed field {i}"))
+                                .selected_text(mapping.field.name())
+                                .show_ui(ui, |ui| {
+                                    for field in Field::ALL {
+                                        ui.selectable_value(
+                                            &mut mapping.field,
+                                            field,
+                                            field.name(),
+                                        );
+                                    }
+                                });
+                            match mapping.last {
+                                Some((value, true)) => ui.label(format!("{value}")),
+                                Some((value, false)) => ui
+                                    .colored_label(ui.visuals().warn_fg_color, format!("{value}"))
+                                    .on_hover_text(tr("feed-rejected")),
+                                None => ui.label("—"),
+                            };
+                            if ui.small_button("🗑").clicked() {
+                                removed = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = removed {
+                    self.mappings.remove(i);
+                }
+                if ui.button(tr("feed-add")).clicked() {
+                    self.mappings.push(Mapping::default());
+                }
+            });
+    }
+}
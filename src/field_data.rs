@@ -0,0 +1,582 @@
+//! Import of field failure logs and Weibull fits per component. A log the
+//! maintenance system exports on a schedule can be read again as it
+//! changes, flagging components whose field data has drifted from the model.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use rand::Rng;
+
+use crate::i18n::{tr, tr_with};
+use crate::kaplan_meier::Observation;
+use crate::nodes::{self, Graph, GraphState, ScaleEntry, Uncertainty};
+use crate::settings::TimeUnit;
+use crate::toasts;
+
+const ITERATIONS: usize = 100;
+/// Resamples drawn for the bootstrap intervals.
+const RESAMPLES: usize = 200;
+
+/// The maximum likelihood Weibull shape and scale for right-censored data,
+/// or `None` without at least one failure at a positive time.
+pub fn fit_weibull(observations: &[Observation]) -> Option<(f64, f64)> {
+    let observations: Vec<&Observation> = observations.iter().filter(|o| o.time > 0.0).collect();
+    let failures = observations.iter().filter(|o| o.failed).count() as f64;
+    if failures == 0.0 {
+        return None;
+    }
+    let mean_log_failure = observations
+        .iter()
+        .filter(|o| o.failed)
+        .map(|o| o.time.ln())
+        .sum::<f64>()
+        / failures;
+    // The profile likelihood equation for the shape, increasing in `shape`.
+    let score = |shape: f64| {
+        let (mut sum, mut weighted) = (0.0, 0.0);
+        for o in &observations {
+            let power = o.time.powf(shape);
+            sum += power;
+            weighted += power * o.time.ln();
+        }
+        weighted / sum - 1.0 / shape - mean_log_failure
+    };
+
+    let (mut lo, mut hi) = (0.01_f64.ln(), 100.0_f64.ln());
+    if score(lo.exp()) > 0.0 || score(hi.exp()) < 0.0 {
+        return None;
+    }
+    for _ in 0..ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if score(mid.exp()) < 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    let shape = (0.5 * (lo + hi)).exp();
+    let sum: f64 = observations.iter().map(|o| o.time.powf(shape)).sum();
+    Some((shape, (sum / failures).powf(1.0 / shape)))
+}
+
+/// Sorted bootstrap replicates of a fit's parameters and the figures that
+/// follow from them.
+#[derive(Clone, Debug, Default)]
+pub struct Bootstrap {
+    pub shape: Vec<f64>,
+    pub scale: Vec<f64>,
+    pub mttf: Vec<f64>,
+    pub b10: Vec<f64>,
+}
+
+/// The central interval of sorted `replicates` holding `level` of them.
+pub fn interval(replicates: &[f64], level: f64) -> (f64, f64) {
+    let quantile = |q: f64| replicates[((replicates.len() - 1) as f64 * q).round() as usize];
+    (quantile((1.0 - level) / 2.0), quantile((1.0 + level) / 2.0))
+}
+
+/// B10 of a Weibull distribution, the age by which 10% have failed.
+fn b10(shape: f64, scale: f64) -> f64 {
+    scale * (-(0.9_f64.ln())).powf(1.0 / shape)
+}
+
+/// A parametric bootstrap of the fit `(shape, scale)`: samples of the same
+/// size are drawn from it, censored where the data was, and fitted again.
+/// Suspended units keep their suspension time, and failed units are taken
+/// as observed up to the last time in the data. `None` if fewer than half
+/// the samples could be fitted.
+pub fn bootstrap(
+    observations: &[Observation],
+    (shape, scale): (f64, f64),
+    rng: &mut impl Rng,
+) -> Option<Bootstrap> {
+    let end = observations.iter().map(|o| o.time).fold(0.0, f64::max);
+    let censoring: Vec<f64> = observations
+        .iter()
+        .map(|o| if o.failed { end } else { o.time })
+        .collect();
+    let mut replicates = Bootstrap::default();
+    for _ in 0..RESAMPLES {
+        let sample: Vec<Observation> = censoring
+            .iter()
+            .map(|censored| {
+                let time = scale * (-rng.gen::<f64>().ln()).powf(1.0 / shape);
+                Observation {
+                    time: time.min(*censored),
+                    failed: time <= *censored,
+                }
+            })
+            .collect();
+        if let Some((shape, scale)) = fit_weibull(&sample) {
+            replicates.shape.push(shape);
+            replicates.scale.push(scale);
+            replicates
+                .mttf
+                .push(ScaleEntry::Mttf.from_scale(shape, scale));
+            replicates.b10.push(b10(shape, scale));
+        }
+    }
+    if replicates.shape.len() < RESAMPLES / 2 {
+        return None;
+    }
+    for values in [
+        &mut replicates.shape,
+        &mut replicates.scale,
+        &mut replicates.mttf,
+        &mut replicates.b10,
+    ] {
+        values.sort_by(f64::total_cmp);
+    }
+    Some(replicates)
+}
+
+/// Days since 1970-01-01 of an ISO `YYYY-MM-DD` date.
+fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Howard Hinnant's days_from_civil.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Some(era * 146_097 + day_of_era - 719_468)
+}
+
+/// How many time steps of `unit` make up a day. Cycles have no calendar
+/// duration, so they are taken as one per day.
+fn steps_per_day(unit: TimeUnit) -> f64 {
+    match unit {
+        TimeUnit::Hours => 24.0,
+        TimeUnit::Days | TimeUnit::Cycles => 1.0,
+        TimeUnit::Years => 1.0 / 365.25,
+    }
+}
+
+/// Parses a failure log with a header naming the columns `component`,
+/// `installed`, `failed` and `censored`; other columns such as a unit id are
+/// ignored. Each row fills in one of the last two dates. Returns the
+/// observations of each component in `unit` time steps.
+pub fn parse_log(text: &str, unit: TimeUnit) -> Result<BTreeMap<String, Vec<Observation>>, String> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+    let Some((_, header)) = lines.next() else {
+        return Ok(BTreeMap::new());
+    };
+    let columns: Vec<String> = header.split(',').map(|c| c.trim().to_lowercase()).collect();
+    let column = |name: &str| {
+        columns
+            .iter()
+            .position(|c| c == name)
+            .ok_or_else(|| tr_with("field-error-column", &[("column", &name)]))
+    };
+    let (component, installed, failed, censored) = (
+        column("component")?,
+        column("installed")?,
+        column("failed")?,
+        column("censored")?,
+    );
+
+    let mut groups: BTreeMap<String, Vec<Observation>> = BTreeMap::new();
+    for (i, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |index: usize| fields.get(index).copied().unwrap_or_default();
+        let error = || tr_with("field-error-line", &[("line", &(i + 1))]);
+        let start = parse_date(field(installed)).ok_or_else(error)?;
+        let (end, is_failure) = match (field(failed), field(censored)) {
+            (date, _) if !date.is_empty() => (date, true),
+            (_, date) if !date.is_empty() => (date, false),
+            _ => return Err(error()),
+        };
+        let end = parse_date(end).ok_or_else(error)?;
+        groups
+            .entry(field(component).to_owned())
+            .or_default()
+            .push(Observation {
+                time: (end - start) as f64 * steps_per_day(unit),
+                failed: is_failure,
+            });
+    }
+    Ok(groups)
+}
+
+/// One component's data and fit, offered for a matching node.
+struct Fit {
+    name: String,
+    units: usize,
+    failures: usize,
+    parameters: Option<(f64, f64)>,
+    bootstrap: Option<Bootstrap>,
+    target: Option<NodeId>,
+    /// Whether the fit was beyond the tolerance of the target's parameters
+    /// at the last check, so each divergence is reported once.
+    diverged: bool,
+}
+
+impl Fit {
+    /// The fitted MTTF over the target's, or `None` without both.
+    fn divergence(&self, graph: &Graph) -> Option<f64> {
+        let (shape, scale) = self.parameters?;
+        let model = nodes::component_parameters(graph, self.target?)?;
+        Some(
+            ScaleEntry::Mttf.from_scale(shape, scale)
+                / ScaleEntry::Mttf.from_scale(model.shape, model.scale),
+        )
+    }
+
+    fn apply(&self, graph: &mut Graph) {
+        if let (Some((shape, scale)), Some(node)) = (self.parameters, self.target) {
+            if let Some(parameters) = nodes::component_parameters_mut(graph, node) {
+                parameters.shape = shape;
+                parameters.scale = scale;
+                parameters.uncertainty = self.bootstrap.as_ref().map(|bootstrap| Uncertainty {
+                    shape: interval(&bootstrap.shape, 0.9),
+                    scale: interval(&bootstrap.scale, 0.9),
+                });
+            }
+        }
+    }
+}
+
+/// A fitted value with its 95% bootstrap interval underneath.
+fn cell(ui: &mut egui::Ui, value: f64, decimals: usize, replicates: Option<&[f64]>) {
+    ui.vertical(|ui| {
+        ui.label(format!("{value:.decimals$}"));
+        if let Some(replicates) = replicates {
+            let (lower, upper) = interval(replicates, 0.95);
+            ui.small(format!("{lower:.decimals$} – {upper:.decimals$}"));
+        }
+    });
+}
+
+pub struct FieldData {
+    fits: Vec<Fit>,
+    error: Option<String>,
+    /// The imported log and when it was last changed, so an export the
+    /// maintenance system rewrites can be read again.
+    source: Option<(PathBuf, Option<SystemTime>)>,
+    refresh: bool,
+    interval_minutes: u32,
+    last_check: Option<Instant>,
+    /// How far the fitted MTTF may be from the model's, as a fraction,
+    /// before the component is flagged.
+    tolerance: f64,
+    /// Whether flagged components take the fitted parameters right away.
+    auto_apply: bool,
+}
+
+impl Default for FieldData {
+    fn default() -> Self {
+        Self {
+            fits: Vec::new(),
+            error: None,
+            source: None,
+            refresh: false,
+            interval_minutes: 5,
+            last_check: None,
+            tolerance: 0.25,
+            auto_apply: false,
+        }
+    }
+}
+
+impl FieldData {
+    fn import(&mut self, graph: &Graph, state: &GraphState) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .pick_file()
+        else {
+            return;
+        };
+        self.fits.clear();
+        self.source = Some((path, None));
+        self.read(graph, state);
+    }
+
+    /// Reads the source again, keeping the nodes picked for components
+    /// that are still in it.
+    fn read(&mut self, graph: &Graph, state: &GraphState) {
+        let Some((path, modified)) = &mut self.source else {
+            return;
+        };
+        *modified = std::fs::metadata(&*path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        let groups = std::fs::read_to_string(&*path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| parse_log(&text, state.settings.time_unit));
+        let mut rng = state.settings.rng();
+        match groups {
+            Ok(groups) => {
+                let components = nodes::components(graph);
+                let previous = std::mem::take(&mut self.fits);
+                self.fits = groups
+                    .into_iter()
+                    .map(|(name, observations)| {
+                        let earlier = previous.iter().find(|fit| fit.name == name);
+                        let parameters = fit_weibull(&observations);
+                        Fit {
+                            units: observations.len(),
+                            failures: observations.iter().filter(|o| o.failed).count(),
+                            parameters,
+                            bootstrap: parameters
+                                .and_then(|fit| bootstrap(&observations, fit, &mut rng)),
+                            target: match earlier {
+                                Some(earlier) => earlier.target,
+                                None => components.iter().copied().find(|node| {
+                                    graph[*node].label.trim().eq_ignore_ascii_case(name.trim())
+                                }),
+                            },
+                            diverged: earlier.is_some_and(|earlier| earlier.diverged),
+                            name,
+                        }
+                    })
+                    .collect();
+                for fit in self.fits.iter().filter(|fit| fit.parameters.is_none()) {
+                    tracing::warn!(component = %fit.name, "no Weibull fit to the field data");
+                }
+                self.error = None;
+            }
+            Err(err) => {
+                tracing::warn!("importing field data failed: {err}");
+                self.fits.clear();
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Reads the log again every `interval_minutes` while refreshing is on,
+    /// if it changed, and flags or updates the components whose fit moved
+    /// beyond the tolerance. Called every frame, whether or not the window
+    /// is open.
+    pub fn poll(&mut self, ctx: &egui::Context, graph: &mut Graph, state: &GraphState) {
+        if !self.refresh || self.source.is_none() {
+            return;
+        }
+        let interval = Duration::from_secs(60 * u64::from(self.interval_minutes.max(1)));
+        if self
+            .last_check
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+        ctx.request_repaint_after(interval);
+        let changed = self.source.as_ref().is_some_and(|(path, modified)| {
+            std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                != *modified
+        });
+        if changed {
+            tracing::info!("reading the changed field data");
+            self.read(graph, state);
+        }
+        for fit in &mut self.fits {
+            let diverged = fit
+                .divergence(graph)
+                .is_some_and(|ratio| (ratio - 1.0).abs() > self.tolerance);
+            if diverged && !fit.diverged {
+                let node = &graph[fit.target.expect("a divergence has a target")].label;
+                if self.auto_apply {
+                    tracing::info!(component = %fit.name, "updated from field data");
+                    toasts::push(
+                        toasts::Kind::Info,
+                        tr_with("field-updated", &[("node", node)]),
+                    );
+                } else {
+                    tracing::warn!(component = %fit.name, "field data diverges from the model");
+                    toasts::error(tr_with("field-diverged", &[("node", node)]));
+                }
+            }
+            if diverged && self.auto_apply {
+                fit.apply(graph);
+                fit.diverged = false;
+            } else {
+                fit.diverged = diverged;
+            }
+        }
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("field-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                ui.label(tr("field-format"));
+                if ui.button(tr("km-import")).clicked() {
+                    self.import(graph, state);
+                }
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                if self.source.is_some() {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .checkbox(&mut self.refresh, tr("field-refresh"))
+                            .changed()
+                        {
+                            self.last_check = None;
+                        }
+                        ui.add(
+                            egui::DragValue::new(&mut self.interval_minutes)
+                                .clamp_range(1..=1440)
+                                .suffix(" min"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("field-tolerance"));
+                        let mut percent = self.tolerance * 100.0;
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut percent)
+                                    .clamp_range(1.0..=500.0)
+                                    .suffix(" %"),
+                            )
+                            .changed()
+                        {
+                            self.tolerance = percent / 100.0;
+                        }
+                        ui.checkbox(&mut self.auto_apply, tr("field-auto-apply"));
+                    });
+                }
+                if self.fits.is_empty() {
+                    return;
+                }
+
+                let mut apply = Vec::new();
+                egui::Grid::new("field fits")
+                    .num_columns(9)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("field-name"));
+                        ui.strong(tr("field-units"));
+                        ui.strong(tr("param-shape"));
+                        ui.strong(tr("param-scale"));
+                        ui.strong(tr("param-entry-mttf"));
+                        ui.strong("B10");
+                        ui.strong(tr("field-node"));
+                        ui.strong(tr("field-ratio"))
+                            .on_hover_text(tr("field-ratio-hint"));
+                        ui.end_row();
+                        for (i, fit) in self.fits.iter_mut().enumerate() {
+                            ui.label(&fit.name);
+                            ui.label(format!("{} / {}", fit.failures, fit.units));
+                            match fit.parameters {
+                                Some((shape, scale)) => {
+                                    let bootstrap = fit.bootstrap.as_ref();
+                                    let mttf = ScaleEntry::Mttf.from_scale(shape, scale);
+                                    cell(ui, shape, 3, bootstrap.map(|b| &b.shape[..]));
+                                    cell(ui, scale, 1, bootstrap.map(|b| &b.scale[..]));
+                                    cell(ui, mttf, 1, bootstrap.map(|b| &b.mttf[..]));
+                                    cell(ui, b10(shape, scale), 1, bootstrap.map(|b| &b.b10[..]));
+                                }
+                                None => {
+                                    for _ in 0..4 {
+                                        ui.label("—");
+                                    }
+                                }
+                            }
+                            nodes::component_combo(
+                                ui,
+                                &format!("field node {i}"),
+                                graph,
+                                &mut fit.target,
+                            );
+                            match fit.divergence(graph) {
+                                Some(ratio) if (ratio - 1.0).abs() > self.tolerance => {
+                                    ui.colored_label(
+                                        ui.visuals().warn_fg_color,
+                                        format!("⚠ {ratio:.2}"),
+                                    );
+                                }
+                                Some(ratio) => {
+                                    ui.label(format!("{ratio:.2}"));
+                                }
+                                None => {
+                                    ui.label("—");
+                                }
+                            }
+                            if ui
+                                .add_enabled(
+                                    fit.parameters.is_some() && fit.target.is_some(),
+                                    egui::Button::new(tr("goal-seek-apply")),
+                                )
+                                .clicked()
+                            {
+                                apply.push(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.label(tr_with(
+                    "field-bootstrap-hint",
+                    &[("resamples", &RESAMPLES)],
+                ));
+                if ui.button(tr("field-apply-all")).clicked() {
+                    apply = (0..self.fits.len()).collect();
+                }
+                for i in apply {
+                    self.fits[i].apply(graph);
+                    self.fits[i].diverged = false;
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Failure times at the quantiles of Weibull(`shape`, `scale`), those
+    /// past `censor` suspended there.
+    fn quantiles(shape: f64, scale: f64, count: usize, censor: f64) -> Vec<Observation> {
+        (0..count)
+            .map(|i| {
+                let p = (i as f64 + 0.5) / count as f64;
+                let time = scale * (-(1.0 - p).ln()).powf(1.0 / shape);
+                Observation {
+                    time: time.min(censor),
+                    failed: time <= censor,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fit_recovers_the_parameters() {
+        let (shape, scale) = fit_weibull(&quantiles(2.0, 100.0, 2_000, f64::INFINITY)).unwrap();
+        assert!((shape - 2.0).abs() < 0.02, "β = {shape}");
+        assert!((scale - 100.0).abs() < 1.0, "η = {scale}");
+    }
+
+    #[test]
+    fn fit_accounts_for_suspensions() {
+        let (shape, scale) = fit_weibull(&quantiles(0.8, 500.0, 2_000, 400.0)).unwrap();
+        assert!((shape - 0.8).abs() < 0.02, "β = {shape}");
+        assert!((scale - 500.0).abs() < 10.0, "η = {scale}");
+    }
+
+    #[test]
+    fn fit_needs_a_failure() {
+        let suspended = [Observation {
+            time: 10.0,
+            failed: false,
+        }];
+        assert_eq!(fit_weibull(&suspended), None);
+    }
+}
@@ -0,0 +1,320 @@
+//! The model as Rust code that rebuilds it through
+//! [`Builder`](crate::examples::Builder), so a model
+//! drawn in the editor can be checked in as a reviewed test fixture.
+//!
+//! Components whose only changes from the defaults are their Weibull and
+//! MTTR are written out as code; other values are embedded as JSON, as are
+//! the mission profile and the variables. The app's settings, such as the
+//! precision, aren't part of the model; the audit header records them.
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use egui_node_graph2::{InputParamKind, NodeId, NodeTemplateTrait};
+
+use crate::audit::Audit;
+use crate::examples::{COLUMN, ROW};
+use crate::nodes::{Dormancy, EditorState, GraphState, NodeParameters, NodeTemplate, ValueType};
+use crate::profile::MissionProfile;
+
+/// Names a label can't become, as keywords or the generated code's own.
+const RESERVED: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "model",
+];
+
+/// A snake case variable name for `label`, unlike any in `taken`.
+fn identifier(label: &str, taken: &mut HashSet<String>) -> String {
+    let lower: String = label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let mut base = lower
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+        base.insert_str(0, "node_");
+    }
+    if RESERVED.contains(&base.as_str()) {
+        base.push_str("_node");
+    }
+    let mut name = base.clone();
+    let mut i = 2;
+    while !taken.insert(name.clone()) {
+        name = format!("{base}_{i}");
+        i += 1;
+    }
+    name
+}
+
+/// `value` as JSON parsed at run time.
+fn json(value: &impl serde::Serialize, what: &str) -> String {
+    let json = serde_json::to_string(value).unwrap_or_default();
+    format!("serde_json::from_str(r##\"{json}\"##).expect(\"valid {what}\")")
+}
+
+/// An expression for the value of a constant input, or `None` if a new
+/// node's own will do.
+fn value(value: &ValueType, imports: &mut BTreeSet<&str>) -> Option<String> {
+    let code = match value {
+        ValueType::Reliability { curve } if curve.is_empty() => return None,
+        ValueType::CompactReliability { curve } if curve.is_empty() => return None,
+        ValueType::Text { text } if text.is_empty() => return None,
+        ValueType::Dormancy { dormancy } if *dormancy == Dormancy::default() => return None,
+        ValueType::Reliability { curve } => {
+            format!("ValueType::Reliability {{ curve: vec!{curve:?} }}")
+        }
+        ValueType::CompactReliability { curve } => {
+            format!("ValueType::CompactReliability {{ curve: vec!{curve:?} }}")
+        }
+        ValueType::Text { text } => format!("ValueType::Text {{ text: {text:?}.to_owned() }}"),
+        other => return Some(json(other, "value")),
+    };
+    imports.insert("ValueType");
+    Some(code)
+}
+
+/// The parameters of a component, as code when they differ from the
+/// defaults only in their Weibull and MTTR.
+fn parameters(parameters: &NodeParameters, imports: &mut BTreeSet<&str>) -> String {
+    let NodeParameters {
+        shape, scale, mttr, ..
+    } = *parameters;
+    let plain = NodeParameters {
+        shape,
+        scale,
+        mttr,
+        ..NodeParameters::default()
+    };
+    if *parameters != plain {
+        return json(parameters, "parameters");
+    }
+    imports.insert("NodeParameters");
+    format!(
+        "NodeParameters {{ shape: {shape:?}, scale: {scale:?}, mttr: {mttr:?}, ..NodeParameters::default() }}"
+    )
+}
+
+/// Rust code defining `fn model(user_state: &mut GraphState) -> EditorState`,
+/// which rebuilds the model on the grid of the examples.
+pub fn encode(editor: &EditorState, state: &GraphState) -> String {
+    let graph = &editor.graph;
+    let mut imports: BTreeSet<&str> = ["EditorState", "GraphState"].into();
+    let mut names: HashMap<NodeId, String> = HashMap::new();
+    let mut taken = HashSet::new();
+    let mut scratch = GraphState::default();
+    let mut body = String::new();
+
+    let order: Vec<NodeId> = editor
+        .node_order
+        .iter()
+        .copied()
+        .filter(|node_id| graph.nodes.contains_key(*node_id))
+        .collect();
+    for node_id in &order {
+        let node = &graph[*node_id];
+        let data = &node.user_data;
+        let name = identifier(&node.label, &mut taken);
+        let position = editor
+            .node_positions
+            .get(*node_id)
+            .copied()
+            .unwrap_or_default();
+        let column = (position.x / COLUMN).round() as i32;
+        let row = (position.y / ROW).round().max(0.0) as usize;
+        let label = format!("{:?}.to_owned()", node.label);
+
+        let component = node
+            .inputs
+            .iter()
+            .find_map(|(_, input)| match &graph[*input].value {
+                ValueType::Parameters { parameters }
+                    if data.template == NodeTemplate::Component =>
+                {
+                    Some(parameters)
+                }
+                _ => None,
+            });
+        match component {
+            Some(component) => {
+                let parameters = parameters(component, &mut imports);
+                let _ = writeln!(
+                    body,
+                    "    let {name} = model.component({label}, {parameters}, {column}, {row});"
+                );
+            }
+            None => {
+                imports.insert("NodeTemplate");
+                let _ = writeln!(
+                    body,
+                    "    let {name} = model.node(NodeTemplate::{:?}, {label}, {column}, {row});",
+                    data.template
+                );
+                for (input_name, input) in &node.inputs {
+                    let param = &graph[*input];
+                    if !matches!(param.kind, InputParamKind::ConstantOnly) {
+                        continue;
+                    }
+                    if let Some(value) = value(&param.value, &mut imports) {
+                        let _ = writeln!(
+                            body,
+                            "    model.set_value({name}, {input_name:?}, {value});"
+                        );
+                    }
+                }
+            }
+        }
+        if data.template == NodeTemplate::Voting && node.inputs.len() != 3 {
+            let _ = writeln!(body, "    model.members({name}, {});", node.inputs.len());
+        }
+
+        let fresh = data.template.user_data(&mut scratch);
+        let mut set = |field: &str, value: String| {
+            let _ = writeln!(body, "    model.data({name}).{field} = {value};");
+        };
+        if data.votes != fresh.votes {
+            set("votes", data.votes.to_string());
+        }
+        if data.coverage != fresh.coverage {
+            set("coverage", format!("{:?}", data.coverage));
+        }
+        if data.feasibility != fresh.feasibility {
+            set("feasibility", format!("{:?}", data.feasibility));
+        }
+        if data.severity != fresh.severity {
+            imports.insert("Severity");
+            set("severity", format!("Severity::{:?}", data.severity));
+        }
+        if let Some(forced) = data.forced {
+            imports.insert("Forced");
+            set("forced", format!("Some(Forced::{forced:?})"));
+        }
+        if let Some(target) = data.target {
+            set("target", format!("Some({target:?})"));
+        }
+        if !data.enabled {
+            set("enabled", "false".to_owned());
+        }
+        if data.locked {
+            set("locked", "true".to_owned());
+        }
+        if data.excluded {
+            set("excluded", "true".to_owned());
+        }
+        if let Some(tag) = &data.tag {
+            set("tag", format!("Some({})", json(tag, "tag")));
+        }
+        if let Some(library) = &data.library {
+            set("library", format!("Some({library:?}.to_owned())"));
+        }
+        names.insert(*node_id, name);
+    }
+
+    for node_id in &order {
+        for (input_name, input) in &graph[*node_id].inputs {
+            let Some(output) = graph.connection(*input) else {
+                continue;
+            };
+            let (Some(from), Some(to)) = (names.get(&graph[output].node), names.get(node_id))
+            else {
+                continue;
+            };
+            let _ = writeln!(body, "    model.connect({from}, {to}, {input_name:?});");
+        }
+    }
+    // Mirrors may follow a node named further down.
+    for node_id in &order {
+        let mirror = graph[*node_id].user_data.mirror;
+        if let (Some(name), Some(mirror)) = (
+            names.get(node_id),
+            mirror.and_then(|mirror| names.get(&mirror)),
+        ) {
+            let _ = writeln!(body, "    model.data({name}).mirror = Some({mirror});");
+        }
+    }
+    if let Some(system) = state.active_node.and_then(|node| names.get(&node)) {
+        let _ = writeln!(body, "    model.user_state.active_node = Some({system});");
+    }
+    let _ = writeln!(
+        body,
+        "    model.user_state.mission_time = {};",
+        state.mission_time
+    );
+    if state.mission_profile != MissionProfile::default() {
+        let _ = writeln!(
+            body,
+            "    model.user_state.mission_profile = {};",
+            json(&state.mission_profile, "mission profile")
+        );
+    }
+    if let Some(severity) = state.severity_filter {
+        imports.insert("Severity");
+        let _ = writeln!(
+            body,
+            "    model.user_state.severity_filter = Some(Severity::{severity:?});"
+        );
+    }
+    if !state.variables.is_empty() {
+        let _ = writeln!(
+            body,
+            "    model.user_state.variables = {};",
+            json(&state.variables, "variables")
+        );
+    }
+    for shock in &state.shocks {
+        let targets: Vec<&str> = shock
+            .targets
+            .iter()
+            .filter_map(|target| names.get(target).map(String::as_str))
+            .collect();
+        let _ = writeln!(
+            body,
+            "    model.user_state.shocks.push(Shock {{ name: {:?}.to_owned(), rate: {:?}, \
+             damage: {:?}, targets: vec![{}] }});",
+            shock.name,
+            shock.rate,
+            shock.damage,
+            targets.join(", ")
+        );
+    }
+    let shocks = match state.shocks.is_empty() {
+        true => "",
+        false => "use block::shocks::Shock;\n",
+    };
+
+    let imports = imports.into_iter().collect::<Vec<_>>().join(", ");
+    format!(
+        "use block::examples::Builder;\n\
+         use block::nodes::{{{imports}}};\n\
+         {shocks}\
+         \n\
+         /// Rebuilds the model, with its system made active in `user_state`.\n\
+         pub fn model(user_state: &mut GraphState) -> EditorState {{\n    \
+         let mut model = Builder::new(user_state);\n\
+         {body}    model.finish()\n\
+         }}\n"
+    )
+}
+
+/// Asks for a file name and writes the model there as Rust code, headed by
+/// `audit` in comments. Returns whether it was written.
+pub fn export(editor: &EditorState, state: &GraphState, audit: &Audit) -> anyhow::Result<bool> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Rust", &["rs"])
+        .set_file_name("model.rs")
+        .save_file()
+    else {
+        return Ok(false);
+    };
+    std::fs::write(path, audit.comments("// ") + &encode(editor, state))?;
+    Ok(true)
+}
@@ -0,0 +1,94 @@
+//! Expected failures across a fleet of identical units, for spares planning.
+use eframe::egui;
+use egui_plot::{Line, Plot, VLine};
+
+use crate::eval::{evaluate_node, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+
+/// The expected cumulative number of the `size` units that have failed by
+/// each time step, N·F(t), when each fails at most once.
+pub fn expected_failures(reliability: &[f64], size: usize) -> Vec<f64> {
+    reliability
+        .iter()
+        .map(|r| size as f64 * (1.0 - r))
+        .collect()
+}
+
+pub struct Fleet {
+    size: usize,
+}
+
+impl Default for Fleet {
+    fn default() -> Self {
+        Self { size: 100 }
+    }
+}
+
+impl Fleet {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("fleet-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let result = state
+                    .active_node
+                    .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))
+                    .and_then(|node| {
+                        evaluate_node(graph, &Options::of(state), node, &mut OutputsCache::new())?
+                            .try_to_reliability()
+                    });
+                let curve = match result {
+                    Ok(curve) => curve,
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                        return;
+                    }
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("fleet-size"));
+                    ui.add(egui::DragValue::new(&mut self.size).clamp_range(1..=10_000_000));
+                });
+                let failures = expected_failures(&curve, self.size);
+                let time = state.mission_time;
+                if let Some(expected) = failures.get(time) {
+                    ui.label(tr_with(
+                        "fleet-expected-failures",
+                        &[("failures", &format!("{expected:.1}"))],
+                    ));
+                }
+                if ui.button(tr("plot-copy-data")).clicked() {
+                    let mut tsv = format!("t\t{}\n", tr("fleet-failures"));
+                    for (t, n) in failures.iter().enumerate() {
+                        tsv.push_str(&format!("{t}\t{n}\n"));
+                    }
+                    ui.output_mut(|o| o.copied_text = tsv);
+                }
+
+                let theme = state.settings.theme.catppuccin();
+                let symbol = state.settings.time_unit.symbol();
+                let points: Vec<[f64; 2]> = failures
+                    .iter()
+                    .enumerate()
+                    .map(|(t, n)| [t as f64, *n])
+                    .collect();
+                Plot::new("fleet")
+                    .height(250.0)
+                    .include_y(0.0)
+                    .x_axis_label(format!("t [{symbol}]"))
+                    .y_axis_label(tr("fleet-failures"))
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(points).color(theme.red).width(2.0));
+                        plot_ui.vline(VLine::new(time as f64).name(tr("mission-time")));
+                    });
+                ui.label(tr("fleet-hint"));
+            });
+    }
+}
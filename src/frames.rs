@@ -0,0 +1,260 @@
+//! Titled rectangles drawn behind related nodes. They group only visually,
+//! and carry their nodes along when dragged by the title bar. A frame can be
+//! locked as a section owned by one author, which makes its nodes read-only
+//! to everyone else editing the same project.
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui::{self, Rect, Sense, Stroke};
+use egui_node_graph2::{InputId, NodeId, OutputId};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, Graph, GraphState};
+
+/// The height of the title bar, which moves the frame, at zoom 1.
+const TITLE_HEIGHT: f32 = 24.0;
+/// The size of the resize handle in the bottom right corner, at zoom 1.
+const HANDLE: f32 = 14.0;
+/// Space left around the nodes a new frame is drawn around, at zoom 1.
+const PADDING: f32 = 20.0;
+/// The size given to nodes that haven't been drawn yet.
+const DEFAULT_NODE_SIZE: egui::Vec2 = egui::vec2(200.0, 120.0);
+const MIN_SIZE: egui::Vec2 = egui::vec2(120.0, 60.0);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupFrame {
+    pub title: String,
+    /// In the same coordinates as the editor's node positions.
+    pub rect: Rect,
+    /// Who locked the section, if it is locked.
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+/// The nodes whose top left corner lies in a locked frame, with its owner.
+pub fn read_only_nodes(editor: &EditorState, frames: &[GroupFrame]) -> HashMap<NodeId, String> {
+    let mut read_only = HashMap::new();
+    for frame in frames {
+        let Some(owner) = &frame.owner else {
+            continue;
+        };
+        for (node, position) in editor.node_positions.iter() {
+            if frame.rect.contains(*position) {
+                read_only.entry(node).or_insert_with(|| owner.clone());
+            }
+        }
+    }
+    read_only
+}
+
+/// The connections to or from read-only nodes, which can't be changed.
+pub fn read_only_connections(
+    graph: &Graph,
+    read_only: &HashMap<NodeId, String>,
+) -> HashSet<(InputId, OutputId)> {
+    graph
+        .iter_connections()
+        .filter(|(input, output)| {
+            read_only.contains_key(&graph[*input].node)
+                || read_only.contains_key(&graph[*output].node)
+        })
+        .collect()
+}
+
+/// Adds a frame around the selected nodes, if any are selected.
+pub fn frame_selection(editor: &EditorState, user_state: &mut GraphState, viewport: Rect) {
+    let offset = editor.pan_zoom.pan + viewport.min.to_vec2();
+    let zoom = editor.pan_zoom.zoom;
+    let bounds = editor
+        .selected_nodes
+        .iter()
+        .filter_map(|node| {
+            let position = editor.node_positions.get(*node)?;
+            let rect = user_state
+                .layout
+                .node_rect(*node)
+                .map(|rect| rect.translate(-offset))
+                .unwrap_or_else(|| Rect::from_min_size(*position, DEFAULT_NODE_SIZE * zoom));
+            Some(rect)
+        })
+        .reduce(|a, b| a.union(b));
+    if let Some(bounds) = bounds {
+        let mut rect = bounds.expand(PADDING * zoom);
+        rect.min.y -= TITLE_HEIGHT * zoom;
+        user_state.frames.push(GroupFrame {
+            title: tr("frame-default-title"),
+            rect,
+            owner: None,
+        });
+    }
+}
+
+/// Paints the frames, before the graph editor so they lie behind the nodes.
+pub fn paint(ui: &egui::Ui, editor: &EditorState, frames: &[GroupFrame]) {
+    let offset = editor.pan_zoom.pan + ui.max_rect().min.to_vec2();
+    let zoom = editor.pan_zoom.zoom;
+    let visuals = ui.visuals();
+    let accent = visuals.selection.bg_fill;
+    let painter = ui.painter();
+    for frame in frames {
+        let rect = frame.rect.translate(offset);
+        let rounding = 6.0 * zoom;
+        painter.rect(
+            rect,
+            rounding,
+            accent.gamma_multiply(0.12),
+            Stroke::new(1.5 * zoom, accent),
+        );
+        let title = title_bar(rect, zoom);
+        painter.rect_filled(
+            title,
+            egui::Rounding {
+                nw: rounding,
+                ne: rounding,
+                ..Default::default()
+            },
+            accent.gamma_multiply(0.35),
+        );
+        let text = match &frame.owner {
+            Some(owner) => tr_with(
+                "frame-locked-title",
+                &[("title", &frame.title), ("owner", owner)],
+            ),
+            None => frame.title.clone(),
+        };
+        painter.text(
+            title.left_center() + egui::vec2(8.0 * zoom, 0.0),
+            egui::Align2::LEFT_CENTER,
+            text,
+            egui::FontId::proportional(14.0 * zoom),
+            visuals.strong_text_color(),
+        );
+        if frame.owner.is_none() {
+            let handle = handle(rect, zoom);
+            painter.line_segment(
+                [handle.left_bottom(), handle.right_top()],
+                Stroke::new(1.5 * zoom, accent),
+            );
+        }
+    }
+}
+
+/// Handles dragging, resizing, renaming, locking and deleting frames, after
+/// the graph editor so the title bars and handles take precedence over its
+/// background. Locked frames can only be unlocked. New locks are taken out
+/// under `author`.
+pub fn interact(
+    ui: &mut egui::Ui,
+    editor: &mut EditorState,
+    frames: &mut Vec<GroupFrame>,
+    author: &str,
+) {
+    let offset = editor.pan_zoom.pan + ui.max_rect().min.to_vec2();
+    let zoom = editor.pan_zoom.zoom;
+    let read_only = read_only_nodes(editor, frames);
+    let mut deleted = None;
+    for (i, frame) in frames.iter_mut().enumerate() {
+        let rect = frame.rect.translate(offset);
+        let id = egui::Id::new(("group frame", i));
+
+        let title = ui.interact(
+            title_bar(rect, zoom),
+            id.with("title"),
+            Sense::click_and_drag(),
+        );
+        if let Some(owner) = &frame.owner {
+            let mut unlock = false;
+            title.context_menu(|ui| {
+                ui.label(tr_with("frame-locked-by", &[("owner", owner)]));
+                if ui.button(tr("frame-unlock")).clicked() {
+                    unlock = true;
+                    ui.close_menu();
+                }
+            });
+            if unlock {
+                frame.owner = None;
+            }
+            continue;
+        }
+        let delta = title.drag_delta();
+        if delta != egui::Vec2::ZERO {
+            // Nodes are carried along if their top left corner lies inside.
+            for node in editor.node_order.iter().copied() {
+                if editor.graph[node].user_data.locked || read_only.contains_key(&node) {
+                    continue;
+                }
+                if let Some(position) = editor.node_positions.get_mut(node) {
+                    if frame.rect.contains(*position) {
+                        *position += delta;
+                    }
+                }
+            }
+            frame.rect = frame.rect.translate(delta);
+        }
+        title.context_menu(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("frame-title"));
+                ui.text_edit_singleline(&mut frame.title);
+            });
+            if ui
+                .button(tr("frame-lock"))
+                .on_hover_text(tr("frame-lock-hint"))
+                .clicked()
+            {
+                let author = author.trim();
+                frame.owner = Some(if author.is_empty() {
+                    tr("frame-anonymous")
+                } else {
+                    author.to_owned()
+                });
+                ui.close_menu();
+            }
+            if ui.button(tr("frame-delete")).clicked() {
+                deleted = Some(i);
+                ui.close_menu();
+            }
+        });
+
+        let resize = ui.interact(handle(rect, zoom), id.with("resize"), Sense::drag());
+        if resize.hovered() || resize.dragged() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeNwSe);
+        }
+        let max = frame.rect.max + resize.drag_delta();
+        frame.rect.max = max.max(frame.rect.min + MIN_SIZE * zoom);
+    }
+    if let Some(i) = deleted {
+        frames.remove(i);
+    }
+}
+
+/// Moves a point the way the graph editor moves node positions when it
+/// zooms from `zoom_before`, towards the center of the view.
+pub fn zoomed(editor: &EditorState, zoom_before: f32, pos: egui::Pos2) -> egui::Pos2 {
+    let delta = editor.pan_zoom.zoom / zoom_before;
+    let half_size = editor.pan_zoom.clip_rect.size() / 2.0;
+    let pan = editor.pan_zoom.pan;
+    ((pos.to_vec2() - half_size + pan) * delta + half_size - pan).to_pos2()
+}
+
+/// Scales the frames the way the graph editor scales node positions when it
+/// zooms.
+pub fn follow_zoom(frames: &mut [GroupFrame], editor: &EditorState, zoom_before: f32) {
+    if editor.pan_zoom.zoom == zoom_before {
+        return;
+    }
+    let scale = |pos| zoomed(editor, zoom_before, pos);
+    for frame in frames {
+        frame.rect = Rect::from_min_max(scale(frame.rect.min), scale(frame.rect.max));
+    }
+}
+
+fn title_bar(rect: Rect, zoom: f32) -> Rect {
+    Rect::from_min_max(
+        rect.min,
+        egui::pos2(rect.max.x, rect.min.y + TITLE_HEIGHT * zoom),
+    )
+}
+
+fn handle(rect: Rect, zoom: f32) -> Rect {
+    Rect::from_min_max(rect.max - egui::Vec2::splat(HANDLE * zoom), rect.max)
+}
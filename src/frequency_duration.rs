@@ -0,0 +1,142 @@
+//! Frequency and duration indices of a repairable system, as asked for in
+//! power-system reliability studies: how often the system goes down, and how
+//! long an outage lasts, on average and at the percentiles planners use.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::Options;
+use crate::i18n::{tr, tr_with};
+use crate::maintenance;
+use crate::nodes::{Graph, GraphState};
+use crate::settings::TimeUnit;
+
+/// The outage durations reported besides the mean.
+pub const PERCENTILES: [f64; 3] = [50.0, 90.0, 95.0];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Indices {
+    /// System outages per time step.
+    pub frequency: f64,
+    /// The mean duration of an outage, in time steps; NaN if none ended.
+    pub mean_duration: f64,
+    /// The durations of `PERCENTILES`, in the same order.
+    pub percentiles: Vec<f64>,
+    /// The fraction of the time the system is down.
+    pub unavailability: f64,
+    /// How many outages ended within the horizon, over all trials.
+    pub outages: usize,
+}
+
+/// Simulates the system over the mission time with the simulation settings,
+/// and sums up its outages.
+pub fn indices(graph: &Graph, state: &GraphState, system: NodeId) -> anyhow::Result<Indices> {
+    let horizon = state.mission_time as f64;
+    if horizon <= 0.0 {
+        anyhow::bail!(tr("frequency-duration-no-horizon"));
+    }
+    let (outcome, mut durations) = maintenance::simulate_outages(
+        graph,
+        &Options::of(state),
+        system,
+        horizon,
+        state.settings.monte_carlo_samples,
+        state.settings.seed,
+    )?;
+    durations.sort_by(f64::total_cmp);
+    let percentile = |percent: f64| match durations.len() {
+        0 => f64::NAN,
+        n => durations[((n - 1) as f64 * percent / 100.0).round() as usize],
+    };
+    Ok(Indices {
+        frequency: outcome.outages / horizon,
+        mean_duration: durations.iter().sum::<f64>() / durations.len() as f64,
+        percentiles: PERCENTILES.iter().map(|p| percentile(*p)).collect(),
+        unavailability: 1.0 - outcome.availability,
+        outages: durations.len(),
+    })
+}
+
+/// The indices as (name, value) rows, for the results panel and reports.
+pub fn rows(indices: &Indices, time_unit: TimeUnit) -> Vec<(String, String)> {
+    let symbol = time_unit.symbol();
+    let duration = |value: f64| {
+        if value.is_finite() {
+            format!("{value:.2} {symbol}")
+        } else {
+            "–".to_owned()
+        }
+    };
+    let mut rows = vec![(
+        tr("frequency-duration-frequency"),
+        format!("{:.4e} / {symbol}", indices.frequency),
+    )];
+    if let Some(hours) = time_unit.hours() {
+        rows.push((
+            tr("frequency-duration-per-year"),
+            format!("{:.4}", indices.frequency / hours * 8_760.0),
+        ));
+    }
+    rows.push((
+        tr("frequency-duration-mean"),
+        duration(indices.mean_duration),
+    ));
+    for (percent, value) in PERCENTILES.iter().zip(&indices.percentiles) {
+        rows.push((
+            tr_with("frequency-duration-percentile", &[("percent", percent)]),
+            duration(*value),
+        ));
+    }
+    rows.push((
+        tr("frequency-duration-unavailability"),
+        format!("{:.6}", indices.unavailability),
+    ));
+    rows
+}
+
+#[derive(Default)]
+pub struct FrequencyDuration {
+    result: Option<Result<Indices, String>>,
+}
+
+impl FrequencyDuration {
+    pub fn ui(&mut self, ui: &mut egui::Ui, graph: &Graph, state: &GraphState) {
+        let Some(system) = state.active_node else {
+            ui.label(tr("goal-seek-no-system"));
+            return;
+        };
+        if ui.button(tr("frequency-duration-run")).clicked() {
+            self.result = Some(indices(graph, state, system).map_err(|err| err.to_string()));
+        }
+        match &self.result {
+            Some(Ok(indices)) => {
+                let rows = rows(indices, state.settings.time_unit);
+                egui::Grid::new("frequency duration")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (name, value) in &rows {
+                            ui.label(name);
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+                ui.label(tr_with(
+                    "frequency-duration-outages",
+                    &[("count", &indices.outages)],
+                ));
+                if ui.button(tr("frequency-duration-copy")).clicked() {
+                    let text: String = rows
+                        .iter()
+                        .map(|(name, value)| format!("{name}\t{value}\n"))
+                        .collect();
+                    ui.output_mut(|o| o.copied_text = text);
+                }
+            }
+            Some(Err(err)) => {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            None => {}
+        }
+        ui.label(tr("frequency-duration-hint"));
+    }
+}
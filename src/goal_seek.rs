@@ -0,0 +1,197 @@
+//! Solves for the component parameter value that makes the system meet a target.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::{reliability_at, Options};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, Parameter};
+use crate::requirement::Requirement;
+
+const ITERATIONS: usize = 100;
+
+pub struct GoalSeek {
+    component: Option<NodeId>,
+    parameter: Parameter,
+    target: f64,
+    time: usize,
+    result: Option<Result<Solution, String>>,
+}
+
+impl Default for GoalSeek {
+    fn default() -> Self {
+        let requirement = Requirement::default();
+        Self {
+            component: None,
+            parameter: Parameter::default(),
+            target: requirement.reliability,
+            time: requirement.time,
+            result: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Solution {
+    component: NodeId,
+    parameter: Parameter,
+    value: f64,
+    reliability: f64,
+}
+
+/// Finds the value of `parameter` of `component` at which the system
+/// reliability at `time` equals `target`. The component's parameters are
+/// restored before returning.
+fn solve(
+    graph: &mut Graph,
+    options: &Options,
+    system: NodeId,
+    component: NodeId,
+    parameter: Parameter,
+    target: f64,
+    time: usize,
+) -> Result<Solution, String> {
+    let original =
+        nodes::component_parameters(graph, component).ok_or_else(|| tr("error-not-component"))?;
+    let current = original.get(parameter);
+    let mut system_at = |value: f64| {
+        if let Some(parameters) = nodes::component_parameters_mut(graph, component) {
+            parameters.set(parameter, value);
+        }
+        reliability_at(graph, options, system, time)
+            .map(|r| r - target)
+            .map_err(|err| err.to_string())
+    };
+    let result = bisect(&mut system_at, current / 1e4, current * 1e4).and_then(|value| {
+        Ok(Solution {
+            component,
+            parameter,
+            value,
+            reliability: system_at(value)? + target,
+        })
+    });
+
+    if let Some(parameters) = nodes::component_parameters_mut(graph, component) {
+        *parameters = original;
+    }
+    result
+}
+
+/// Bisects for a root of `f` between `lo` and `hi` in log space, since
+/// parameters like the scale span orders of magnitude.
+fn bisect(f: &mut impl FnMut(f64) -> Result<f64, String>, lo: f64, hi: f64) -> Result<f64, String> {
+    let (mut lo, mut hi) = (lo.ln(), hi.ln());
+    let f_lo = f(lo.exp())?;
+    if f_lo.signum() == f(hi.exp())?.signum() {
+        return Err(tr("goal-seek-no-solution"));
+    }
+    for _ in 0..ITERATIONS {
+        let mid = 0.5 * (lo + hi);
+        if f(mid.exp())?.signum() == f_lo.signum() {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok((0.5 * (lo + hi)).exp())
+}
+
+impl GoalSeek {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &mut GraphState,
+    ) {
+        egui::Window::new(tr("goal-seek-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                let time_unit = state.settings.time_unit;
+                if self
+                    .component
+                    .is_some_and(|node| !graph.nodes.contains_key(node))
+                {
+                    self.component = None;
+                    self.result = None;
+                }
+
+                egui::Grid::new("goal seek").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("goal-seek-component"));
+                    nodes::component_combo(ui, "goal seek component", graph, &mut self.component);
+                    ui.end_row();
+
+                    ui.label(tr("goal-seek-parameter"));
+                    self.parameter.combo(ui, "goal seek parameter");
+                    ui.end_row();
+
+                    ui.label(tr("goal-seek-target"));
+                    ui.horizontal(|ui| {
+                        ui.label("R ≥");
+                        ui.add(
+                            egui::DragValue::new(&mut self.target)
+                                .speed(0.001)
+                                .clamp_range(0.0..=1.0)
+                                .max_decimals(6),
+                        );
+                        ui.label(tr("requirement-at"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.time)
+                                .suffix(format!(" {}", time_unit.symbol())),
+                        );
+                        if ui.button(tr("goal-seek-from-requirement")).clicked() {
+                            self.target = state.requirement.reliability;
+                            self.time = state.requirement.time;
+                        }
+                    });
+                    ui.end_row();
+                });
+
+                let system = state.active_node;
+                let ready = system.is_some() && self.component.is_some();
+                if system.is_none() {
+                    ui.label(tr("goal-seek-no-system"));
+                }
+                if ui
+                    .add_enabled(ready, egui::Button::new(tr("goal-seek-solve")))
+                    .clicked()
+                {
+                    if let (Some(system), Some(component)) = (system, self.component) {
+                        self.result = Some(solve(
+                            graph,
+                            &Options::of(state),
+                            system,
+                            component,
+                            self.parameter,
+                            self.target,
+                            self.time,
+                        ));
+                    }
+                }
+
+                match &self.result {
+                    Some(Ok(solution)) => {
+                        ui.label(tr_with(
+                            "goal-seek-solution",
+                            &[
+                                ("parameter", &solution.parameter.name()),
+                                ("value", &format!("{:.4}", solution.value)),
+                                ("reliability", &format!("{:.6}", solution.reliability)),
+                            ],
+                        ));
+                        if ui.button(tr("goal-seek-apply")).clicked() {
+                            if let Some(parameters) =
+                                nodes::component_parameters_mut(graph, solution.component)
+                            {
+                                parameters.set(solution.parameter, solution.value);
+                            }
+                            self.result = None;
+                        }
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                    None => {}
+                }
+            });
+    }
+}
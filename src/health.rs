@@ -0,0 +1,148 @@
+//! Checks of evaluated curves for values no reliability can take, so that a
+//! broken model is pointed out instead of plotted.
+use egui_node_graph2::NodeId;
+
+use crate::eval::{evaluate_curve, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, NodeTemplate};
+
+/// How far R(t) may rise from one step to the next before it counts, as
+/// single precision curves round each step on its own.
+const TOLERANCE: f64 = 1e-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Defect {
+    /// R(t) is NaN or infinite from this time step.
+    NotFinite(usize),
+    /// R(t) rises at this time step, so the CDF 1 - R(t) falls.
+    Increasing(usize),
+}
+
+/// The first defect of `curve`, if it has one.
+pub fn defect(curve: &[f64]) -> Option<Defect> {
+    if let Some(time) = curve.iter().position(|r| !r.is_finite()) {
+        return Some(Defect::NotFinite(time));
+    }
+    curve
+        .windows(2)
+        .position(|w| w[1] > w[0] + TOLERANCE)
+        .map(|step| Defect::Increasing(step + 1))
+}
+
+/// A defective curve traced back to the node it starts at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnosis {
+    pub node: NodeId,
+    pub defect: Defect,
+    /// The node's parameters likely at fault, as (name, value).
+    pub parameters: Vec<(String, f64)>,
+}
+
+impl Diagnosis {
+    pub fn message(&self, graph: &Graph) -> String {
+        let node = &graph[self.node].label;
+        let mut message = match self.defect {
+            Defect::NotFinite(time) => {
+                tr_with("health-not-finite", &[("node", node), ("time", &time)])
+            }
+            Defect::Increasing(time) => {
+                tr_with("health-increasing", &[("node", node), ("time", &time)])
+            }
+        };
+        if !self.parameters.is_empty() {
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|(name, value)| format!("{name} = {value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            message += " ";
+            message += &tr_with("health-parameters", &[("parameters", &parameters)]);
+        }
+        message
+    }
+}
+
+/// Checks the curve `system` evaluated to. A defective one is traced to the
+/// most upstream node whose curve has a defect while its inputs' don't.
+pub fn diagnose(
+    graph: &Graph,
+    options: &Options,
+    system: NodeId,
+    curve: &[f64],
+) -> Option<Diagnosis> {
+    let mut found = (system, defect(curve)?);
+    // Fewer upstream nodes come first, so the first defect found is where
+    // it starts.
+    let mut upstream: Vec<(usize, NodeId)> = nodes::upstream(graph, system)
+        .into_iter()
+        .filter(|node| *node != system && graph[*node].user_data.template.evaluates())
+        .map(|node| (nodes::upstream(graph, node).len(), node))
+        .collect();
+    upstream.sort();
+    let mut cache = OutputsCache::new();
+    for (_, node) in upstream {
+        let Ok(curve) = evaluate_curve(graph, options, node, &mut cache) else {
+            continue;
+        };
+        if let Some(defect) = defect(&curve) {
+            found = (node, defect);
+            break;
+        }
+    }
+    let (node, defect) = found;
+    Some(Diagnosis {
+        node,
+        defect,
+        parameters: suspect_parameters(graph, node),
+    })
+}
+
+/// The parameters of a component that no distribution can take, or its
+/// shape and scale if they all look valid.
+fn suspect_parameters(graph: &Graph, node: NodeId) -> Vec<(String, f64)> {
+    if graph[node].user_data.template != NodeTemplate::Component {
+        return Vec::new();
+    }
+    let Some(parameters) = nodes::component_parameters(graph, node) else {
+        return Vec::new();
+    };
+    // Each with whether its value is in range; NaN never is.
+    let mut all = vec![
+        (tr("param-shape"), parameters.shape, parameters.shape > 0.0),
+        (tr("param-scale"), parameters.scale, parameters.scale > 0.0),
+        (
+            tr("param-duty-cycle"),
+            parameters.duty_cycle,
+            parameters.duty_cycle >= 0.0,
+        ),
+        (
+            tr("param-environment-factor"),
+            parameters.environment_factor,
+            parameters.environment_factor > 0.0,
+        ),
+        (tr("param-mttr"), parameters.mttr, parameters.mttr >= 0.0),
+        (
+            tr("param-temperature"),
+            parameters.temperature,
+            parameters.temperature.is_finite(),
+        ),
+    ];
+    for mode in &parameters.modes {
+        let name = |parameter| format!("{} {}", mode.name, tr(parameter));
+        all.push((name("param-shape"), mode.shape, mode.shape > 0.0));
+        all.push((name("param-scale"), mode.scale, mode.scale > 0.0));
+    }
+    let suspect: Vec<(String, f64)> = all
+        .iter()
+        .filter(|(_, value, valid)| !valid || value.is_infinite())
+        .map(|(name, value, _)| (name.clone(), *value))
+        .collect();
+    if !suspect.is_empty() {
+        return suspect;
+    }
+    all.into_iter()
+        .take(2)
+        .map(|(name, value, _)| (name, value))
+        .collect()
+}
@@ -0,0 +1,112 @@
+//! Where evaluation spends its time: each node's own compute time, measured
+//! as the diagram is evaluated, and a heatmap tinting the nodes by it, so the
+//! parts of a model that make recomputation slow stand out.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use catppuccin_egui::Theme;
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::i18n::tr_with;
+use crate::nodes::EditorState;
+use crate::wires::Layout;
+
+/// How much each new evaluation counts towards the times shown, which are
+/// smoothed over frames so the tints don't flicker.
+const SMOOTHING: f64 = 0.2;
+
+/// How strongly the slowest node is tinted.
+const MAX_TINT: f32 = 0.55;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hotspots {
+    /// Each node's own time per evaluation, in seconds.
+    pub own: HashMap<NodeId, f64>,
+}
+
+impl Hotspots {
+    /// Folds in the timings of one evaluation. Nodes it didn't evaluate are
+    /// dropped.
+    pub fn record(&mut self, timings: HashMap<NodeId, Duration>) {
+        self.own = timings
+            .into_iter()
+            .map(|(node, time)| {
+                let time = time.as_secs_f64();
+                let smoothed = match self.own.get(&node) {
+                    Some(before) => before + SMOOTHING * (time - before),
+                    None => time,
+                };
+                (node, smoothed)
+            })
+            .collect();
+    }
+
+    pub fn total(&self) -> f64 {
+        self.own.values().sum()
+    }
+
+    /// The node taking longest, and its time.
+    pub fn slowest(&self) -> Option<(NodeId, f64)> {
+        self.own
+            .iter()
+            .map(|(node, time)| (*node, *time))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+fn milliseconds(seconds: f64) -> String {
+    format!("{:.2} ms", seconds * 1e3)
+}
+
+/// Tints every evaluated node by its share of the slowest node's time, with
+/// its time above it, and sums up the evaluation in the bottom corner of the
+/// canvas.
+pub fn paint(
+    ui: &egui::Ui,
+    editor: &EditorState,
+    layout: &Layout,
+    hotspots: &Hotspots,
+    theme: &Theme,
+) {
+    let Some((slowest, longest)) = hotspots.slowest() else {
+        return;
+    };
+    let painter = ui.painter();
+    let font = egui::FontId::proportional(12.0);
+    for (node, time) in &hotspots.own {
+        let Some(rect) = layout.node_rect(*node) else {
+            continue;
+        };
+        let share = if longest > 0.0 { time / longest } else { 0.0 };
+        painter.rect_filled(rect, 6.0, theme.red.gamma_multiply(MAX_TINT * share as f32));
+        painter.text(
+            rect.right_top() - egui::vec2(0.0, 2.0),
+            egui::Align2::RIGHT_BOTTOM,
+            milliseconds(*time),
+            font.clone(),
+            theme.subtext0,
+        );
+    }
+    let total = hotspots.total();
+    let label = editor
+        .graph
+        .nodes
+        .get(slowest)
+        .map_or("", |node| node.label.as_str());
+    let share = if total > 0.0 { longest / total } else { 0.0 };
+    painter.text(
+        ui.max_rect().left_bottom() + egui::vec2(8.0, -8.0),
+        egui::Align2::LEFT_BOTTOM,
+        tr_with(
+            "hotspots-summary",
+            &[
+                ("total", &milliseconds(total)),
+                ("node", &label),
+                ("share", &format!("{:.0}", 100.0 * share)),
+            ],
+        ),
+        egui::FontId::proportional(14.0),
+        theme.red,
+    );
+}
@@ -0,0 +1,113 @@
+//! Localized UI strings, backed by the Fluent resources in `locales/`.
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The language's name as written in that language, for the language picker.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// The BCP 47 tag used to persist the language choice.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::German => "de-DE",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        Language::ALL.into_iter().find(|language| language.tag() == tag)
+    }
+
+    fn identifier(self) -> LanguageIdentifier {
+        self.tag().parse().expect("valid language tag")
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+        }
+    }
+
+    fn bundle(self) -> FluentBundle<FluentResource> {
+        let resource = FluentResource::try_new(self.source().to_owned())
+            .expect("locale files should parse");
+        let mut bundle = FluentBundle::new(vec![self.identifier()]);
+        // egui renders the bidi isolation marks literally, so leave them out.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .expect("locale files should not redefine messages");
+        bundle
+    }
+}
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static BUNDLES: RefCell<Vec<Option<FluentBundle<FluentResource>>>> =
+        RefCell::new(Language::ALL.iter().map(|_| None).collect());
+}
+
+pub fn language() -> Language {
+    Language::ALL[CURRENT.load(Ordering::Relaxed)]
+}
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as usize, Ordering::Relaxed);
+}
+
+/// Looks up a message in the current language. Messages missing from a
+/// translation fall back to English, and finally to the id itself.
+pub fn tr(id: &str) -> String {
+    tr_args(id, None)
+}
+
+/// Like [`tr`], filling in the message's `{ $placeholders }`.
+pub fn tr_with(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.to_string());
+    }
+    tr_args(id, Some(&fluent_args))
+}
+
+/// The lookup behind [`tr`] and [`tr_with`].
+fn tr_args(id: &str, args: Option<&FluentArgs>) -> String {
+    let mut languages = vec![language()];
+    if language() != Language::English {
+        languages.push(Language::English);
+    }
+    languages
+        .into_iter()
+        .find_map(|language| format(language, id, args))
+        .unwrap_or_else(|| id.to_owned())
+}
+
+fn format(language: Language, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    BUNDLES.with(|bundles| {
+        let mut bundles = bundles.borrow_mut();
+        let bundle = bundles[language as usize].get_or_insert_with(|| language.bundle());
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+    })
+}
@@ -0,0 +1,236 @@
+//! An append-only journal of the edits made since the project was last
+//! saved, so that work lost to a crash can be replayed at the next start.
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app::APP_NAME;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, GraphState};
+use crate::project::{self, Project};
+
+/// How often the model is compared with the journal for new edits.
+const INTERVAL: Duration = Duration::from_secs(2);
+
+/// Past this many entries the journal starts over from the current model,
+/// so replaying it stays quick.
+const MAX_ENTRIES: usize = 500;
+
+/// A line of the journal.
+#[derive(Serialize, Deserialize)]
+enum Entry {
+    /// The project as it was when the journal started, in its JSON layout.
+    Base(Value),
+    /// What changed since the previous entry.
+    Changes(Vec<Change>),
+}
+
+/// A change to the project's JSON layout, at a JSON pointer.
+#[derive(Serialize, Deserialize)]
+enum Change {
+    Set(String, Value),
+    Remove(String),
+}
+
+/// Edits left behind by a session that didn't exit cleanly.
+struct Recovery {
+    model: Value,
+    edits: usize,
+}
+
+#[derive(Default)]
+pub struct Journal {
+    /// Where the journal is kept; `None` keeps no journal.
+    path: Option<PathBuf>,
+    /// The model as of the last entry, or `None` to start over at the next.
+    last: Option<Value>,
+    entries: usize,
+    checked: Option<Instant>,
+    /// Journaling waits until these are recovered or discarded, so they
+    /// aren't overwritten.
+    recovery: Option<Recovery>,
+}
+
+impl Journal {
+    /// Opens the journal in the app's data directory, picking up the edits
+    /// of a previous session that crashed.
+    pub fn open() -> Self {
+        let path = eframe::storage_dir(APP_NAME).map(|dir| dir.join("journal.jsonl"));
+        let recovery = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| replay(&text));
+        Self {
+            path,
+            recovery,
+            ..Default::default()
+        }
+    }
+
+    /// Appends what changed since the last entry, every few seconds.
+    pub fn record(&mut self, editor: &EditorState, state: &GraphState) {
+        if self.recovery.is_some() || self.checked.is_some_and(|at| at.elapsed() < INTERVAL) {
+            return;
+        }
+        self.checked = Some(Instant::now());
+        let Some(path) = &self.path else {
+            return;
+        };
+        let model = project::to_value(editor, state);
+        let entry = match &self.last {
+            Some(last) if self.entries < MAX_ENTRIES => {
+                let mut changes = Vec::new();
+                diff(&mut String::new(), last, &model, &mut changes);
+                if changes.is_empty() {
+                    return;
+                }
+                Entry::Changes(changes)
+            }
+            _ => Entry::Base(model.clone()),
+        };
+        let line = serde_json::to_string(&entry).expect("entries always serialize") + "\n";
+        let written = match entry {
+            Entry::Base(_) => {
+                self.entries = 0;
+                fs::create_dir_all(path.parent().unwrap_or(path))
+                    .and_then(|()| fs::write(path, line))
+            }
+            Entry::Changes(_) => {
+                self.entries += 1;
+                OpenOptions::new()
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(line.as_bytes()))
+            }
+        };
+        // A journal that can't be written is no worse than having none, so
+        // it just tries again from a fresh start.
+        self.last = written.is_ok().then_some(model);
+    }
+
+    /// Starts over from the current model, once it has been saved.
+    pub fn saved(&mut self) {
+        self.last = None;
+        self.checked = None;
+    }
+
+    /// Removes the journal when the app exits cleanly.
+    pub fn close(&mut self) {
+        if let (Some(path), None) = (&self.path, &self.recovery) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Offers to replay the edits of a session that crashed, and returns the
+    /// model they lead to if the user accepts.
+    pub fn recovery_window(&mut self, ctx: &egui::Context) -> anyhow::Result<Option<Project>> {
+        let Some(recovery) = &self.recovery else {
+            return Ok(None);
+        };
+        let (mut recover, mut discard) = (false, false);
+        egui::Window::new(tr("journal-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr_with("journal-message", &[("edits", &recovery.edits)]));
+                ui.horizontal(|ui| {
+                    recover = ui.button(tr("journal-recover")).clicked();
+                    discard = ui.button(tr("journal-discard")).clicked();
+                });
+            });
+        if !recover && !discard {
+            return Ok(None);
+        }
+        let recovery = self.recovery.take().expect("checked above");
+        self.saved();
+        if discard {
+            return Ok(None);
+        }
+        project::from_json(&recovery.model.to_string()).map(Some)
+    }
+}
+
+/// The model a journal's entries lead to, if it holds any edits. A last line
+/// cut short by the crash is left out.
+fn replay(text: &str) -> Option<Recovery> {
+    let mut entries = text
+        .lines()
+        .map_while(|line| serde_json::from_str::<Entry>(line).ok());
+    let Some(Entry::Base(mut model)) = entries.next() else {
+        return None;
+    };
+    let mut edits = 0;
+    for entry in entries {
+        let Entry::Changes(changes) = entry else {
+            break;
+        };
+        for change in changes {
+            apply(&mut model, change)?;
+        }
+        edits += 1;
+    }
+    (edits > 0).then_some(Recovery { model, edits })
+}
+
+/// Adds the changes turning `old` into `new` below the JSON pointer `path`.
+/// Objects and arrays of the same length are compared item by item, so an
+/// edited parameter only changes that parameter.
+fn diff(path: &mut String, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, value) in new {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                match old.get(key) {
+                    Some(old) => diff(path, old, value, changes),
+                    None => changes.push(Change::Set(path.clone(), value.clone())),
+                }
+                path.truncate(len);
+            }
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                changes.push(Change::Remove(format!("{path}/{key}")));
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{i}"));
+                diff(path, old, new, changes);
+                path.truncate(len);
+            }
+        }
+        _ if old != new => changes.push(Change::Set(path.clone(), new.clone())),
+        _ => {}
+    }
+}
+
+fn apply(model: &mut Value, change: Change) -> Option<()> {
+    let (pointer, value) = match change {
+        Change::Set(pointer, value) => (pointer, Some(value)),
+        Change::Remove(pointer) => (pointer, None),
+    };
+    let Some((parent, key)) = pointer.rsplit_once('/') else {
+        *model = value?;
+        return Some(());
+    };
+    let key = key.replace("~1", "/").replace("~0", "~");
+    match (model.pointer_mut(parent)?, value) {
+        (Value::Object(map), Some(value)) => {
+            map.insert(key, value);
+        }
+        (Value::Object(map), None) => {
+            map.remove(&key);
+        }
+        (Value::Array(items), Some(value)) => *items.get_mut(key.parse::<usize>().ok()?)? = value,
+        _ => return None,
+    }
+    Some(())
+}
@@ -0,0 +1,323 @@
+//! Kaplan-Meier survival estimates from field failure and suspension data.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Legend, Line, LineStyle, Plot};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, NodeTemplate, ValueType};
+
+/// z for a two-sided 95% confidence band.
+const Z_95: f64 = 1.959_964;
+
+/// A unit's time on test, ending either in failure or in suspension (censoring).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observation {
+    pub time: f64,
+    pub failed: bool,
+}
+
+/// The estimate just after a failure time.
+#[derive(Clone, Copy, Debug)]
+pub struct Step {
+    pub time: f64,
+    pub survival: f64,
+    /// Greenwood 95% confidence band.
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Parses one observation per line as `time[,status]`, where status is `1`/`F`
+/// for a failure and `0`/`S` for a suspension. Fields may also be separated by
+/// tabs, semicolons or spaces; a header line and `#` comments are skipped.
+pub fn parse(text: &str) -> Result<Vec<Observation>, String> {
+    let mut observations = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line
+            .split([',', ';', '\t', ' '])
+            .filter(|field| !field.is_empty());
+        let time = fields.next().unwrap_or_default();
+        let Ok(time) = time.parse::<f64>() else {
+            if observations.is_empty() {
+                continue;
+            }
+            return Err(tr_with("km-error-line", &[("line", &(i + 1))]));
+        };
+        let failed = match fields.next().map(str::to_lowercase).as_deref() {
+            None | Some("1" | "f" | "failure" | "failed") => true,
+            Some("0" | "s" | "suspension" | "suspended" | "censored") => false,
+            Some(_) => return Err(tr_with("km-error-line", &[("line", &(i + 1))])),
+        };
+        observations.push(Observation { time, failed });
+    }
+    Ok(observations)
+}
+
+/// The product-limit estimate with Greenwood confidence bands. Suspensions at
+/// a failure time are counted as still at risk.
+pub fn estimate(observations: &[Observation]) -> Vec<Step> {
+    let mut sorted = observations.to_vec();
+    sorted.sort_by(|a, b| a.time.total_cmp(&b.time).then(b.failed.cmp(&a.failed)));
+
+    let mut steps = Vec::new();
+    let mut at_risk = sorted.len();
+    let mut survival = 1.0;
+    let mut greenwood = 0.0;
+    let mut i = 0;
+    while i < sorted.len() {
+        let time = sorted[i].time;
+        let tied = sorted[i..].iter().take_while(|o| o.time == time).count();
+        let failures = sorted[i..i + tied].iter().filter(|o| o.failed).count();
+        if failures > 0 {
+            let (n, d) = (at_risk as f64, failures as f64);
+            survival *= 1.0 - d / n;
+            if at_risk > failures {
+                greenwood += d / (n * (n - d));
+            }
+            let half_width = Z_95 * survival * greenwood.sqrt();
+            steps.push(Step {
+                time,
+                survival,
+                lower: (survival - half_width).max(0.0),
+                upper: (survival + half_width).min(1.0),
+            });
+        }
+        at_risk -= tied;
+        i += tied;
+    }
+    steps
+}
+
+/// The estimate at each whole time step up to `horizon`, held at its last
+/// value past the last observation.
+pub fn sample(steps: &[Step], horizon: f64) -> Vec<f64> {
+    let mut survival = 1.0;
+    let mut next = steps.iter().peekable();
+    (0..=horizon.ceil() as usize)
+        .map(|t| {
+            while let Some(step) = next.next_if(|step| step.time <= t as f64) {
+                survival = step.survival;
+            }
+            survival
+        })
+        .collect()
+}
+
+/// The estimate drawn as a step function from t = 0, using `value` of each step.
+fn step_line(steps: &[Step], value: impl Fn(&Step) -> f64) -> Vec<[f64; 2]> {
+    let mut points = vec![[0.0, 1.0]];
+    let mut previous = 1.0;
+    for step in steps {
+        points.push([step.time, previous]);
+        previous = value(step);
+        points.push([step.time, previous]);
+    }
+    points
+}
+
+#[derive(Default)]
+pub struct KaplanMeier {
+    text: String,
+    observations: Vec<Observation>,
+    steps: Vec<Step>,
+    error: Option<String>,
+    target: Option<NodeId>,
+    /// The time step the attached curve runs to, the last observation's
+    /// unless changed to match the blocks next to the node.
+    horizon: usize,
+}
+
+impl KaplanMeier {
+    fn update_estimate(&mut self) {
+        match parse(&self.text) {
+            Ok(observations) => {
+                self.steps = estimate(&observations);
+                let last = observations.iter().map(|o| o.time).fold(0.0, f64::max);
+                self.horizon = last.ceil() as usize;
+                self.observations = observations;
+                self.error = None;
+            }
+            Err(err) => {
+                self.observations.clear();
+                self.steps.clear();
+                self.error = Some(err);
+            }
+        }
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("km-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                ui.label(tr("km-format"));
+                let edited = ui
+                    .add(
+                        egui::TextEdit::multiline(&mut self.text)
+                            .code_editor()
+                            .desired_rows(6)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .changed();
+                if ui.button(tr("km-import")).clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("CSV", &["csv", "tsv", "txt"])
+                        .pick_file()
+                    {
+                        match std::fs::read_to_string(path) {
+                            Ok(text) => {
+                                self.text = text;
+                                self.update_estimate();
+                            }
+                            Err(err) => {
+                                tracing::warn!("reading failure data failed: {err}");
+                                self.error = Some(err.to_string());
+                            }
+                        }
+                    }
+                }
+                if edited {
+                    self.update_estimate();
+                }
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                if self.steps.is_empty() {
+                    return;
+                }
+
+                let failures = self.observations.iter().filter(|o| o.failed).count();
+                ui.label(tr_with(
+                    "km-summary",
+                    &[
+                        ("failures", &failures),
+                        ("suspensions", &(self.observations.len() - failures)),
+                    ],
+                ));
+                let theme = state.settings.theme.catppuccin();
+                Plot::new("kaplan meier")
+                    .height(250.0)
+                    .include_y(0.0)
+                    .include_y(1.0)
+                    .x_axis_label(format!("t [{}]", state.settings.time_unit.symbol()))
+                    .y_axis_label("R(t)")
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(
+                            Line::new(step_line(&self.steps, |step| step.survival))
+                                .color(theme.blue)
+                                .name(tr("km-estimate")),
+                        );
+                        let bounds: [fn(&Step) -> f64; 2] = [|step| step.lower, |step| step.upper];
+                        for bound in bounds {
+                            plot_ui.line(
+                                Line::new(step_line(&self.steps, bound))
+                                    .color(theme.overlay2)
+                                    .style(LineStyle::dashed_loose())
+                                    .name(tr("km-confidence")),
+                            );
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("km-attach-to"));
+                    let empirical = nodes::nodes_of(graph, NodeTemplate::Empirical);
+                    if self.target.is_some_and(|node| !empirical.contains(&node)) {
+                        self.target = None;
+                    }
+                    let text = self
+                        .target
+                        .map(|node| graph[node].label.clone())
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_source("km target")
+                        .selected_text(text)
+                        .show_ui(ui, |ui| {
+                            for node in empirical {
+                                ui.selectable_value(
+                                    &mut self.target,
+                                    Some(node),
+                                    &graph[node].label,
+                                );
+                            }
+                        });
+                    ui.label(tr("km-horizon"));
+                    ui.add(egui::DragValue::new(&mut self.horizon).clamp_range(1..=usize::MAX))
+                        .on_hover_text(tr("km-horizon-hint"));
+                    if ui
+                        .add_enabled(self.target.is_some(), egui::Button::new(tr("km-attach")))
+                        .clicked()
+                    {
+                        let curve = sample(&self.steps, self.horizon as f64);
+                        if let Some(input) = self
+                            .target
+                            .and_then(|node| graph[node].get_input("curve").ok())
+                        {
+                            graph[input].value = ValueType::Reliability { curve };
+                        }
+                    }
+                });
+                if nodes::nodes_of(graph, NodeTemplate::Empirical).is_empty() {
+                    ui.label(tr("km-no-empirical-nodes"));
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observations(data: &[(f64, bool)]) -> Vec<Observation> {
+        data.iter()
+            .map(|&(time, failed)| Observation { time, failed })
+            .collect()
+    }
+
+    #[test]
+    fn estimate_steps_down_at_failures_only() {
+        let steps = estimate(&observations(&[
+            (4.0, true),
+            (2.0, false),
+            (1.0, true),
+            (5.0, false),
+            (3.0, true),
+        ]));
+        let survival: Vec<(f64, f64)> = steps.iter().map(|s| (s.time, s.survival)).collect();
+        let expected = [(1.0, 0.8), (3.0, 0.8 * 2.0 / 3.0), (4.0, 0.8 / 3.0)];
+        assert_eq!(survival.len(), expected.len());
+        for ((time, s), (expected_time, expected_s)) in survival.iter().zip(expected) {
+            assert_eq!(*time, expected_time);
+            assert!((s - expected_s).abs() < 1e-12, "S({time}) = {s}");
+        }
+        // Greenwood: Var S(1) = S(1)² · 1 / (5 · 4).
+        let half_width = Z_95 * 0.8 * 0.05_f64.sqrt();
+        assert!((steps[0].lower - (0.8 - half_width)).abs() < 1e-12);
+        assert_eq!(steps[0].upper, 1.0);
+    }
+
+    #[test]
+    fn suspensions_at_a_failure_time_are_still_at_risk() {
+        let steps = estimate(&observations(&[(1.0, false), (1.0, true), (2.0, true)]));
+        assert!((steps[0].survival - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(steps[1].survival, 0.0);
+    }
+
+    #[test]
+    fn sample_holds_the_last_value() {
+        let steps = estimate(&observations(&[(1.5, true), (2.0, false), (3.0, false)]));
+        let survival = 1.0 - 1.0 / 3.0;
+        assert_eq!(
+            sample(&steps, 4.0),
+            vec![1.0, 1.0, survival, survival, survival]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Keyboard-only editing, for users who can't rely on precise mouse drags.
+//!
+//! Shift+A opens the node finder at the viewport center, N / Shift+N move the
+//! selection between nodes, and pressing C on two nodes in turn connects the
+//! first node's output to the next free input of the second. Ctrl+V pastes a
+//! node copied as JSON at the pointer.
+use eframe::egui::{self, Key, Modifiers};
+use egui_node_graph2::{InputParamKind, NodeFinder, NodeId, NodeResponse};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, GraphState, NodeData, Response};
+use crate::snippet;
+
+#[derive(Default)]
+pub struct KeyboardEditing {
+    /// The node picked as the source of a keyboard connection.
+    connect_from: Option<NodeId>,
+    /// Feedback on the last keyboard action, shown in the status bar.
+    status: Option<String>,
+}
+
+impl KeyboardEditing {
+    /// Handles this frame's shortcuts, returning the responses to feed into the graph editor.
+    pub fn handle(
+        &mut self,
+        ctx: &egui::Context,
+        state: &mut EditorState,
+        user_state: &mut GraphState,
+        viewport: egui::Rect,
+    ) -> Vec<NodeResponse<Response, NodeData>> {
+        let mut responses = Vec::new();
+
+        if let Some(from) = self.connect_from {
+            if !state.graph.nodes.contains_key(from) {
+                self.connect_from = None;
+            }
+        }
+        if ctx.wants_keyboard_input() || state.node_finder.is_some() {
+            return responses;
+        }
+        let pressed = |modifiers, key| ctx.input_mut(|i| i.consume_key(modifiers, key));
+
+        let pasted = ctx.input(|i| {
+            i.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            })
+        });
+        if let Some(text) = pasted {
+            let position = ctx
+                .pointer_hover_pos()
+                .filter(|pos| viewport.contains(*pos))
+                .unwrap_or(viewport.center());
+            match snippet::paste(state, user_state, &text, position, viewport) {
+                Ok(node) => {
+                    self.status = Some(tr_with(
+                        "snippet-pasted",
+                        &[("node", &state.graph[node].label)],
+                    ));
+                    responses.push(NodeResponse::SelectNode(node));
+                }
+                Err(err) => self.status = Some(err.to_string()),
+            }
+        }
+
+        if pressed(Modifiers::SHIFT, Key::A) {
+            state.node_finder = Some(NodeFinder::new_at(viewport.center()));
+        }
+
+        // Shift is ignored when matching plain keys, so check Shift+N first.
+        let step = if pressed(Modifiers::SHIFT, Key::N) {
+            Some(-1)
+        } else if pressed(Modifiers::NONE, Key::N) {
+            Some(1)
+        } else {
+            None
+        };
+        if let Some(node) = step.and_then(|step| cycle_selection(state, step)) {
+            responses.push(NodeResponse::SelectNode(node));
+            responses.push(NodeResponse::RaiseNode(node));
+        }
+
+        let selected = match state.selected_nodes.as_slice() {
+            [node] => Some(*node),
+            _ => None,
+        };
+
+        if pressed(Modifiers::NONE, Key::Enter) {
+            if let Some(node) = selected {
+                responses.push(NodeResponse::User(Response::SetActiveNode(node)));
+            }
+        }
+
+        if pressed(Modifiers::NONE, Key::Delete) {
+            responses.extend(
+                state
+                    .selected_nodes
+                    .iter()
+                    .filter(|node| !user_state.read_only.contains_key(node))
+                    .map(|node| NodeResponse::DeleteNodeUi(*node)),
+            );
+        }
+
+        if pressed(Modifiers::NONE, Key::C) {
+            match (self.connect_from.take(), selected) {
+                (_, None) => self.status = Some(tr("keyboard-select-one")),
+                (None, Some(from)) => {
+                    self.connect_from = Some(from);
+                    self.status = Some(tr_with(
+                        "keyboard-connect-from",
+                        &[("node", &state.graph[from].label)],
+                    ));
+                }
+                (Some(from), Some(to)) => match connection_event(state, from, to) {
+                    Some(event) => {
+                        self.status = Some(tr_with(
+                            "keyboard-connected",
+                            &[
+                                ("from", &state.graph[from].label),
+                                ("to", &state.graph[to].label),
+                            ],
+                        ));
+                        responses.push(event);
+                    }
+                    None => self.status = Some(tr("keyboard-no-free-input")),
+                },
+            }
+        }
+
+        if self.connect_from.is_some() && pressed(Modifiers::NONE, Key::Escape) {
+            self.connect_from = None;
+            self.status = Some(tr("keyboard-connect-cancelled"));
+        }
+
+        responses
+    }
+
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+}
+
+/// The node `step` places away from the current selection, wrapping around.
+fn cycle_selection(state: &EditorState, step: isize) -> Option<NodeId> {
+    let nodes: Vec<NodeId> = state.graph.iter_nodes().collect();
+    if nodes.is_empty() {
+        return None;
+    }
+    let next = match state
+        .selected_nodes
+        .first()
+        .and_then(|selected| nodes.iter().position(|node| node == selected))
+    {
+        Some(current) => (current as isize + step).rem_euclid(nodes.len() as isize) as usize,
+        None => 0,
+    };
+    Some(nodes[next])
+}
+
+/// Connects the first output of `from` to the first free, compatible input of `to`.
+fn connection_event(
+    state: &EditorState,
+    from: NodeId,
+    to: NodeId,
+) -> Option<NodeResponse<Response, NodeData>> {
+    if from == to {
+        return None;
+    }
+    let graph = &state.graph;
+    let (_, output) = graph[from].outputs.first()?;
+    let input = graph[to].input_ids().find(|input| {
+        let param = &graph[*input];
+        param.typ == graph[*output].typ
+            && !matches!(param.kind, InputParamKind::ConstantOnly)
+            && graph.connection(*input).is_none()
+    })?;
+    Some(NodeResponse::ConnectEventEnded {
+        output: *output,
+        input,
+    })
+}
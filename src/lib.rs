@@ -1 +1,81 @@
+pub mod age_replacement;
+pub mod allocation;
 pub mod app;
+pub mod arrange;
+pub mod audit;
+pub mod availability;
+pub mod bands;
+pub mod batch;
+pub mod bayes;
+pub mod budget;
+pub mod calendar;
+pub mod canvas;
+pub mod card;
+pub mod checkpoints;
+pub mod compare;
+pub mod competing_risks;
+pub mod config;
+pub mod criticality;
+pub mod distribution;
+pub mod doe;
+pub mod dot;
+pub mod equivalent;
+pub mod eval;
+pub mod event_tree;
+pub mod examples;
+pub mod feed;
+pub mod field_data;
+pub mod fixture;
+pub mod fleet;
+pub mod frames;
+pub mod frequency_duration;
+pub mod goal_seek;
+pub mod health;
+pub mod hotspots;
+pub mod i18n;
+pub mod journal;
+pub mod kaplan_meier;
+pub mod keyboard;
+pub mod library;
+pub mod locale;
+pub mod log;
+pub mod maintenance;
+pub mod metrics;
+pub mod mirrors;
+pub mod nodes;
+pub mod palette;
+pub mod pareto;
+pub mod petri;
+pub mod plot;
+pub mod profile;
+pub mod project;
+pub mod quantile;
+pub mod redundancy;
+pub mod regression;
+pub mod rename;
+pub mod render;
+pub mod report;
+pub mod requirement;
+pub mod results;
+pub mod robustness;
+pub mod rocof;
+pub mod scaling;
+pub mod settings;
+pub mod shocks;
+pub mod sil;
+pub mod simulate;
+pub mod snippet;
+pub mod software;
+pub mod statistics;
+pub mod sweep;
+pub mod tags;
+pub mod toasts;
+pub mod topology;
+pub mod validation;
+pub mod variables;
+pub mod viewport;
+pub mod watch;
+pub mod weak_link;
+pub mod what_if;
+pub mod wires;
+pub mod wizard;
@@ -0,0 +1,323 @@
+//! Centrally managed component data: snippets kept in a `components` folder
+//! of the configured model library. Nodes made from an entry keep its name,
+//! so that later changes to the entry can be reviewed and pushed to them.
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde_json::Value;
+
+use crate::config;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, Graph, GraphState, ValueType};
+use crate::snippet::{self, Snippet};
+use crate::toasts;
+
+pub const FOLDER: &str = "components";
+
+/// The folder entries are read from and saved to, if a library is configured.
+pub fn folder() -> Option<PathBuf> {
+    config::get().library().map(|library| library.join(FOLDER))
+}
+
+pub struct Entry {
+    /// The file name without its extension, which derived nodes link to.
+    pub name: String,
+    pub snippet: Snippet,
+}
+
+/// A value of a derived node that differs from its entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub node: NodeId,
+    pub input: String,
+    pub field: String,
+    pub current: String,
+    pub library: String,
+}
+
+/// Every entry in `folder`, sorted by name, and why any file couldn't be read.
+pub fn load(folder: &Path) -> (Vec<Entry>, Vec<String>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    let files = match std::fs::read_dir(folder) {
+        Ok(files) => files,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return (entries, errors),
+        Err(err) => return (entries, vec![err.to_string()]),
+    };
+    for path in files.filter_map(|file| Some(file.ok()?.path())) {
+        if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let snippet = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Snippet::from_json(&text));
+        match snippet {
+            Ok(snippet) => entries.push(Entry {
+                name: name.to_owned(),
+                snippet,
+            }),
+            Err(err) => errors.push(format!("{name}: {err}")),
+        }
+    }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    (entries, errors)
+}
+
+/// The nodes linked to the entry called `name`.
+pub fn derived(graph: &Graph, name: &str) -> Vec<NodeId> {
+    graph
+        .nodes
+        .iter()
+        .filter(|(_, node)| node.user_data.library.as_deref() == Some(name))
+        .map(|(node_id, _)| node_id)
+        .collect()
+}
+
+/// The value inside a `ValueType`'s variant and field, as JSON.
+fn inner(value: &ValueType) -> Value {
+    let mut value = serde_json::to_value(value).unwrap_or_default();
+    for _ in 0..2 {
+        value = match value {
+            Value::Object(map) if map.len() == 1 => map.into_iter().next().unwrap_or_default().1,
+            value => value,
+        };
+    }
+    value
+}
+
+/// The leaves of `value` by their dotted path. Arrays are leaves themselves,
+/// as a curve differs as a whole.
+fn flatten(path: String, value: Value, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten(path, value, leaves);
+            }
+        }
+        value => {
+            leaves.insert(path, value);
+        }
+    }
+}
+
+fn describe(value: Option<&Value>) -> String {
+    match value {
+        None => "—".to_owned(),
+        Some(Value::Array(values)) if values.len() > 4 => {
+            tr_with("library-values", &[("count", &values.len())])
+        }
+        Some(value) => value.to_string(),
+    }
+}
+
+/// How the nodes derived from `entry` differ from it, field by field.
+pub fn changes(graph: &Graph, entry: &Entry) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for node in derived(graph, &entry.name) {
+        for (input, value) in &entry.snippet.values {
+            let Ok(input_id) = graph[node].get_input(input) else {
+                continue;
+            };
+            let (mut current, mut library) = (BTreeMap::new(), BTreeMap::new());
+            flatten(String::new(), inner(&graph[input_id].value), &mut current);
+            flatten(String::new(), inner(value), &mut library);
+            let fields: BTreeSet<&String> = current.keys().chain(library.keys()).collect();
+            for field in fields {
+                if current.get(field) != library.get(field) {
+                    changes.push(Change {
+                        node,
+                        input: input.clone(),
+                        field: field.clone(),
+                        current: describe(current.get(field)),
+                        library: describe(library.get(field)),
+                    });
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Sets the values of every node derived from `entry` to the entry's.
+pub fn push(graph: &mut Graph, entry: &Entry) {
+    for node in derived(graph, &entry.name) {
+        for (input, value) in &entry.snippet.values {
+            if let Ok(input) = graph[node].get_input(input) {
+                if graph[input].typ == snippet::value_type(value) {
+                    graph[input].value = value.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Saves `node_id` as the entry named after its label and links it to it.
+fn save(graph: &mut Graph, node_id: NodeId, folder: &Path) -> anyhow::Result<String> {
+    let name: String = graph[node_id]
+        .label
+        .chars()
+        .map(|c| if std::path::is_separator(c) { '_' } else { c })
+        .collect();
+    std::fs::create_dir_all(folder)?;
+    let path = folder.join(format!("{name}.json"));
+    std::fs::write(path, Snippet::from_node(graph, node_id).to_json())?;
+    graph[node_id].user_data.library = Some(name.clone());
+    Ok(name)
+}
+
+#[derive(Default)]
+pub struct Library {
+    entries: Vec<Entry>,
+    errors: Vec<String>,
+    loaded: bool,
+}
+
+impl Library {
+    fn reload(&mut self, folder: &Path) {
+        (self.entries, self.errors) = load(folder);
+        self.loaded = true;
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        editor: &mut EditorState,
+        state: &mut GraphState,
+        viewport: egui::Rect,
+    ) {
+        egui::Window::new(tr("library-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let Some(folder) = folder() else {
+                    ui.label(tr("library-no-folder"));
+                    return;
+                };
+                if !self.loaded {
+                    self.reload(&folder);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("library-reload")).clicked() {
+                        self.reload(&folder);
+                    }
+                    if ui
+                        .add_enabled(
+                            state.active_node.is_some(),
+                            egui::Button::new(tr("library-save")),
+                        )
+                        .on_hover_text(tr("library-save-hint"))
+                        .clicked()
+                    {
+                        if let Some(node) = state.active_node {
+                            match save(&mut editor.graph, node, &folder) {
+                                Ok(name) => {
+                                    toasts::success(tr_with(
+                                        "toast-library-saved",
+                                        &[("name", &name)],
+                                    ));
+                                    self.reload(&folder);
+                                }
+                                Err(err) => {
+                                    tracing::warn!("saving a library entry failed: {err}");
+                                    toasts::error(err.to_string());
+                                }
+                            }
+                        }
+                    }
+                });
+                for error in &self.errors {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if self.entries.is_empty() {
+                    ui.label(tr_with(
+                        "library-empty",
+                        &[("folder", &folder.display().to_string())],
+                    ));
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for entry in &self.entries {
+                        Self::entry_ui(ui, entry, editor, state, viewport);
+                    }
+                });
+            });
+    }
+
+    fn entry_ui(
+        ui: &mut egui::Ui,
+        entry: &Entry,
+        editor: &mut EditorState,
+        state: &mut GraphState,
+        viewport: egui::Rect,
+    ) {
+        let nodes = derived(&editor.graph, &entry.name);
+        let changes = changes(&editor.graph, entry);
+        ui.horizontal(|ui| {
+            ui.strong(&entry.name);
+            ui.label(format!("({})", entry.snippet.template.name()));
+            ui.label(tr_with("library-derived", &[("count", &nodes.len())]));
+            if ui.button(tr("library-insert")).clicked() {
+                let snippet = entry.snippet.clone();
+                match snippet::insert(editor, state, snippet, viewport.center(), viewport) {
+                    Ok(node) => {
+                        editor.graph[node].user_data.library = Some(entry.name.clone());
+                        editor.selected_nodes = vec![node];
+                    }
+                    Err(err) => toasts::error(err.to_string()),
+                }
+            }
+        });
+        if changes.is_empty() {
+            if !nodes.is_empty() {
+                ui.label(tr("library-up-to-date"));
+            }
+            ui.separator();
+            return;
+        }
+        egui::CollapsingHeader::new(tr_with("library-review", &[("count", &changes.len())]))
+            .id_source(("library review", &entry.name))
+            .show(ui, |ui| {
+                egui::Grid::new(("library changes", &entry.name))
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("library-node"));
+                        ui.strong(tr("library-field"));
+                        ui.strong(tr("library-current"));
+                        ui.strong(tr("library-entry"));
+                        ui.end_row();
+                        for change in &changes {
+                            ui.label(&editor.graph[change.node].label);
+                            ui.label(format!("{} › {}", change.input, change.field));
+                            ui.label(&change.current);
+                            ui.label(&change.library);
+                            ui.end_row();
+                        }
+                    });
+            });
+        let affected = {
+            let mut affected: Vec<NodeId> = changes.iter().map(|change| change.node).collect();
+            affected.dedup();
+            affected.len()
+        };
+        if ui
+            .button(tr_with("library-push", &[("count", &affected)]))
+            .clicked()
+        {
+            push(&mut editor.graph, entry);
+            toasts::success(tr_with("toast-library-pushed", &[("count", &affected)]));
+        }
+        ui.separator();
+    }
+}
@@ -0,0 +1,58 @@
+//! How numbers and dates are written to exported files: with a decimal
+//! point and ISO dates, or with a decimal comma and day-first dates as
+//! spreadsheets in much of Europe expect. CSV files written with a decimal
+//! comma separate their fields with semicolons.
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::i18n::{self, Language};
+use crate::settings::NumberFormat;
+
+static FORMAT: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_format(format: NumberFormat) {
+    FORMAT.store(format as usize, Ordering::Relaxed);
+}
+
+fn decimal_comma() -> bool {
+    match NumberFormat::ALL[FORMAT.load(Ordering::Relaxed)] {
+        NumberFormat::Language => i18n::language() == Language::German,
+        NumberFormat::Point => false,
+        NumberFormat::Comma => true,
+    }
+}
+
+/// The separator between the fields of a CSV row.
+pub fn separator() -> char {
+    if decimal_comma() {
+        ';'
+    } else {
+        ','
+    }
+}
+
+/// `value` as it displays, with the decimal separator of the exports. Takes
+/// numbers already formatted to a precision too.
+pub fn number(value: impl Display) -> String {
+    let text = value.to_string();
+    if decimal_comma() {
+        text.replace('.', ",")
+    } else {
+        text
+    }
+}
+
+pub fn date(year: i64, month: i64, day: i64) -> String {
+    if decimal_comma() {
+        format!("{day:02}.{month:02}.{year:04}")
+    } else {
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// Joins the fields of a CSV row, already quoted, with the separator.
+pub fn row(fields: &[String]) -> String {
+    let mut row = fields.join(&separator().to_string());
+    row.push('\n');
+    row
+}
@@ -0,0 +1,205 @@
+//! The Log panel: recent tracing events kept in memory, so users who didn't
+//! start the app from a terminal still see its warnings.
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use eframe::egui;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+use crate::i18n::tr;
+
+/// The most events kept; older ones are dropped first.
+const CAPACITY: usize = 1_000;
+
+pub struct Record {
+    pub time: SystemTime,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Default)]
+struct Buffer {
+    records: VecDeque<Record>,
+    /// Warnings and errors since the panel was last looked at.
+    unseen: usize,
+}
+
+static BUFFER: OnceLock<Mutex<Buffer>> = OnceLock::new();
+
+fn buffer() -> &'static Mutex<Buffer> {
+    BUFFER.get_or_init(Default::default)
+}
+
+/// Logs to stdout as before, and to the panel.
+pub fn init() {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO))
+        .with(Recorder.with_filter(LevelFilter::INFO))
+        .init();
+}
+
+/// The layer adding events to the buffer.
+struct Recorder;
+
+impl<S: Subscriber> Layer<S> for Recorder {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = Message::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let record = Record {
+            time: SystemTime::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_owned(),
+            message: visitor.0,
+        };
+        let mut buffer = buffer().lock().unwrap_or_else(|err| err.into_inner());
+        if record.level <= Level::WARN {
+            buffer.unseen += 1;
+        }
+        if buffer.records.len() == CAPACITY {
+            buffer.records.pop_front();
+        }
+        buffer.records.push_back(record);
+    }
+}
+
+/// An event's message followed by its other fields as `name=value`.
+#[derive(Default)]
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{value:?}"));
+        } else {
+            let _ = write!(self.0, " {}={value:?}", field.name());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, value);
+        } else {
+            let _ = write!(self.0, " {}={value}", field.name());
+        }
+    }
+}
+
+/// Warnings and errors logged since the panel was last open.
+pub fn unseen() -> usize {
+    buffer().lock().map_or(0, |buffer| buffer.unseen)
+}
+
+/// The panel's filters.
+pub struct LogPanel {
+    level: Level,
+    /// Whether to hide the events of the libraries the app is built on.
+    app_only: bool,
+}
+
+impl Default for LogPanel {
+    fn default() -> Self {
+        Self {
+            level: Level::INFO,
+            app_only: false,
+        }
+    }
+}
+
+impl LogPanel {
+    pub fn panel(&mut self, ctx: &egui::Context, open: bool) {
+        egui::TopBottomPanel::bottom("log")
+            .resizable(true)
+            .default_height(160.0)
+            .show_animated(ctx, open, |ui| {
+                // Copied out, so events logged while drawing can't wait on
+                // the lock.
+                let lines: Vec<(Level, String)> = {
+                    let mut buffer = buffer().lock().unwrap_or_else(|err| err.into_inner());
+                    buffer.unseen = 0;
+                    buffer
+                        .records
+                        .iter()
+                        .filter(|record| self.shows(record))
+                        .map(|record| (record.level, line(record)))
+                        .collect()
+                };
+                ui.horizontal(|ui| {
+                    ui.strong(tr("log-title"));
+                    egui::ComboBox::from_id_source("log level")
+                        .selected_text(self.level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [Level::ERROR, Level::WARN, Level::INFO] {
+                                ui.selectable_value(&mut self.level, level, level.as_str());
+                            }
+                        });
+                    ui.checkbox(&mut self.app_only, tr("log-app-only"));
+                    if ui.button(tr("log-copy")).clicked() {
+                        let text: String =
+                            lines.iter().map(|(_, line)| format!("{line}\n")).collect();
+                        ui.output_mut(|o| o.copied_text = text);
+                    }
+                    if ui.button(tr("log-clear")).clicked() {
+                        clear();
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .auto_shrink(false)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for (level, line) in lines {
+                            let color = match level {
+                                Level::ERROR => ui.visuals().error_fg_color,
+                                Level::WARN => ui.visuals().warn_fg_color,
+                                _ => ui.visuals().text_color(),
+                            };
+                            ui.colored_label(color, egui::RichText::new(line).monospace());
+                        }
+                    });
+            });
+    }
+
+    fn shows(&self, record: &Record) -> bool {
+        record.level <= self.level && (!self.app_only || record.target.starts_with("block"))
+    }
+}
+
+fn clear() {
+    let mut buffer = buffer().lock().unwrap_or_else(|err| err.into_inner());
+    buffer.records.clear();
+}
+
+/// A record as `hh:mm:ss LEVEL target: message`.
+fn line(record: &Record) -> String {
+    format!(
+        "{} {:5} {}: {}",
+        clock(record.time),
+        record.level,
+        record.target,
+        record.message
+    )
+}
+
+/// A time as `hh:mm:ss` in UTC, for telling events apart.
+pub fn clock(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        % 86_400;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
@@ -1,18 +1,34 @@
-use block::app::MyApp;
+use block::app::{MyApp, APP_NAME};
 use eframe::egui;
 
 fn main() -> Result<(), eframe::Error> {
-    // Log to stdout (if you run with `RUST_LOG=debug`).
-    tracing_subscriber::fmt::init();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("batch") => std::process::exit(block::batch::run(&args[1..])),
+        Some("render") => std::process::exit(block::render::run(&args[1..])),
+        _ => {}
+    }
+    let seed = match args.iter().position(|arg| arg == "--seed") {
+        Some(i) => match args.get(i + 1).and_then(|seed| seed.parse().ok()) {
+            Some(seed) => Some(seed),
+            None => {
+                eprintln!("usage: block [--seed <u64>]");
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+
+    block::log::init();
 
     let options = eframe::NativeOptions {
-        initial_window_size: Some(egui::vec2(320.0, 240.0)),
+        viewport: egui::ViewportBuilder::default().with_inner_size([1024.0, 768.0]),
         ..Default::default()
     };
 
     eframe::run_native(
-        "Block: Reliability Block Programming",
+        APP_NAME,
         options,
-        Box::new(|_cc| Box::<MyApp>::default()),
+        Box::new(move |cc| Box::new(MyApp::new(cc, seed))),
     )
 }
@@ -0,0 +1,447 @@
+//! Opportunistic maintenance: while the system is down for a repair, other
+//! components past an age threshold are replaced too, trading preventive
+//! replacements for fewer system outages later.
+use std::collections::HashSet;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::eval::{evaluate_node, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Forced, Graph, GraphState, NodeParameters, NodeTemplate, Repair};
+use crate::settings::{self, TimeUnit};
+
+/// When components are replaced early, and what each outcome costs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Policy {
+    /// The age from which a working component is replaced at an outage.
+    pub threshold: f64,
+    pub corrective_cost: f64,
+    pub preventive_cost: f64,
+    /// The cost of the system being down, per time step.
+    pub downtime_cost: f64,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            threshold: 4_380.0,
+            corrective_cost: 1_000.0,
+            preventive_cost: 200.0,
+            downtime_cost: 50.0,
+        }
+    }
+}
+
+/// The means over the trials of one policy.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Outcome {
+    pub availability: f64,
+    pub repairs: f64,
+    pub replacements: f64,
+    pub cost: f64,
+    /// How often the system went down.
+    pub outages: f64,
+}
+
+/// A row of the comparison: its message, the figure and its decimals.
+type Row = (&'static str, fn(&Outcome) -> f64, usize);
+
+/// A component in a trial: up since `since` with `age` at that time and
+/// failing at `until`, or down and back up at `until`.
+struct Unit {
+    parameters: NodeParameters,
+    up: bool,
+    since: f64,
+    age: f64,
+    until: f64,
+}
+
+/// Simulates the components `system` depends on over `0..horizon`, without
+/// and then with opportunistic replacements, from the same seed.
+pub fn simulate_maintenance(
+    graph: &Graph,
+    options: &Options,
+    system: NodeId,
+    horizon: f64,
+    trials: usize,
+    seed: u64,
+    policy: Policy,
+) -> anyhow::Result<(Outcome, Outcome)> {
+    let (components, parameters) = components(graph, options, system)?;
+    let outcome = |opportunistic: bool| {
+        let mut rng = settings::rng(seed);
+        let mut total = Outcome::default();
+        for _ in 0..trials {
+            let trial = Trial {
+                graph,
+                options,
+                system,
+                components: &components,
+                horizon,
+                policy,
+                opportunistic,
+            };
+            let outcome = trial.run(&parameters, &mut rng, &mut Vec::new());
+            total.availability += outcome.availability;
+            total.repairs += outcome.repairs;
+            total.replacements += outcome.replacements;
+            total.cost += outcome.cost;
+            total.outages += outcome.outages;
+        }
+        let n = trials.max(1) as f64;
+        Outcome {
+            availability: total.availability / n,
+            repairs: total.repairs / n,
+            replacements: total.replacements / n,
+            cost: total.cost / n,
+            outages: total.outages / n,
+        }
+    };
+    Ok((outcome(false), outcome(true)))
+}
+
+/// Simulates the system's outages over `0..horizon` with repairs only.
+/// Gives the mean outcome of the trials and how long each outage lasted,
+/// leaving out those still going on at the horizon.
+pub fn simulate_outages(
+    graph: &Graph,
+    options: &Options,
+    system: NodeId,
+    horizon: f64,
+    trials: usize,
+    seed: u64,
+) -> anyhow::Result<(Outcome, Vec<f64>)> {
+    let (components, parameters) = components(graph, options, system)?;
+    let trial = Trial {
+        graph,
+        options,
+        system,
+        components: &components,
+        horizon,
+        policy: Policy::default(),
+        opportunistic: false,
+    };
+    let mut rng = settings::rng(seed);
+    let mut total = Outcome::default();
+    let mut durations = Vec::new();
+    for _ in 0..trials {
+        let outcome = trial.run(&parameters, &mut rng, &mut durations);
+        total.availability += outcome.availability;
+        total.repairs += outcome.repairs;
+        total.outages += outcome.outages;
+    }
+    let n = trials.max(1) as f64;
+    Ok((
+        Outcome {
+            availability: total.availability / n,
+            repairs: total.repairs / n,
+            outages: total.outages / n,
+            ..Outcome::default()
+        },
+        durations,
+    ))
+}
+
+/// The components `system` depends on and their parameters, once the
+/// system is known to evaluate.
+fn components(
+    graph: &Graph,
+    options: &Options,
+    system: NodeId,
+) -> anyhow::Result<(Vec<NodeId>, Vec<NodeParameters>)> {
+    evaluate_node(graph, options, system, &mut OutputsCache::new())?;
+    let components = nodes::upstream_components(graph, system);
+    let parameters = components
+        .iter()
+        .map(|node| {
+            nodes::component_parameters(graph, *node)
+                .ok_or_else(|| anyhow::anyhow!(tr("error-not-component")))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok((components, parameters))
+}
+
+struct Trial<'a> {
+    graph: &'a Graph,
+    options: &'a Options,
+    system: NodeId,
+    components: &'a [NodeId],
+    horizon: f64,
+    policy: Policy,
+    opportunistic: bool,
+}
+
+impl Trial<'_> {
+    /// The absolute time a unit coming up at `now` with `age` fails.
+    fn failure(
+        &self,
+        parameters: &NodeParameters,
+        now: f64,
+        age: f64,
+        rng: &mut ChaCha8Rng,
+    ) -> f64 {
+        let aged = parameters.cumulative_hazard(age);
+        let target = -(1.0 - rng.gen::<f64>()).ln();
+        now + self.options.profile.time_at_hazard(
+            now,
+            |t| parameters.cumulative_hazard(age + t) - aged,
+            |hazard| parameters.time_at_hazard(hazard + aged) - age,
+            target,
+        )
+    }
+
+    /// Runs one trial, adding the durations of the system outages that ended
+    /// to `durations`.
+    fn run(
+        &self,
+        parameters: &[NodeParameters],
+        rng: &mut ChaCha8Rng,
+        durations: &mut Vec<f64>,
+    ) -> Outcome {
+        let mut units: Vec<Unit> = parameters
+            .iter()
+            .map(|parameters| Unit {
+                parameters: parameters.clone(),
+                up: true,
+                since: 0.0,
+                age: 0.0,
+                until: 0.0,
+            })
+            .collect();
+        for unit in &mut units {
+            unit.until = self.failure(&unit.parameters, 0.0, 0.0, rng);
+        }
+        let mut down = HashSet::new();
+        let (mut time, mut uptime) = (0.0, 0.0);
+        let (mut repairs, mut replacements, mut outages) = (0usize, 0usize, 0usize);
+        let mut working = works(self.graph, self.options, self.system, &down);
+        let mut down_since = 0.0;
+        loop {
+            let Some((i, next)) = units
+                .iter()
+                .map(|unit| unit.until)
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .filter(|(_, next)| *next < self.horizon)
+            else {
+                if working {
+                    uptime += self.horizon - time;
+                }
+                break;
+            };
+            if working {
+                uptime += next - time;
+            }
+            time = next;
+            let unit = &mut units[i];
+            if unit.up {
+                unit.up = false;
+                unit.age += time - unit.since;
+                // Components with no MTTR aren't repaired, so stay down.
+                unit.until = if unit.parameters.mttr > 0.0 {
+                    repairs += 1;
+                    time - unit.parameters.mttr * (1.0 - rng.gen::<f64>()).ln()
+                } else {
+                    f64::INFINITY
+                };
+                down.insert(self.components[i]);
+                let was_working = working;
+                working = works(self.graph, self.options, self.system, &down);
+                if was_working && !working {
+                    outages += 1;
+                    down_since = time;
+                }
+                if !working && self.opportunistic {
+                    for unit in units.iter_mut().filter(|unit| unit.up) {
+                        if unit.age + time - unit.since >= self.policy.threshold {
+                            replacements += 1;
+                            unit.since = time;
+                            unit.age = 0.0;
+                            unit.until = self.failure(&unit.parameters, time, 0.0, rng);
+                        }
+                    }
+                }
+            } else {
+                unit.up = true;
+                unit.since = time;
+                if unit.parameters.repair == Repair::Renewal {
+                    unit.age = 0.0;
+                }
+                unit.until = self.failure(&unit.parameters, time, unit.age, rng);
+                down.remove(&self.components[i]);
+                let was_working = working;
+                working = works(self.graph, self.options, self.system, &down);
+                if !was_working && working {
+                    durations.push(time - down_since);
+                }
+            }
+        }
+        let availability = uptime / self.horizon;
+        Outcome {
+            availability,
+            repairs: repairs as f64,
+            replacements: replacements as f64,
+            cost: repairs as f64 * self.policy.corrective_cost
+                + replacements as f64 * self.policy.preventive_cost
+                + (self.horizon - uptime) * self.policy.downtime_cost,
+            outages: outages as f64,
+        }
+    }
+}
+
+/// Whether `node_id` works with the components in `down` failed. Blocks
+/// treat disabled, excluded and forced inputs as the analytic evaluation
+/// does, with perfect coverage, and empirical curves, human actions,
+/// software, one-shot devices and Petri nets as working.
+fn works(graph: &Graph, options: &Options, node_id: NodeId, down: &HashSet<NodeId>) -> bool {
+    let node = &graph[node_id].user_data;
+    let input = |name: &str| {
+        let Some(source) = graph[node_id]
+            .get_input(name)
+            .ok()
+            .and_then(|input| graph.connection(input))
+            .map(|output| graph[output].node)
+        else {
+            return true;
+        };
+        let data = &graph[source].user_data;
+        let redundant = matches!(
+            node.template,
+            NodeTemplate::Parallel | NodeTemplate::Standby
+        );
+        if data.excluded || (!data.enabled && options.disabled_absent()) {
+            !redundant
+        } else if !data.enabled {
+            true
+        } else {
+            works(graph, options, source, down)
+        }
+    };
+    match node.template {
+        _ if node.forced == Some(Forced::Failed) => false,
+        _ if node.forced == Some(Forced::Working) => true,
+        _ if options.filtered_out(node) => true,
+        NodeTemplate::Component => !down.contains(&node_id),
+        NodeTemplate::Series => input("A") && input("B"),
+        NodeTemplate::Parallel | NodeTemplate::Standby => input("A") || input("B"),
+        NodeTemplate::Voting => {
+            let working = graph[node_id]
+                .inputs
+                .iter()
+                .filter(|(name, _)| input(name))
+                .count();
+            working >= node.votes
+        }
+        NodeTemplate::Empirical
+        | NodeTemplate::Human
+        | NodeTemplate::Software
+        | NodeTemplate::OneShot
+        | NodeTemplate::PetriNet
+        | NodeTemplate::Note => true,
+    }
+}
+
+#[derive(Default)]
+pub struct Maintenance {
+    policy: Policy,
+    result: Option<Result<(Outcome, Outcome), String>>,
+}
+
+impl Maintenance {
+    pub fn run(&mut self, graph: &Graph, state: &GraphState, system: NodeId) {
+        let settings = &state.settings;
+        self.result = Some(
+            simulate_maintenance(
+                graph,
+                &Options::of(state),
+                system,
+                state.mission_time as f64,
+                settings.monte_carlo_samples,
+                settings.seed,
+                self.policy,
+            )
+            .map_err(|err| err.to_string()),
+        );
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, time_unit: TimeUnit) {
+        let symbol = time_unit.symbol();
+        egui::Grid::new("maintenance policy")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr("maintenance-threshold"));
+                ui.add(
+                    egui::DragValue::new(&mut self.policy.threshold)
+                        .speed(10.0)
+                        .clamp_range(0.0..=f64::MAX)
+                        .suffix(format!(" {symbol}")),
+                )
+                .on_hover_text(tr("maintenance-threshold-hint"));
+                ui.end_row();
+                for (key, cost) in [
+                    (
+                        "maintenance-corrective-cost",
+                        &mut self.policy.corrective_cost,
+                    ),
+                    (
+                        "maintenance-preventive-cost",
+                        &mut self.policy.preventive_cost,
+                    ),
+                    ("maintenance-downtime-cost", &mut self.policy.downtime_cost),
+                ] {
+                    ui.label(tr(key));
+                    ui.add(
+                        egui::DragValue::new(cost)
+                            .speed(1.0)
+                            .clamp_range(0.0..=f64::MAX),
+                    );
+                    ui.end_row();
+                }
+            });
+
+        match &self.result {
+            Some(Ok((without, with))) => {
+                egui::Grid::new("maintenance outcome")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.strong(tr("maintenance-without"));
+                        ui.strong(tr("maintenance-with"));
+                        ui.strong(tr("maintenance-difference"));
+                        ui.end_row();
+                        let rows: [Row; 4] = [
+                            ("maintenance-availability", |o| o.availability, 5),
+                            ("maintenance-repairs", |o| o.repairs, 3),
+                            ("maintenance-replacements", |o| o.replacements, 3),
+                            ("maintenance-cost", |o| o.cost, 1),
+                        ];
+                        for (key, value, precision) in rows {
+                            ui.label(tr(key));
+                            ui.label(format!("{:.precision$}", value(without)));
+                            ui.label(format!("{:.precision$}", value(with)));
+                            ui.label(format!("{:+.precision$}", value(with) - value(without)));
+                            ui.end_row();
+                        }
+                    });
+                let saving = without.cost - with.cost;
+                ui.label(tr_with(
+                    if saving > 0.0 {
+                        "maintenance-pays"
+                    } else {
+                        "maintenance-does-not-pay"
+                    },
+                    &[("amount", &format!("{:.1}", saving.abs()))],
+                ));
+            }
+            Some(Err(err)) => {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            None => {}
+        }
+        ui.label(tr("maintenance-hint"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Performance metrics of the session, kept only when the user opts in and
+//! written to a file in the app's data directory, never sent anywhere. They
+//! give an objective picture of how a large model performs, to attach to an
+//! issue about it.
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+
+use crate::app::APP_NAME;
+use crate::audit;
+use crate::nodes::{self, Graph, GraphState};
+
+pub const FILE_NAME: &str = "metrics.jsonl";
+
+/// How often a line of metrics is written.
+const INTERVAL: Duration = Duration::from_secs(60);
+
+/// The most samples of each kind kept between lines, so a stalled write
+/// can't grow them without bound.
+const MAX_SAMPLES: usize = 100_000;
+
+/// Percentiles of a sample of times, in milliseconds.
+#[derive(Serialize)]
+struct Percentiles {
+    p50: f64,
+    p95: f64,
+    p99: f64,
+    max: f64,
+}
+
+impl Percentiles {
+    fn of(samples: &mut [f64]) -> Option<Self> {
+        samples.sort_by(f64::total_cmp);
+        let last = samples.len().checked_sub(1)?;
+        let at = |fraction: f64| samples[(fraction * last as f64).round() as usize];
+        Some(Self {
+            p50: at(0.5),
+            p95: at(0.95),
+            p99: at(0.99),
+            max: samples[last],
+        })
+    }
+}
+
+/// A line of the metrics file.
+#[derive(Serialize)]
+struct Line {
+    time: String,
+    software: String,
+    nodes: usize,
+    connections: usize,
+    components: usize,
+    mission_time: usize,
+    frames: usize,
+    frame_ms: Option<Percentiles>,
+    evaluations: usize,
+    evaluation_ms: Option<Percentiles>,
+}
+
+#[derive(Default)]
+pub struct Metrics {
+    /// How long each frame took to build, in milliseconds.
+    frames: Vec<f64>,
+    /// How long each evaluation of the active node took, in milliseconds.
+    evaluations: Vec<f64>,
+    written: Option<Instant>,
+}
+
+impl Metrics {
+    /// Where the metrics are written, if the app has a data directory.
+    pub fn path() -> Option<PathBuf> {
+        eframe::storage_dir(APP_NAME).map(|dir| dir.join(FILE_NAME))
+    }
+
+    pub fn frame(&mut self, elapsed: Duration) {
+        if self.frames.len() < MAX_SAMPLES {
+            self.frames.push(elapsed.as_secs_f64() * 1e3);
+        }
+    }
+
+    pub fn evaluation(&mut self, elapsed: Duration) {
+        if self.evaluations.len() < MAX_SAMPLES {
+            self.evaluations.push(elapsed.as_secs_f64() * 1e3);
+        }
+    }
+
+    /// Appends a line summing up the samples taken since the last one, every
+    /// minute while `enabled`. Samples are dropped while it isn't.
+    pub fn record(&mut self, enabled: bool, graph: &Graph, state: &GraphState) {
+        if !enabled {
+            self.frames.clear();
+            self.evaluations.clear();
+            self.written = None;
+            return;
+        }
+        let Some(written) = self.written else {
+            self.written = Some(Instant::now());
+            return;
+        };
+        if written.elapsed() < INTERVAL {
+            return;
+        }
+        self.written = Some(Instant::now());
+        let line = Line {
+            time: audit::utc(SystemTime::now()),
+            software: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            nodes: graph.nodes.len(),
+            connections: graph.iter_connections().count(),
+            components: nodes::components(graph).len(),
+            mission_time: state.mission_time,
+            frames: self.frames.len(),
+            frame_ms: Percentiles::of(&mut self.frames),
+            evaluations: self.evaluations.len(),
+            evaluation_ms: Percentiles::of(&mut self.evaluations),
+        };
+        self.frames.clear();
+        self.evaluations.clear();
+        let Some(path) = Self::path() else {
+            return;
+        };
+        let line = serde_json::to_string(&line).expect("lines always serialize") + "\n";
+        let written = fs::create_dir_all(path.parent().unwrap_or(&path)).and_then(|()| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .and_then(|mut file| file.write_all(line.as_bytes()))
+        });
+        if let Err(err) = written {
+            tracing::warn!("{}: {err}", path.display());
+        }
+    }
+}
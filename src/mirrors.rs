@@ -0,0 +1,103 @@
+//! Components that mirror another's parameters, so identical installed units
+//! share one set of them: editing the master updates every mirror, and the
+//! copies can't drift apart.
+use std::collections::HashSet;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph};
+
+/// The component whose parameters `node` ends up with, following mirrors of
+/// mirrors. `None` if the chain leads to a missing node or back on itself.
+pub fn master(graph: &Graph, node: NodeId) -> Option<NodeId> {
+    let mut seen = HashSet::from([node]);
+    let mut current = graph.nodes.get(node)?.user_data.mirror?;
+    loop {
+        if !seen.insert(current) {
+            return None;
+        }
+        match graph.nodes.get(current)?.user_data.mirror {
+            Some(next) => current = next,
+            None => return Some(current),
+        }
+    }
+}
+
+/// Whether `node` mirrors `master`, directly or through other mirrors.
+fn mirrors(graph: &Graph, mut node: NodeId, master: NodeId) -> bool {
+    let mut seen = HashSet::new();
+    while let Some(next) = graph.nodes.get(node).and_then(|n| n.user_data.mirror) {
+        if next == master {
+            return true;
+        }
+        if !seen.insert(next) {
+            return false;
+        }
+        node = next;
+    }
+    false
+}
+
+/// Copies each master's parameters to its mirrors. Mirrors of deleted nodes,
+/// or caught in a loop, are unlinked and keep the parameters they had.
+pub fn apply(graph: &mut Graph) {
+    for node in nodes::components(graph) {
+        if graph[node].user_data.mirror.is_none() {
+            continue;
+        }
+        let parameters =
+            master(graph, node).and_then(|master| nodes::component_parameters(graph, master));
+        let Some(parameters) = parameters else {
+            graph[node].user_data.mirror = None;
+            continue;
+        };
+        if let Some(mirror) = nodes::component_parameters_mut(graph, node) {
+            if *mirror != parameters {
+                *mirror = parameters;
+            }
+        }
+    }
+}
+
+/// The menu choosing which component `node` mirrors, if any. Components
+/// that mirror `node` themselves aren't offered.
+pub fn menu(ui: &mut egui::Ui, graph: &Graph, node: NodeId) -> Option<Option<NodeId>> {
+    let mirror = graph[node].user_data.mirror;
+    let text = match mirror.and_then(|master| graph.nodes.get(master)) {
+        Some(master) => tr_with("mirror-of", &[("master", &master.label)]),
+        None => tr("mirror-none"),
+    };
+    let mut chosen = None;
+    ui.menu_button(text, |ui| {
+        if ui
+            .selectable_label(mirror.is_none(), tr("mirror-none"))
+            .clicked()
+        {
+            chosen = Some(None);
+        }
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for other in nodes::components(graph) {
+                    if other == node || mirrors(graph, other, node) {
+                        continue;
+                    }
+                    if ui
+                        .selectable_label(mirror == Some(other), &graph[other].label)
+                        .clicked()
+                    {
+                        chosen = Some(Some(other));
+                    }
+                }
+            });
+        if chosen.is_some() {
+            ui.close_menu();
+        }
+    })
+    .response
+    .on_hover_text(tr("mirror-hint"));
+    chosen
+}
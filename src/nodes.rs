@@ -0,0 +1,2639 @@
+//! The reliability block diagram node types plugged into the graph editor.
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+
+use eframe::egui;
+use egui_node_graph2::{
+    AnyParameterId, CategoryTrait, DataTypeTrait, GraphEditorState, InputParamKind, NodeDataTrait,
+    NodeId, NodeResponse, NodeTemplateIter, NodeTemplateTrait, OutputId, UserResponseTrait,
+    WidgetValueTrait,
+};
+use serde::{Deserialize, Serialize};
+use statrs::function::gamma::gamma;
+
+use crate::allocation::AllocationMethod;
+use crate::budget;
+use crate::card;
+use crate::eval;
+use crate::frames::GroupFrame;
+use crate::i18n::{tr, tr_with};
+use crate::mirrors;
+use crate::petri::PetriNet;
+use crate::profile::MissionProfile;
+use crate::project::Panels;
+use crate::regression::LockedResults;
+use crate::report;
+use crate::requirement::Requirement;
+use crate::settings::{Settings, WireStyle};
+use crate::shocks::Shock;
+use crate::snippet::Snippet;
+use crate::software::SoftwareGrowth;
+use crate::tags::{self, ColorTag};
+use crate::variables::{self, Bound, Variable};
+use crate::wires;
+
+/// The parameters of a Weibull distributed component.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeParameters {
+    pub shape: f64,
+    pub scale: f64,
+    /// The form the scale is typed in; only `scale` itself is stored.
+    pub scale_entry: ScaleEntry,
+    /// Further failure modes competing with the primary one above; the unit
+    /// fails with whichever comes first, so their hazards add up.
+    pub modes: Vec<FailureMode>,
+    /// Number of time steps the reliability curve is evaluated over.
+    pub time_steps: usize,
+    /// The fraction of calendar time the component operates, and so ages.
+    pub duty_cycle: f64,
+    /// When the component operates within a repeating period, replacing the
+    /// duty cycle for equipment that runs at set times.
+    pub schedule: Option<Schedule>,
+    /// π_E, multiplying the hazard rate for the operating environment.
+    pub environment_factor: f64,
+    /// Mean time to repair after a failure; zero for components that aren't
+    /// repaired.
+    pub mttr: f64,
+    /// What a repair restores, for components with an MTTR.
+    pub repair: Repair,
+    /// Periodic inspections for components whose failures stay hidden until
+    /// one finds them, like relief valves and other protective devices.
+    pub inspection: Option<Inspection>,
+    /// Operating temperature in °C, used by `derating`.
+    pub temperature: f64,
+    pub derating: Derating,
+    /// How well the shape and scale are known, when they came from data.
+    pub uncertainty: Option<Uncertainty>,
+    /// Parameters given as expressions of the project's variables, which
+    /// set them every frame.
+    pub expressions: BTreeMap<Bound, String>,
+}
+
+/// A window of operation repeating every `period`, so a pump run for two
+/// hours a day ages two hours for every twenty-four on the mission clock.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub period: f64,
+    /// When the window opens, from the start of each period.
+    pub start: f64,
+    pub operating: f64,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            period: 24.0,
+            start: 0.0,
+            operating: 2.0,
+        }
+    }
+}
+
+impl Schedule {
+    /// The operating time accumulated by calendar time `t`.
+    pub fn operating_time(&self, t: f64) -> f64 {
+        let cycles = (t / self.period).floor();
+        let within = t - cycles * self.period - self.start;
+        cycles * self.operating + within.clamp(0.0, self.operating)
+    }
+
+    /// The earliest calendar time by which `operating` has been accumulated.
+    pub fn calendar_time(&self, operating: f64) -> f64 {
+        if operating <= 0.0 {
+            return 0.0;
+        }
+        // The window that ends the accumulation, counted from zero.
+        let cycles = (operating / self.operating).ceil() - 1.0;
+        cycles * self.period + self.start + (operating - cycles * self.operating)
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                variables::drag_value(&mut self.operating)
+                    .speed(0.1)
+                    .clamp_range(0.01..=self.period),
+            )
+            .on_hover_text(tr("schedule-operating"));
+            ui.label(tr("schedule-every"));
+            ui.add(
+                variables::drag_value(&mut self.period)
+                    .speed(0.1)
+                    .clamp_range(0.01..=f64::MAX),
+            )
+            .on_hover_text(tr("schedule-period"));
+            ui.label(tr("schedule-from"));
+            ui.add(
+                variables::drag_value(&mut self.start)
+                    .speed(0.1)
+                    .clamp_range(0.0..=f64::MAX),
+            )
+            .on_hover_text(tr("schedule-start"));
+        });
+        self.operating = self.operating.min(self.period);
+        self.start = self.start.min(self.period - self.operating);
+    }
+}
+
+/// Inspections every `interval`, each finding a hidden failure with
+/// probability `effectiveness`. A failure found is repaired as good as new.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Inspection {
+    pub interval: f64,
+    pub effectiveness: f64,
+}
+
+impl Default for Inspection {
+    fn default() -> Self {
+        Self {
+            interval: 730.0,
+            effectiveness: 1.0,
+        }
+    }
+}
+
+impl Inspection {
+    /// The mean time a failure stays hidden: half an interval until the
+    /// next inspection, and a whole one for each inspection that misses it.
+    pub fn mean_hidden_time(&self) -> f64 {
+        self.interval * (1.0 / self.effectiveness - 0.5)
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("inspection-every"));
+            ui.add(
+                variables::drag_value(&mut self.interval)
+                    .speed(1.0)
+                    .clamp_range(1.0..=f64::MAX),
+            )
+            .on_hover_text(tr("inspection-interval"));
+            ui.add(
+                variables::drag_value(&mut self.effectiveness)
+                    .speed(0.01)
+                    .clamp_range(0.01..=1.0),
+            )
+            .on_hover_text(tr("inspection-effectiveness"));
+        });
+    }
+}
+
+/// 90% intervals of a component's shape and scale, from a fit or Bayesian
+/// updating, which the reliability plot turns into a band.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Uncertainty {
+    pub shape: (f64, f64),
+    pub scale: (f64, f64),
+}
+
+/// An independent Weibull failure mode of a component, sharing its duty
+/// cycle, environment and derating.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FailureMode {
+    pub name: String,
+    pub shape: f64,
+    pub scale: f64,
+}
+
+/// The quantity entered alongside the shape β to fix a Weibull distribution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScaleEntry {
+    /// The scale η itself, the age by which 63.2% of units have failed.
+    #[default]
+    Scale,
+    /// The mean time to failure, η Γ(1 + 1/β).
+    Mttf,
+    /// The characteristic rate 1/η.
+    Rate,
+}
+
+impl ScaleEntry {
+    pub const ALL: [ScaleEntry; 3] = [ScaleEntry::Scale, ScaleEntry::Mttf, ScaleEntry::Rate];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            ScaleEntry::Scale => "param-scale",
+            ScaleEntry::Mttf => "param-entry-mttf",
+            ScaleEntry::Rate => "param-entry-rate",
+        })
+    }
+
+    /// The entered quantity for a distribution with the given shape and scale.
+    pub fn from_scale(self, shape: f64, scale: f64) -> f64 {
+        match self {
+            ScaleEntry::Scale => scale,
+            ScaleEntry::Mttf => scale * gamma(1.0 + 1.0 / shape),
+            ScaleEntry::Rate => 1.0 / scale,
+        }
+    }
+
+    /// How the entered quantity was converted to the scale.
+    fn hint(self) -> String {
+        tr(match self {
+            ScaleEntry::Scale => "param-scale-hint",
+            ScaleEntry::Mttf => "param-entry-mttf-hint",
+            ScaleEntry::Rate => "param-entry-rate-hint",
+        })
+    }
+
+    /// The scale that gives the entered `value` with the given shape.
+    pub fn to_scale(self, shape: f64, value: f64) -> f64 {
+        match self {
+            ScaleEntry::Scale => value,
+            ScaleEntry::Mttf => value / gamma(1.0 + 1.0 / shape),
+            ScaleEntry::Rate => 1.0 / value,
+        }
+    }
+}
+
+/// The state a repaired unit returns to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Repair {
+    /// As good as new: the unit is replaced, and its age starts over.
+    #[default]
+    Renewal,
+    /// As bad as old: the unit is patched up at the age it failed, so
+    /// failures follow a power-law non-homogeneous Poisson process.
+    Minimal,
+}
+
+impl Repair {
+    pub fn name(self) -> String {
+        match self {
+            Repair::Renewal => tr("repair-renewal"),
+            Repair::Minimal => tr("repair-minimal"),
+        }
+    }
+}
+
+/// How the operating temperature scales a component's hazard rate.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Derating {
+    #[default]
+    None,
+    /// The Arrhenius model, relative to the temperature the scale was
+    /// specified at.
+    Arrhenius {
+        /// Activation energy in eV.
+        activation_energy: f64,
+        /// Reference temperature in °C.
+        reference: f64,
+    },
+    /// Hazard multipliers at given temperatures, interpolated linearly and
+    /// held constant beyond the first and last point.
+    Curve(Vec<[f64; 2]>),
+}
+
+/// Boltzmann's constant in eV/K.
+const BOLTZMANN: f64 = 8.617_333e-5;
+const ZERO_CELSIUS: f64 = 273.15;
+
+impl Derating {
+    pub fn name(&self) -> String {
+        match self {
+            Derating::None => tr("derating-none"),
+            Derating::Arrhenius { .. } => tr("derating-arrhenius"),
+            Derating::Curve(_) => tr("derating-curve"),
+        }
+    }
+
+    /// The hazard multiplier at `temperature` °C.
+    pub fn factor(&self, temperature: f64) -> f64 {
+        match self {
+            Derating::None => 1.0,
+            Derating::Arrhenius {
+                activation_energy,
+                reference,
+            } => (activation_energy / BOLTZMANN
+                * (1.0 / (reference + ZERO_CELSIUS) - 1.0 / (temperature + ZERO_CELSIUS)))
+                .exp(),
+            Derating::Curve(points) => {
+                let Some(first) = points.first() else {
+                    return 1.0;
+                };
+                if temperature <= first[0] {
+                    return first[1];
+                }
+                for pair in points.windows(2) {
+                    let ([t0, f0], [t1, f1]) = (pair[0], pair[1]);
+                    if temperature <= t1 {
+                        return f0 + (f1 - f0) * (temperature - t0) / (t1 - t0);
+                    }
+                }
+                points[points.len() - 1][1]
+            }
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_id_source("derating")
+            .selected_text(self.name())
+            .show_ui(ui, |ui| {
+                let choices = [
+                    Derating::None,
+                    Derating::Arrhenius {
+                        activation_energy: 0.7,
+                        reference: 25.0,
+                    },
+                    Derating::Curve(vec![[25.0, 1.0], [85.0, 4.0]]),
+                ];
+                for choice in choices {
+                    let selected = std::mem::discriminant(self) == std::mem::discriminant(&choice);
+                    let name = choice.name();
+                    if ui.selectable_label(selected, name).clicked() && !selected {
+                        *self = choice;
+                    }
+                }
+            });
+        match self {
+            Derating::None => {}
+            Derating::Arrhenius {
+                activation_energy,
+                reference,
+            } => {
+                egui::Grid::new("arrhenius").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("derating-activation-energy"));
+                    ui.add(
+                        variables::drag_value(activation_energy)
+                            .speed(0.01)
+                            .clamp_range(0.0..=5.0)
+                            .suffix(" eV"),
+                    );
+                    ui.end_row();
+                    ui.label(tr("derating-reference"));
+                    ui.add(variables::drag_value(reference).suffix(" °C"));
+                    ui.end_row();
+                });
+            }
+            Derating::Curve(points) => {
+                let mut remove = None;
+                egui::Grid::new("derating curve")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for (i, [temperature, factor]) in points.iter_mut().enumerate() {
+                            ui.add(variables::drag_value(temperature).suffix(" °C"));
+                            ui.add(
+                                variables::drag_value(factor)
+                                    .speed(0.01)
+                                    .clamp_range(0.0..=1_000.0)
+                                    .suffix("×"),
+                            );
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = remove {
+                    points.remove(i);
+                }
+                if ui.small_button(tr("derating-add-point")).clicked() {
+                    let next = points.last().map_or([25.0, 1.0], |[t, f]| [t + 10.0, *f]);
+                    points.push(next);
+                }
+                points.sort_by(|a, b| a[0].total_cmp(&b[0]));
+            }
+        }
+    }
+}
+
+impl Default for NodeParameters {
+    fn default() -> Self {
+        Self {
+            shape: 1.5,
+            scale: 10_000.0,
+            scale_entry: ScaleEntry::default(),
+            modes: Vec::new(),
+            time_steps: 8_760,
+            duty_cycle: 1.0,
+            schedule: None,
+            environment_factor: 1.0,
+            mttr: 0.0,
+            repair: Repair::default(),
+            inspection: None,
+            temperature: 25.0,
+            derating: Derating::default(),
+            uncertainty: None,
+            expressions: BTreeMap::new(),
+        }
+    }
+}
+
+/// Named environment factors, after the MIL-HDBK-217F microcircuit π_E.
+const ENVIRONMENTS: [(&str, f64); 7] = [
+    ("env-ground-benign", 0.5),
+    ("env-ground-fixed", 2.0),
+    ("env-ground-mobile", 4.0),
+    ("env-naval-sheltered", 4.0),
+    ("env-airborne-inhabited", 4.0),
+    ("env-airborne-uninhabited", 6.0),
+    ("env-space-flight", 0.5),
+];
+
+/// R = e^-40 is as good as zero when integrating for the MTTF.
+const NEGLIGIBLE_HAZARD: f64 = 40.0;
+const MTTF_STEPS: usize = 2_000;
+const BISECTIONS: usize = 60;
+
+/// A numeric component parameter that analyses can vary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Parameter {
+    Shape,
+    #[default]
+    Scale,
+}
+
+impl Parameter {
+    pub const ALL: [Parameter; 2] = [Parameter::Shape, Parameter::Scale];
+
+    pub fn name(self) -> String {
+        match self {
+            Parameter::Shape => tr("param-shape"),
+            Parameter::Scale => tr("param-scale"),
+        }
+    }
+
+    pub fn combo(&mut self, ui: &mut egui::Ui, id_source: &str) {
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(self.name())
+            .show_ui(ui, |ui| {
+                for parameter in Parameter::ALL {
+                    ui.selectable_value(self, parameter, parameter.name());
+                }
+            });
+    }
+}
+
+impl NodeParameters {
+    pub fn get(&self, parameter: Parameter) -> f64 {
+        match parameter {
+            Parameter::Shape => self.shape,
+            Parameter::Scale => self.scale,
+        }
+    }
+
+    pub fn set(&mut self, parameter: Parameter, value: f64) {
+        match parameter {
+            Parameter::Shape => self.shape = value,
+            Parameter::Scale => self.scale = value,
+        }
+    }
+
+    /// The Weibull scale with the environment and temperature factors folded
+    /// in: scaling the hazard by π divides the scale by π^(1/β).
+    pub fn effective_scale(&self) -> f64 {
+        self.mode_effective_scale(self.shape, self.scale)
+    }
+
+    pub fn mode_effective_scale(&self, shape: f64, scale: f64) -> f64 {
+        let factor = self.environment_factor * self.derating.factor(self.temperature);
+        scale / factor.powf(1.0 / shape)
+    }
+
+    /// The shape and scale of every failure mode, the primary one first.
+    pub fn all_modes(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        std::iter::once((self.shape, self.scale))
+            .chain(self.modes.iter().map(|mode| (mode.shape, mode.scale)))
+    }
+
+    /// The operating time by calendar time `t`, from the schedule or else
+    /// the duty cycle.
+    pub fn operating_time(&self, t: f64) -> f64 {
+        match self.schedule {
+            Some(schedule) => schedule.operating_time(t),
+            None => self.duty_cycle * t,
+        }
+    }
+
+    /// The inverse of `operating_time`.
+    pub fn calendar_time(&self, operating: f64) -> f64 {
+        match self.schedule {
+            Some(schedule) => schedule.calendar_time(operating),
+            None => operating / self.duty_cycle,
+        }
+    }
+
+    /// H₀ of one failure mode, (τ(t) / η')^β at calendar time t, with τ(t)
+    /// the operating time and the effective scale η'.
+    pub fn mode_cumulative_hazard(&self, shape: f64, scale: f64, t: f64) -> f64 {
+        (self.operating_time(t) / self.mode_effective_scale(shape, scale)).powf(shape)
+    }
+
+    /// H₀(t), the sum over all failure modes.
+    pub fn cumulative_hazard(&self, t: f64) -> f64 {
+        self.all_modes()
+            .map(|(shape, scale)| self.mode_cumulative_hazard(shape, scale, t))
+            .sum()
+    }
+
+    /// H₀ at each of the time steps `times`, as `cumulative_hazard` gives it,
+    /// but with each mode's constants worked out once and a plain loop over
+    /// the curve that the compiler can vectorize.
+    pub fn cumulative_hazards(&self, times: Range<usize>) -> Vec<f64> {
+        if self.schedule.is_some() {
+            return times.map(|t| self.cumulative_hazard(t as f64)).collect();
+        }
+        let mut hazards = vec![0.0; times.len()];
+        for (shape, scale) in self.all_modes() {
+            let rate = self.duty_cycle / self.mode_effective_scale(shape, scale);
+            if shape == 1.0 {
+                for (t, hazard) in times.clone().zip(&mut hazards) {
+                    *hazard += rate * t as f64;
+                }
+            } else {
+                // (r·t)^β = r^β · t^β
+                let coefficient = rate.powf(shape);
+                for (t, hazard) in times.clone().zip(&mut hazards) {
+                    *hazard += coefficient * (t as f64).powf(shape);
+                }
+            }
+        }
+        hazards
+    }
+
+    /// The mean calendar time to failure, ignoring the mission profile.
+    /// Competing modes have no closed form, so ∫R(t)dt is integrated up to
+    /// where R(t) is negligible.
+    pub fn mean_time_to_failure(&self) -> f64 {
+        if self.modes.is_empty() && self.schedule.is_none() {
+            return self.effective_scale() / self.duty_cycle * gamma(1.0 + 1.0 / self.shape);
+        }
+        let end = self.time_at_hazard(NEGLIGIBLE_HAZARD);
+        let dt = end / MTTF_STEPS as f64;
+        (0..MTTF_STEPS)
+            .map(|i| {
+                let r = |t: f64| (-self.cumulative_hazard(t)).exp();
+                0.5 * (r(i as f64 * dt) + r((i + 1) as f64 * dt)) * dt
+            })
+            .sum()
+    }
+
+    /// The calendar time at which H₀ reaches `hazard`. With competing modes it
+    /// is found by bisection, below the earliest time any single mode gets
+    /// there on its own.
+    pub fn time_at_hazard(&self, hazard: f64) -> f64 {
+        let mode_time = |shape: f64, scale: f64| {
+            self.calendar_time(self.mode_effective_scale(shape, scale) * hazard.powf(1.0 / shape))
+        };
+        let earliest = self
+            .all_modes()
+            .map(|(shape, scale)| mode_time(shape, scale))
+            .fold(f64::INFINITY, f64::min);
+        if self.modes.is_empty() || !earliest.is_finite() {
+            return earliest;
+        }
+        let (mut lo, mut hi) = (0.0, earliest);
+        for _ in 0..BISECTIONS {
+            let mid = 0.5 * (lo + hi);
+            if self.cumulative_hazard(mid) < hazard {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5 * (lo + hi)
+    }
+
+    /// Whether `bound` is set by an expression, and so not edited directly.
+    pub fn is_bound(&self, bound: Bound) -> bool {
+        self.expressions.contains_key(&bound)
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("parameters").num_columns(2).show(ui, |ui| {
+            // The shape and the entered quantity are what the user fixes, so
+            // a new shape keeps, say, the MTTF and moves η instead.
+            let mut value = self.scale_entry.from_scale(self.shape, self.scale);
+            ui.label(tr("param-shape"));
+            let shape = ui.add_enabled(
+                !self.is_bound(Bound::Shape),
+                variables::drag_value(&mut self.shape)
+                    .speed(0.01)
+                    .clamp_range(0.01..=f64::MAX),
+            );
+            ui.end_row();
+
+            egui::ComboBox::from_id_source("scale entry")
+                .selected_text(self.scale_entry.name())
+                .show_ui(ui, |ui| {
+                    for entry in ScaleEntry::ALL {
+                        if ui
+                            .selectable_value(&mut self.scale_entry, entry, entry.name())
+                            .clicked()
+                        {
+                            value = entry.from_scale(self.shape, self.scale);
+                        }
+                    }
+                });
+            let speed = value * 0.01;
+            let scale_free = !self.is_bound(Bound::Scale);
+            let edited = match self.scale_entry {
+                ScaleEntry::Scale | ScaleEntry::Mttf => ui.add_enabled(
+                    scale_free,
+                    variables::drag_value(&mut value)
+                        .speed(10.0)
+                        .clamp_range(0.01..=f64::MAX),
+                ),
+                ScaleEntry::Rate => ui.add_enabled(
+                    scale_free,
+                    variables::drag_value(&mut value)
+                        .speed(speed)
+                        .clamp_range(1e-12..=100.0)
+                        .custom_formatter(|n, _| format!("{n:.3e}")),
+                ),
+            };
+            if shape.changed() || edited.changed() {
+                self.scale = self.scale_entry.to_scale(self.shape, value);
+            }
+            ui.end_row();
+
+            // Show what the other quantities come to, so it's clear which
+            // distribution the entered pair stands for.
+            if self.scale_entry != ScaleEntry::Scale {
+                ui.label(tr("param-scale"));
+                ui.label(format!("{:.1}", self.scale))
+                    .on_hover_text(self.scale_entry.hint());
+                ui.end_row();
+            }
+            if self.scale_entry != ScaleEntry::Mttf {
+                ui.label(tr("param-entry-mttf"));
+                ui.label(format!(
+                    "{:.1}",
+                    ScaleEntry::Mttf.from_scale(self.shape, self.scale)
+                ))
+                .on_hover_text(ScaleEntry::Mttf.hint());
+                ui.end_row();
+            }
+            if let Some(uncertainty) = self.uncertainty {
+                ui.label(tr("param-uncertainty"));
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "β {:.3}–{:.3}, η {:.1}–{:.1}",
+                        uncertainty.shape.0,
+                        uncertainty.shape.1,
+                        uncertainty.scale.0,
+                        uncertainty.scale.1
+                    ));
+                    if ui
+                        .small_button("✖")
+                        .on_hover_text(tr("param-uncertainty-clear"))
+                        .clicked()
+                    {
+                        self.uncertainty = None;
+                    }
+                });
+                ui.end_row();
+            }
+
+            ui.label(tr("param-time-steps"));
+            ui.add_enabled(
+                !self.is_bound(Bound::TimeSteps),
+                variables::drag_value(&mut self.time_steps).clamp_range(1..=1_000_000),
+            );
+            ui.end_row();
+
+            ui.label(tr("param-duty-cycle"));
+            ui.add_enabled(
+                self.schedule.is_none() && !self.is_bound(Bound::DutyCycle),
+                variables::drag_value(&mut self.duty_cycle)
+                    .speed(0.01)
+                    .clamp_range(0.01..=1.0),
+            );
+            ui.end_row();
+
+            let mut scheduled = self.schedule.is_some();
+            if ui
+                .checkbox(&mut scheduled, tr("param-schedule"))
+                .on_hover_text(tr("param-schedule-hint"))
+                .changed()
+            {
+                self.schedule = scheduled.then(Schedule::default);
+            }
+            match &mut self.schedule {
+                Some(schedule) => schedule.ui(ui),
+                None => {
+                    ui.label("");
+                }
+            }
+            ui.end_row();
+
+            ui.label(tr("param-mttr"));
+            ui.add_enabled(
+                !self.is_bound(Bound::Mttr),
+                variables::drag_value(&mut self.mttr)
+                    .speed(1.0)
+                    .clamp_range(0.0..=f64::MAX),
+            )
+            .on_hover_text(tr("param-mttr-hint"));
+            ui.end_row();
+
+            ui.label(tr("param-repair"));
+            ui.add_enabled_ui(self.mttr > 0.0, |ui| {
+                egui::ComboBox::from_id_source("repair")
+                    .selected_text(self.repair.name())
+                    .show_ui(ui, |ui| {
+                        for repair in [Repair::Renewal, Repair::Minimal] {
+                            ui.selectable_value(&mut self.repair, repair, repair.name());
+                        }
+                    });
+            });
+            ui.end_row();
+
+            let mut hidden = self.inspection.is_some();
+            if ui
+                .checkbox(&mut hidden, tr("param-hidden-failures"))
+                .on_hover_text(tr("param-hidden-failures-hint"))
+                .changed()
+            {
+                self.inspection = hidden.then(Inspection::default);
+            }
+            match &mut self.inspection {
+                Some(inspection) => inspection.ui(ui),
+                None => {
+                    ui.label("");
+                }
+            }
+            ui.end_row();
+
+            ui.label(tr("param-environment-factor"));
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!self.is_bound(Bound::EnvironmentFactor), |ui| {
+                    ui.add(
+                        variables::drag_value(&mut self.environment_factor)
+                            .speed(0.01)
+                            .clamp_range(0.01..=100.0),
+                    );
+                    ui.menu_button("▾", |ui| {
+                        for (name, factor) in ENVIRONMENTS {
+                            if ui.button(format!("{} ({factor})", tr(name))).clicked() {
+                                self.environment_factor = factor;
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                });
+            });
+            ui.end_row();
+        });
+        ui.collapsing(tr("param-derating"), |ui| {
+            self.derating.ui(ui);
+            if self.derating != Derating::None {
+                ui.horizontal(|ui| {
+                    ui.label(tr("param-temperature"));
+                    ui.add_enabled(
+                        !self.is_bound(Bound::Temperature),
+                        variables::drag_value(&mut self.temperature).suffix(" °C"),
+                    );
+                    ui.label(format!("→ {:.3}×", self.derating.factor(self.temperature)));
+                });
+            }
+        });
+    }
+
+    /// The failure modes, with each mode's share of the probability of having
+    /// failed by the mission time.
+    pub fn modes_ui(&mut self, ui: &mut egui::Ui, state: &GraphState) {
+        let shares = eval::mode_failure_probabilities(self, &state.mission_profile)
+            .ok()
+            .map(|curves| {
+                let time = state.mission_time.min(self.time_steps);
+                let failed: Vec<f64> = curves.iter().map(|curve| curve[time]).collect();
+                let total: f64 = failed.iter().sum();
+                failed
+                    .into_iter()
+                    .map(|f| if total > 0.0 { f / total } else { 0.0 })
+                    .collect::<Vec<f64>>()
+            })
+            .unwrap_or_default();
+        let share = |i: usize| {
+            shares
+                .get(i)
+                .map_or_else(|| "—".to_owned(), |share| format!("{:.1} %", 100.0 * share))
+        };
+
+        let mut remove = None;
+        egui::Grid::new("failure modes")
+            .num_columns(5)
+            .show(ui, |ui| {
+                ui.strong(tr("mode-name"));
+                ui.strong(tr("param-shape"));
+                ui.strong(tr("param-scale"));
+                ui.strong(tr("mode-share"))
+                    .on_hover_text(tr("mode-share-hint"));
+                ui.end_row();
+                ui.label(tr("mode-primary"));
+                ui.label(format!("{:.2}", self.shape));
+                ui.label(format!("{:.1}", self.scale));
+                ui.label(share(0));
+                ui.end_row();
+                for (i, mode) in self.modes.iter_mut().enumerate() {
+                    ui.add(egui::TextEdit::singleline(&mut mode.name).desired_width(80.0));
+                    ui.add(
+                        variables::drag_value(&mut mode.shape)
+                            .speed(0.01)
+                            .clamp_range(0.01..=f64::MAX),
+                    );
+                    ui.add(
+                        variables::drag_value(&mut mode.scale)
+                            .speed(10.0)
+                            .clamp_range(0.01..=f64::MAX),
+                    );
+                    ui.label(share(i + 1));
+                    if ui.small_button("🗑").clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(i) = remove {
+            self.modes.remove(i);
+        }
+        if ui.small_button(tr("mode-add")).clicked() {
+            self.modes.push(FailureMode {
+                name: format!("{} {}", tr("mode-name"), self.modes.len() + 2),
+                shape: 1.0,
+                scale: self.scale,
+            });
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NodeData {
+    pub template: NodeTemplate,
+    #[serde(default)]
+    pub tag: Option<ColorTag>,
+    /// How hard the component is to make more reliable, from 1 (easy) to
+    /// 10 (hard), used by feasibility-weighted allocation.
+    #[serde(default = "default_feasibility")]
+    pub feasibility: f64,
+    /// Fault injection: evaluates the node as failed or working regardless
+    /// of its inputs and parameters.
+    #[serde(default)]
+    pub forced: Option<Forced>,
+    /// Temporarily taken out of the blocks it feeds, for what-if analysis.
+    #[serde(skip)]
+    pub excluded: bool,
+    /// Kept in place when dragged, so a finished layout isn't disturbed.
+    #[serde(default)]
+    pub locked: bool,
+    /// Cleared to leave the node out of evaluation, for staged builds of a
+    /// system; see [`crate::settings::DisabledNodes`].
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// What a failure of the component costs, for filtering the system
+    /// curve down to one class of failures.
+    #[serde(default)]
+    pub severity: Severity,
+    /// For parallel and standby blocks, the probability that a unit's
+    /// failure is detected and isolated; one that isn't brings the block
+    /// down despite the redundancy.
+    #[serde(default = "default_coverage")]
+    pub coverage: f64,
+    /// For voting blocks, how many members must work.
+    #[serde(default = "default_votes")]
+    pub votes: usize,
+    /// The reliability budgeted to the node at the requirement time, adopted
+    /// from an allocation.
+    #[serde(default)]
+    pub target: Option<f64>,
+    /// The library entry the node was made from, which its values can be
+    /// updated from.
+    #[serde(default)]
+    pub library: Option<String>,
+    /// The component whose parameters this one's follow, for identical
+    /// installed units; see [`crate::mirrors`].
+    #[serde(default)]
+    pub mirror: Option<NodeId>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Forced {
+    Failed,
+    Working,
+}
+
+impl Forced {
+    /// The reliability, availability or probability of a forced node.
+    pub fn value(self) -> f64 {
+        match self {
+            Forced::Failed => 0.0,
+            Forced::Working => 1.0,
+        }
+    }
+}
+
+/// How serious a component's failure is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    SafetyCritical,
+    #[default]
+    Mission,
+    Convenience,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 3] = [
+        Severity::SafetyCritical,
+        Severity::Mission,
+        Severity::Convenience,
+    ];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Severity::SafetyCritical => "severity-safety-critical",
+            Severity::Mission => "severity-mission",
+            Severity::Convenience => "severity-convenience",
+        })
+    }
+}
+
+/// How a standby spare ages while it waits to take over.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Dormancy {
+    /// Running alongside the primary, aging as fast as it.
+    Hot,
+    /// Powered but unloaded, aging at this fraction of the active rate.
+    Warm(f64),
+    /// Switched off, not aging at all.
+    Cold,
+}
+
+impl Default for Dormancy {
+    fn default() -> Self {
+        Dormancy::Warm(0.1)
+    }
+}
+
+impl Dormancy {
+    /// How fast the spare ages while dormant, relative to when active.
+    pub fn aging(self) -> f64 {
+        match self {
+            Dormancy::Hot => 1.0,
+            Dormancy::Warm(factor) => factor,
+            Dormancy::Cold => 0.0,
+        }
+    }
+
+    fn name(self) -> String {
+        tr(match self {
+            Dormancy::Hot => "dormancy-hot",
+            Dormancy::Warm(_) => "dormancy-warm",
+            Dormancy::Cold => "dormancy-cold",
+        })
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("dormancy-title"));
+            egui::ComboBox::from_id_source("dormancy")
+                .selected_text(self.name())
+                .show_ui(ui, |ui| {
+                    let warm = match *self {
+                        Dormancy::Warm(factor) => Dormancy::Warm(factor),
+                        _ => Dormancy::default(),
+                    };
+                    for mode in [Dormancy::Hot, warm, Dormancy::Cold] {
+                        ui.selectable_value(self, mode, mode.name());
+                    }
+                })
+                .response
+                .on_hover_text(tr("dormancy-hint"));
+            if let Dormancy::Warm(factor) = self {
+                ui.add(
+                    variables::drag_value(factor)
+                        .speed(0.01)
+                        .clamp_range(0.0..=1.0)
+                        .prefix("× "),
+                )
+                .on_hover_text(tr("dormancy-factor"));
+            }
+        });
+    }
+}
+
+/// A performance-shaping factor of SPAR-H, which scales the nominal human
+/// error probability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Psf {
+    AvailableTime,
+    Stress,
+    Complexity,
+    Experience,
+    Procedures,
+    Ergonomics,
+    Fitness,
+    WorkProcesses,
+}
+
+impl Psf {
+    pub const ALL: [Psf; 8] = [
+        Psf::AvailableTime,
+        Psf::Stress,
+        Psf::Complexity,
+        Psf::Experience,
+        Psf::Procedures,
+        Psf::Ergonomics,
+        Psf::Fitness,
+        Psf::WorkProcesses,
+    ];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Psf::AvailableTime => "psf-available-time",
+            Psf::Stress => "psf-stress",
+            Psf::Complexity => "psf-complexity",
+            Psf::Experience => "psf-experience",
+            Psf::Procedures => "psf-procedures",
+            Psf::Ergonomics => "psf-ergonomics",
+            Psf::Fitness => "psf-fitness",
+            Psf::WorkProcesses => "psf-work-processes",
+        })
+    }
+}
+
+/// A human action, which fails when called for with its human error
+/// probability (HEP). The HEP doesn't change with time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HumanAction {
+    /// The nominal HEP, before any performance-shaping factors.
+    pub hep: f64,
+    /// Multipliers of the nominal HEP; factors left out are nominal (1).
+    #[serde(default)]
+    pub psfs: Vec<(Psf, f64)>,
+    pub time_steps: usize,
+}
+
+impl Default for HumanAction {
+    fn default() -> Self {
+        Self {
+            hep: 1e-3,
+            psfs: Vec::new(),
+            time_steps: NodeParameters::default().time_steps,
+        }
+    }
+}
+
+impl HumanAction {
+    pub fn factor(&self, psf: Psf) -> f64 {
+        self.psfs
+            .iter()
+            .find(|(factor, _)| *factor == psf)
+            .map_or(1.0, |(_, multiplier)| *multiplier)
+    }
+
+    fn set_factor(&mut self, psf: Psf, multiplier: f64) {
+        self.psfs.retain(|(factor, _)| *factor != psf);
+        if multiplier != 1.0 {
+            self.psfs.push((psf, multiplier));
+        }
+    }
+
+    /// The HEP with the factors applied. With three or more negative factors,
+    /// SPAR-H's adjustment keeps it from reaching one.
+    pub fn adjusted_hep(&self) -> f64 {
+        let composite: f64 = self.psfs.iter().map(|(_, multiplier)| multiplier).product();
+        let negative = self
+            .psfs
+            .iter()
+            .filter(|(_, multiplier)| *multiplier > 1.0)
+            .count();
+        if negative >= 3 {
+            self.hep * composite / (self.hep * (composite - 1.0) + 1.0)
+        } else {
+            (self.hep * composite).min(1.0)
+        }
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("human action")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr("human-hep"));
+                ui.add(
+                    variables::drag_value(&mut self.hep)
+                        .speed(1e-4)
+                        .clamp_range(0.0..=1.0)
+                        .custom_formatter(|n, _| format!("{n:.2e}")),
+                )
+                .on_hover_text(tr("human-hep-hint"));
+                ui.end_row();
+                ui.label(tr("param-time-steps"));
+                ui.add(variables::drag_value(&mut self.time_steps).clamp_range(1..=1_000_000));
+                ui.end_row();
+            });
+        let title = tr_with("human-psfs", &[("count", &self.psfs.len())]);
+        ui.collapsing(title, |ui| {
+            egui::Grid::new("psfs").num_columns(2).show(ui, |ui| {
+                for psf in Psf::ALL {
+                    let mut multiplier = self.factor(psf);
+                    ui.label(psf.name());
+                    let response = ui.add(
+                        variables::drag_value(&mut multiplier)
+                            .speed(0.1)
+                            .clamp_range(0.01..=50.0)
+                            .prefix("× "),
+                    );
+                    if response.changed() {
+                        self.set_factor(psf, multiplier);
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.label(tr("human-psfs-hint"));
+        });
+        ui.label(tr_with(
+            "human-adjusted",
+            &[("hep", &format!("{:.2e}", self.adjusted_hep()))],
+        ));
+    }
+}
+
+/// A single-use device, such as a pyrotechnic or an airbag, that works when
+/// demanded with a fixed probability instead of wearing out over time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OneShot {
+    pub success: f64,
+    /// The steps the device's own curve spans. Next to time-based blocks it
+    /// holds over their horizon instead.
+    pub time_steps: usize,
+}
+
+impl Default for OneShot {
+    fn default() -> Self {
+        Self {
+            success: 0.999,
+            time_steps: NodeParameters::default().time_steps,
+        }
+    }
+}
+
+impl OneShot {
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("one shot").num_columns(2).show(ui, |ui| {
+            ui.label(tr("one-shot-success"));
+            ui.add(
+                variables::drag_value(&mut self.success)
+                    .speed(1e-4)
+                    .clamp_range(0.0..=1.0)
+                    .max_decimals(6),
+            )
+            .on_hover_text(tr("one-shot-success-hint"));
+            ui.end_row();
+            ui.label(tr("param-time-steps"));
+            ui.add(variables::drag_value(&mut self.time_steps).clamp_range(1..=1_000_000))
+                .on_hover_text(tr("one-shot-time-steps-hint"));
+            ui.end_row();
+        });
+    }
+}
+
+fn default_feasibility() -> f64 {
+    5.0
+}
+
+pub(crate) fn default_enabled() -> bool {
+    true
+}
+
+pub(crate) fn default_coverage() -> f64 {
+    1.0
+}
+
+pub(crate) fn default_votes() -> usize {
+    2
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DataType {
+    /// A reliability curve R(t), one value per time step.
+    Reliability,
+    Parameters,
+    Text,
+    Dormancy,
+    HumanAction,
+    SoftwareGrowth,
+    OneShot,
+    PetriNet,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ValueType {
+    Reliability {
+        curve: Vec<f64>,
+    },
+    /// A curve held at single precision in evaluation caches, to halve their
+    /// size in the single precision mode. Never set on inputs.
+    CompactReliability {
+        curve: Vec<f32>,
+    },
+    Parameters {
+        parameters: NodeParameters,
+    },
+    Text {
+        text: String,
+    },
+    Dormancy {
+        dormancy: Dormancy,
+    },
+    HumanAction {
+        action: HumanAction,
+    },
+    SoftwareGrowth {
+        growth: SoftwareGrowth,
+    },
+    OneShot {
+        device: OneShot,
+    },
+    PetriNet {
+        net: PetriNet,
+    },
+}
+
+impl Default for ValueType {
+    fn default() -> Self {
+        // NOTE: This is just a dummy `Default` implementation. The library
+        // requires it to circumvent some internal borrow checker issues.
+        Self::Reliability { curve: Vec::new() }
+    }
+}
+
+impl ValueType {
+    pub fn try_to_reliability(self) -> anyhow::Result<Vec<f64>> {
+        match self {
+            ValueType::Reliability { curve } => Ok(curve),
+            ValueType::CompactReliability { curve } => {
+                Ok(curve.into_iter().map(f64::from).collect())
+            }
+            _ => anyhow::bail!(tr("error-expected-reliability")),
+        }
+    }
+
+    pub fn try_to_parameters(self) -> anyhow::Result<NodeParameters> {
+        match self {
+            ValueType::Parameters { parameters } => Ok(parameters),
+            _ => anyhow::bail!(tr("error-expected-parameters")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeTemplate {
+    Component,
+    Series,
+    Parallel,
+    /// A spare on B that takes over through a perfect switch when the
+    /// primary on A fails.
+    Standby,
+    /// Works while at least k of its members do.
+    Voting,
+    /// A reliability curve estimated from field data rather than a distribution.
+    Empirical,
+    /// A human action with an error probability, alongside the hardware.
+    Human,
+    /// Software whose reliability grows as defects are found and fixed.
+    Software,
+    /// A single-use device that works on demand with a fixed probability.
+    OneShot,
+    /// A subsystem of shared resources or sequences, simulated as a
+    /// stochastic Petri net.
+    PetriNet,
+    /// A free-text annotation that takes no part in evaluation.
+    Note,
+}
+
+impl NodeTemplate {
+    pub fn name(self) -> String {
+        match self {
+            NodeTemplate::Component => tr("node-component"),
+            NodeTemplate::Series => tr("node-series"),
+            NodeTemplate::Parallel => tr("node-parallel"),
+            NodeTemplate::Standby => tr("node-standby"),
+            NodeTemplate::Voting => tr("node-voting"),
+            NodeTemplate::Empirical => tr("node-empirical"),
+            NodeTemplate::Human => tr("node-human"),
+            NodeTemplate::Software => tr("node-software"),
+            NodeTemplate::OneShot => tr("node-one-shot"),
+            NodeTemplate::PetriNet => tr("node-petri-net"),
+            NodeTemplate::Note => tr("node-note"),
+        }
+    }
+
+    pub fn evaluates(self) -> bool {
+        self != NodeTemplate::Note
+    }
+
+    pub fn category(self) -> NodeCategory {
+        match self {
+            NodeTemplate::Component
+            | NodeTemplate::Human
+            | NodeTemplate::Software
+            | NodeTemplate::OneShot
+            | NodeTemplate::PetriNet => NodeCategory::Components,
+            NodeTemplate::Series
+            | NodeTemplate::Parallel
+            | NodeTemplate::Standby
+            | NodeTemplate::Voting => NodeCategory::Structures,
+            NodeTemplate::Empirical => NodeCategory::Data,
+            NodeTemplate::Note => NodeCategory::Annotation,
+        }
+    }
+}
+
+/// The collapsible groups of the node finder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeCategory {
+    Components,
+    Structures,
+    /// Nodes that derive figures from other nodes' results.
+    Analysis,
+    Data,
+    Annotation,
+}
+
+impl CategoryTrait for NodeCategory {
+    fn name(&self) -> String {
+        tr(match self {
+            NodeCategory::Components => "category-components",
+            NodeCategory::Structures => "category-structures",
+            NodeCategory::Analysis => "category-analysis",
+            NodeCategory::Data => "category-data",
+            NodeCategory::Annotation => "category-annotation",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Response {
+    SetActiveNode(NodeId),
+    ClearActiveNode,
+    SetTag(NodeId, Option<ColorTag>),
+    SetForced(NodeId, Option<Forced>),
+    SetLocked(NodeId, bool),
+    SetEnabled(NodeId, bool),
+    SetSeverity(NodeId, Severity),
+    SetCoverage(NodeId, f64),
+    SetVotes(NodeId, usize),
+    SetMirror(NodeId, Option<NodeId>),
+    AddMember(NodeId),
+    RemoveMember(NodeId),
+    /// Lengthens the curves of the components feeding a node to this horizon.
+    ExtendHorizon(NodeId, usize),
+}
+
+/// Below this zoom nodes are drawn as summaries of their name and R at the
+/// mission time, as their widgets would be too small to read.
+pub const SUMMARY_ZOOM: f32 = 0.5;
+/// The text size of node summaries, whatever the zoom.
+const SUMMARY_TEXT_SIZE: f32 = 14.0;
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphState {
+    /// The node whose reliability curve is shown in the plot panel.
+    pub active_node: Option<NodeId>,
+    /// The time step at which point results such as R(mission) are reported.
+    pub mission_time: usize,
+    /// Further time steps that results are reported at, next to the mission time.
+    pub checkpoints: Vec<usize>,
+    pub requirement: Requirement,
+    pub allocation_method: AllocationMethod,
+    pub mission_profile: MissionProfile,
+    /// Whether the plot shows the point availability A(t) next to R(t).
+    pub show_availability: bool,
+    /// Whether the plot adds the hazard rate h(t) on a right-hand axis.
+    pub show_hazard: bool,
+    /// Whether connected outputs are labeled with the reliability they carry.
+    pub show_edge_values: bool,
+    /// Whether the active node's dominant cut set is outlined on the canvas.
+    pub show_weak_link: bool,
+    /// Whether nodes are tinted by how long they take to evaluate.
+    pub show_hotspots: bool,
+    /// Whether the canvas shows a grid, the origin and coordinates.
+    pub show_grid: bool,
+    /// Whether the system's results are summed up on a card on the canvas.
+    pub show_summary_card: bool,
+    /// Where the summary card lies, in the editor's node coordinates.
+    pub summary_card: egui::Pos2,
+    /// Only failures of this class count, with the other components taken
+    /// as never failing.
+    pub severity_filter: Option<Severity>,
+    /// Reference results that later evaluations are checked against.
+    pub locked_results: Option<LockedResults>,
+    pub report: report::Template,
+    pub frames: Vec<GroupFrame>,
+    /// Named values that component parameters can be expressions of.
+    pub variables: Vec<Variable>,
+    /// Events that shorten the lives of the components they hit, in Monte
+    /// Carlo runs.
+    pub shocks: Vec<Shock>,
+    /// Where the canvas origin lies, in the same coordinates as the editor's
+    /// node positions.
+    pub origin: egui::Pos2,
+    pub panels: Panels,
+    #[serde(skip)]
+    pub settings: Settings,
+    /// Name typed into a node's tag menu before the tag is created.
+    #[serde(skip)]
+    pub new_tag_name: String,
+    /// R(mission time) carried by each output, refreshed every frame while
+    /// edge labels are shown.
+    #[serde(skip)]
+    pub edge_values: HashMap<OutputId, f64>,
+    /// Whether nodes are drawn as summaries, as the canvas is zoomed out
+    /// below [`SUMMARY_ZOOM`].
+    #[serde(skip)]
+    pub summarized: bool,
+    /// R(mission time) of each block, refreshed every frame while nodes are
+    /// drawn as summaries.
+    #[serde(skip)]
+    pub summaries: HashMap<NodeId, f64>,
+    /// Reliability targets suggested by the allocation tool while it is open.
+    #[serde(skip)]
+    pub allocated_targets: HashMap<NodeId, f64>,
+    /// R(requirement time) of the nodes with a budget, refreshed every frame
+    /// while any has one.
+    #[serde(skip)]
+    pub achieved: HashMap<NodeId, f64>,
+    /// The nodes in locked sections, with who locked them, refreshed every
+    /// frame. They can't be edited, moved, connected or deleted.
+    #[serde(skip)]
+    pub read_only: HashMap<NodeId, String>,
+    /// Why the expressions of a component's parameters couldn't be worked
+    /// out, refreshed every frame.
+    #[serde(skip)]
+    pub variable_errors: HashMap<NodeId, String>,
+    /// Where nodes and ports were drawn this frame, for orthogonal wires.
+    #[serde(skip)]
+    pub layout: wires::Layout,
+    /// Read-only presentation: nodes show their values but can't be edited,
+    /// moved or deleted.
+    #[serde(skip)]
+    pub presenting: bool,
+    /// Whether a value in a node is being dragged, found while the nodes are
+    /// drawn.
+    #[serde(skip)]
+    pub dragging_value: bool,
+    /// Whether the model is evaluated coarsely this frame; see
+    /// [`crate::eval::Options::coarse`].
+    #[serde(skip)]
+    pub coarse: bool,
+}
+
+impl Default for GraphState {
+    fn default() -> Self {
+        Self {
+            active_node: None,
+            mission_time: NodeParameters::default().time_steps,
+            checkpoints: vec![730, 4_380, 8_760],
+            requirement: Requirement::default(),
+            allocation_method: AllocationMethod::default(),
+            mission_profile: MissionProfile::default(),
+            show_availability: false,
+            show_hazard: false,
+            show_edge_values: false,
+            show_weak_link: false,
+            show_hotspots: false,
+            show_grid: true,
+            show_summary_card: false,
+            summary_card: card::DEFAULT_POSITION,
+            severity_filter: None,
+            locked_results: None,
+            report: report::Template::default(),
+            frames: Vec::new(),
+            variables: Vec::new(),
+            shocks: Vec::new(),
+            origin: egui::Pos2::ZERO,
+            panels: Panels::default(),
+            settings: Settings::default(),
+            new_tag_name: String::new(),
+            edge_values: HashMap::new(),
+            summarized: false,
+            summaries: HashMap::new(),
+            allocated_targets: HashMap::new(),
+            achieved: HashMap::new(),
+            read_only: HashMap::new(),
+            variable_errors: HashMap::new(),
+            layout: wires::Layout::default(),
+            presenting: false,
+            dragging_value: false,
+            coarse: false,
+        }
+    }
+}
+
+impl GraphState {
+    pub const KEY: &'static str = "graph_state";
+}
+
+pub type Graph = egui_node_graph2::Graph<NodeData, DataType, ValueType>;
+pub type EditorState = GraphEditorState<NodeData, DataType, ValueType, NodeTemplate, GraphState>;
+
+/// The inline parameters of a component node.
+pub fn component_parameters(graph: &Graph, node_id: NodeId) -> Option<NodeParameters> {
+    let input = graph[node_id].get_input("parameters").ok()?;
+    match &graph[input].value {
+        ValueType::Parameters { parameters } => Some(parameters.clone()),
+        _ => None,
+    }
+}
+
+pub fn component_parameters_mut(graph: &mut Graph, node_id: NodeId) -> Option<&mut NodeParameters> {
+    let input = graph[node_id].get_input("parameters").ok()?;
+    match &mut graph[input].value {
+        ValueType::Parameters { parameters } => Some(parameters),
+        _ => None,
+    }
+}
+
+pub fn human_action(graph: &Graph, node_id: NodeId) -> Option<&HumanAction> {
+    let input = graph[node_id].get_input("action").ok()?;
+    match &graph[input].value {
+        ValueType::HumanAction { action } => Some(action),
+        _ => None,
+    }
+}
+
+pub fn human_action_mut(graph: &mut Graph, node_id: NodeId) -> Option<&mut HumanAction> {
+    let input = graph[node_id].get_input("action").ok()?;
+    match &mut graph[input].value {
+        ValueType::HumanAction { action } => Some(action),
+        _ => None,
+    }
+}
+
+pub fn software_growth(graph: &Graph, node_id: NodeId) -> Option<&SoftwareGrowth> {
+    let input = graph[node_id].get_input("growth").ok()?;
+    match &graph[input].value {
+        ValueType::SoftwareGrowth { growth } => Some(growth),
+        _ => None,
+    }
+}
+
+pub fn software_growth_mut(graph: &mut Graph, node_id: NodeId) -> Option<&mut SoftwareGrowth> {
+    let input = graph[node_id].get_input("growth").ok()?;
+    match &mut graph[input].value {
+        ValueType::SoftwareGrowth { growth } => Some(growth),
+        _ => None,
+    }
+}
+
+pub fn one_shot(graph: &Graph, node_id: NodeId) -> Option<&OneShot> {
+    let input = graph[node_id].get_input("device").ok()?;
+    match &graph[input].value {
+        ValueType::OneShot { device } => Some(device),
+        _ => None,
+    }
+}
+
+pub fn one_shot_mut(graph: &mut Graph, node_id: NodeId) -> Option<&mut OneShot> {
+    let input = graph[node_id].get_input("device").ok()?;
+    match &mut graph[input].value {
+        ValueType::OneShot { device } => Some(device),
+        _ => None,
+    }
+}
+
+pub fn petri_net(graph: &Graph, node_id: NodeId) -> Option<&PetriNet> {
+    let input = graph[node_id].get_input("net").ok()?;
+    match &graph[input].value {
+        ValueType::PetriNet { net } => Some(net),
+        _ => None,
+    }
+}
+
+pub fn petri_net_mut(graph: &mut Graph, node_id: NodeId) -> Option<&mut PetriNet> {
+    let input = graph[node_id].get_input("net").ok()?;
+    match &mut graph[input].value {
+        ValueType::PetriNet { net } => Some(net),
+        _ => None,
+    }
+}
+
+/// The component nodes of the graph, sorted by label.
+pub fn components(graph: &Graph) -> Vec<NodeId> {
+    nodes_of(graph, NodeTemplate::Component)
+}
+
+/// The nodes of the graph built from `template`, sorted by label.
+pub fn nodes_of(graph: &Graph, template: NodeTemplate) -> Vec<NodeId> {
+    let mut nodes: Vec<NodeId> = graph
+        .nodes
+        .iter()
+        .filter(|(_, node)| node.user_data.template == template)
+        .map(|(node_id, _)| node_id)
+        .collect();
+    nodes.sort_by_key(|node_id| graph[*node_id].label.clone());
+    nodes
+}
+
+/// The nodes `node_id` depends on, itself included.
+pub fn upstream(graph: &Graph, node_id: NodeId) -> HashSet<NodeId> {
+    let mut stack = vec![node_id];
+    let mut visited = HashSet::new();
+    while let Some(node_id) = stack.pop() {
+        if !visited.insert(node_id) {
+            continue;
+        }
+        stack.extend(
+            graph[node_id]
+                .input_ids()
+                .filter_map(|input| graph.connection(input))
+                .map(|output| graph[output].node),
+        );
+    }
+    visited
+}
+
+/// The components `node_id` depends on, sorted by label.
+pub fn upstream_components(graph: &Graph, node_id: NodeId) -> Vec<NodeId> {
+    let upstream = upstream(graph, node_id);
+    components(graph)
+        .into_iter()
+        .filter(|node| upstream.contains(node))
+        .collect()
+}
+
+/// The free port of `node_id` that a wire from `from`, carrying `typ`, could
+/// join: an input for a wire from an output, and an output for one from an input.
+pub fn matching_port(
+    graph: &Graph,
+    node_id: NodeId,
+    from: AnyParameterId,
+    typ: DataType,
+) -> Option<AnyParameterId> {
+    let node = &graph[node_id];
+    match from {
+        AnyParameterId::Output(_) => node
+            .input_ids()
+            .find(|input| {
+                let param = &graph[*input];
+                param.typ == typ
+                    && !matches!(param.kind, InputParamKind::ConstantOnly)
+                    && graph.connection(*input).is_none()
+            })
+            .map(AnyParameterId::Input),
+        AnyParameterId::Input(_) => node
+            .output_ids()
+            .find(|output| graph[*output].typ == typ)
+            .map(AnyParameterId::Output),
+    }
+}
+
+/// Those of `templates` whose nodes could take a wire from `from`.
+pub fn compatible_templates(
+    templates: Vec<NodeTemplate>,
+    user_state: &mut GraphState,
+    from: AnyParameterId,
+    typ: DataType,
+) -> Vec<NodeTemplate> {
+    templates
+        .into_iter()
+        .filter(|template| {
+            let mut scratch = Graph::new();
+            let node_id = scratch.add_node(
+                String::new(),
+                template.user_data(user_state),
+                |graph, node_id| template.build_node(graph, user_state, node_id),
+            );
+            matching_port(&scratch, node_id, from, typ).is_some()
+        })
+        .collect()
+}
+
+/// Joins `from` to the matching port of `node_id`, if it has one.
+pub fn connect_dropped_wire(graph: &mut Graph, node_id: NodeId, from: AnyParameterId) {
+    let Ok(typ) = graph.any_param_type(from).copied() else {
+        return;
+    };
+    match (from, matching_port(graph, node_id, from, typ)) {
+        (AnyParameterId::Output(output), Some(AnyParameterId::Input(input)))
+        | (AnyParameterId::Input(input), Some(AnyParameterId::Output(output))) => {
+            graph.add_connection(output, input)
+        }
+        _ => {}
+    }
+}
+
+/// Puts a new node on top with its top left corner at `position` on screen,
+/// where `viewport` is the area the graph editor is drawn in.
+pub fn place_node(
+    editor: &mut EditorState,
+    node_id: NodeId,
+    position: egui::Pos2,
+    viewport: egui::Rect,
+) {
+    editor.node_positions.insert(
+        node_id,
+        position - editor.pan_zoom.pan - viewport.min.to_vec2(),
+    );
+    editor.node_order.push(node_id);
+}
+
+/// A label for a new `template` node from `pattern`, where `{type}` is the
+/// template's name, `{parent}` the label of the node it is connected to, or
+/// the template's name without one, and `{n}` and `{letter}` the first number
+/// (01, 02, …) and letter (A, B, …) that give a label no other node has.
+pub fn auto_name(
+    graph: &Graph,
+    template: NodeTemplate,
+    pattern: &str,
+    parent: Option<&str>,
+) -> String {
+    let name = template.name();
+    let base = pattern
+        .replace("{type}", &name)
+        .replace("{parent}", parent.unwrap_or(&name));
+    if base.trim().is_empty() {
+        return name;
+    }
+    if !base.contains("{n}") && !base.contains("{letter}") {
+        return base;
+    }
+    let taken: HashSet<&str> = graph
+        .nodes
+        .values()
+        .map(|node| node.label.as_str())
+        .collect();
+    (1..)
+        .map(|i| {
+            base.replace("{n}", &format!("{i:02}"))
+                .replace("{letter}", &letter(i))
+        })
+        .find(|label| !taken.contains(label.as_str()))
+        .unwrap_or(base)
+}
+
+/// A, B, …, Z, AA, AB, … for 1, 2, …
+fn letter(mut i: usize) -> String {
+    let mut letters = Vec::new();
+    while i > 0 {
+        i -= 1;
+        letters.push(b'A' + (i % 26) as u8);
+        i /= 26;
+    }
+    letters.iter().rev().map(|b| *b as char).collect()
+}
+
+/// The label of the node at the other end of a dropped wire.
+pub fn wire_parent(graph: &Graph, from: AnyParameterId) -> Option<String> {
+    let node = match from {
+        AnyParameterId::Output(output) => graph.outputs.get(output)?.node,
+        AnyParameterId::Input(input) => graph.inputs.get(input)?.node,
+    };
+    Some(graph.nodes.get(node)?.label.clone())
+}
+
+pub fn add_node_at(
+    editor: &mut EditorState,
+    user_state: &mut GraphState,
+    template: NodeTemplate,
+    position: egui::Pos2,
+    viewport: egui::Rect,
+) -> NodeId {
+    let label = auto_name(
+        &editor.graph,
+        template,
+        &user_state.settings.node_names,
+        None,
+    );
+    let node_id = editor
+        .graph
+        .add_node(label, template.user_data(user_state), |graph, node_id| {
+            template.build_node(graph, user_state, node_id)
+        });
+    place_node(editor, node_id, position, viewport);
+    node_id
+}
+
+/// Cuts `curve` off after `horizon`, or holds its last value up to it, so
+/// an empirical curve covers the time steps of the blocks next to it. Held
+/// values take the units that outlived the data as surviving.
+pub fn resample_curve(curve: &mut Vec<f64>, horizon: usize) {
+    let last = curve.last().copied().unwrap_or(1.0);
+    curve.resize(horizon + 1, last);
+}
+
+/// Raises the time steps of every component upstream of `node_id` to at least
+/// `horizon`, and of every human action, piece of software, one-shot device
+/// and Petri net. Shorter empirical curves hold their last value up to it.
+pub fn extend_horizon(graph: &mut Graph, node_id: NodeId, horizon: usize) {
+    for node_id in upstream(graph, node_id) {
+        if graph[node_id].user_data.template == NodeTemplate::Empirical {
+            if let Ok(input) = graph[node_id].get_input("curve") {
+                if let ValueType::Reliability { curve } = &mut graph[input].value {
+                    if !curve.is_empty() && curve.len() <= horizon {
+                        resample_curve(curve, horizon);
+                    }
+                }
+            }
+        }
+        if let Some(parameters) = component_parameters_mut(graph, node_id) {
+            parameters.time_steps = parameters.time_steps.max(horizon);
+        }
+        if let Some(action) = human_action_mut(graph, node_id) {
+            action.time_steps = action.time_steps.max(horizon);
+        }
+        if let Some(growth) = software_growth_mut(graph, node_id) {
+            growth.time_steps = growth.time_steps.max(horizon);
+        }
+        if let Some(device) = one_shot_mut(graph, node_id) {
+            device.time_steps = device.time_steps.max(horizon);
+        }
+        if let Some(net) = petri_net_mut(graph, node_id) {
+            net.time_steps = net.time_steps.max(horizon);
+        }
+    }
+}
+
+/// Gives a voting block another member input.
+pub fn add_member(graph: &mut Graph, node_id: NodeId) {
+    let name = (graph[node_id].inputs.len() + 1).to_string();
+    graph.add_input_param(
+        node_id,
+        name,
+        DataType::Reliability,
+        ValueType::default(),
+        InputParamKind::ConnectionOnly,
+        true,
+    );
+}
+
+/// Takes the last member input off a voting block, keeping at least two and
+/// no fewer than the votes needed.
+pub fn remove_member(graph: &mut Graph, node_id: NodeId) {
+    let node = &graph[node_id];
+    let Some((_, input)) = node.inputs.last() else {
+        return;
+    };
+    if node.inputs.len() <= 2 {
+        return;
+    }
+    let (input, members) = (*input, node.inputs.len() - 1);
+    graph.remove_input_param(input);
+    let data = &mut graph[node_id].user_data;
+    data.votes = data.votes.min(members);
+}
+
+/// A combo box picking one of the graph's component nodes.
+pub fn component_combo(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    graph: &Graph,
+    selected: &mut Option<NodeId>,
+) {
+    if selected.is_some_and(|node| !graph.nodes.contains_key(node)) {
+        *selected = None;
+    }
+    let text = selected
+        .map(|node| graph[node].label.clone())
+        .unwrap_or_default();
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for node in components(graph) {
+                ui.selectable_value(selected, Some(node), &graph[node].label);
+            }
+        });
+}
+
+impl DataType {
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            DataType::Reliability => egui::Color32::from_rgb(138, 173, 244),
+            DataType::Parameters => egui::Color32::from_rgb(238, 212, 159),
+            DataType::Text => egui::Color32::from_rgb(165, 173, 203),
+            DataType::Dormancy => egui::Color32::from_rgb(198, 160, 246),
+            DataType::HumanAction => egui::Color32::from_rgb(245, 169, 127),
+            DataType::SoftwareGrowth => egui::Color32::from_rgb(139, 213, 202),
+            DataType::OneShot => egui::Color32::from_rgb(237, 135, 150),
+            DataType::PetriNet => egui::Color32::from_rgb(166, 218, 149),
+        }
+    }
+}
+
+impl DataTypeTrait<GraphState> for DataType {
+    fn data_type_color(&self, user_state: &mut GraphState) -> egui::Color32 {
+        // Orthogonal wires and their ports are painted by `wires::paint`.
+        match user_state.settings.wire_style {
+            WireStyle::Curved => self.color(),
+            WireStyle::Orthogonal => egui::Color32::TRANSPARENT,
+        }
+    }
+
+    fn name(&self) -> Cow<'_, str> {
+        match self {
+            DataType::Reliability => tr("type-reliability").into(),
+            DataType::Parameters => tr("type-parameters").into(),
+            DataType::Text => tr("type-text").into(),
+            DataType::Dormancy => tr("type-dormancy").into(),
+            DataType::HumanAction => tr("type-human-action").into(),
+            DataType::SoftwareGrowth => tr("type-software-growth").into(),
+            DataType::OneShot => tr("type-one-shot").into(),
+            DataType::PetriNet => tr("type-petri-net").into(),
+        }
+    }
+}
+
+impl NodeTemplateTrait for NodeTemplate {
+    type NodeData = NodeData;
+    type DataType = DataType;
+    type ValueType = ValueType;
+    type UserState = GraphState;
+    type CategoryType = NodeCategory;
+
+    fn node_finder_label(&self, user_state: &mut Self::UserState) -> Cow<'_, str> {
+        let settings = &user_state.settings;
+        if settings.favorite_templates.contains(self) {
+            format!("⭐ {}", self.name()).into()
+        } else if settings.recent_templates.contains(self) {
+            format!("🕘 {}", self.name()).into()
+        } else {
+            self.name().into()
+        }
+    }
+
+    /// Favorite and recent templates stay out of the categories, so the finder
+    /// lists them without a header to expand first.
+    fn node_finder_categories(&self, user_state: &mut Self::UserState) -> Vec<NodeCategory> {
+        let settings = &user_state.settings;
+        if settings.favorite_templates.contains(self) || settings.recent_templates.contains(self) {
+            Vec::new()
+        } else {
+            vec![self.category()]
+        }
+    }
+
+    fn node_graph_label(&self, _user_state: &mut Self::UserState) -> String {
+        self.name()
+    }
+
+    fn user_data(&self, _user_state: &mut Self::UserState) -> Self::NodeData {
+        NodeData {
+            template: *self,
+            tag: None,
+            feasibility: default_feasibility(),
+            forced: None,
+            excluded: false,
+            locked: false,
+            enabled: true,
+            severity: Severity::default(),
+            coverage: default_coverage(),
+            votes: default_votes(),
+            target: None,
+            library: None,
+            mirror: None,
+        }
+    }
+
+    fn build_node(&self, graph: &mut Graph, user_state: &mut Self::UserState, node_id: NodeId) {
+        let input_reliability = |graph: &mut Graph, name: &str| {
+            graph.add_input_param(
+                node_id,
+                name.to_string(),
+                DataType::Reliability,
+                ValueType::default(),
+                InputParamKind::ConnectionOnly,
+                true,
+            );
+        };
+
+        match self {
+            NodeTemplate::Component => {
+                graph.add_input_param(
+                    node_id,
+                    "parameters".to_string(),
+                    DataType::Parameters,
+                    ValueType::Parameters {
+                        parameters: user_state.settings.default_parameters.clone(),
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::Series | NodeTemplate::Parallel => {
+                input_reliability(graph, "A");
+                input_reliability(graph, "B");
+            }
+            NodeTemplate::Standby => {
+                input_reliability(graph, "A");
+                input_reliability(graph, "B");
+                graph.add_input_param(
+                    node_id,
+                    "dormancy".to_string(),
+                    DataType::Dormancy,
+                    ValueType::Dormancy {
+                        dormancy: Dormancy::default(),
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::Voting => {
+                for name in ["1", "2", "3"] {
+                    input_reliability(graph, name);
+                }
+            }
+            NodeTemplate::Empirical => {
+                graph.add_input_param(
+                    node_id,
+                    "curve".to_string(),
+                    DataType::Reliability,
+                    ValueType::default(),
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::Human => {
+                graph.add_input_param(
+                    node_id,
+                    "action".to_string(),
+                    DataType::HumanAction,
+                    ValueType::HumanAction {
+                        action: HumanAction {
+                            time_steps: user_state.settings.default_parameters.time_steps,
+                            ..HumanAction::default()
+                        },
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::Software => {
+                graph.add_input_param(
+                    node_id,
+                    "growth".to_string(),
+                    DataType::SoftwareGrowth,
+                    ValueType::SoftwareGrowth {
+                        growth: SoftwareGrowth {
+                            time_steps: user_state.settings.default_parameters.time_steps,
+                            ..SoftwareGrowth::default()
+                        },
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::OneShot => {
+                graph.add_input_param(
+                    node_id,
+                    "device".to_string(),
+                    DataType::OneShot,
+                    ValueType::OneShot {
+                        device: OneShot {
+                            time_steps: user_state.settings.default_parameters.time_steps,
+                            ..OneShot::default()
+                        },
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::PetriNet => {
+                graph.add_input_param(
+                    node_id,
+                    "net".to_string(),
+                    DataType::PetriNet,
+                    ValueType::PetriNet {
+                        net: PetriNet {
+                            time_steps: user_state.settings.default_parameters.time_steps,
+                            ..PetriNet::default()
+                        },
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+            }
+            NodeTemplate::Note => {
+                graph.add_input_param(
+                    node_id,
+                    "text".to_string(),
+                    DataType::Text,
+                    ValueType::Text {
+                        text: String::new(),
+                    },
+                    InputParamKind::ConstantOnly,
+                    true,
+                );
+                return;
+            }
+        }
+        graph.add_output_param(node_id, "reliability".to_string(), DataType::Reliability);
+    }
+}
+
+pub struct AllNodeTemplates;
+
+/// The templates in the order the node finder lists them.
+pub struct FinderTemplates(pub Vec<NodeTemplate>);
+
+impl NodeTemplateIter for FinderTemplates {
+    type Item = NodeTemplate;
+
+    fn all_kinds(&self) -> Vec<Self::Item> {
+        self.0.clone()
+    }
+}
+
+impl NodeTemplateIter for AllNodeTemplates {
+    type Item = NodeTemplate;
+
+    fn all_kinds(&self) -> Vec<Self::Item> {
+        vec![
+            NodeTemplate::Component,
+            NodeTemplate::Human,
+            NodeTemplate::Software,
+            NodeTemplate::OneShot,
+            NodeTemplate::PetriNet,
+            NodeTemplate::Series,
+            NodeTemplate::Parallel,
+            NodeTemplate::Standby,
+            NodeTemplate::Voting,
+            NodeTemplate::Empirical,
+            NodeTemplate::Note,
+        ]
+    }
+}
+
+impl WidgetValueTrait for ValueType {
+    type Response = Response;
+    type UserState = GraphState;
+    type NodeData = NodeData;
+
+    fn value_widget(
+        &mut self,
+        param_name: &str,
+        node_id: NodeId,
+        ui: &mut egui::Ui,
+        user_state: &mut GraphState,
+        node_data: &NodeData,
+    ) -> Vec<Response> {
+        user_state.layout.begin_row(ui);
+        if user_state.read_only.contains_key(&node_id) || node_data.mirror.is_some() {
+            ui.set_enabled(false);
+        }
+        if user_state.summarized {
+            ui.label(param_name);
+            return Vec::new();
+        }
+        match self {
+            ValueType::Parameters { parameters } if user_state.presenting => {
+                ui.label(tr_with(
+                    "node-parameters-summary",
+                    &[("shape", &parameters.shape), ("scale", &parameters.scale)],
+                ));
+            }
+            ValueType::Text { text } if user_state.presenting => {
+                ui.label(text.as_str());
+            }
+            ValueType::Dormancy { dormancy } if user_state.presenting => {
+                ui.label(dormancy.name());
+            }
+            ValueType::HumanAction { action } if user_state.presenting => {
+                ui.label(format!("HEP = {:.2e}", action.adjusted_hep()));
+            }
+            ValueType::OneShot { device } if user_state.presenting => {
+                ui.label(format!("P = {}", device.success));
+            }
+            ValueType::PetriNet { net } if user_state.presenting => {
+                ui.label(tr_with(
+                    "petri-summary",
+                    &[
+                        ("places", &net.places.len()),
+                        ("transitions", &net.transitions.len()),
+                    ],
+                ));
+            }
+            ValueType::SoftwareGrowth { growth } if user_state.presenting => {
+                if let Ok(model) = growth.model() {
+                    ui.label(tr_with(
+                        "software-remaining",
+                        &[(
+                            "remaining",
+                            &format!("{:.2}", model.remaining(growth.test_time)),
+                        )],
+                    ));
+                }
+            }
+            ValueType::Reliability { curve } if !curve.is_empty() => {
+                ui.horizontal(|ui| {
+                    ui.label(tr_with("node-empirical-steps", &[("steps", &curve.len())]));
+                    ui.menu_button(tr("node-resample"), |ui| {
+                        let id = egui::Id::new((node_id, "resample"));
+                        let mut horizon = ui.data(|d| d.get_temp(id)).unwrap_or(curve.len() - 1);
+                        ui.horizontal(|ui| {
+                            ui.label(tr("node-resample-horizon"));
+                            ui.add(egui::DragValue::new(&mut horizon).clamp_range(1..=usize::MAX));
+                        });
+                        ui.data_mut(|d| d.insert_temp(id, horizon));
+                        if ui.button(tr("node-resample-apply")).clicked() {
+                            resample_curve(curve, horizon);
+                            ui.close_menu();
+                        }
+                        ui.label(tr("node-resample-hint"));
+                    });
+                });
+            }
+            ValueType::Reliability { .. } | ValueType::CompactReliability { .. } => {
+                ui.label(param_name);
+            }
+            ValueType::Parameters { parameters } => {
+                parameters.ui(ui);
+                let title = tr_with(
+                    "param-failure-modes",
+                    &[("count", &(parameters.modes.len() + 1))],
+                );
+                ui.collapsing(title, |ui| parameters.modes_ui(ui, user_state));
+                let title = tr_with(
+                    "param-expressions",
+                    &[("count", &parameters.expressions.len())],
+                );
+                ui.collapsing(title, |ui| {
+                    variables::expressions_ui(ui, parameters, node_id, user_state)
+                });
+            }
+            ValueType::Text { text } => {
+                ui.add(
+                    egui::TextEdit::multiline(text)
+                        .hint_text(tr("note-hint"))
+                        .desired_rows(3),
+                );
+            }
+            ValueType::Dormancy { dormancy } => dormancy.ui(ui),
+            ValueType::HumanAction { action } => action.ui(ui),
+            ValueType::SoftwareGrowth { growth } => growth.ui(ui),
+            ValueType::OneShot { device } => device.ui(ui),
+            ValueType::PetriNet { net } => net.ui(ui),
+        }
+        // Moving the node or panning the canvas drags a widget that covers
+        // more than the values drawn here.
+        let ctx = ui.ctx().clone();
+        if let Some(dragged) = ctx.dragged_id().and_then(|id| ctx.read_response(id)) {
+            user_state.dragging_value |= ui.min_rect().contains_rect(dragged.rect);
+        }
+        // This allows you to return your responses from the inline widgets.
+        Vec::new()
+    }
+
+    fn value_widget_connected(
+        &mut self,
+        param_name: &str,
+        _node_id: NodeId,
+        ui: &mut egui::Ui,
+        user_state: &mut GraphState,
+        _node_data: &NodeData,
+    ) -> Vec<Response> {
+        user_state.layout.begin_row(ui);
+        ui.label(param_name);
+        Vec::new()
+    }
+}
+
+impl UserResponseTrait for Response {}
+
+/// A node's name and R at the mission time, in text that stays readable
+/// however far the canvas is zoomed out.
+fn summary_ui(ui: &mut egui::Ui, node_id: NodeId, graph: &Graph, user_state: &GraphState) {
+    let theme = user_state.settings.theme.catppuccin();
+    egui::Frame::none()
+        .fill(theme.surface0)
+        .rounding(4.0)
+        .inner_margin(egui::vec2(6.0, 3.0))
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(&graph[node_id].label)
+                    .size(SUMMARY_TEXT_SIZE)
+                    .strong()
+                    .color(theme.text),
+            );
+            if let Some(r) = user_state.summaries.get(&node_id) {
+                ui.label(
+                    egui::RichText::new(format!("R = {r:.4}"))
+                        .size(SUMMARY_TEXT_SIZE)
+                        .color(theme.subtext1),
+                );
+            }
+        });
+}
+
+impl NodeDataTrait for NodeData {
+    type Response = Response;
+    type UserState = GraphState;
+    type DataType = DataType;
+    type ValueType = ValueType;
+
+    fn separator(
+        &self,
+        ui: &mut egui::Ui,
+        _node_id: NodeId,
+        param_id: AnyParameterId,
+        graph: &Graph,
+        user_state: &mut Self::UserState,
+    ) {
+        let has_port = match param_id {
+            AnyParameterId::Input(input) => {
+                !matches!(graph[input].kind, InputParamKind::ConstantOnly)
+            }
+            AnyParameterId::Output(_) => true,
+        };
+        if has_port {
+            user_state.layout.end_row(ui, param_id);
+        }
+    }
+
+    fn bottom_ui(
+        &self,
+        ui: &mut egui::Ui,
+        node_id: NodeId,
+        graph: &Graph,
+        user_state: &mut Self::UserState,
+    ) -> Vec<NodeResponse<Response, NodeData>>
+    where
+        Response: UserResponseTrait,
+    {
+        let mut responses = vec![];
+        if user_state.summarized {
+            summary_ui(ui, node_id, graph, user_state);
+            user_state.layout.end_node(ui, node_id);
+            return responses;
+        }
+        if user_state.presenting {
+            user_state.layout.end_node(ui, node_id);
+            return responses;
+        }
+        let is_active = user_state.active_node == Some(node_id);
+
+        ui.horizontal(|ui| {
+            if self.template.evaluates() {
+                if !is_active {
+                    if ui.button(tr("node-set-active")).clicked() {
+                        responses.push(NodeResponse::User(Response::SetActiveNode(node_id)));
+                    }
+                } else {
+                    let button = egui::Button::new(
+                        egui::RichText::new(tr("node-active")).color(egui::Color32::BLACK),
+                    )
+                    .fill(egui::Color32::GOLD);
+                    if ui.add(button).clicked() {
+                        responses.push(NodeResponse::User(Response::ClearActiveNode));
+                    }
+                }
+            }
+
+            if let Some(owner) = user_state.read_only.get(&node_id) {
+                ui.label(tr_with("node-read-only", &[("owner", owner)]))
+                    .on_hover_text(tr("node-read-only-hint"));
+                ui.set_enabled(false);
+            }
+
+            if let Some((shortest, longest, _)) = eval::horizon_mismatch(graph, node_id) {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    tr_with(
+                        "node-horizon-mismatch",
+                        &[("shortest", &shortest), ("longest", &longest)],
+                    ),
+                )
+                .on_hover_text(tr("node-horizon-mismatch-hint"));
+                if ui
+                    .button(tr_with("node-extend-horizon", &[("longest", &longest)]))
+                    .clicked()
+                {
+                    responses.push(NodeResponse::User(Response::ExtendHorizon(
+                        node_id, longest,
+                    )));
+                }
+            }
+
+            if let Some(target) = user_state.allocated_targets.get(&node_id) {
+                ui.label(tr_with(
+                    "node-allocated-target",
+                    &[("target", &format!("{target:.4}"))],
+                ));
+            }
+
+            if let Some(target) = self.target {
+                let text = tr_with("node-budget", &[("target", &format!("{target:.4}"))]);
+                match user_state.achieved.get(&node_id) {
+                    Some(achieved) => {
+                        let margin = achieved - target;
+                        let theme = user_state.settings.theme.catppuccin();
+                        ui.colored_label(
+                            budget::margin_color(&theme, margin),
+                            format!("{text} ({margin:+.4})"),
+                        )
+                    }
+                    None => ui.label(text),
+                }
+                .on_hover_text(tr("node-budget-hint"));
+            }
+
+            ui.menu_button(tr("node-tag"), |ui| {
+                let known = tags::graph_tags(graph);
+                let mut chosen = None;
+                if ui
+                    .selectable_label(self.tag.is_none(), tr("node-tag-none"))
+                    .clicked()
+                {
+                    chosen = Some(None);
+                }
+                for (tag, _) in &known {
+                    let selected = self.tag.as_ref().map(|t| &t.name) == Some(&tag.name);
+                    let label = egui::RichText::new(&tag.name).color(tag.color);
+                    if ui.selectable_label(selected, label).clicked() {
+                        chosen = Some(Some(tag.clone()));
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut user_state.new_tag_name);
+                    let name = user_state.new_tag_name.trim();
+                    if ui
+                        .add_enabled(!name.is_empty(), egui::Button::new(tr("node-tag-add")))
+                        .clicked()
+                    {
+                        let theme = user_state.settings.theme.catppuccin();
+                        chosen = Some(Some(ColorTag {
+                            name: name.to_owned(),
+                            color: tags::next_color(&theme, known.len()),
+                        }));
+                        user_state.new_tag_name.clear();
+                    }
+                });
+                if let Some(tag) = chosen {
+                    responses.push(NodeResponse::User(Response::SetTag(node_id, tag)));
+                    ui.close_menu();
+                }
+            });
+
+            if self.excluded {
+                ui.colored_label(ui.visuals().warn_fg_color, tr("node-excluded"));
+            }
+            if self.template == NodeTemplate::Component {
+                ui.menu_button(self.severity.name(), |ui| {
+                    for severity in Severity::ALL {
+                        if ui
+                            .selectable_label(self.severity == severity, severity.name())
+                            .clicked()
+                        {
+                            responses
+                                .push(NodeResponse::User(Response::SetSeverity(node_id, severity)));
+                            ui.close_menu();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr("node-severity"));
+                if let Some(mirror) = mirrors::menu(ui, graph, node_id) {
+                    responses.push(NodeResponse::User(Response::SetMirror(node_id, mirror)));
+                }
+            }
+            if self.template == NodeTemplate::Voting {
+                let members = graph[node_id].inputs.len();
+                let mut votes = self.votes;
+                if ui
+                    .add(
+                        variables::drag_value(&mut votes)
+                            .clamp_range(1..=members)
+                            .suffix(format!(" / {members}")),
+                    )
+                    .on_hover_text(tr("node-votes-hint"))
+                    .changed()
+                {
+                    responses.push(NodeResponse::User(Response::SetVotes(node_id, votes)));
+                }
+                if ui
+                    .small_button("+")
+                    .on_hover_text(tr("node-add-member"))
+                    .clicked()
+                {
+                    responses.push(NodeResponse::User(Response::AddMember(node_id)));
+                }
+                if ui
+                    .add_enabled(members > 2, egui::Button::new("−").small())
+                    .on_hover_text(tr("node-remove-member"))
+                    .clicked()
+                {
+                    responses.push(NodeResponse::User(Response::RemoveMember(node_id)));
+                }
+            }
+            if matches!(
+                self.template,
+                NodeTemplate::Parallel | NodeTemplate::Standby | NodeTemplate::Voting
+            ) {
+                let mut coverage = self.coverage;
+                if ui
+                    .add(
+                        variables::drag_value(&mut coverage)
+                            .speed(0.001)
+                            .clamp_range(0.0..=1.0)
+                            .prefix("c = "),
+                    )
+                    .on_hover_text(tr("node-coverage-hint"))
+                    .changed()
+                {
+                    responses.push(NodeResponse::User(Response::SetCoverage(node_id, coverage)));
+                }
+            }
+            if self.template.evaluates() {
+                let mut enabled = self.enabled;
+                if ui
+                    .checkbox(&mut enabled, tr("node-enabled"))
+                    .on_hover_text(tr("node-enabled-hint"))
+                    .changed()
+                {
+                    responses.push(NodeResponse::User(Response::SetEnabled(node_id, enabled)));
+                }
+            }
+            if self.template.evaluates() {
+                let text = match self.forced {
+                    Some(Forced::Failed) => egui::RichText::new(tr("node-forced-failed"))
+                        .color(ui.visuals().error_fg_color),
+                    Some(Forced::Working) => egui::RichText::new(tr("node-forced-working"))
+                        .color(ui.visuals().warn_fg_color),
+                    None => egui::RichText::new(tr("node-force")),
+                };
+                ui.menu_button(text, |ui| {
+                    let options = [
+                        (None, "node-force-none"),
+                        (Some(Forced::Failed), "node-forced-failed"),
+                        (Some(Forced::Working), "node-forced-working"),
+                    ];
+                    for (forced, label) in options {
+                        if ui
+                            .selectable_label(self.forced == forced, tr(label))
+                            .clicked()
+                        {
+                            responses
+                                .push(NodeResponse::User(Response::SetForced(node_id, forced)));
+                            ui.close_menu();
+                        }
+                    }
+                });
+            }
+            let lock = if self.locked { "🔒" } else { "🔓" };
+            if ui
+                .selectable_label(self.locked, lock)
+                .on_hover_text(tr("node-lock"))
+                .clicked()
+            {
+                responses.push(NodeResponse::User(Response::SetLocked(
+                    node_id,
+                    !self.locked,
+                )));
+            }
+            if ui.button(tr("node-copy-json")).clicked() {
+                let json = Snippet::from_node(graph, node_id).to_json();
+                ui.output_mut(|o| o.copied_text = json);
+            }
+        });
+
+        user_state.layout.end_node(ui, node_id);
+        responses
+    }
+
+    fn can_delete(&self, node_id: NodeId, _graph: &Graph, user_state: &mut GraphState) -> bool {
+        !user_state.presenting && !user_state.read_only.contains_key(&node_id)
+    }
+
+    fn titlebar_color(
+        &self,
+        _ui: &egui::Ui,
+        _node_id: NodeId,
+        _graph: &Graph,
+        _user_state: &mut Self::UserState,
+    ) -> Option<egui::Color32> {
+        self.tag.as_ref().map(|tag| tag.color)
+    }
+
+    fn output_ui(
+        &self,
+        ui: &mut egui::Ui,
+        node_id: NodeId,
+        graph: &Graph,
+        user_state: &mut Self::UserState,
+        param_name: &str,
+    ) -> Vec<NodeResponse<Response, NodeData>>
+    where
+        Response: UserResponseTrait,
+    {
+        user_state.layout.begin_row(ui);
+        ui.horizontal(|ui| {
+            ui.label(tr("type-reliability"));
+            let Ok(output) = graph[node_id].get_output(param_name) else {
+                return;
+            };
+            let connected = graph.connections.values().any(|source| *source == output);
+            if user_state.show_edge_values && connected {
+                let value = match user_state.edge_values.get(&output) {
+                    Some(r) => format!("{r:.4}"),
+                    None => "—".to_owned(),
+                };
+                ui.label(egui::RichText::new(format!("→ {value}")).strong());
+            }
+        });
+        Vec::new()
+    }
+}
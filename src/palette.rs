@@ -0,0 +1,361 @@
+//! The Ctrl+P command palette: every menu action, found by fuzzy search.
+use eframe::egui::{self, Key, Modifiers};
+use egui_node_graph2::NodeTemplateIter;
+
+use crate::arrange::Arrangement;
+use crate::examples::Example;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{AllNodeTemplates, NodeTemplate};
+use crate::topology::StructureFormat;
+
+/// The analysis windows, in the order the Analysis menu lists them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tool {
+    Allocation,
+    Budget,
+    GoalSeek,
+    Sweep,
+    Doe,
+    Robustness,
+    Simulation,
+    KaplanMeier,
+    FieldData,
+    Bayes,
+    Availability,
+    Rocof,
+    AgeReplacement,
+    Calendar,
+    Redundancy,
+    EventTree,
+    Sil,
+    Fleet,
+    CompetingRisks,
+    Criticality,
+    Pareto,
+    Profile,
+    Variables,
+    Scaling,
+    Compare,
+    Statistics,
+    Feed,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 27] = [
+        Tool::Allocation,
+        Tool::Budget,
+        Tool::GoalSeek,
+        Tool::Sweep,
+        Tool::Doe,
+        Tool::Robustness,
+        Tool::Simulation,
+        Tool::KaplanMeier,
+        Tool::FieldData,
+        Tool::Bayes,
+        Tool::Availability,
+        Tool::Rocof,
+        Tool::AgeReplacement,
+        Tool::Calendar,
+        Tool::Redundancy,
+        Tool::EventTree,
+        Tool::Sil,
+        Tool::Fleet,
+        Tool::CompetingRisks,
+        Tool::Criticality,
+        Tool::Pareto,
+        Tool::Profile,
+        Tool::Variables,
+        Tool::Scaling,
+        Tool::Compare,
+        Tool::Statistics,
+        Tool::Feed,
+    ];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Tool::Allocation => "menu-analysis-allocation",
+            Tool::Budget => "menu-analysis-budget",
+            Tool::GoalSeek => "menu-analysis-goal-seek",
+            Tool::Sweep => "menu-analysis-sweep",
+            Tool::Doe => "menu-analysis-doe",
+            Tool::Robustness => "menu-analysis-robustness",
+            Tool::Simulation => "menu-analysis-simulation",
+            Tool::KaplanMeier => "menu-analysis-kaplan-meier",
+            Tool::FieldData => "menu-analysis-field-data",
+            Tool::Bayes => "menu-analysis-bayes",
+            Tool::Availability => "menu-analysis-availability",
+            Tool::Rocof => "menu-analysis-rocof",
+            Tool::AgeReplacement => "menu-analysis-age-replacement",
+            Tool::Calendar => "menu-analysis-calendar",
+            Tool::Redundancy => "menu-analysis-redundancy",
+            Tool::EventTree => "menu-analysis-event-tree",
+            Tool::Sil => "menu-analysis-sil",
+            Tool::Fleet => "menu-analysis-fleet",
+            Tool::CompetingRisks => "menu-analysis-competing-risks",
+            Tool::Criticality => "menu-analysis-criticality",
+            Tool::Pareto => "menu-analysis-pareto",
+            Tool::Profile => "menu-analysis-profile",
+            Tool::Variables => "menu-analysis-variables",
+            Tool::Scaling => "menu-analysis-scaling",
+            Tool::Compare => "menu-analysis-compare",
+            Tool::Statistics => "menu-analysis-statistics",
+            Tool::Feed => "menu-analysis-feed",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    NewProject,
+    OpenProject,
+    SaveProject,
+    ImportModel,
+    ImportDot,
+    /// Adds a system exported from another model as one empirical node.
+    ImportDistribution,
+    /// Opens the shared component library.
+    Library,
+    ExportStructure(StructureFormat),
+    ExportDot,
+    /// Saves the model as Rust code that rebuilds it, for test fixtures.
+    ExportBuilder,
+    /// Saves the active node's curve for use as a component elsewhere.
+    ExportDistribution,
+    Report,
+    /// Adds a node at the center of the graph editor.
+    AddNode(NodeTemplate),
+    Tool(Tool),
+    ToggleLegend,
+    ToggleWhatIf,
+    ToggleLog,
+    ToggleNotifications,
+    ToggleEdgeValues,
+    ToggleWeakLink,
+    ToggleHotspots,
+    ToggleGrid,
+    ToggleSummaryCard,
+    TogglePresentation,
+    Arrange(Arrangement),
+    LockSelection,
+    UnlockSelection,
+    FrameSelection,
+    /// Finds and replaces text in node names and notes.
+    FindReplace,
+    Settings,
+    Example(Example),
+}
+
+impl Command {
+    pub fn all() -> Vec<Command> {
+        let mut commands = vec![
+            Command::NewProject,
+            Command::OpenProject,
+            Command::SaveProject,
+            Command::ImportModel,
+            Command::ImportDot,
+            Command::ImportDistribution,
+            Command::Library,
+        ];
+        commands.extend(StructureFormat::ALL.map(Command::ExportStructure));
+        commands.extend([
+            Command::ExportDot,
+            Command::ExportBuilder,
+            Command::ExportDistribution,
+            Command::Report,
+        ]);
+        commands.extend(
+            AllNodeTemplates
+                .all_kinds()
+                .into_iter()
+                .map(Command::AddNode),
+        );
+        commands.extend(Tool::ALL.map(Command::Tool));
+        commands.extend([
+            Command::ToggleLegend,
+            Command::ToggleWhatIf,
+            Command::ToggleLog,
+            Command::ToggleNotifications,
+            Command::ToggleEdgeValues,
+            Command::ToggleWeakLink,
+            Command::ToggleHotspots,
+            Command::ToggleGrid,
+            Command::ToggleSummaryCard,
+            Command::TogglePresentation,
+        ]);
+        commands.extend(Arrangement::ALL.map(Command::Arrange));
+        commands.extend([
+            Command::LockSelection,
+            Command::UnlockSelection,
+            Command::FrameSelection,
+            Command::FindReplace,
+            Command::Settings,
+        ]);
+        commands.extend(Example::ALL.map(Command::Example));
+        commands
+    }
+
+    /// The label in the palette, which names what the menus leave to context.
+    pub fn palette_name(self) -> String {
+        match self {
+            Command::AddNode(template) => {
+                tr_with("palette-add-node", &[("node", &template.name())])
+            }
+            Command::Example(example) => {
+                tr_with("palette-example", &[("example", &example.name())])
+            }
+            Command::ExportStructure(format) => {
+                format!("{}: {}", tr("menu-file-export-structure"), format.name())
+            }
+            command => command.name(),
+        }
+    }
+
+    /// The label in the menus.
+    pub fn name(self) -> String {
+        match self {
+            Command::NewProject => tr("menu-file-new"),
+            Command::OpenProject => tr("menu-file-open"),
+            Command::SaveProject => tr("menu-file-save-as"),
+            Command::ImportModel => tr("menu-file-import"),
+            Command::ImportDot => tr("menu-file-import-dot"),
+            Command::ImportDistribution => tr("menu-file-import-distribution"),
+            Command::Library => tr("menu-file-library"),
+            Command::ExportStructure(format) => format.name(),
+            Command::ExportDot => tr("menu-file-export-dot"),
+            Command::ExportBuilder => tr("menu-file-export-builder"),
+            Command::ExportDistribution => tr("menu-file-export-distribution"),
+            Command::Report => tr("menu-file-report"),
+            Command::AddNode(template) => template.name(),
+            Command::Tool(tool) => tool.name(),
+            Command::ToggleLegend => tr("menu-view-legend"),
+            Command::ToggleWhatIf => tr("menu-view-what-if"),
+            Command::ToggleLog => tr("menu-view-log"),
+            Command::ToggleNotifications => tr("menu-view-notifications"),
+            Command::ToggleEdgeValues => tr("menu-view-edge-values"),
+            Command::ToggleWeakLink => tr("menu-view-weak-link"),
+            Command::ToggleHotspots => tr("menu-view-hotspots"),
+            Command::ToggleGrid => tr("menu-view-grid"),
+            Command::ToggleSummaryCard => tr("menu-view-summary-card"),
+            Command::TogglePresentation => tr("menu-view-presentation"),
+            Command::Arrange(arrangement) => arrangement.name(),
+            Command::LockSelection => tr("menu-arrange-lock"),
+            Command::UnlockSelection => tr("menu-arrange-unlock"),
+            Command::FrameSelection => tr("menu-arrange-frame"),
+            Command::FindReplace => tr("menu-arrange-find-replace"),
+            Command::Settings => tr("menu-settings"),
+            Command::Example(example) => example.name(),
+        }
+    }
+}
+
+/// How well `query` matches `text`, or `None` if its characters don't all
+/// appear in order. Matches that run on or start a word score higher, and
+/// the query appearing as a whole scores highest.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query = query.trim().to_lowercase();
+    let lowercase = text.to_lowercase();
+    if !query.is_empty() && lowercase.contains(&query) {
+        return Some(100 + query.chars().count() as i32);
+    }
+    let text: Vec<char> = lowercase.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|c| *c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+#[derive(Default)]
+pub struct Palette {
+    open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl Palette {
+    /// Opens on Ctrl+P and returns the command the user picks, if any.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Command> {
+        if ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::P)) {
+            self.open = !self.open;
+            self.query.clear();
+            self.selected = 0;
+        }
+        if !self.open {
+            return None;
+        }
+
+        let mut matches: Vec<(i32, Command, String)> = Command::all()
+            .into_iter()
+            .filter_map(|command| {
+                let name = command.palette_name();
+                Some((fuzzy_score(&self.query, &name)?, command, name))
+            })
+            .collect();
+        // A stable sort keeps equally good matches in menu order.
+        matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        });
+        if down {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(matches.len().saturating_sub(1));
+
+        let mut chosen = (enter && !matches.is_empty()).then(|| matches[self.selected].1);
+        egui::Window::new(tr("palette-title"))
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+            .resizable(false)
+            .show(ctx, |ui| {
+                let edit = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(tr("palette-hint"))
+                        .desired_width(360.0),
+                );
+                edit.request_focus();
+                if edit.changed() {
+                    self.selected = 0;
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for (i, (_, command, name)) in matches.iter().enumerate() {
+                            let label = ui.selectable_label(i == self.selected, name);
+                            if i == self.selected && (up || down) {
+                                label.scroll_to_me(None);
+                            }
+                            if label.clicked() {
+                                chosen = Some(*command);
+                            }
+                        }
+                        if matches.is_empty() {
+                            ui.label(tr("palette-no-match"));
+                        }
+                    });
+            });
+        if chosen.is_some() || escape {
+            self.open = false;
+        }
+        chosen
+    }
+}
@@ -0,0 +1,158 @@
+//! Pareto chart of the components' contributions to system unreliability.
+use eframe::egui;
+use egui_plot::{uniform_grid_spacer, Bar, BarChart, HLine, Legend, Line, LineStyle, Plot};
+
+use crate::criticality::{rank, Row};
+use crate::eval::Options;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+
+/// Each component's share of the system unreliability in percent, by its
+/// criticality importance, largest first. Shares are normalised to add up to
+/// 100 %, as redundant components can jointly cause a failure.
+pub fn contributions(rows: &[Row]) -> Vec<(usize, f64)> {
+    let total: f64 = rows.iter().map(|row| row.criticality).sum();
+    let mut shares: Vec<(usize, f64)> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let share = if total > 0.0 {
+                100.0 * row.criticality / total
+            } else {
+                0.0
+            };
+            (i, share)
+        })
+        .collect();
+    shares.sort_by(|a, b| b.1.total_cmp(&a.1));
+    shares
+}
+
+pub struct Pareto {
+    /// The cumulative share marked on the chart, the "80" of 80/20.
+    threshold: f64,
+}
+
+impl Default for Pareto {
+    fn default() -> Self {
+        Self { threshold: 80.0 }
+    }
+}
+
+impl Pareto {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("pareto-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let result = state
+                    .active_node
+                    .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))
+                    .and_then(|system| {
+                        rank(graph, &Options::of(state), system, state.mission_time)
+                    });
+                let rows = match result {
+                    Ok(rows) => rows,
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                        return;
+                    }
+                };
+                let shares = contributions(&rows);
+                let names: Vec<String> = shares
+                    .iter()
+                    .map(|(i, _)| graph[rows[*i].component].label.clone())
+                    .collect();
+
+                let theme = state.settings.theme.catppuccin();
+                let bars = shares
+                    .iter()
+                    .enumerate()
+                    .map(|(x, (i, share))| {
+                        Bar::new(x as f64, *share)
+                            .width(0.8)
+                            .name(&graph[rows[*i].component].label)
+                    })
+                    .collect();
+                let mut cumulative = 0.0;
+                let line: Vec<[f64; 2]> = shares
+                    .iter()
+                    .enumerate()
+                    .map(|(x, (_, share))| {
+                        cumulative += share;
+                        [x as f64, cumulative]
+                    })
+                    .collect();
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("pareto-threshold"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.threshold)
+                            .clamp_range(0.0..=100.0)
+                            .suffix(" %"),
+                    );
+                    let vital = line
+                        .iter()
+                        .position(|[_, cumulative]| *cumulative >= self.threshold)
+                        .map_or(line.len(), |i| i + 1);
+                    ui.label(tr_with(
+                        "pareto-vital-few",
+                        &[("count", &vital), ("total", &line.len())],
+                    ));
+                });
+
+                let plot_names = names.clone();
+                Plot::new("pareto")
+                    .height(300.0)
+                    .include_y(0.0)
+                    .include_y(100.0)
+                    .y_axis_label(tr("pareto-share"))
+                    .x_grid_spacer(uniform_grid_spacer(|_| [1.0, 5.0, 10.0]))
+                    .x_axis_formatter(move |mark, _, _| {
+                        let x = mark.value;
+                        if x.fract() == 0.0 && x >= 0.0 {
+                            plot_names.get(x as usize).cloned().unwrap_or_default()
+                        } else {
+                            String::new()
+                        }
+                    })
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(
+                            BarChart::new(bars)
+                                .color(theme.blue)
+                                .name(tr("pareto-contribution")),
+                        );
+                        plot_ui.line(
+                            Line::new(line)
+                                .color(theme.peach)
+                                .name(tr("pareto-cumulative")),
+                        );
+                        plot_ui.hline(
+                            HLine::new(self.threshold)
+                                .color(theme.overlay2)
+                                .style(LineStyle::dashed_loose()),
+                        );
+                    });
+
+                if ui.button(tr("plot-copy-data")).clicked() {
+                    let mut tsv = format!(
+                        "{}\t{}\n",
+                        tr("goal-seek-component"),
+                        tr("pareto-contribution")
+                    );
+                    for (name, (_, share)) in names.iter().zip(&shares) {
+                        tsv.push_str(&format!("{name}\t{share}\n"));
+                    }
+                    ui.output_mut(|o| o.copied_text = tsv);
+                }
+                ui.label(tr("pareto-hint"));
+            });
+    }
+}
@@ -0,0 +1,530 @@
+//! Stochastic Petri nets, for dependencies that series and parallel logic
+//! can't express, like spares and repair crews shared between units or
+//! steps that must happen in order. Tokens sit in places; a transition is
+//! enabled while its input places hold enough tokens, fires after a random
+//! delay, and moves tokens from its inputs to its outputs. The subsystem
+//! works while one place holds enough tokens.
+//!
+//! Transitions race: the enabled one whose delay runs out first fires, and
+//! the others keep the delays they drew for as long as they stay enabled.
+//! Each transition fires one at a time, however many tokens enable it.
+//!
+//! R(t) and A(t) are estimated by simulation, and kept for as long as the
+//! net is unchanged, as evaluation runs every frame.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use eframe::egui;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::NodeParameters;
+use crate::settings;
+use crate::variables;
+
+/// Firings after which a trial is taken to be stuck in a loop of
+/// transitions without delay.
+const MAX_FIRINGS: usize = 1_000_000;
+
+/// Nets simulated are kept up to this many, then all dropped.
+const CACHE_SIZE: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    pub name: String,
+    /// The tokens in the place at the start.
+    pub tokens: u32,
+}
+
+/// How long a transition takes to fire once enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Delay {
+    /// At a constant rate per time step, like a random failure.
+    Exponential {
+        rate: f64,
+    },
+    /// After a fixed time; zero fires at once.
+    Deterministic {
+        time: f64,
+    },
+    Weibull {
+        shape: f64,
+        scale: f64,
+    },
+}
+
+impl Delay {
+    fn name(self) -> String {
+        tr(match self {
+            Delay::Exponential { .. } => "petri-delay-exponential",
+            Delay::Deterministic { .. } => "petri-delay-deterministic",
+            Delay::Weibull { .. } => "petri-delay-weibull",
+        })
+    }
+
+    fn sample(self, rng: &mut impl Rng) -> f64 {
+        // 1 − U lies in (0, 1], so its logarithm is finite.
+        let mut exponential = || -(1.0 - rng.gen::<f64>()).ln();
+        match self {
+            Delay::Exponential { rate } => exponential() / rate,
+            Delay::Deterministic { time } => time,
+            Delay::Weibull { shape, scale } => scale * exponential().powf(1.0 / shape),
+        }
+    }
+
+    fn check(self) -> Result<(), String> {
+        let valid = match self {
+            Delay::Exponential { rate } => rate > 0.0 && rate.is_finite(),
+            Delay::Deterministic { time } => time >= 0.0 && time.is_finite(),
+            Delay::Weibull { shape, scale } => {
+                shape > 0.0 && scale > 0.0 && shape.is_finite() && scale.is_finite()
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(tr("petri-error-delay"))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub name: String,
+    pub delay: Delay,
+    /// The tokens taken from each place when it fires, by place index.
+    pub takes: Vec<u32>,
+    /// The tokens put into each place when it fires, by place index.
+    pub gives: Vec<u32>,
+}
+
+impl Transition {
+    fn takes(&self, place: usize) -> u32 {
+        self.takes.get(place).copied().unwrap_or(0)
+    }
+
+    fn gives(&self, place: usize) -> u32 {
+        self.gives.get(place).copied().unwrap_or(0)
+    }
+}
+
+/// R(t) and A(t) of a net, at each time step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Curves {
+    pub reliability: Vec<f64>,
+    pub availability: Vec<f64>,
+}
+
+thread_local! {
+    static SIMULATED: RefCell<HashMap<String, Rc<Result<Curves, String>>>> =
+        RefCell::new(HashMap::new());
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PetriNet {
+    pub places: Vec<Place>,
+    pub transitions: Vec<Transition>,
+    /// The place whose tokens tell whether the subsystem works.
+    pub watched: usize,
+    /// The fewest tokens in `watched` with which the subsystem works.
+    pub needed: u32,
+    pub trials: usize,
+    pub seed: u64,
+    pub time_steps: usize,
+}
+
+impl Default for PetriNet {
+    /// A unit repaired by a crew, which must be free to start on it.
+    fn default() -> Self {
+        let place = |name: &str, tokens| Place {
+            name: tr(name),
+            tokens,
+        };
+        let transition = |name: &str, delay, takes: [u32; 4], gives: [u32; 4]| Transition {
+            name: tr(name),
+            delay,
+            takes: takes.to_vec(),
+            gives: gives.to_vec(),
+        };
+        Self {
+            places: vec![
+                place("petri-example-up", 1),
+                place("petri-example-down", 0),
+                place("petri-example-crew", 1),
+                place("petri-example-repairing", 0),
+            ],
+            transitions: vec![
+                transition(
+                    "petri-example-fail",
+                    Delay::Exponential { rate: 1e-3 },
+                    [1, 0, 0, 0],
+                    [0, 1, 0, 0],
+                ),
+                transition(
+                    "petri-example-start",
+                    Delay::Deterministic { time: 0.0 },
+                    [0, 1, 1, 0],
+                    [0, 0, 0, 1],
+                ),
+                transition(
+                    "petri-example-repair",
+                    Delay::Exponential { rate: 0.05 },
+                    [0, 0, 0, 1],
+                    [1, 0, 1, 0],
+                ),
+            ],
+            watched: 0,
+            needed: 1,
+            trials: 2_000,
+            seed: 1,
+            time_steps: NodeParameters::default().time_steps,
+        }
+    }
+}
+
+impl PetriNet {
+    fn check(&self) -> Result<(), String> {
+        if self.watched >= self.places.len() {
+            return Err(tr("petri-error-no-place"));
+        }
+        if self.trials == 0 {
+            return Err(tr("petri-error-no-trials"));
+        }
+        for transition in &self.transitions {
+            transition
+                .delay
+                .check()
+                .map_err(|err| format!("{}: {err}", transition.name))?;
+        }
+        Ok(())
+    }
+
+    fn works(&self, marking: &[u32]) -> bool {
+        marking[self.watched] >= self.needed
+    }
+
+    fn enabled(&self, transition: &Transition, marking: &[u32]) -> bool {
+        marking
+            .iter()
+            .enumerate()
+            .all(|(place, tokens)| *tokens >= transition.takes(place))
+    }
+
+    /// Plays the net out once until `horizon`, telling `visit` when the
+    /// subsystem starts or stops working, from time 0. Stops early when
+    /// `visit` returns false or nothing is left to fire.
+    fn play(
+        &self,
+        horizon: f64,
+        rng: &mut impl Rng,
+        mut visit: impl FnMut(f64, bool) -> bool,
+    ) -> Result<(), String> {
+        let mut marking: Vec<u32> = self.places.iter().map(|place| place.tokens).collect();
+        let mut working = self.works(&marking);
+        if !visit(0.0, working) {
+            return Ok(());
+        }
+        let mut due: Vec<Option<f64>> = self
+            .transitions
+            .iter()
+            .map(|transition| {
+                self.enabled(transition, &marking)
+                    .then(|| transition.delay.sample(rng))
+            })
+            .collect();
+        for _ in 0..MAX_FIRINGS {
+            let next = due
+                .iter()
+                .enumerate()
+                .filter_map(|(i, due)| due.map(|time| (i, time)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            let Some((fired, now)) = next else {
+                return Ok(());
+            };
+            if now > horizon {
+                return Ok(());
+            }
+            let transition = &self.transitions[fired];
+            for (place, tokens) in marking.iter_mut().enumerate() {
+                *tokens = *tokens - transition.takes(place) + transition.gives(place);
+            }
+            if self.works(&marking) != working {
+                working = !working;
+                if !visit(now, working) {
+                    return Ok(());
+                }
+            }
+            for (i, transition) in self.transitions.iter().enumerate() {
+                due[i] = if !self.enabled(transition, &marking) {
+                    None
+                } else if i == fired || due[i].is_none() {
+                    Some(now + transition.delay.sample(rng))
+                } else {
+                    due[i]
+                };
+            }
+        }
+        Err(tr("petri-error-loop"))
+    }
+
+    fn simulate(&self) -> Result<Curves, String> {
+        self.check()?;
+        let steps = self.time_steps + 1;
+        let mut up_throughout = vec![0usize; steps];
+        let mut up = vec![0usize; steps];
+        let mut rng = settings::rng(self.seed);
+        for _ in 0..self.trials {
+            let mut changes = Vec::new();
+            self.play(self.time_steps as f64, &mut rng, |time, working| {
+                changes.push((time, working));
+                true
+            })?;
+            let first_failure = changes
+                .iter()
+                .find(|(_, working)| !working)
+                .map_or(f64::INFINITY, |(time, _)| *time);
+            let mut change = 0;
+            let mut working = false;
+            for (step, (throughout, up)) in up_throughout.iter_mut().zip(&mut up).enumerate() {
+                let time = step as f64;
+                while change < changes.len() && changes[change].0 <= time {
+                    working = changes[change].1;
+                    change += 1;
+                }
+                *throughout += usize::from(time < first_failure);
+                *up += usize::from(working);
+            }
+        }
+        let share = |counts: Vec<usize>| {
+            counts
+                .into_iter()
+                .map(|count| count as f64 / self.trials as f64)
+                .collect()
+        };
+        Ok(Curves {
+            reliability: share(up_throughout),
+            availability: share(up),
+        })
+    }
+
+    /// The net's R(t) and A(t), simulated once for each version of it.
+    pub fn curves(&self) -> Result<Curves, String> {
+        let key = serde_json::to_string(self).expect("nets always serialize");
+        SIMULATED.with(|simulated| {
+            let mut simulated = simulated.borrow_mut();
+            if let Some(curves) = simulated.get(&key) {
+                return curves.as_ref().clone();
+            }
+            if simulated.len() >= CACHE_SIZE {
+                simulated.clear();
+            }
+            let curves = Rc::new(self.simulate());
+            simulated.insert(key, curves.clone());
+            curves.as_ref().clone()
+        })
+    }
+
+    /// One time to failure of the subsystem, infinite if it never fails.
+    pub fn sample_failure(&self, rng: &mut impl Rng) -> Result<f64, String> {
+        self.check()?;
+        let mut failure = f64::INFINITY;
+        self.play(f64::INFINITY, rng, |time, working| {
+            if !working {
+                failure = time;
+            }
+            working
+        })?;
+        Ok(failure)
+    }
+
+    fn add_place(&mut self) {
+        self.places.push(Place {
+            name: format!("{} {}", tr("petri-place"), self.places.len() + 1),
+            tokens: 0,
+        });
+    }
+
+    fn remove_place(&mut self, place: usize) {
+        self.places.remove(place);
+        for transition in &mut self.transitions {
+            for arcs in [&mut transition.takes, &mut transition.gives] {
+                if place < arcs.len() {
+                    arcs.remove(place);
+                }
+            }
+        }
+        if self.watched > place {
+            self.watched -= 1;
+        }
+        self.watched = self.watched.min(self.places.len().saturating_sub(1));
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("petri net").num_columns(2).show(ui, |ui| {
+            ui.label(tr("petri-works-while"));
+            ui.horizontal(|ui| {
+                let watched = self
+                    .places
+                    .get(self.watched)
+                    .map(|place| place.name.clone());
+                egui::ComboBox::from_id_source("petri watched")
+                    .selected_text(watched.unwrap_or_default())
+                    .show_ui(ui, |ui| {
+                        for (i, place) in self.places.iter().enumerate() {
+                            ui.selectable_value(&mut self.watched, i, &place.name);
+                        }
+                    });
+                ui.label("≥");
+                ui.add(variables::drag_value(&mut self.needed).clamp_range(0..=u32::MAX));
+            });
+            ui.end_row();
+            ui.label(tr("petri-trials"));
+            ui.add(variables::drag_value(&mut self.trials).clamp_range(100..=1_000_000))
+                .on_hover_text(tr("petri-trials-hint"));
+            ui.end_row();
+            ui.label(tr("settings-seed"));
+            ui.add(variables::drag_value(&mut self.seed));
+            ui.end_row();
+            ui.label(tr("param-time-steps"));
+            ui.add(variables::drag_value(&mut self.time_steps).clamp_range(1..=1_000_000));
+            ui.end_row();
+        });
+
+        let title = tr_with("petri-places", &[("count", &self.places.len())]);
+        ui.collapsing(title, |ui| {
+            let mut remove = None;
+            egui::Grid::new("petri places")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.strong(tr("petri-place"));
+                    ui.strong(tr("petri-tokens"));
+                    ui.end_row();
+                    for (i, place) in self.places.iter_mut().enumerate() {
+                        ui.add(egui::TextEdit::singleline(&mut place.name).desired_width(100.0));
+                        ui.add(variables::drag_value(&mut place.tokens));
+                        if ui.small_button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                self.remove_place(i);
+            }
+            if ui.small_button(tr("petri-add-place")).clicked() {
+                self.add_place();
+            }
+        });
+
+        let title = tr_with("petri-transitions", &[("count", &self.transitions.len())]);
+        ui.collapsing(title, |ui| {
+            let mut remove = None;
+            for (i, transition) in self.transitions.iter_mut().enumerate() {
+                ui.push_id(i, |ui| {
+                    transition_ui(ui, transition, &self.places);
+                    if ui.small_button(tr("petri-remove-transition")).clicked() {
+                        remove = Some(i);
+                    }
+                });
+                ui.separator();
+            }
+            if let Some(i) = remove {
+                self.transitions.remove(i);
+            }
+            if ui.small_button(tr("petri-add-transition")).clicked() {
+                self.transitions.push(Transition {
+                    name: format!("{} {}", tr("petri-transition"), self.transitions.len() + 1),
+                    delay: Delay::Exponential { rate: 1e-3 },
+                    takes: vec![0; self.places.len()],
+                    gives: vec![0; self.places.len()],
+                });
+            }
+        });
+
+        if let Err(err) = self.check() {
+            ui.colored_label(ui.visuals().warn_fg_color, err);
+        }
+    }
+}
+
+/// A transition's name and delay, and the tokens it moves in a grid with a
+/// column for each place.
+fn transition_ui(ui: &mut egui::Ui, transition: &mut Transition, places: &[Place]) {
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut transition.name).desired_width(100.0));
+        egui::ComboBox::from_id_source("delay")
+            .selected_text(transition.delay.name())
+            .show_ui(ui, |ui| {
+                let options = [
+                    Delay::Exponential { rate: 1e-3 },
+                    Delay::Deterministic { time: 0.0 },
+                    Delay::Weibull {
+                        shape: 1.0,
+                        scale: 1_000.0,
+                    },
+                ];
+                for option in options {
+                    let selected = std::mem::discriminant(&option)
+                        == std::mem::discriminant(&transition.delay);
+                    if ui.selectable_label(selected, option.name()).clicked() && !selected {
+                        transition.delay = option;
+                    }
+                }
+            });
+        match &mut transition.delay {
+            Delay::Exponential { rate } => {
+                ui.add(
+                    variables::drag_value(rate)
+                        .speed(1e-5)
+                        .clamp_range(1e-12..=f64::MAX)
+                        .prefix("λ = "),
+                );
+            }
+            Delay::Deterministic { time } => {
+                ui.add(
+                    variables::drag_value(time)
+                        .speed(1.0)
+                        .clamp_range(0.0..=f64::MAX)
+                        .prefix("t = "),
+                );
+            }
+            Delay::Weibull { shape, scale } => {
+                ui.add(
+                    variables::drag_value(shape)
+                        .speed(0.01)
+                        .clamp_range(0.01..=f64::MAX)
+                        .prefix("β = "),
+                );
+                ui.add(
+                    variables::drag_value(scale)
+                        .speed(10.0)
+                        .clamp_range(0.01..=f64::MAX)
+                        .prefix("η = "),
+                );
+            }
+        }
+    });
+    transition.takes.resize(places.len(), 0);
+    transition.gives.resize(places.len(), 0);
+    egui::Grid::new("arcs")
+        .num_columns(places.len() + 1)
+        .show(ui, |ui| {
+            ui.label("");
+            for place in places {
+                ui.strong(&place.name);
+            }
+            ui.end_row();
+            for (label, arcs) in [
+                (tr("petri-takes"), &mut transition.takes),
+                (tr("petri-gives"), &mut transition.gives),
+            ] {
+                ui.label(label);
+                for tokens in arcs.iter_mut() {
+                    ui.add(variables::drag_value(tokens));
+                }
+                ui.end_row();
+            }
+        });
+}
@@ -0,0 +1,347 @@
+//! The reliability plot shown above the graph editor.
+use eframe::egui;
+use egui_plot::{
+    AxisHints, HLine, HPlacement, Legend, Line, LineStyle, Plot, PlotPoints, Polygon, VLine,
+};
+
+use crate::audit::Audit;
+use crate::bands::{Band, Level};
+use crate::eval;
+use crate::i18n::tr;
+use crate::locale;
+use crate::nodes::{Graph, GraphState};
+use crate::viewport;
+
+/// Segments a band is drawn with at most; egui only fills convex shapes, so
+/// it is drawn as a strip of quadrilaterals.
+const BAND_SEGMENTS: usize = 300;
+
+/// Plots R(t), the point availability A(t) when given, and the hazard rate
+/// h(t) on a second axis when the user asks for it. A band, when given, is
+/// shaded at the level picked in the plot's context menu.
+pub fn reliability_plot(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    height: f32,
+    curve: &[f64],
+    availability: Option<&[f64]>,
+    band: Option<&Band>,
+    state: &GraphState,
+) -> egui::Response {
+    let level = Level::of(ui.ctx(), id_source);
+    let theme = state.settings.theme.catppuccin();
+    let requirement = state.requirement;
+    let points: Vec<[f64; 2]> = curve
+        .iter()
+        .enumerate()
+        .map(|(t, r)| [t as f64, *r])
+        .collect();
+    let mut line = Line::new(PlotPoints::from(points.clone()));
+    match requirement.is_met(curve) {
+        Some(true) => line = line.color(theme.green),
+        Some(false) => line = line.color(theme.red),
+        None => {}
+    }
+
+    // h(t) shares the unit interval with R(t), scaled by its largest finite
+    // value, which the right-hand axis undoes in its labels.
+    let hazard = state.show_hazard.then(|| eval::hazard_rate(curve));
+    let hazard_max = hazard
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|h| h.is_finite())
+        .fold(0.0, f64::max);
+    let y_label = if availability.is_some() {
+        "R(t), A(t)"
+    } else {
+        "R(t)"
+    };
+    let mut plot = Plot::new(id_source)
+        .height(height)
+        .include_y(0.0)
+        .include_y(1.0)
+        .x_axis_label(format!("t [{}]", state.settings.time_unit.symbol()))
+        .y_axis_label(y_label)
+        .legend(Legend::default());
+    if hazard_max > 0.0 {
+        let unit = state.settings.time_unit.symbol();
+        plot = plot.custom_y_axes(vec![
+            AxisHints::new_y().label(y_label),
+            AxisHints::new_y()
+                .label(format!("h(t) [1/{unit}]"))
+                .placement(HPlacement::Right)
+                .formatter(move |mark, _, _| format!("{:.2e}", mark.value * hazard_max)),
+        ]);
+    }
+
+    let response = plot
+        .show(ui, |plot_ui| {
+            if let Some((lower, upper)) = band.and_then(|band| band.at(level)) {
+                let color = theme.blue.gamma_multiply(0.25);
+                let step = (lower.len() / BAND_SEGMENTS).max(1);
+                let ts: Vec<usize> = (0..lower.len())
+                    .step_by(step)
+                    .chain([lower.len().saturating_sub(1)])
+                    .collect();
+                for pair in ts.windows(2).filter(|pair| pair[0] < pair[1]) {
+                    let (a, b) = (pair[0], pair[1]);
+                    let quad = vec![
+                        [a as f64, lower[a]],
+                        [b as f64, lower[b]],
+                        [b as f64, upper[b]],
+                        [a as f64, upper[a]],
+                    ];
+                    plot_ui.polygon(
+                        Polygon::new(quad)
+                            .fill_color(color)
+                            .stroke(egui::Stroke::NONE)
+                            .name(level.name()),
+                    );
+                }
+            }
+            plot_ui.line(line.name("R(t)"));
+            if let Some(hazard) = hazard.as_ref().filter(|_| hazard_max > 0.0) {
+                let points: Vec<[f64; 2]> = hazard
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, h)| h.is_finite())
+                    .map(|(t, h)| [t as f64, h / hazard_max])
+                    .collect();
+                plot_ui.line(Line::new(points).color(theme.peach).name("h(t)"));
+            }
+            if let Some(availability) = availability {
+                let points: Vec<[f64; 2]> = availability
+                    .iter()
+                    .enumerate()
+                    .map(|(t, a)| [t as f64, *a])
+                    .collect();
+                plot_ui.line(Line::new(points).color(theme.sapphire).name("A(t)"));
+            }
+            plot_ui.vline(VLine::new(state.mission_time as f64).name(tr("mission-time")));
+            if requirement.enabled {
+                let style = LineStyle::dashed_loose();
+                plot_ui.hline(
+                    HLine::new(requirement.reliability)
+                        .color(theme.overlay2)
+                        .style(style)
+                        .name(tr("requirement")),
+                );
+                plot_ui.vline(
+                    VLine::new(requirement.time as f64)
+                        .color(theme.overlay2)
+                        .style(style)
+                        .name(tr("requirement")),
+                );
+            }
+        })
+        .response;
+    response.context_menu(|ui| {
+        if ui.button(tr("plot-copy-data")).clicked() {
+            ui.output_mut(|o| o.copied_text = to_tsv(&points));
+            ui.close_menu();
+        }
+        if band.is_some() {
+            ui.separator();
+            for choice in Level::ALL {
+                if ui.radio(level == choice, choice.name()).clicked() {
+                    choice.set(ui.ctx(), id_source);
+                    ui.close_menu();
+                }
+            }
+        }
+    });
+    response
+}
+
+/// How much larger text is in the enlarged plot.
+const ENLARGED_TEXT_SCALE: f32 = 1.5;
+
+/// The reliability plot in a window of its own, nearly filling the screen.
+#[derive(Default)]
+pub struct EnlargedPlot {
+    export_error: Option<String>,
+}
+
+impl EnlargedPlot {
+    #[allow(clippy::too_many_arguments)]
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        curve: &[f64],
+        availability: Option<&[f64]>,
+        band: Option<&Band>,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("plot-enlarged-title"))
+            .open(open)
+            .collapsible(false)
+            .default_size(ctx.screen_rect().size() * 0.9)
+            .show(ctx, |ui| {
+                for font in ui.style_mut().text_styles.values_mut() {
+                    font.size *= ENLARGED_TEXT_SCALE;
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("plot-export-csv")).clicked() {
+                        self.export_error =
+                            export(curve, availability, &Audit::new(graph, state)).err();
+                    }
+                    if let Some(err) = &self.export_error {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                });
+                let height = ui.available_height();
+                reliability_plot(
+                    ui,
+                    "enlarged reliability plot",
+                    height,
+                    curve,
+                    availability,
+                    band,
+                    state,
+                );
+            });
+    }
+}
+
+/// The plot and the system's figures at the mission time and checkpoints, in
+/// an OS window of its own that can be moved to another monitor. Backends
+/// without multiple viewports show it as a window inside the app instead.
+#[derive(Default)]
+pub struct PoppedOutPlot {
+    export_error: Option<String>,
+}
+
+impl PoppedOutPlot {
+    #[allow(clippy::too_many_arguments)]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        curve: &[f64],
+        availability: Option<&[f64]>,
+        band: Option<&Band>,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        if !*open {
+            return;
+        }
+        viewport::show(
+            ctx,
+            "popped out plot",
+            tr("plot-enlarged-title"),
+            [900.0, 650.0],
+            open,
+            |ui| self.contents(ui, curve, availability, band, graph, state),
+        );
+    }
+
+    fn contents(
+        &mut self,
+        ui: &mut egui::Ui,
+        curve: &[f64],
+        availability: Option<&[f64]>,
+        band: Option<&Band>,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        let symbol = state.settings.time_unit.symbol();
+        ui.horizontal(|ui| {
+            if let Some(node) = state.active_node.and_then(|node| graph.nodes.get(node)) {
+                ui.strong(&node.label);
+                ui.separator();
+            }
+            ui.label(format!("MTTF = {:.1} {symbol}", eval::mttf(curve)));
+            for percent in &state.settings.b_lives {
+                let life = eval::format_b_life(eval::b_life(curve, *percent));
+                ui.label(format!("B{percent} = {life} {symbol}"));
+            }
+            ui.separator();
+            if ui.button(tr("plot-export-csv")).clicked() {
+                self.export_error = export(curve, availability, &Audit::new(graph, state)).err();
+            }
+            if let Some(err) = &self.export_error {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+        });
+        let mut times = vec![state.mission_time];
+        times.extend(
+            state
+                .checkpoints
+                .iter()
+                .filter(|t| **t != state.mission_time),
+        );
+        egui::Grid::new("popped out figures")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("t");
+                ui.strong("R(t)");
+                ui.strong("A(t)");
+                ui.end_row();
+                let value = |curve: Option<&[f64]>, time: usize| {
+                    curve
+                        .and_then(|curve| curve.get(time))
+                        .map_or_else(|| "—".to_owned(), |value| format!("{value:.6}"))
+                };
+                for time in times {
+                    ui.label(format!("{time} {symbol}"));
+                    ui.label(value(Some(curve), time));
+                    ui.label(value(availability, time));
+                    ui.end_row();
+                }
+            });
+        let height = ui.available_height().max(200.0);
+        reliability_plot(
+            ui,
+            "popped out reliability plot",
+            height,
+            curve,
+            availability,
+            band,
+            state,
+        );
+    }
+}
+
+/// The curves as CSV rows of `t`, `R` and, when given, `A`.
+fn to_csv(curve: &[f64], availability: Option<&[f64]>) -> String {
+    let mut header = vec!["t".to_owned(), "R".to_owned()];
+    if availability.is_some() {
+        header.push("A".to_owned());
+    }
+    let mut csv = locale::row(&header);
+    for (t, r) in curve.iter().enumerate() {
+        let mut fields = vec![t.to_string(), locale::number(r)];
+        if let Some(a) = availability.and_then(|a| a.get(t)) {
+            fields.push(locale::number(a));
+        }
+        csv.push_str(&locale::row(&fields));
+    }
+    csv
+}
+
+/// Asks for a file name and writes the curves there, headed by `audit`.
+fn export(curve: &[f64], availability: Option<&[f64]>, audit: &Audit) -> Result<(), String> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .set_file_name("reliability.csv")
+        .save_file()
+    else {
+        return Ok(());
+    };
+    std::fs::write(path, audit.comments("# ") + &to_csv(curve, availability))
+        .map_err(|err| err.to_string())
+}
+
+/// Formats plot points as tab-separated `t`, `value` rows for pasting into a spreadsheet.
+fn to_tsv(points: &[[f64; 2]]) -> String {
+    let mut tsv = String::from("t\tvalue\n");
+    for [t, value] in points {
+        tsv.push_str(&format!("{t}\t{value}\n"));
+    }
+    tsv
+}
@@ -0,0 +1,237 @@
+//! Piecewise-constant stress profiles that scale component hazards over the mission.
+use std::ops::Range;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::settings::TimeUnit;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    pub name: String,
+    pub duration: f64,
+    /// Multiplies every component's hazard rate during the segment.
+    pub stress: f64,
+}
+
+/// A sequence of segments that repeats for as long as the system is evaluated.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MissionProfile {
+    pub enabled: bool,
+    pub segments: Vec<Segment>,
+}
+
+impl MissionProfile {
+    fn period(&self) -> f64 {
+        self.segments.iter().map(|segment| segment.duration).sum()
+    }
+
+    /// Whether the profile scales hazards at all.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.period() > 0.0
+    }
+
+    /// The stress at time `t` and the time the segment containing it ends.
+    fn segment_at(&self, t: f64) -> (f64, f64) {
+        let period = self.period();
+        let mut end = (t / period).floor() * period;
+        for segment in &self.segments {
+            end += segment.duration;
+            if end > t {
+                return (segment.stress, end);
+            }
+        }
+        // Rounding put `t` at the very end of a cycle.
+        let first = self
+            .segments
+            .iter()
+            .find(|segment| segment.duration > 0.0)
+            .expect("an active profile has a segment with a duration");
+        (first.stress, t + first.duration)
+    }
+
+    /// H(t) = ∫₀ᵗ s(u) dH₀(u) at each time step 0..=steps, for a base
+    /// cumulative hazard H₀, integrated segment by segment.
+    pub fn cumulative_hazards(&self, base: impl Fn(f64) -> f64, steps: usize) -> Vec<f64> {
+        if !self.is_active() {
+            return (0..=steps).map(|t| base(t as f64)).collect();
+        }
+        self.cumulative_hazards_from(base, (0.0, 0.0), 0..steps + 1)
+            .0
+    }
+
+    /// Like [`Self::cumulative_hazards`], for the time steps `times` only,
+    /// carrying on from H = `from.1` at time `from.0 <= times.start`. Also
+    /// returns where the integration stopped, to carry on from next.
+    pub fn cumulative_hazards_from(
+        &self,
+        base: impl Fn(f64) -> f64,
+        from: (f64, f64),
+        times: Range<usize>,
+    ) -> (Vec<f64>, (f64, f64)) {
+        let mut hazards = Vec::with_capacity(times.len());
+        let (mut start, mut hazard) = from;
+        for t in times {
+            let t = t as f64;
+            while start < t {
+                let (stress, end) = self.segment_at(start);
+                let end = end.min(t);
+                hazard += stress * (base(end) - base(start));
+                start = end;
+            }
+            hazards.push(hazard);
+        }
+        (hazards, (start, hazard))
+    }
+
+    /// The age at which the cumulative hazard of a unit installed at `start`
+    /// reaches `target`, given H₀ of its age and the inverse. Used to sample
+    /// failure times from H = E, E ~ Exp(1).
+    pub fn time_at_hazard(
+        &self,
+        start: f64,
+        base: impl Fn(f64) -> f64,
+        inverse: impl Fn(f64) -> f64,
+        target: f64,
+    ) -> f64 {
+        if !self.is_active() {
+            return inverse(target);
+        }
+        if !self
+            .segments
+            .iter()
+            .any(|segment| segment.stress > 0.0 && segment.duration > 0.0)
+        {
+            return f64::INFINITY;
+        }
+        let (mut hazard, mut time) = (0.0, start);
+        loop {
+            let (stress, end) = self.segment_at(time);
+            let (from, to) = (time - start, end - start);
+            let step = stress * (base(to) - base(from));
+            if hazard + step >= target && stress > 0.0 {
+                return inverse(base(from) + (target - hazard) / stress);
+            }
+            hazard += step;
+            time = end;
+        }
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context, open: &mut bool, time_unit: TimeUnit) {
+        egui::Window::new(tr("profile-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                ui.checkbox(&mut self.enabled, tr("profile-enabled"));
+                ui.label(tr("profile-hint"));
+
+                let mut remove = None;
+                egui::Grid::new("mission profile")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("profile-segment"));
+                        ui.strong(tr("profile-duration"));
+                        ui.strong(tr("profile-stress"));
+                        ui.end_row();
+                        for (i, segment) in self.segments.iter_mut().enumerate() {
+                            ui.text_edit_singleline(&mut segment.name);
+                            ui.add(
+                                egui::DragValue::new(&mut segment.duration)
+                                    .clamp_range(0.0..=f64::MAX)
+                                    .suffix(format!(" {}", time_unit.symbol())),
+                            );
+                            ui.add(
+                                egui::DragValue::new(&mut segment.stress)
+                                    .speed(0.01)
+                                    .clamp_range(0.0..=100.0)
+                                    .suffix("×"),
+                            );
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(i) = remove {
+                    self.segments.remove(i);
+                }
+                if ui.button(tr("profile-add-segment")).clicked() {
+                    self.segments.push(Segment {
+                        name: format!("{} {}", tr("profile-segment"), self.segments.len() + 1),
+                        duration: 1.0,
+                        stress: 1.0,
+                    });
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// H₀ of a wear-out unit with a characteristic life of 100.
+    fn base(t: f64) -> f64 {
+        (t / 100.0).powf(2.0)
+    }
+
+    fn profile(segments: &[(f64, f64)]) -> MissionProfile {
+        MissionProfile {
+            enabled: true,
+            segments: segments
+                .iter()
+                .map(|&(duration, stress)| Segment {
+                    name: String::new(),
+                    duration,
+                    stress,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn a_single_phase_at_nominal_stress_changes_nothing() {
+        let plain = MissionProfile::default().cumulative_hazards(base, 250);
+        let nominal = profile(&[(40.0, 1.0)]).cumulative_hazards(base, 250);
+        for (t, (a, b)) in nominal.iter().zip(&plain).enumerate() {
+            assert!(
+                (a - b).abs() < 1e-12,
+                "H({t}) = {a} under the profile, {b} without"
+            );
+        }
+    }
+
+    #[test]
+    fn phase_boundaries_carry_the_cumulative_hazard_across() {
+        // Boundaries at 30.5, 100, 130.5 and 200 fall between and on steps.
+        let profile = profile(&[(30.5, 1.0), (69.5, 3.0)]);
+        let hazards = profile.cumulative_hazards(base, 250);
+        let mut expected = 0.0;
+        let mut start = 0.0;
+        for (t, hazard) in hazards.iter().enumerate() {
+            let t = t as f64;
+            // The stress-weighted increments of every phase passed so far.
+            for boundary in [30.5, 100.0, 130.5, 200.0, 230.5, t] {
+                if start < boundary && boundary <= t {
+                    let stress = if start % 100.0 < 30.5 { 1.0 } else { 3.0 };
+                    expected += stress * (base(boundary) - base(start));
+                    start = boundary;
+                }
+            }
+            assert!(
+                (hazard - expected).abs() < 1e-12,
+                "H({t}) = {hazard}, expected {expected}"
+            );
+            // Sampling inverts the same curve across the boundaries.
+            if t > 0.0 {
+                let time = profile.time_at_hazard(0.0, base, |h| 100.0 * h.sqrt(), *hazard);
+                assert!(
+                    (time - t).abs() < 1e-9,
+                    "H = {hazard} is reached at {time}, not {t}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,544 @@
+//! Project files: the graph, its layout and the analysis settings as JSON,
+//! RON, YAML or a compact binary encoding.
+use std::collections::HashMap;
+use std::path::Path;
+
+use eframe::egui::{self, containers::panel::PanelState};
+use egui_node_graph2::{InputId, NodeId, OutputId};
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, GraphState};
+
+/// Bumped whenever a change to the file layout breaks older projects, along
+/// with a step in [`MIGRATIONS`] that upgrades them.
+pub const VERSION: u32 = 1;
+
+/// What binary project files start with, ahead of their version and the
+/// checksum of the rest, each as four little-endian bytes.
+const BINARY_MAGIC: &[u8; 4] = b"BLK\0";
+
+/// The first line of a RON project, followed by the checksum of the rest of
+/// the file in hex.
+const RON_CHECKSUM: &str = "// checksum: ";
+
+/// Upgrades a project file by one version, editing its JSON in place.
+type Migration = fn(&mut serde_json::Value) -> anyhow::Result<()>;
+
+/// `MIGRATIONS[i]` brings a file from version `i + 1` to `i + 2`, so a file
+/// of any older version goes through each step after its own in turn.
+const MIGRATIONS: [Migration; VERSION as usize - 1] = [];
+
+/// How far right of the rightmost node's left edge an imported model starts,
+/// leaving room for that node's width.
+const IMPORT_GAP: f32 = 400.0;
+
+/// The sizes of the panels around the graph editor as last drawn, so a
+/// project reopens looking as it was saved. The editor's pan and zoom are
+/// saved with the editor itself.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub plot_height: Option<f32>,
+    pub legend_width: Option<f32>,
+    pub what_if_width: Option<f32>,
+}
+
+impl Panels {
+    const PLOT: &'static str = "vis panel";
+    const LEGEND: &'static str = "legend";
+    const WHAT_IF: &'static str = "what-if";
+
+    /// Takes the sizes the panels were last drawn at, keeping the saved size
+    /// of any not drawn yet.
+    pub fn record(&mut self, ctx: &egui::Context) {
+        let size = |panel| PanelState::load(ctx, egui::Id::new(panel)).map(|state| state.size());
+        self.plot_height = size(Self::PLOT).map(|size| size.y).or(self.plot_height);
+        self.legend_width = size(Self::LEGEND).map(|size| size.x).or(self.legend_width);
+        self.what_if_width = size(Self::WHAT_IF)
+            .map(|size| size.x)
+            .or(self.what_if_width);
+    }
+
+    /// Has the panels drawn at these sizes from the next frame on, whether or
+    /// not they are open now.
+    pub fn restore(self, ctx: &egui::Context) {
+        let sizes = [
+            (
+                Self::PLOT,
+                self.plot_height.map(|height| egui::vec2(0.0, height)),
+            ),
+            (
+                Self::LEGEND,
+                self.legend_width.map(|width| egui::vec2(width, 0.0)),
+            ),
+            (
+                Self::WHAT_IF,
+                self.what_if_width.map(|width| egui::vec2(width, 0.0)),
+            ),
+        ];
+        for (panel, size) in sizes {
+            if let Some(size) = size {
+                let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, size);
+                ctx.data_mut(|d| d.insert_persisted(egui::Id::new(panel), PanelState { rect }));
+            }
+        }
+    }
+}
+
+/// The formats a project can be saved in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Json,
+    /// Rusty Object Notation, which names enum variants the way the code
+    /// does and allows comments, so models are easier to read and review.
+    Ron,
+    /// The JSON layout written as YAML, for pipelines that template models.
+    Yaml,
+    /// A compact encoding that isn't meant to be read, for models with
+    /// thousands of nodes: files are a fraction of the size and load faster.
+    Binary,
+}
+
+impl Format {
+    pub const ALL: [Format; 4] = [Format::Json, Format::Ron, Format::Yaml, Format::Binary];
+
+    /// The file extensions of the format, the usual one first.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Format::Json => &["json"],
+            Format::Ron => &["ron"],
+            Format::Yaml => &["yaml", "yml"],
+            Format::Binary => &["blk"],
+        }
+    }
+
+    fn filter_name(self) -> String {
+        tr(match self {
+            Format::Json => "project-filter-json",
+            Format::Ron => "project-filter-ron",
+            Format::Yaml => "project-filter-yaml",
+            Format::Binary => "project-filter-binary",
+        })
+    }
+
+    /// The format of a file, going by its extension.
+    pub fn of(path: &Path) -> Format {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+        Format::ALL
+            .into_iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Project {
+    pub version: u32,
+    pub editor: EditorState,
+    pub state: GraphState,
+    /// Whether the file's checksum doesn't match its content, as after a
+    /// truncated download or a bad copy. The project is read regardless.
+    #[serde(skip)]
+    pub damaged: bool,
+}
+
+/// The borrowed counterpart of [`Project`], so saving needn't clone the graph.
+#[derive(Serialize)]
+struct Saved<'a> {
+    version: u32,
+    editor: &'a EditorState,
+    state: &'a GraphState,
+}
+
+pub fn to_json(editor: &EditorState, state: &GraphState) -> String {
+    let saved = Saved {
+        version: VERSION,
+        editor,
+        state,
+    };
+    serde_json::to_string_pretty(&with_checksum(&saved)).expect("projects always serialize")
+}
+
+/// The project in its JSON layout, without a checksum.
+pub fn to_value(editor: &EditorState, state: &GraphState) -> serde_json::Value {
+    let saved = Saved {
+        version: VERSION,
+        editor,
+        state,
+    };
+    serde_json::to_value(saved).expect("projects always serialize")
+}
+
+/// `saved` in its JSON layout, with a checksum of the rest added as
+/// `checksum`. It is taken over the compact JSON of the layout, so it holds
+/// however the file is laid out.
+fn with_checksum(saved: &Saved) -> serde_json::Value {
+    let mut value = serde_json::to_value(saved).expect("projects always serialize");
+    let checksum = crc32fast::hash(value.to_string().as_bytes());
+    value["checksum"] = checksum.into();
+    value
+}
+
+/// Takes the checksum out of a project in its JSON layout, and whether it
+/// matches the rest; files without one are taken as intact.
+fn take_checksum(value: &mut serde_json::Value) -> bool {
+    let Some(checksum) = value.as_object_mut().and_then(|map| map.remove("checksum")) else {
+        return true;
+    };
+    checksum.as_u64() == Some(u64::from(crc32fast::hash(value.to_string().as_bytes())))
+}
+
+/// A copy of `editor` that diffs well under version control: the nodes in
+/// the order they were added rather than the order last clicked, positions
+/// on whole pixels, and no selection or half-finished interaction.
+fn for_file(editor: &EditorState) -> EditorState {
+    let mut node_order = editor.node_order.clone();
+    node_order.sort();
+    let mut node_positions = editor.node_positions.clone();
+    for position in node_positions.values_mut() {
+        *position = position.round();
+    }
+    let mut pan_zoom = editor.pan_zoom.clone();
+    pan_zoom.pan = pan_zoom.pan.round();
+    EditorState {
+        graph: editor.graph.clone(),
+        node_order,
+        node_positions,
+        pan_zoom,
+        ..EditorState::default()
+    }
+}
+
+/// The project as a file of `format`. Saving the same model always gives the
+/// same file.
+pub fn encode(editor: &EditorState, state: &GraphState, format: Format) -> Vec<u8> {
+    let editor = &for_file(editor);
+    let saved = Saved {
+        version: VERSION,
+        editor,
+        state,
+    };
+    match format {
+        Format::Json => {
+            serde_json::to_vec_pretty(&with_checksum(&saved)).expect("projects always serialize")
+        }
+        Format::Ron => {
+            let text = ron::ser::to_string_pretty(&saved, ron::ser::PrettyConfig::default())
+                .expect("projects always serialize");
+            format!(
+                "{RON_CHECKSUM}{:08x}\n{text}",
+                crc32fast::hash(text.as_bytes())
+            )
+            .into_bytes()
+        }
+        // Going through JSON keeps enums as plain maps rather than YAML tags,
+        // so the file reads back through the same migrations.
+        Format::Yaml => serde_yaml::to_string(&with_checksum(&saved))
+            .expect("projects always serialize")
+            .into_bytes(),
+        Format::Binary => {
+            let payload = bincode::serialize(&(editor, state)).expect("projects always serialize");
+            let mut bytes = BINARY_MAGIC.to_vec();
+            bytes.extend(VERSION.to_le_bytes());
+            bytes.extend(crc32fast::hash(&payload).to_le_bytes());
+            bytes.extend(payload);
+            bytes
+        }
+    }
+}
+
+pub fn decode(bytes: &[u8], format: Format) -> anyhow::Result<Project> {
+    let text = || std::str::from_utf8(bytes).map_err(|_| anyhow::anyhow!(tr("project-not-text")));
+    match format {
+        Format::Json => from_json(text()?),
+        Format::Ron => from_ron(text()?),
+        Format::Yaml => from_yaml(text()?),
+        Format::Binary => from_binary(bytes),
+    }
+}
+
+/// Rejects file versions this version of the app can't upgrade.
+fn check_version(version: u64) -> anyhow::Result<()> {
+    if version > u64::from(VERSION) {
+        anyhow::bail!(tr_with(
+            "project-newer-version",
+            &[("version", &version), ("supported", &VERSION)]
+        ));
+    }
+    if version == 0 {
+        anyhow::bail!(tr_with(
+            "project-unsupported-version",
+            &[("version", &version), ("supported", &VERSION)]
+        ));
+    }
+    Ok(())
+}
+
+pub fn from_json(text: &str) -> anyhow::Result<Project> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|err| anyhow::anyhow!(tr_with("project-not-json", &[("error", &err)])))?;
+    from_value(value, Some(text))
+}
+
+/// Reads a YAML project, which has the same layout as a JSON one.
+fn from_yaml(text: &str) -> anyhow::Result<Project> {
+    let value: serde_json::Value = serde_yaml::from_str(text)
+        .map_err(|err| anyhow::anyhow!(tr_with("project-not-yaml", &[("error", &err)])))?;
+    from_value(value, None)
+}
+
+/// Upgrades and reads a project in its JSON layout. The JSON `text` it was
+/// parsed from, if any, is read again for errors with a line and column.
+fn from_value(mut value: serde_json::Value, text: Option<&str>) -> anyhow::Result<Project> {
+    let intact = take_checksum(&mut value);
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| anyhow::anyhow!(tr("project-no-version")))?;
+    check_version(version)?;
+    for (from, migrate) in (version..).zip(&MIGRATIONS[version as usize - 1..]) {
+        migrate(&mut value).map_err(|err| {
+            anyhow::anyhow!(tr_with(
+                "project-migration-failed",
+                &[("from", &from), ("to", &(from + 1)), ("error", &err)]
+            ))
+        })?;
+    }
+    value["version"] = VERSION.into();
+
+    let project = match text {
+        Some(text) if version == u64::from(VERSION) => serde_json::from_str(text),
+        _ => serde_json::from_value(value.clone()),
+    };
+    let project = project.map_err(|err| {
+        anyhow::anyhow!(match invalid_field(&value) {
+            Some(field) => tr_with(
+                "project-invalid-field",
+                &[("field", &field), ("error", &err)]
+            ),
+            None => tr_with("project-invalid", &[("error", &err)]),
+        })
+    })?;
+    Ok(Project {
+        damaged: !intact,
+        ..project
+    })
+}
+
+/// The part of a project that can't be read, such as `state.mission_profile`.
+/// The model's settings all have defaults, so each can be read on its own;
+/// the editor's graph only as a whole.
+fn invalid_field(value: &serde_json::Value) -> Option<String> {
+    let editor = value.get("editor")?.clone();
+    if serde_json::from_value::<EditorState>(editor).is_err() {
+        return Some("editor".to_owned());
+    }
+    let state = value.get("state")?.as_object()?;
+    state
+        .iter()
+        .find(|(key, field)| {
+            let alone = serde_json::Map::from_iter([((*key).clone(), (*field).clone())]);
+            serde_json::from_value::<GraphState>(alone.into()).is_err()
+        })
+        .map(|(key, _)| format!("state.{key}"))
+}
+
+/// Reads a RON project. Migrations work on JSON, which RON's enums don't
+/// map onto, so only files of the current version are read.
+fn from_ron(text: &str) -> anyhow::Result<Project> {
+    /// Just the version, with the rest of the file skipped; there never was
+    /// a version 0.
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct Header {
+        version: u64,
+    }
+    let Header { version } = ron::from_str(text)
+        .map_err(|err| anyhow::anyhow!(tr_with("project-not-ron", &[("error", &err)])))?;
+    if version == 0 {
+        anyhow::bail!(tr("project-no-version"));
+    }
+    check_version(version)?;
+    if version != u64::from(VERSION) {
+        anyhow::bail!(tr_with(
+            "project-ron-older",
+            &[("version", &version), ("supported", &VERSION)]
+        ));
+    }
+    let project: Project = ron::from_str(text)
+        .map_err(|err| anyhow::anyhow!(tr_with("project-invalid", &[("error", &err)])))?;
+    let intact = match text
+        .strip_prefix(RON_CHECKSUM)
+        .and_then(|rest| rest.split_once('\n'))
+    {
+        Some((checksum, rest)) => {
+            u32::from_str_radix(checksum.trim(), 16) == Ok(crc32fast::hash(rest.as_bytes()))
+        }
+        None => true,
+    };
+    Ok(Project {
+        damaged: !intact,
+        ..project
+    })
+}
+
+/// Reads a binary project. Like RON, it can only be read as a whole, so
+/// only files of the current version are read.
+fn from_binary(bytes: &[u8]) -> anyhow::Result<Project> {
+    let payload = bytes
+        .strip_prefix(BINARY_MAGIC)
+        .ok_or_else(|| anyhow::anyhow!(tr("project-not-binary")))?;
+    let (version, payload) = payload
+        .split_first_chunk::<4>()
+        .ok_or_else(|| anyhow::anyhow!(tr("project-no-version")))?;
+    let version = u64::from(u32::from_le_bytes(*version));
+    check_version(version)?;
+    let (checksum, payload) = payload
+        .split_first_chunk::<4>()
+        .ok_or_else(|| anyhow::anyhow!(tr("project-not-binary")))?;
+    if version != u64::from(VERSION) {
+        anyhow::bail!(tr_with(
+            "project-binary-older",
+            &[("version", &version), ("supported", &VERSION)]
+        ));
+    }
+    let (editor, state) = bincode::deserialize(payload)
+        .map_err(|err| anyhow::anyhow!(tr_with("project-invalid", &[("error", &err)])))?;
+    Ok(Project {
+        version: VERSION,
+        editor,
+        state,
+        damaged: u32::from_le_bytes(*checksum) != crc32fast::hash(payload),
+    })
+}
+
+/// A file dialog starting in the configured model library, if there is one.
+fn dialog() -> rfd::FileDialog {
+    let dialog = rfd::FileDialog::new();
+    match config::get().library() {
+        Some(library) => dialog.set_directory(library),
+        None => dialog,
+    }
+}
+
+/// Asks for a project file and reads it, or `None` if the dialog was cancelled.
+pub fn open() -> anyhow::Result<Option<Project>> {
+    Ok(open_named()?.map(|(_, project)| project))
+}
+
+/// Like [`open`], but also gives the file's name.
+pub fn open_named() -> anyhow::Result<Option<(String, Project)>> {
+    let extensions: Vec<&str> = Format::ALL
+        .iter()
+        .flat_map(|format| format.extensions())
+        .copied()
+        .collect();
+    let Some(path) = dialog()
+        .add_filter(tr("project-filter"), &extensions)
+        .pick_file()
+    else {
+        return Ok(None);
+    };
+    let name = path
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    Ok(Some((name, read(&path)?)))
+}
+
+/// Reads the project file at `path`, in the format its extension names.
+pub fn read(path: &Path) -> anyhow::Result<Project> {
+    decode(&std::fs::read(path)?, Format::of(path))
+}
+
+/// Asks for a file name and saves the project there, in the format its
+/// extension names. Returns whether it was saved.
+pub fn save_as(editor: &EditorState, state: &GraphState) -> anyhow::Result<bool> {
+    let mut dialog = dialog().set_file_name("model.json");
+    for format in Format::ALL {
+        dialog = dialog.add_filter(format.filter_name(), format.extensions());
+    }
+    let Some(path) = dialog.save_file() else {
+        return Ok(false);
+    };
+    std::fs::write(&path, encode(editor, state, Format::of(&path)))?;
+    Ok(true)
+}
+
+/// Copies the nodes and connections of `imported` into `editor`, to the right
+/// of the existing nodes, and selects them.
+pub fn merge(editor: &mut EditorState, imported: EditorState) -> Vec<NodeId> {
+    let right = editor
+        .node_positions
+        .values()
+        .map(|pos| pos.x)
+        .fold(f32::NEG_INFINITY, f32::max);
+    let top = editor
+        .node_positions
+        .values()
+        .map(|pos| pos.y)
+        .fold(f32::INFINITY, f32::min);
+    let (left, imported_top) = imported
+        .node_positions
+        .values()
+        .fold((f32::INFINITY, f32::INFINITY), |(x, y), pos| {
+            (x.min(pos.x), y.min(pos.y))
+        });
+    let offset = if right.is_finite() && left.is_finite() {
+        egui::vec2(right + IMPORT_GAP - left, top - imported_top)
+    } else {
+        egui::Vec2::ZERO
+    };
+
+    let mut source = imported.graph;
+    let mut inputs: HashMap<InputId, InputId> = HashMap::new();
+    let mut outputs: HashMap<OutputId, OutputId> = HashMap::new();
+    let mut added = Vec::new();
+    for old in imported.node_order {
+        let Some(node) = source.nodes.remove(old) else {
+            continue;
+        };
+        let graph = &mut editor.graph;
+        let new = graph.add_node(node.label, node.user_data, |_, _| {});
+        for (name, input) in node.inputs {
+            let Some(param) = source.inputs.remove(input) else {
+                continue;
+            };
+            let id = graph.add_input_param(
+                new,
+                name,
+                param.typ,
+                param.value,
+                param.kind,
+                param.shown_inline,
+            );
+            inputs.insert(input, id);
+        }
+        for (name, output) in node.outputs {
+            if let Some(param) = source.outputs.get(output) {
+                outputs.insert(output, graph.add_output_param(new, name, param.typ));
+            }
+        }
+        let position = imported
+            .node_positions
+            .get(old)
+            .copied()
+            .unwrap_or_default();
+        editor.node_positions.insert(new, position + offset);
+        editor.node_order.push(new);
+        added.push(new);
+    }
+    for (input, output) in source.connections {
+        if let (Some(input), Some(output)) = (inputs.get(&input), outputs.get(&output)) {
+            editor.graph.add_connection(*output, *input);
+        }
+    }
+    editor.selected_nodes.clone_from(&added);
+    added
+}
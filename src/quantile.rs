@@ -0,0 +1,93 @@
+//! A calculator for reading the active node's curve both ways: the time by
+//! which a given fraction has failed, and the fraction failed by a given time.
+//! It also answers how likely a unit that has already survived to some age
+//! is to last a while longer.
+use eframe::egui;
+
+use crate::eval;
+use crate::i18n::tr;
+use crate::settings::TimeUnit;
+
+pub struct Quantile {
+    /// The failure probability F(t) to find the time for.
+    probability: f64,
+    time: f64,
+    /// The age T a unit is known to have survived to.
+    survived: f64,
+    /// How much longer it has to last after T.
+    further: f64,
+}
+
+impl Default for Quantile {
+    fn default() -> Self {
+        Self {
+            probability: 0.1,
+            time: 1_000.0,
+            survived: 1_000.0,
+            further: 1_000.0,
+        }
+    }
+}
+
+impl Quantile {
+    pub fn ui(&mut self, ui: &mut egui::Ui, curve: &[f64], time_unit: TimeUnit) {
+        let symbol = time_unit.symbol();
+        egui::Grid::new("quantile").num_columns(3).show(ui, |ui| {
+            ui.label(tr("quantile-probability"));
+            ui.add(
+                egui::DragValue::new(&mut self.probability)
+                    .speed(0.001)
+                    .clamp_range(0.0001..=0.9999),
+            );
+            let time = eval::b_life(curve, self.probability * 100.0);
+            ui.label(format!("t = {} {symbol}", eval::format_b_life(time)));
+            ui.end_row();
+
+            ui.label(tr("quantile-time"));
+            ui.add(
+                egui::DragValue::new(&mut self.time)
+                    .clamp_range(0.0..=f64::MAX)
+                    .suffix(format!(" {symbol}")),
+            );
+            match reliability_at(curve, self.time) {
+                Some(r) => ui.label(format!("F(t) = {:.6} · R(t) = {r:.6}", 1.0 - r)),
+                None => ui.label(tr("quantile-beyond-horizon")),
+            };
+            ui.end_row();
+
+            ui.label(tr("quantile-survived"));
+            ui.add(
+                egui::DragValue::new(&mut self.survived)
+                    .clamp_range(0.0..=f64::MAX)
+                    .suffix(format!(" {symbol}")),
+            );
+            ui.end_row();
+            ui.label(tr("quantile-further"));
+            ui.add(
+                egui::DragValue::new(&mut self.further)
+                    .clamp_range(0.0..=f64::MAX)
+                    .suffix(format!(" {symbol}")),
+            );
+            let conditional = reliability_at(curve, self.survived).and_then(|at_survived| {
+                let later = reliability_at(curve, self.survived + self.further)?;
+                Some(later / at_survived)
+            });
+            match conditional {
+                Some(r) if r.is_finite() => ui.label(format!("R(t | T) = {r:.6}")),
+                Some(_) => ui.label(tr("quantile-never-survives")),
+                None => ui.label(tr("quantile-beyond-horizon")),
+            };
+            ui.end_row();
+        });
+    }
+}
+
+/// R at a time between steps, interpolated linearly; `None` past the curve's end.
+fn reliability_at(curve: &[f64], time: f64) -> Option<f64> {
+    let step = time.floor() as usize;
+    let before = *curve.get(step)?;
+    let Some(after) = curve.get(step + 1) else {
+        return (time == step as f64).then_some(before);
+    };
+    Some(before + (after - before) * (time - step as f64))
+}
@@ -0,0 +1,222 @@
+//! How the system's reliability grows with the number of units in a
+//! redundant block, to weigh each extra unit against what it buys.
+//!
+//! The block's units are taken to be copies of the one on its input A. The
+//! system is then found by conditioning on the block, R_s = R_b · R_s(block
+//! working) + (1 − R_b) · R_s(block failed), which holds as long as nothing
+//! else in the system shares nodes with the block.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Bar, BarChart, Legend, Plot};
+
+use crate::eval::{self, evaluate_curve, reliability_at, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Forced, Graph, GraphState, NodeTemplate, ValueType};
+
+/// One redundancy level at the mission time.
+#[derive(Clone, Copy, Debug)]
+pub struct Level {
+    pub units: usize,
+    pub block: f64,
+    pub system: f64,
+}
+
+/// The block's and the system's reliability at the mission time with 1 to
+/// `max_units` units in `block`.
+pub fn levels(
+    graph: &Graph,
+    state: &GraphState,
+    block: NodeId,
+    system: NodeId,
+    max_units: usize,
+) -> anyhow::Result<Vec<Level>> {
+    let options = &Options::of(state);
+    let input = graph[block].get_input("A")?;
+    let source = graph
+        .connection(input)
+        .map(|output| graph[output].node)
+        .ok_or_else(|| anyhow::anyhow!(tr_with("error-unconnected-input", &[("input", &"A")])))?;
+    let unit = evaluate_curve(graph, options, source, &mut OutputsCache::new())?;
+    let time = state.mission_time.min(unit.len().saturating_sub(1));
+    let unit = &unit[..=time];
+    let coverage = graph[block].user_data.coverage;
+
+    let mut graph = graph.clone();
+    let mut system_with = |forced| {
+        graph[block].user_data.forced = Some(forced);
+        reliability_at(&graph, options, system, time)
+    };
+    let working = system_with(Forced::Working)?;
+    let failed = system_with(Forced::Failed)?;
+
+    let mut curve = unit.to_vec();
+    let mut levels = Vec::with_capacity(max_units);
+    for units in 1..=max_units {
+        if units > 1 {
+            curve = match graph[block].user_data.template {
+                NodeTemplate::Standby => {
+                    let input = graph[block].get_input("dormancy")?;
+                    let ValueType::Dormancy { dormancy } = graph[input].value else {
+                        anyhow::bail!(tr("error-expected-dormancy"));
+                    };
+                    eval::standby(&curve, unit, dormancy.aging(), coverage)
+                }
+                _ => eval::voting(&vec![unit.to_vec(); units], 1, coverage),
+            };
+        }
+        let r = curve[time];
+        levels.push(Level {
+            units,
+            block: r,
+            system: r * working + (1.0 - r) * failed,
+        });
+    }
+    Ok(levels)
+}
+
+/// The parallel and standby blocks of the graph, sorted by label.
+fn blocks(graph: &Graph) -> Vec<NodeId> {
+    let mut blocks = nodes::nodes_of(graph, NodeTemplate::Parallel);
+    blocks.extend(nodes::nodes_of(graph, NodeTemplate::Standby));
+    blocks.sort_by_key(|node| graph[*node].label.clone());
+    blocks
+}
+
+pub struct Redundancy {
+    block: Option<NodeId>,
+    max_units: usize,
+    result: Option<Result<Vec<Level>, String>>,
+}
+
+impl Default for Redundancy {
+    fn default() -> Self {
+        Self {
+            block: None,
+            max_units: 5,
+            result: None,
+        }
+    }
+}
+
+impl Redundancy {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("redundancy-title"))
+            .open(open)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                let blocks = blocks(graph);
+                if self.block.is_some_and(|block| !blocks.contains(&block)) {
+                    self.block = None;
+                }
+                if self.block.is_none() {
+                    self.block = state.active_node.filter(|node| blocks.contains(node));
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr("redundancy-block"));
+                    egui::ComboBox::from_id_source("redundancy block")
+                        .selected_text(
+                            self.block
+                                .map(|block| graph[block].label.clone())
+                                .unwrap_or_default(),
+                        )
+                        .show_ui(ui, |ui| {
+                            for block in &blocks {
+                                ui.selectable_value(
+                                    &mut self.block,
+                                    Some(*block),
+                                    &graph[*block].label,
+                                );
+                            }
+                        });
+                    ui.label(tr("redundancy-max-units"));
+                    ui.add(egui::DragValue::new(&mut self.max_units).clamp_range(2..=10));
+                });
+                if blocks.is_empty() {
+                    ui.label(tr("redundancy-no-blocks"));
+                }
+                if ui
+                    .add_enabled(
+                        self.block.is_some(),
+                        egui::Button::new(tr("redundancy-run")),
+                    )
+                    .clicked()
+                {
+                    if let Some(block) = self.block {
+                        let system = state.active_node.unwrap_or(block);
+                        self.result = Some(
+                            levels(graph, state, block, system, self.max_units)
+                                .map_err(|err| err.to_string()),
+                        );
+                    }
+                }
+
+                let levels = match &self.result {
+                    Some(Ok(levels)) => levels,
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                        return;
+                    }
+                    None => return,
+                };
+                egui::Grid::new("redundancy levels")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("redundancy-units"));
+                        ui.strong(tr("redundancy-block-reliability"));
+                        ui.strong(tr("redundancy-system-reliability"));
+                        ui.strong(tr("redundancy-gain"));
+                        ui.end_row();
+                        let mut previous: Option<f64> = None;
+                        for level in levels {
+                            ui.label(level.units.to_string());
+                            ui.label(format!("{:.6}", level.block));
+                            ui.label(format!("{:.6}", level.system));
+                            match previous {
+                                Some(previous) => {
+                                    ui.label(format!("{:+.6}", level.system - previous))
+                                }
+                                None => ui.label("—"),
+                            };
+                            ui.end_row();
+                            previous = Some(level.system);
+                        }
+                    });
+                let theme = state.settings.theme.catppuccin();
+                Plot::new("redundancy")
+                    .height(200.0)
+                    .include_y(0.0)
+                    .include_y(1.0)
+                    .x_axis_label(tr("redundancy-units"))
+                    .y_axis_label("R(t_m)")
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        let bars = |value: fn(&Level) -> f64, offset: f64| {
+                            levels
+                                .iter()
+                                .map(|level| {
+                                    Bar::new(level.units as f64 + offset, value(level)).width(0.35)
+                                })
+                                .collect::<Vec<Bar>>()
+                        };
+                        plot_ui.bar_chart(
+                            BarChart::new(bars(|level| level.block, -0.2))
+                                .color(theme.overlay2)
+                                .name(tr("redundancy-block-reliability")),
+                        );
+                        plot_ui.bar_chart(
+                            BarChart::new(bars(|level| level.system, 0.2))
+                                .color(theme.blue)
+                                .name(tr("redundancy-system-reliability")),
+                        );
+                    });
+                ui.label(tr("redundancy-hint"));
+            });
+    }
+}
@@ -0,0 +1,194 @@
+//! Locked results: reference figures stored with the project, which later
+//! evaluations are checked against so that accidental edits show up as drift.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::checkpoints;
+use crate::eval::{self, evaluate_curve, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+use crate::toasts;
+
+/// The MTTF and R at the mission time and checkpoints of one node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedResults {
+    pub node: NodeId,
+    pub mttf: f64,
+    pub reliability: Vec<(usize, f64)>,
+    /// The largest relative change that doesn't count as drift.
+    pub tolerance: f64,
+}
+
+/// A locked figure that has moved beyond the tolerance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drift {
+    pub figure: String,
+    pub locked: f64,
+    pub now: f64,
+}
+
+impl LockedResults {
+    pub fn lock(node: NodeId, curve: &[f64], times: &[usize]) -> Self {
+        Self {
+            node,
+            mttf: eval::mttf(curve),
+            reliability: times
+                .iter()
+                .filter_map(|time| Some((*time, *curve.get(*time)?)))
+                .collect(),
+            tolerance: 1e-4,
+        }
+    }
+
+    /// The figures of `curve` that differ from the locked ones by more than
+    /// the tolerance. A time step the curve no longer reaches counts too.
+    pub fn drift(&self, curve: &[f64]) -> Vec<Drift> {
+        let drifted = |locked: f64, now: f64| {
+            now.is_nan() || (now - locked).abs() > self.tolerance * locked.abs()
+        };
+        let mut drift = Vec::new();
+        let mttf = eval::mttf(curve);
+        if drifted(self.mttf, mttf) {
+            drift.push(Drift {
+                figure: "MTTF".to_owned(),
+                locked: self.mttf,
+                now: mttf,
+            });
+        }
+        for (time, locked) in &self.reliability {
+            let now = curve.get(*time).copied().unwrap_or(f64::NAN);
+            if drifted(*locked, now) {
+                drift.push(Drift {
+                    figure: format!("R({time})"),
+                    locked: *locked,
+                    now,
+                });
+            }
+        }
+        drift
+    }
+}
+
+fn finite(value: f64) -> Option<f64> {
+    value.is_finite().then_some(value)
+}
+
+/// Checks the locked results every frame and warns once when they drift.
+#[derive(Default)]
+pub struct Regression {
+    drift: Vec<Drift>,
+}
+
+impl Regression {
+    /// Compares the locked node's curve with its locked results. `active`
+    /// is the active node's curve, which is reused when it is the locked one.
+    pub fn check(&mut self, graph: &Graph, state: &GraphState, active: Option<&[f64]>) {
+        let Some(locked) = &state.locked_results else {
+            self.drift.clear();
+            return;
+        };
+        let drift = if !graph.nodes.contains_key(locked.node) {
+            vec![Drift {
+                figure: tr("regression-node-deleted"),
+                locked: f64::NAN,
+                now: f64::NAN,
+            }]
+        } else if let Some(curve) = active.filter(|_| state.active_node == Some(locked.node)) {
+            locked.drift(curve)
+        } else {
+            match evaluate_curve(
+                graph,
+                &Options::of(state),
+                locked.node,
+                &mut OutputsCache::new(),
+            ) {
+                Ok(curve) => locked.drift(&curve),
+                Err(err) => vec![Drift {
+                    figure: err.to_string(),
+                    locked: f64::NAN,
+                    now: f64::NAN,
+                }],
+            }
+        };
+        if self.drift.is_empty() && !drift.is_empty() {
+            toasts::error(tr("toast-results-drifted"));
+        }
+        self.drift = drift;
+    }
+
+    pub fn is_drifting(&self) -> bool {
+        !self.drift.is_empty()
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, graph: &Graph, state: &mut GraphState, curve: &[f64]) {
+        let theme = state.settings.theme.catppuccin();
+        ui.horizontal(|ui| {
+            let active = state.active_node.filter(|_| !curve.is_empty());
+            if ui
+                .add_enabled(active.is_some(), egui::Button::new(tr("regression-lock")))
+                .on_hover_text(tr("regression-lock-hint"))
+                .clicked()
+            {
+                if let Some(node) = active {
+                    let mut times = vec![state.mission_time];
+                    times.extend(&state.checkpoints);
+                    state.locked_results = Some(LockedResults::lock(node, curve, &times));
+                    self.drift.clear();
+                }
+            }
+            if ui
+                .add_enabled(
+                    state.locked_results.is_some(),
+                    egui::Button::new(tr("regression-unlock")),
+                )
+                .clicked()
+            {
+                state.locked_results = None;
+                self.drift.clear();
+            }
+        });
+        let Some(locked) = &mut state.locked_results else {
+            ui.label(tr("regression-none"));
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.label(tr("regression-tolerance"));
+            ui.add(
+                egui::DragValue::new(&mut locked.tolerance)
+                    .speed(1e-5)
+                    .clamp_range(0.0..=1.0)
+                    .custom_formatter(|n, _| format!("{n:.1e}")),
+            )
+            .on_hover_text(tr("regression-tolerance-hint"));
+        });
+        if let Some(node) = graph.nodes.get(locked.node) {
+            ui.label(tr_with(
+                "regression-locked",
+                &[
+                    ("node", &node.label),
+                    ("figures", &(locked.reliability.len() + 1)),
+                ],
+            ));
+        }
+        if self.drift.is_empty() {
+            ui.colored_label(theme.green, tr("regression-match"));
+            return;
+        }
+        egui::Grid::new("drift")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong(tr("regression-figure"));
+                ui.strong(tr("regression-locked-value"));
+                ui.strong(tr("regression-now"));
+                ui.end_row();
+                for drift in &self.drift {
+                    ui.colored_label(theme.red, &drift.figure);
+                    ui.label(checkpoints::format(finite(drift.locked), 6));
+                    ui.label(checkpoints::format(finite(drift.now), 6));
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,227 @@
+//! Find and replace across node names and notes, with plain text or regular
+//! expressions, to tidy up the naming of many imported components at once.
+//! Nodes in sections locked by someone else are left alone.
+use std::collections::HashMap;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use regex::{NoExpand, Regex, RegexBuilder};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState, NodeTemplate, ValueType};
+
+/// How many changes the preview lists.
+const PREVIEW: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Name,
+    Note,
+}
+
+/// A replacement about to be made.
+struct Change {
+    node: NodeId,
+    field: Field,
+    after: String,
+}
+
+pub struct FindReplace {
+    find: String,
+    replace: String,
+    /// Whether `find` is a regular expression, and `replace` can refer to
+    /// its groups as `$1` or `${name}`.
+    regex: bool,
+    match_case: bool,
+    names: bool,
+    notes: bool,
+    selected_only: bool,
+}
+
+impl Default for FindReplace {
+    fn default() -> Self {
+        Self {
+            find: String::new(),
+            replace: String::new(),
+            regex: false,
+            match_case: true,
+            names: true,
+            notes: false,
+            selected_only: false,
+        }
+    }
+}
+
+/// The text of a note node.
+fn note_text(graph: &Graph, node: NodeId) -> Option<&str> {
+    let input = graph[node].get_input("text").ok()?;
+    match &graph[input].value {
+        ValueType::Text { text } => Some(text),
+        _ => None,
+    }
+}
+
+impl FindReplace {
+    fn pattern(&self) -> Result<Regex, regex::Error> {
+        let pattern = if self.regex {
+            self.find.clone()
+        } else {
+            regex::escape(&self.find)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.match_case)
+            .build()
+    }
+
+    fn replaced(&self, pattern: &Regex, text: &str) -> Option<String> {
+        if !pattern.is_match(text) {
+            return None;
+        }
+        let after = if self.regex {
+            pattern.replace_all(text, self.replace.as_str())
+        } else {
+            pattern.replace_all(text, NoExpand(&self.replace))
+        };
+        Some(after.into_owned()).filter(|after| after != text)
+    }
+
+    /// The replacements in the nodes searched, and how many nodes that match
+    /// are read-only.
+    fn changes(
+        &self,
+        pattern: &Regex,
+        graph: &Graph,
+        selected: &[NodeId],
+        read_only: &HashMap<NodeId, String>,
+    ) -> (Vec<Change>, usize) {
+        let mut changes = Vec::new();
+        let mut locked = 0;
+        let mut nodes: Vec<NodeId> = graph.nodes.keys().collect();
+        nodes.sort_by(|a, b| graph[*a].label.cmp(&graph[*b].label));
+        for node in nodes {
+            if self.selected_only && !selected.contains(&node) {
+                continue;
+            }
+            let mut found = Vec::new();
+            if self.names {
+                if let Some(after) = self.replaced(pattern, &graph[node].label) {
+                    found.push((Field::Name, after));
+                }
+            }
+            if self.notes && graph[node].user_data.template == NodeTemplate::Note {
+                let after = note_text(graph, node).and_then(|text| self.replaced(pattern, text));
+                if let Some(after) = after {
+                    found.push((Field::Note, after));
+                }
+            }
+            if found.is_empty() {
+                continue;
+            }
+            if read_only.contains_key(&node) {
+                locked += 1;
+                continue;
+            }
+            changes.extend(
+                found
+                    .into_iter()
+                    .map(|(field, after)| Change { node, field, after }),
+            );
+        }
+        (changes, locked)
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        selected: &[NodeId],
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("rename-title"))
+            .open(open)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("rename fields")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("rename-find"));
+                        ui.text_edit_singleline(&mut self.find);
+                        ui.end_row();
+                        ui.label(tr("rename-replace"));
+                        ui.text_edit_singleline(&mut self.replace);
+                        ui.end_row();
+                    });
+                ui.horizontal_wrapped(|ui| {
+                    ui.checkbox(&mut self.regex, tr("rename-regex"));
+                    ui.checkbox(&mut self.match_case, tr("rename-match-case"));
+                    ui.checkbox(&mut self.names, tr("rename-names"));
+                    ui.checkbox(&mut self.notes, tr("rename-notes"));
+                    ui.checkbox(&mut self.selected_only, tr("rename-selected-only"));
+                });
+                if self.regex {
+                    ui.label(tr("rename-groups-hint"));
+                }
+                if self.find.is_empty() {
+                    return;
+                }
+                let pattern = match self.pattern() {
+                    Ok(pattern) => pattern,
+                    Err(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                        return;
+                    }
+                };
+                let (changes, locked) = self.changes(&pattern, graph, selected, &state.read_only);
+                ui.separator();
+                ui.label(tr_with("rename-count", &[("count", &changes.len())]));
+                if locked > 0 {
+                    ui.label(tr_with("rename-locked", &[("count", &locked)]));
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("rename preview")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for change in changes.iter().take(PREVIEW) {
+                                    let before = match change.field {
+                                        Field::Name => graph[change.node].label.as_str(),
+                                        Field::Note => {
+                                            note_text(graph, change.node).unwrap_or_default()
+                                        }
+                                    };
+                                    ui.label(before);
+                                    ui.label(format!("→ {}", change.after));
+                                    ui.end_row();
+                                }
+                            });
+                        if changes.len() > PREVIEW {
+                            ui.label(tr_with(
+                                "rename-more",
+                                &[("count", &(changes.len() - PREVIEW))],
+                            ));
+                        }
+                    });
+                if ui
+                    .add_enabled(
+                        !changes.is_empty(),
+                        egui::Button::new(tr("rename-replace-all")),
+                    )
+                    .clicked()
+                {
+                    for change in changes {
+                        match change.field {
+                            Field::Name => graph[change.node].label = change.after,
+                            Field::Note => {
+                                if let Ok(input) = graph[change.node].get_input("text") {
+                                    graph[input].value = ValueType::Text { text: change.after };
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+    }
+}
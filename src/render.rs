@@ -0,0 +1,366 @@
+//! `block render`: draws a saved model's R(t) and A(t) plot to an SVG or PNG
+//! file without opening a window, so builds can attach fresh curves.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use eframe::egui::Color32;
+use resvg::{tiny_skia, usvg};
+
+use crate::audit::Audit;
+use crate::batch;
+use crate::eval::{evaluate_measure, Measure, Options, OutputsCache};
+use crate::i18n::tr;
+use crate::project;
+use crate::requirement::Requirement;
+use crate::settings::{Settings, Theme};
+
+const USAGE: &str = "usage: block render <project> [--output <file.svg|file.png>] \
+                     [--size <width>x<height>] [--theme latte|frappe|macchiato|mocha] \
+                     [--seed <u64>] [--watch]";
+
+/// Space around the plot area for the axes' ticks and labels.
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 30.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 55.0;
+
+/// How the plot is drawn.
+pub struct Style {
+    pub width: u32,
+    pub height: u32,
+    pub theme: Theme,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            width: 1200,
+            height: 600,
+            theme: Theme::Latte,
+        }
+    }
+}
+
+/// Runs the command with the arguments after `render`, returning the exit
+/// code: 0 if the plot was written, 1 if it couldn't be, 2 for bad usage.
+/// With `--watch` it never returns, drawing again whenever the project changes.
+pub fn run(args: &[String]) -> i32 {
+    let mut input = None;
+    let mut output = None;
+    let mut style = Style::default();
+    let mut seed = Settings::default().seed;
+    let mut watch = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => return usage(),
+            },
+            "--size" => match args.next().and_then(|size| parse_size(size)) {
+                Some((width, height)) => (style.width, style.height) = (width, height),
+                None => return usage(),
+            },
+            "--theme" => {
+                let theme = args.next().and_then(|name| {
+                    Theme::ALL
+                        .into_iter()
+                        .find(|theme| format!("{theme:?}").eq_ignore_ascii_case(name))
+                });
+                match theme {
+                    Some(theme) => style.theme = theme,
+                    None => return usage(),
+                }
+            }
+            "--seed" => match args.next().and_then(|seed| seed.parse().ok()) {
+                Some(value) => seed = value,
+                None => return usage(),
+            },
+            "--watch" => watch = true,
+            path if input.is_none() => input = Some(PathBuf::from(path)),
+            _ => return usage(),
+        }
+    }
+    let Some(input) = input else {
+        return usage();
+    };
+    let output = output.unwrap_or_else(|| input.with_extension("svg"));
+    let once = || match render(&input, &output, &style, seed) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{}: {err}", input.display());
+            1
+        }
+    };
+    if watch {
+        crate::watch::watch(|| vec![input.clone()], once);
+    }
+    once()
+}
+
+fn usage() -> i32 {
+    eprintln!("{USAGE}");
+    2
+}
+
+/// `1200x600` as a width and height in pixels.
+fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let (width, height) = size.split_once('x')?;
+    let (width, height) = (width.parse().ok()?, height.parse().ok()?);
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+/// Draws the plot of the project at `input` to `output`, as PNG if its
+/// extension says so and as SVG otherwise, with random parts seeded by
+/// `seed`. The settings it was drawn with are kept in the file, as a comment
+/// of the SVG or text chunks of the PNG.
+pub fn render(input: &Path, output: &Path, style: &Style, seed: u64) -> anyhow::Result<()> {
+    let project = project::read(input)?;
+    let graph = &project.editor.graph;
+    let mut state = project.state;
+    state.active_node = batch::system(graph, &state);
+    state.settings.seed = seed;
+    let system = state
+        .active_node
+        .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))?;
+    let evaluate = |measure| {
+        evaluate_measure(
+            graph,
+            &Options::of(&state),
+            measure,
+            system,
+            &mut OutputsCache::new(),
+        )?
+        .try_to_reliability()
+    };
+    let plot = Plot {
+        reliability: evaluate(Measure::Reliability)?,
+        availability: evaluate(Measure::Availability)?,
+        mission_time: state.mission_time,
+        requirement: state.requirement,
+        time_unit: state.settings.time_unit.symbol(),
+    };
+    let svg = plot.svg(style);
+    let audit = Audit::new(graph, &state);
+    let is_png = output
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+    if is_png {
+        std::fs::write(output, with_text(to_png(&svg)?, &audit))?;
+    } else {
+        // A comment can't hold `--`.
+        let comment = audit.comments("  ").replace("--", "- -");
+        std::fs::write(output, format!("<!--\n{comment}-->\n{svg}"))?;
+    }
+    Ok(())
+}
+
+/// The curves and markers of one plot.
+pub struct Plot {
+    pub reliability: Vec<f64>,
+    pub availability: Vec<f64>,
+    pub mission_time: usize,
+    pub requirement: Requirement,
+    pub time_unit: String,
+}
+
+impl Plot {
+    pub fn svg(&self, style: &Style) -> String {
+        let theme = style.theme.catppuccin();
+        let (width, height) = (f64::from(style.width), f64::from(style.height));
+        let horizon = self.reliability.len().saturating_sub(1).max(1) as f64;
+        let x_ticks = ticks(horizon);
+        let x_max = x_ticks.last().copied().unwrap_or(horizon).max(horizon);
+        let plot_width = width - MARGIN_LEFT - MARGIN_RIGHT;
+        let plot_height = height - MARGIN_TOP - MARGIN_BOTTOM;
+        let x = |t: f64| MARGIN_LEFT + t / x_max * plot_width;
+        let y = |value: f64| MARGIN_TOP + (1.0 - value) * plot_height;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"DejaVu Sans, Arial, Helvetica, sans-serif\" font-size=\"13\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            hex(theme.base)
+        );
+        let text = hex(theme.text);
+        let grid = hex(theme.surface0);
+        for tick in &x_ticks {
+            svg.push_str(&format!(
+                "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"{grid}\"/>\n\
+                 <text x=\"{0:.1}\" y=\"{3:.1}\" fill=\"{text}\" text-anchor=\"middle\">{tick}</text>\n",
+                x(*tick),
+                y(1.0),
+                y(0.0),
+                y(0.0) + 18.0,
+            ));
+        }
+        for tick in [0.0, 0.2, 0.4, 0.6, 0.8, 1.0] {
+            svg.push_str(&format!(
+                "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{2:.1}\" y2=\"{1:.1}\" stroke=\"{grid}\"/>\n\
+                 <text x=\"{3:.1}\" y=\"{4:.1}\" fill=\"{text}\" text-anchor=\"end\">{tick:.1}</text>\n",
+                x(0.0),
+                y(tick),
+                x(x_max),
+                x(0.0) - 8.0,
+                y(tick) + 4.0,
+            ));
+        }
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{plot_width:.1}\" height=\"{plot_height:.1}\" \
+             fill=\"none\" stroke=\"{}\"/>\n\
+             <text x=\"{:.1}\" y=\"{:.1}\" fill=\"{text}\" text-anchor=\"middle\">t [{}]</text>\n\
+             <text x=\"18\" y=\"{:.1}\" fill=\"{text}\" text-anchor=\"middle\" \
+             transform=\"rotate(-90 18 {:.1})\">R(t), A(t)</text>\n",
+            x(0.0),
+            y(1.0),
+            hex(theme.overlay0),
+            x(x_max / 2.0),
+            height - 12.0,
+            escape(&self.time_unit),
+            y(0.5),
+            y(0.5),
+        ));
+
+        let marker = hex(theme.overlay2);
+        let vline = |svg: &mut String, time: f64, dashed: bool| {
+            svg.push_str(&format!(
+                "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{0:.1}\" y2=\"{2:.1}\" stroke=\"{marker}\"{3}/>\n",
+                x(time),
+                y(1.0),
+                y(0.0),
+                if dashed { " stroke-dasharray=\"6 6\"" } else { "" },
+            ));
+        };
+        vline(&mut svg, self.mission_time as f64, false);
+        if self.requirement.enabled {
+            vline(&mut svg, self.requirement.time as f64, true);
+            svg.push_str(&format!(
+                "<line x1=\"{0:.1}\" y1=\"{1:.1}\" x2=\"{2:.1}\" y2=\"{1:.1}\" stroke=\"{marker}\" \
+                 stroke-dasharray=\"6 6\"/>\n",
+                x(0.0),
+                y(self.requirement.reliability),
+                x(x_max),
+            ));
+        }
+
+        let reliability_color = match self.requirement.is_met(&self.reliability) {
+            Some(true) => theme.green,
+            Some(false) => theme.red,
+            None => theme.blue,
+        };
+        let series = [
+            ("R(t)", &self.reliability, reliability_color),
+            ("A(t)", &self.availability, theme.sapphire),
+        ];
+        // Drawing more points than there are pixels only makes the file larger.
+        let step = (self.reliability.len() / plot_width as usize).max(1);
+        for (i, (name, curve, color)) in series.iter().enumerate() {
+            let points: Vec<String> = curve
+                .iter()
+                .enumerate()
+                .filter(|(t, _)| t % step == 0 || t + 1 == curve.len())
+                .map(|(t, value)| format!("{:.1},{:.1}", x(t as f64), y(*value)))
+                .collect();
+            let color = hex(*color);
+            svg.push_str(&format!(
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>\n",
+                points.join(" ")
+            ));
+            let legend_x = x(0.0) + i as f64 * 90.0;
+            svg.push_str(&format!(
+                "<line x1=\"{legend_x:.1}\" y1=\"20\" x2=\"{:.1}\" y2=\"20\" stroke=\"{color}\" \
+                 stroke-width=\"2\"/>\n\
+                 <text x=\"{:.1}\" y=\"24\" fill=\"{text}\">{name}</text>\n",
+                legend_x + 24.0,
+                legend_x + 30.0,
+            ));
+        }
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"{marker}\">{}</text>\n</svg>\n",
+            x(self.mission_time as f64) + 4.0,
+            y(1.0) + 14.0,
+            escape(&tr("mission-time")),
+        ));
+        svg
+    }
+}
+
+/// `png` with each setting of `audit` added as an international text chunk
+/// right after the header, where image viewers list them as properties.
+fn with_text(mut png: Vec<u8>, audit: &Audit) -> Vec<u8> {
+    // The 8-byte signature, then the header chunk with its 13 bytes of data.
+    let header_end = 8 + 12 + 13;
+    let mut chunks = Vec::new();
+    for (key, value) in &audit.entries {
+        // Keyword, then no compression, an empty language tag and an empty
+        // translated keyword.
+        let mut data = format!("{key}\0\0\0\0\0").into_bytes();
+        data.extend_from_slice(value.as_bytes());
+        chunks.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = chunks.len();
+        chunks.extend_from_slice(b"iTXt");
+        chunks.extend_from_slice(&data);
+        let crc = crc32fast::hash(&chunks[start..]);
+        chunks.extend_from_slice(&crc.to_be_bytes());
+    }
+    png.splice(header_end..header_end, chunks);
+    png
+}
+
+/// Rasterizes `svg`, with the system's fonts for its text.
+pub fn to_png(svg: &str) -> anyhow::Result<Vec<u8>> {
+    let mut options = usvg::Options::default();
+    Arc::make_mut(&mut options.fontdb).load_system_fonts();
+    let tree = usvg::Tree::from_str(svg, &options)?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| anyhow::anyhow!("{}x{}", size.width(), size.height()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    Ok(pixmap.encode_png()?)
+}
+
+/// Round tick positions from 0 up to at least `max`, about five of them.
+fn ticks(max: f64) -> Vec<f64> {
+    let rough = max / 5.0;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(rough);
+    let count = (max / step).ceil() as usize;
+    (0..=count).map(|i| i as f64 * step).collect()
+}
+
+fn hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn png_keeps_the_audit_in_valid_chunks() {
+        let plot = Plot {
+            reliability: vec![1.0, 0.5, 0.25],
+            availability: vec![1.0, 0.75, 0.5],
+            mission_time: 1,
+            requirement: Requirement::default(),
+            time_unit: "h".to_owned(),
+        };
+        let audit = Audit {
+            entries: vec![("seed", "42".to_owned())],
+        };
+        let png = with_text(to_png(&plot.svg(&Style::default())).unwrap(), &audit);
+        assert!(tiny_skia::Pixmap::decode_png(&png).is_ok());
+        let chunk = b"iTXtseed\0\0\0\0\x0042";
+        assert!(png.windows(chunk.len()).any(|window| window == chunk));
+    }
+}
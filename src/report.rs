@@ -0,0 +1,730 @@
+//! Reports of the active system, laid out by a user-editable template.
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::Audit;
+use crate::checkpoints;
+use crate::eval::{self, evaluate_curve, Options, OutputsCache};
+use crate::frequency_duration;
+use crate::i18n::{tr, tr_with};
+use crate::locale;
+use crate::nodes::{self, Graph, GraphState};
+use crate::requirement::Requirement;
+use crate::results::{self, EvaluationResult};
+use crate::toasts;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Section {
+    /// R, MTTF and availability of the system, and the requirement.
+    Metrics,
+    /// The parameters and reliability of each component.
+    Components,
+    /// The importance measures of each component.
+    Criticality,
+    /// How often the system goes down and for how long, simulated.
+    FrequencyDuration,
+    /// The template's free text.
+    Notes,
+}
+
+impl Section {
+    pub const ALL: [Section; 5] = [
+        Section::Metrics,
+        Section::Components,
+        Section::Criticality,
+        Section::FrequencyDuration,
+        Section::Notes,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            Section::Metrics => tr("report-metrics"),
+            Section::Components => tr("report-components"),
+            Section::Criticality => tr("criticality-title"),
+            Section::FrequencyDuration => tr("frequency-duration-title"),
+            Section::Notes => tr("report-notes"),
+        }
+    }
+}
+
+/// The header fields and section layout of a report, saved with the project.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Template {
+    pub title: String,
+    pub project: String,
+    pub author: String,
+    pub revision: String,
+    /// Path or URL of an image shown above the title.
+    pub logo: String,
+    pub notes: String,
+    /// The sections in the order they appear, and whether each is included.
+    pub sections: Vec<(Section, bool)>,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Self {
+            title: tr("report-default-title"),
+            project: String::new(),
+            author: String::new(),
+            revision: String::new(),
+            logo: String::new(),
+            notes: String::new(),
+            sections: vec![
+                (Section::Metrics, true),
+                (Section::Components, true),
+                (Section::Criticality, true),
+                (Section::FrequencyDuration, false),
+                (Section::Notes, false),
+            ],
+        }
+    }
+}
+
+pub struct ComponentRow {
+    pub name: String,
+    pub shape: f64,
+    pub scale: f64,
+    pub environment_factor: f64,
+    pub mttr: f64,
+    pub reliability: f64,
+    /// The component's Bx lives, in the order of `EvaluationResult::b_lives`.
+    pub b_lives: Vec<Option<f64>>,
+}
+
+/// The figures a report presents, gathered from the active system.
+pub struct Content {
+    pub result: EvaluationResult,
+    pub requirement: Option<(Requirement, bool)>,
+    pub components: Vec<ComponentRow>,
+    /// The frequency and duration indices, if their section is included.
+    pub frequency_duration: Vec<(String, String)>,
+    /// The settings the figures were calculated with.
+    pub audit: Audit,
+}
+
+pub fn collect(graph: &mut Graph, state: &GraphState) -> anyhow::Result<Content> {
+    let options = &Options::of(state);
+    let result = results::evaluate(graph, state, options)?;
+    let system = state
+        .active_node
+        .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))?;
+    let time = state.mission_time;
+    let b_lives = |curve: &[f64]| {
+        state
+            .settings
+            .b_lives
+            .iter()
+            .map(|percent| eval::b_life(curve, *percent))
+            .collect::<Vec<_>>()
+    };
+
+    let mut components = Vec::new();
+    for node in nodes::upstream_components(graph, system) {
+        let parameters = nodes::component_parameters(graph, node)
+            .ok_or_else(|| anyhow::anyhow!(tr("error-not-component")))?;
+        components.push(ComponentRow {
+            name: graph[node].label.clone(),
+            shape: parameters.shape,
+            scale: parameters.scale,
+            environment_factor: parameters.environment_factor,
+            mttr: parameters.mttr,
+            reliability: eval::reliability_at(graph, options, node, time)?,
+            b_lives: b_lives(&evaluate_curve(
+                graph,
+                options,
+                node,
+                &mut OutputsCache::new(),
+            )?),
+        });
+    }
+
+    let included = state
+        .report
+        .sections
+        .contains(&(Section::FrequencyDuration, true));
+    let frequency_duration = if included {
+        let indices = frequency_duration::indices(graph, state, system)?;
+        frequency_duration::rows(&indices, state.settings.time_unit)
+    } else {
+        Vec::new()
+    };
+
+    let audit = Audit::new(graph, state);
+
+    Ok(Content {
+        requirement: result.requirement_met.map(|met| (state.requirement, met)),
+        result,
+        components,
+        frequency_duration,
+        audit,
+    })
+}
+
+/// The building blocks every output format provides.
+trait Markup {
+    fn header(&mut self, template: &Template);
+    fn heading(&mut self, text: &str);
+    fn paragraph(&mut self, text: &str);
+    fn table(&mut self, headers: &[String], rows: &[Vec<String>]);
+    fn finish(self) -> String;
+}
+
+fn render(template: &Template, content: &Content, mut markup: impl Markup) -> String {
+    markup.header(template);
+    for (section, _) in template.sections.iter().filter(|(_, enabled)| *enabled) {
+        markup.heading(&section.name());
+        match section {
+            Section::Metrics => {
+                let result = &content.result;
+                let unit = &result.time_unit;
+                let mut rows = vec![
+                    vec![tr("report-system"), result.system.clone()],
+                    vec![
+                        tr("mission-time"),
+                        format!("{} {unit}", result.mission_time),
+                    ],
+                    vec![
+                        "R(t_m)".to_owned(),
+                        locale::number(format!("{:.6}", result.reliability)),
+                    ],
+                    vec![
+                        "A(t_m)".to_owned(),
+                        locale::number(format!("{:.6}", result.availability)),
+                    ],
+                    vec![
+                        "MTTF".to_owned(),
+                        format!("{} {unit}", locale::number(format!("{:.1}", result.mttf))),
+                    ],
+                ];
+                for (percent, life) in &result.b_lives {
+                    rows.push(vec![
+                        format!("B{}", locale::number(percent)),
+                        format!("{} {unit}", locale::number(eval::format_b_life(*life))),
+                    ]);
+                }
+                if let Some((requirement, met)) = content.requirement {
+                    rows.push(vec![
+                        tr("requirement"),
+                        format!(
+                            "R ≥ {} @ {} {unit}: {}",
+                            locale::number(requirement.reliability),
+                            requirement.time,
+                            tr(if met {
+                                "requirement-pass"
+                            } else {
+                                "requirement-fail"
+                            })
+                        ),
+                    ]);
+                }
+                markup.table(&[tr("report-metric"), tr("report-value")], &rows);
+                if !content.result.checkpoints.is_empty() {
+                    let rows: Vec<Vec<String>> = content
+                        .result
+                        .checkpoints
+                        .iter()
+                        .map(|checkpoint| {
+                            vec![
+                                format!("{} {unit}", checkpoint.time),
+                                locale::number(checkpoints::format(checkpoint.reliability, 6)),
+                                locale::number(checkpoints::format(checkpoint.availability, 6)),
+                                locale::number(checkpoints::format(checkpoint.failures, 3)),
+                            ]
+                        })
+                        .collect();
+                    markup.table(
+                        &[
+                            "t".to_owned(),
+                            "R(t)".to_owned(),
+                            "A(t)".to_owned(),
+                            "N(t)".to_owned(),
+                        ],
+                        &rows,
+                    );
+                }
+            }
+            Section::Components => {
+                let rows: Vec<Vec<String>> = content
+                    .components
+                    .iter()
+                    .map(|row| {
+                        let mut cells = vec![
+                            row.name.clone(),
+                            locale::number(format!("{:.3}", row.shape)),
+                            locale::number(format!("{:.1}", row.scale)),
+                            locale::number(format!("{:.2}", row.environment_factor)),
+                            locale::number(format!("{:.1}", row.mttr)),
+                            locale::number(format!("{:.6}", row.reliability)),
+                        ];
+                        cells.extend(
+                            row.b_lives
+                                .iter()
+                                .map(|life| locale::number(eval::format_b_life(*life))),
+                        );
+                        cells
+                    })
+                    .collect();
+                let mut headers = vec![
+                    tr("goal-seek-component"),
+                    "β".to_owned(),
+                    "η".to_owned(),
+                    "π_E".to_owned(),
+                    "MTTR".to_owned(),
+                    "R(t_m)".to_owned(),
+                ];
+                headers.extend(
+                    content
+                        .result
+                        .b_lives
+                        .iter()
+                        .map(|(percent, _)| format!("B{}", locale::number(percent))),
+                );
+                markup.table(&headers, &rows);
+            }
+            Section::Criticality => {
+                let rows: Vec<Vec<String>> = content
+                    .result
+                    .importance
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        vec![
+                            row.component.clone(),
+                            locale::number(format!("{:.4}", row.birnbaum)),
+                            locale::number(format!("{:.4}", row.fussell_vesely)),
+                            locale::number(format!("{:.4}", row.criticality)),
+                        ]
+                    })
+                    .collect();
+                markup.table(
+                    &[
+                        tr("goal-seek-component"),
+                        tr("criticality-birnbaum"),
+                        tr("criticality-fussell-vesely"),
+                        tr("criticality-index"),
+                    ],
+                    &rows,
+                );
+            }
+            Section::FrequencyDuration => {
+                let rows: Vec<Vec<String>> = content
+                    .frequency_duration
+                    .iter()
+                    .map(|(name, value)| vec![name.clone(), locale::number(value)])
+                    .collect();
+                markup.table(&[tr("report-metric"), tr("report-value")], &rows);
+                markup.paragraph(&tr("frequency-duration-hint"));
+            }
+            Section::Notes => {
+                for paragraph in template.notes.split("\n\n") {
+                    markup.paragraph(paragraph.trim());
+                }
+            }
+        }
+    }
+    // Always last, whatever the template, so a report can be traced back.
+    markup.heading(&tr("report-audit"));
+    let rows: Vec<Vec<String>> = content
+        .audit
+        .entries
+        .iter()
+        .map(|(key, value)| vec![key.to_string(), value.clone()])
+        .collect();
+    markup.table(&[tr("report-setting"), tr("report-value")], &rows);
+    markup.finish()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[derive(Default)]
+struct Html(String);
+
+impl Markup for Html {
+    fn header(&mut self, template: &Template) {
+        let title = escape_html(&template.title);
+        self.0.push_str(&format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>body {{ font-family: sans-serif; }} table {{ border-collapse: collapse; }} \
+             td, th {{ border: 1px solid #999; padding: 2px 8px; }}</style>\n</head>\n<body>\n"
+        ));
+        if !template.logo.is_empty() {
+            self.0.push_str(&format!(
+                "<img src=\"{}\" alt=\"\" style=\"max-height: 80px\">\n",
+                escape_html(&template.logo)
+            ));
+        }
+        self.0.push_str(&format!("<h1>{title}</h1>\n"));
+        let fields = [
+            ("report-project", &template.project),
+            ("report-author", &template.author),
+            ("report-revision", &template.revision),
+        ];
+        for (id, value) in fields.into_iter().filter(|(_, value)| !value.is_empty()) {
+            self.0.push_str(&format!(
+                "<p><b>{}:</b> {}</p>\n",
+                escape_html(&tr(id)),
+                escape_html(value)
+            ));
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.0
+            .push_str(&format!("<h2>{}</h2>\n", escape_html(text)));
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.0.push_str(&format!("<p>{}</p>\n", escape_html(text)));
+    }
+
+    fn table(&mut self, headers: &[String], rows: &[Vec<String>]) {
+        self.0.push_str("<table>\n<tr>");
+        for header in headers {
+            self.0
+                .push_str(&format!("<th>{}</th>", escape_html(header)));
+        }
+        self.0.push_str("</tr>\n");
+        for row in rows {
+            self.0.push_str("<tr>");
+            for cell in row {
+                self.0.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            self.0.push_str("</tr>\n");
+        }
+        self.0.push_str("</table>\n");
+    }
+
+    fn finish(mut self) -> String {
+        self.0.push_str("</body>\n</html>\n");
+        self.0
+    }
+}
+
+/// Escapes text for LaTeX, typesetting the report's symbols in math mode.
+fn escape_latex(text: &str) -> String {
+    match text {
+        "β" => return r"$\beta$".to_owned(),
+        "η" => return r"$\eta$".to_owned(),
+        "π_E" => return r"$\pi_E$".to_owned(),
+        "R(t_m)" | "A(t_m)" => return format!("${text}$"),
+        _ => {}
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str(r"\textbackslash{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str(r"\textasciitilde{}"),
+            '^' => escaped.push_str(r"\textasciicircum{}"),
+            '≥' => escaped.push_str(r"$\geq$"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Table fragments for `\input` into a document using the booktabs package.
+/// Each section heading becomes the caption of the table that follows it.
+#[derive(Default)]
+struct Latex {
+    text: String,
+    caption: String,
+}
+
+impl Markup for Latex {
+    fn header(&mut self, template: &Template) {
+        self.text.push_str(&format!("% {}\n", template.title));
+        let fields = [&template.project, &template.author, &template.revision];
+        for value in fields.into_iter().filter(|value| !value.is_empty()) {
+            self.text.push_str(&format!("% {value}\n"));
+        }
+        self.text.push_str("% Requires \\usepackage{booktabs}.\n\n");
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.caption = escape_latex(text);
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.text.push_str(&format!("{}\n\n", escape_latex(text)));
+    }
+
+    fn table(&mut self, headers: &[String], rows: &[Vec<String>]) {
+        let columns = "l".to_owned() + &"r".repeat(headers.len().saturating_sub(1));
+        let row = |cells: &[String]| {
+            let cells: Vec<String> = cells.iter().map(|cell| escape_latex(cell)).collect();
+            format!("  {} \\\\\n", cells.join(" & "))
+        };
+        self.text.push_str(&format!(
+            "\\begin{{table}}[htbp]\n\\centering\n\\caption{{{}}}\n\\begin{{tabular}}{{{columns}}}\n\\toprule\n",
+            self.caption
+        ));
+        self.text.push_str(&row(headers));
+        self.text.push_str("\\midrule\n");
+        for cells in rows {
+            self.text.push_str(&row(cells));
+        }
+        self.text
+            .push_str("\\bottomrule\n\\end{tabular}\n\\end{table}\n\n");
+    }
+
+    fn finish(self) -> String {
+        self.text
+    }
+}
+
+/// Escapes text for Markdown, including the pipes that would split a table cell.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '`' | '<' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A GitHub-flavoured Markdown summary for wikis and pull requests.
+#[derive(Default)]
+struct Markdown(String);
+
+impl Markup for Markdown {
+    fn header(&mut self, template: &Template) {
+        if !template.logo.is_empty() {
+            self.0.push_str(&format!("![]({})\n\n", template.logo));
+        }
+        self.0
+            .push_str(&format!("# {}\n\n", escape_markdown(&template.title)));
+        let fields = [
+            ("report-project", &template.project),
+            ("report-author", &template.author),
+            ("report-revision", &template.revision),
+        ];
+        for (id, value) in fields.into_iter().filter(|(_, value)| !value.is_empty()) {
+            self.0.push_str(&format!(
+                "**{}:** {}  \n",
+                escape_markdown(&tr(id)),
+                escape_markdown(value)
+            ));
+        }
+        self.0.push('\n');
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.0
+            .push_str(&format!("## {}\n\n", escape_markdown(text)));
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        self.0.push_str(&format!("{}\n\n", escape_markdown(text)));
+    }
+
+    fn table(&mut self, headers: &[String], rows: &[Vec<String>]) {
+        let row = |cells: &[String]| {
+            let cells: Vec<String> = cells.iter().map(|cell| escape_markdown(cell)).collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+        self.0.push_str(&row(headers));
+        let alignment: Vec<&str> = (0..headers.len())
+            .map(|i| if i == 0 { ":--" } else { "--:" })
+            .collect();
+        self.0.push_str(&format!("| {} |\n", alignment.join(" | ")));
+        for cells in rows {
+            self.0.push_str(&row(cells));
+        }
+        self.0.push('\n');
+    }
+
+    fn finish(self) -> String {
+        self.0
+    }
+}
+
+#[derive(Default)]
+pub struct Report {
+    message: Option<Result<String, String>>,
+}
+
+impl Report {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &mut GraphState,
+    ) {
+        egui::Window::new(tr("report-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                let template = &mut state.report;
+                egui::Grid::new("report header")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        let fields = [
+                            ("report-document-title", &mut template.title),
+                            ("report-project", &mut template.project),
+                            ("report-author", &mut template.author),
+                            ("report-revision", &mut template.revision),
+                            ("report-logo", &mut template.logo),
+                        ];
+                        for (id, value) in fields {
+                            ui.label(tr(id));
+                            ui.text_edit_singleline(value);
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                ui.label(tr("report-sections"));
+                // Templates saved before a section existed don't list it.
+                for section in Section::ALL {
+                    if !template.sections.iter().any(|(s, _)| *s == section) {
+                        template.sections.push((section, false));
+                    }
+                }
+                let mut swap = None;
+                let count = template.sections.len();
+                for (i, (section, enabled)) in template.sections.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(enabled, section.name());
+                        if ui
+                            .add_enabled(i > 0, egui::Button::new("⏶").small())
+                            .clicked()
+                        {
+                            swap = Some(i - 1);
+                        }
+                        if ui
+                            .add_enabled(i + 1 < count, egui::Button::new("⏷").small())
+                            .clicked()
+                        {
+                            swap = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = swap {
+                    template.sections.swap(i, i + 1);
+                }
+                if template
+                    .sections
+                    .iter()
+                    .any(|(section, enabled)| *section == Section::Notes && *enabled)
+                {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut template.notes)
+                            .hint_text(tr("report-notes"))
+                            .desired_width(f32::INFINITY),
+                    );
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    for format in Format::ALL {
+                        if ui.button(format.label()).clicked() {
+                            let template = state.report.clone();
+                            self.message = Some(
+                                collect(graph, state)
+                                    .map_err(|err| err.to_string())
+                                    .and_then(|content| {
+                                        format.save(&format.render(&template, &content))
+                                    }),
+                            );
+                        }
+                    }
+                });
+                if ui.button(tr("report-results-json")).clicked() {
+                    let exported = results::evaluate(graph, state, &Options::of(state))
+                        .and_then(|result| results::export(&result, &Audit::new(graph, state)));
+                    match exported {
+                        Ok(true) => {
+                            toasts::success(tr("toast-results-exported"));
+                            self.message = None;
+                        }
+                        Ok(false) => {}
+                        Err(err) => self.message = Some(Err(err.to_string())),
+                    }
+                }
+                if ui.button(tr("report-copy-markdown")).clicked() {
+                    let template = state.report.clone();
+                    match collect(graph, state) {
+                        Ok(content) => {
+                            let text = Format::Markdown.render(&template, &content);
+                            ui.output_mut(|o| o.copied_text = text);
+                            self.message = None;
+                        }
+                        Err(err) => self.message = Some(Err(err.to_string())),
+                    }
+                }
+                match &self.message {
+                    Some(Ok(path)) if !path.is_empty() => {
+                        ui.label(tr_with("report-saved", &[("path", path)]));
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                    _ => {}
+                }
+            });
+    }
+}
+
+/// The file formats a report can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Html,
+    Latex,
+    Markdown,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Html, Format::Latex, Format::Markdown];
+
+    fn label(self) -> String {
+        match self {
+            Format::Html => tr("report-export-html"),
+            Format::Latex => tr("report-export-latex"),
+            Format::Markdown => tr("report-export-markdown"),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Latex => "tex",
+            Format::Markdown => "md",
+        }
+    }
+
+    pub fn render(self, template: &Template, content: &Content) -> String {
+        match self {
+            Format::Html => render(template, content, Html::default()),
+            Format::Latex => render(template, content, Latex::default()),
+            Format::Markdown => render(template, content, Markdown::default()),
+        }
+    }
+
+    /// Asks for a file name and writes `text` there, returning the path, or
+    /// an empty string if the dialog was cancelled.
+    fn save(self, text: &str) -> Result<String, String> {
+        let extension = self.extension();
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(extension.to_uppercase(), &[extension])
+            .set_file_name(format!("report.{extension}"))
+            .save_file()
+        else {
+            return Ok(String::new());
+        };
+        std::fs::write(&path, text).map_err(|err| err.to_string())?;
+        Ok(path.display().to_string())
+    }
+}
@@ -0,0 +1,54 @@
+//! A reliability requirement such as R ≥ 0.99 at 8760 h.
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::settings::TimeUnit;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Requirement {
+    pub enabled: bool,
+    pub reliability: f64,
+    /// The time step at which `reliability` must be reached.
+    pub time: usize,
+}
+
+impl Default for Requirement {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reliability: 0.99,
+            time: 8_760,
+        }
+    }
+}
+
+impl Requirement {
+    /// Whether the curve meets the requirement, or `None` if the requirement is
+    /// disabled or the curve doesn't reach the required time.
+    pub fn is_met(&self, curve: &[f64]) -> Option<bool> {
+        if !self.enabled {
+            return None;
+        }
+        curve.get(self.time).map(|r| *r >= self.reliability)
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, time_unit: TimeUnit) {
+        ui.checkbox(&mut self.enabled, tr("requirement"));
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.label("R ≥");
+            ui.add(
+                egui::DragValue::new(&mut self.reliability)
+                    .speed(0.001)
+                    .clamp_range(0.0..=1.0)
+                    .max_decimals(6),
+            );
+            ui.label(tr("requirement-at"));
+            ui.add(
+                egui::DragValue::new(&mut self.time)
+                    .suffix(format!(" {}", time_unit.symbol())),
+            );
+        });
+    }
+}
@@ -0,0 +1,233 @@
+//! The results of evaluating a system as plain values, which the report, the
+//! command line and the exports all take their figures from, so no two of
+//! them can show different numbers for the same model.
+use std::collections::BTreeMap;
+
+use egui_node_graph2::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{self, Audit};
+use crate::checkpoints::{self, Checkpoint};
+use crate::criticality;
+use crate::eval::{self, evaluate_measure, Measure, Options, OutputsCache, Revision};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+use crate::requirement::Requirement;
+use crate::settings::TimeUnit;
+
+/// A curve over the time steps, from t = 0.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CurveSeries {
+    /// What the values are of, such as `R(t)`.
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+impl CurveSeries {
+    pub fn at(&self, time: usize) -> anyhow::Result<f64> {
+        self.values
+            .get(time)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!(tr_with("error-time-out-of-range", &[("time", &time)])))
+    }
+}
+
+/// One component's importance to the system, as in [`criticality::Row`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportanceRow {
+    pub component: String,
+    pub reliability: f64,
+    pub birnbaum: f64,
+    pub fussell_vesely: f64,
+    pub criticality: f64,
+}
+
+/// The importance measures of every component at one time.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImportanceTable {
+    pub time: usize,
+    pub rows: Vec<ImportanceRow>,
+}
+
+impl ImportanceTable {
+    pub fn new(graph: &Graph, time: usize, rows: &[criticality::Row]) -> Self {
+        Self {
+            time,
+            rows: rows
+                .iter()
+                .map(|row| ImportanceRow {
+                    component: graph[row.component].label.clone(),
+                    reliability: row.reliability,
+                    birnbaum: row.birnbaum,
+                    fussell_vesely: row.fussell_vesely,
+                    criticality: row.criticality,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationResult {
+    pub system: String,
+    pub mission_time: usize,
+    pub time_unit: String,
+    /// R and A at the mission time.
+    pub reliability: f64,
+    pub availability: f64,
+    pub mttf: f64,
+    /// The reported percentages and the system's Bx lives for each.
+    pub b_lives: Vec<(f64, Option<f64>)>,
+    /// Whether the requirement is met, if one is set.
+    pub requirement_met: Option<bool>,
+    pub reliability_curve: CurveSeries,
+    pub availability_curve: CurveSeries,
+    pub checkpoints: Vec<Checkpoint>,
+    /// Empty in a [`summarize`]d result.
+    pub importance: ImportanceTable,
+    /// The CRC-32 of the evaluated model, to tell which revision it is of.
+    pub model_crc32: String,
+}
+
+fn system(state: &GraphState) -> anyhow::Result<NodeId> {
+    state
+        .active_node
+        .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))
+}
+
+/// Evaluates the active node of `state` as the system, but for the
+/// importance of its components, which takes two evaluations each.
+pub fn summarize(
+    graph: &Graph,
+    state: &GraphState,
+    options: &Options,
+) -> anyhow::Result<EvaluationResult> {
+    let system = system(state)?;
+    let time = state.mission_time;
+    let series = |measure, name: &str| {
+        let values = evaluate_measure(graph, options, measure, system, &mut OutputsCache::new())?
+            .try_to_reliability()?;
+        anyhow::Ok(CurveSeries {
+            name: name.to_owned(),
+            values,
+        })
+    };
+    let reliability_curve = series(Measure::Reliability, "R(t)")?;
+    let availability_curve = series(Measure::Availability, "A(t)")?;
+    let curve = &reliability_curve.values;
+
+    Ok(EvaluationResult {
+        system: graph[system].label.clone(),
+        mission_time: time,
+        time_unit: state.settings.time_unit.symbol(),
+        reliability: reliability_curve.at(time)?,
+        availability: availability_curve.at(time)?,
+        mttf: eval::mttf(curve),
+        b_lives: state
+            .settings
+            .b_lives
+            .iter()
+            .map(|percent| (*percent, eval::b_life(curve, *percent)))
+            .collect(),
+        requirement_met: state.requirement.is_met(curve),
+        checkpoints: checkpoints::read(
+            graph,
+            options,
+            system,
+            &state.checkpoints,
+            curve,
+            &availability_curve.values,
+        ),
+        importance: ImportanceTable::default(),
+        model_crc32: format!("{:08x}", audit::checksum(graph, state)),
+        reliability_curve,
+        availability_curve,
+    })
+}
+
+/// Evaluates the active node of `state` as the system, with everything the
+/// report and the exports give.
+pub fn evaluate(
+    graph: &mut Graph,
+    state: &GraphState,
+    options: &Options,
+) -> anyhow::Result<EvaluationResult> {
+    let mut result = summarize(graph, state, options)?;
+    let time = state.mission_time;
+    let rows = criticality::rank(graph, options, system(state)?, time)?;
+    result.importance = ImportanceTable::new(graph, time, &rows);
+    Ok(result)
+}
+
+/// What a summary was made of.
+#[derive(PartialEq)]
+struct Key {
+    revision: Revision,
+    system: Option<NodeId>,
+    mission_time: usize,
+    checkpoints: Vec<usize>,
+    b_lives: Vec<f64>,
+    requirement: Requirement,
+    time_unit: TimeUnit,
+}
+
+/// The [`summarize`]d active node, which the results panel, the checkpoints
+/// and the summary card all read, made again only once the model or what is
+/// reported of it changes.
+#[derive(Default)]
+pub struct Summary {
+    key: Option<Key>,
+    result: Option<Result<EvaluationResult, String>>,
+}
+
+impl Summary {
+    /// Summarizes the active node again if anything it depends on changed.
+    pub fn update(&mut self, graph: &Graph, state: &GraphState) {
+        let key = Key {
+            revision: Revision::of(graph, state),
+            system: state.active_node,
+            mission_time: state.mission_time,
+            checkpoints: state.checkpoints.clone(),
+            b_lives: state.settings.b_lives.clone(),
+            requirement: state.requirement,
+            time_unit: state.settings.time_unit,
+        };
+        if self.key.as_ref() != Some(&key) {
+            self.result = key.system.map(|_| {
+                summarize(graph, state, &key.revision.options).map_err(|err| err.to_string())
+            });
+            self.key = Some(key);
+        }
+    }
+
+    /// The summary of the active node, `None` if there is none.
+    pub fn result(&self) -> Option<&Result<EvaluationResult, String>> {
+        self.result.as_ref()
+    }
+}
+
+/// `result` as exported, with the settings it was calculated with.
+#[derive(Serialize)]
+struct Export<'a> {
+    audit: BTreeMap<String, String>,
+    #[serde(flatten)]
+    result: &'a EvaluationResult,
+}
+
+/// Asks for a file name and writes `result` there as JSON. Returns whether
+/// a file was written, `false` if the dialog was cancelled.
+pub fn export(result: &EvaluationResult, audit: &Audit) -> anyhow::Result<bool> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name("results.json")
+        .save_file()
+    else {
+        return Ok(false);
+    };
+    let json = serde_json::to_string_pretty(&Export {
+        audit: audit.to_map(),
+        result,
+    })?;
+    std::fs::write(path, json + "\n")?;
+    Ok(true)
+}
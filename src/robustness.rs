@@ -0,0 +1,266 @@
+//! Robustness of a design to uncertain data: every component's Weibull shape
+//! and scale is perturbed at random within a tolerance, many times over, and
+//! the spread of the system's R(mission) is reported along with how often
+//! it still meets the requirement.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Bar, BarChart, Plot, VLine};
+use rand::Rng;
+
+use crate::eval::{evaluate_curve, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, NodeParameters};
+
+const BINS: usize = 30;
+
+/// The spread of R(mission) over the perturbed trials.
+struct Outcome {
+    nominal: f64,
+    nominal_met: Option<bool>,
+    /// R(mission) of each trial, sorted.
+    values: Vec<f64>,
+    /// How many trials met the requirement, if it is enabled.
+    met: Option<usize>,
+    seed: u64,
+}
+
+impl Outcome {
+    /// The value below which `fraction` of the trials fall.
+    fn percentile(&self, fraction: f64) -> f64 {
+        let i = (fraction * (self.values.len() - 1) as f64).round() as usize;
+        self.values[i]
+    }
+
+    fn mean(&self) -> f64 {
+        self.values.iter().sum::<f64>() / self.values.len() as f64
+    }
+
+    fn histogram(&self, color: egui::Color32) -> BarChart {
+        let (low, high) = (self.values[0], self.values[self.values.len() - 1]);
+        let width = ((high - low) / BINS as f64).max(f64::EPSILON);
+        let mut counts = [0usize; BINS];
+        for value in &self.values {
+            counts[(((value - low) / width) as usize).min(BINS - 1)] += 1;
+        }
+        BarChart::new(
+            counts
+                .into_iter()
+                .enumerate()
+                .map(|(i, count)| {
+                    Bar::new(low + (i as f64 + 0.5) * width, count as f64).width(width)
+                })
+                .collect(),
+        )
+        .color(color)
+    }
+}
+
+pub struct Robustness {
+    /// How far each shape may stray from its value, in percent of it.
+    shape_tolerance: f64,
+    /// How far each scale may stray from its value, in percent of it.
+    scale_tolerance: f64,
+    trials: usize,
+    /// The percentage of trials that must meet the requirement for
+    /// compliance to count as robust.
+    confidence: f64,
+    result: Option<Result<Outcome, String>>,
+}
+
+impl Default for Robustness {
+    fn default() -> Self {
+        Self {
+            shape_tolerance: 10.0,
+            scale_tolerance: 20.0,
+            trials: 500,
+            confidence: 90.0,
+            result: None,
+        }
+    }
+}
+
+impl Robustness {
+    /// Evaluates the system with each trial's perturbed parameters,
+    /// restoring the components' parameters afterwards.
+    fn run(
+        &self,
+        graph: &mut Graph,
+        system: NodeId,
+        state: &GraphState,
+    ) -> anyhow::Result<Outcome> {
+        let originals: Vec<(NodeId, NodeParameters)> = nodes::components(graph)
+            .into_iter()
+            .filter_map(|node| Some((node, nodes::component_parameters(graph, node)?)))
+            .collect();
+        let options = Options::of(state);
+        let evaluate =
+            |graph: &Graph| evaluate_curve(graph, &options, system, &mut OutputsCache::new());
+        let at_mission = |curve: &[f64]| curve.get(state.mission_time).copied().unwrap_or(f64::NAN);
+        let nominal = evaluate(graph)?;
+
+        let mut rng = state.settings.rng();
+        let mut values = Vec::with_capacity(self.trials);
+        let mut met = 0;
+        let mut result = Ok(());
+        for _ in 0..self.trials {
+            for (node, original) in &originals {
+                let mut spread =
+                    |tolerance: f64| 1.0 + tolerance / 100.0 * rng.gen_range(-1.0..=1.0);
+                let shape = original.shape * spread(self.shape_tolerance);
+                let scale = original.scale * spread(self.scale_tolerance);
+                if let Some(parameters) = nodes::component_parameters_mut(graph, *node) {
+                    parameters.shape = shape.max(f64::MIN_POSITIVE);
+                    parameters.scale = scale.max(f64::MIN_POSITIVE);
+                }
+            }
+            match evaluate(graph) {
+                Ok(curve) => {
+                    values.push(at_mission(&curve));
+                    met += usize::from(state.requirement.is_met(&curve) == Some(true));
+                }
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+        for (node, original) in originals {
+            if let Some(parameters) = nodes::component_parameters_mut(graph, node) {
+                *parameters = original;
+            }
+        }
+        result?;
+
+        values.sort_by(f64::total_cmp);
+        let enabled = state.requirement.enabled;
+        Ok(Outcome {
+            nominal: at_mission(&nominal),
+            nominal_met: state.requirement.is_met(&nominal),
+            values,
+            met: enabled.then_some(met),
+            seed: state.settings.seed,
+        })
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("robustness-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("robustness setup")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("robustness-shape-tolerance"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.shape_tolerance)
+                                .clamp_range(0.0..=90.0)
+                                .suffix(" %"),
+                        );
+                        ui.end_row();
+                        ui.label(tr("robustness-scale-tolerance"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.scale_tolerance)
+                                .clamp_range(0.0..=90.0)
+                                .suffix(" %"),
+                        );
+                        ui.end_row();
+                        ui.label(tr("robustness-trials"));
+                        ui.add(egui::DragValue::new(&mut self.trials).clamp_range(10..=100_000));
+                        ui.end_row();
+                        ui.label(tr("robustness-confidence"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.confidence)
+                                .clamp_range(50.0..=100.0)
+                                .suffix(" %"),
+                        )
+                        .on_hover_text(tr("robustness-confidence-hint"));
+                        ui.end_row();
+                    });
+
+                let system = state.active_node;
+                if system.is_none() {
+                    ui.label(tr("goal-seek-no-system"));
+                }
+                if ui
+                    .add_enabled(system.is_some(), egui::Button::new(tr("robustness-run")))
+                    .clicked()
+                {
+                    if let Some(system) = system {
+                        self.result = Some(
+                            self.run(graph, system, state)
+                                .map_err(|err| err.to_string()),
+                        );
+                    }
+                }
+
+                let outcome = match &self.result {
+                    Some(Ok(outcome)) => outcome,
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                        return;
+                    }
+                    None => return,
+                };
+                let theme = state.settings.theme.catppuccin();
+                ui.label(format!("{} {}", tr("settings-seed"), outcome.seed));
+                egui::Grid::new("robustness results")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        let rows = [
+                            (tr("robustness-nominal"), outcome.nominal),
+                            (tr("robustness-mean"), outcome.mean()),
+                            ("P5".to_owned(), outcome.percentile(0.05)),
+                            ("P50".to_owned(), outcome.percentile(0.5)),
+                            ("P95".to_owned(), outcome.percentile(0.95)),
+                        ];
+                        for (name, value) in rows {
+                            ui.label(name);
+                            ui.label(format!("{value:.6}"));
+                            ui.end_row();
+                        }
+                    });
+
+                match (outcome.met, outcome.nominal_met) {
+                    (Some(met), Some(nominal_met)) => {
+                        let share = 100.0 * met as f64 / outcome.values.len() as f64;
+                        let text =
+                            tr_with("robustness-share", &[("share", &format!("{share:.1}"))]);
+                        ui.label(text);
+                        if !nominal_met {
+                            ui.colored_label(theme.red, tr("robustness-not-met"));
+                        } else if share < self.confidence {
+                            ui.colored_label(theme.peach, tr("robustness-fragile"));
+                        } else {
+                            ui.colored_label(theme.green, tr("robustness-robust"));
+                        }
+                    }
+                    _ => {
+                        ui.label(tr("robustness-no-requirement"));
+                    }
+                }
+
+                Plot::new("robustness histogram")
+                    .height(200.0)
+                    .x_axis_label("R(t_m)")
+                    .y_axis_label(tr("robustness-trials"))
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(outcome.histogram(theme.blue));
+                        plot_ui.vline(VLine::new(outcome.nominal).color(theme.text));
+                        // The requirement only marks the axis if it is set at the mission time.
+                        if state.requirement.enabled && state.requirement.time == state.mission_time
+                        {
+                            plot_ui
+                                .vline(VLine::new(state.requirement.reliability).color(theme.red));
+                        }
+                    });
+                ui.label(tr("robustness-hint"));
+            });
+    }
+}
@@ -0,0 +1,226 @@
+//! The rate of occurrence of failures (ROCOF) of repaired components.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Legend, Line, Plot, VLine};
+
+use crate::eval::{self, evaluate_node, Options, OutputsCache, Revision};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState};
+
+/// Relative change in w(t) over the second half of the mission below which
+/// the trend is called steady.
+const STEADY: f64 = 0.01;
+
+/// Failure intensities of the components a system depends on.
+pub struct Intensity {
+    /// Each component's w(t), sorted by label.
+    pub components: Vec<(NodeId, Vec<f64>)>,
+    /// The sum of the component intensities, up to the shortest horizon.
+    pub total: Vec<f64>,
+}
+
+pub fn intensity(graph: &Graph, options: &Options, system: NodeId) -> anyhow::Result<Intensity> {
+    evaluate_node(graph, options, system, &mut OutputsCache::new())?;
+    let components = nodes::upstream_components(graph, system)
+        .into_iter()
+        .map(|node| {
+            let parameters = nodes::component_parameters(graph, node)
+                .ok_or_else(|| anyhow::anyhow!(tr("error-not-component")))?;
+            Ok((
+                node,
+                eval::component_failure_intensity(&parameters, &options.profile)?,
+            ))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let horizon = components
+        .iter()
+        .map(|(_, curve)| curve.len())
+        .min()
+        .unwrap_or_default();
+    let total = (0..horizon)
+        .map(|t| components.iter().map(|(_, curve)| curve[t]).sum())
+        .collect();
+    Ok(Intensity { components, total })
+}
+
+/// The expected number of failures up to `time`, ∫w(t)dt by the trapezoidal rule.
+pub fn expected_failures(intensity: &[f64], time: usize) -> f64 {
+    let end = (time + 1).min(intensity.len());
+    intensity[..end]
+        .windows(2)
+        .map(|w| 0.5 * (w[0] + w[1]))
+        .sum()
+}
+
+/// The expected cumulative number of failures N(t) at every time step, the
+/// running integral of the intensity.
+pub fn cumulative_failures(intensity: &[f64]) -> Vec<f64> {
+    let mut total = 0.0;
+    let mut cumulative = Vec::with_capacity(intensity.len());
+    for (t, w) in intensity.iter().enumerate() {
+        if t > 0 {
+            total += 0.5 * (intensity[t - 1] + w);
+        }
+        cumulative.push(total);
+    }
+    cumulative
+}
+
+/// Whether failures become more or less frequent over the second half of
+/// `0..=time`.
+fn trend(intensity: &[f64], time: usize) -> Option<&'static str> {
+    let end = *intensity.get(time)?;
+    let middle = intensity[time / 2];
+    let change = (end - middle) / middle.max(f64::MIN_POSITIVE);
+    Some(if change > STEADY {
+        "rocof-deteriorating"
+    } else if change < -STEADY {
+        "rocof-improving"
+    } else {
+        "rocof-steady"
+    })
+}
+
+impl Intensity {
+    /// The expected number of failures up to each time step instead of the
+    /// rate they occur at.
+    fn cumulative(&self) -> Intensity {
+        Intensity {
+            components: self
+                .components
+                .iter()
+                .map(|(node, curve)| (*node, cumulative_failures(curve)))
+                .collect(),
+            total: cumulative_failures(&self.total),
+        }
+    }
+
+    /// Formats the curves as tab-separated rows of t, the total and each component.
+    fn to_tsv(&self, graph: &Graph) -> String {
+        let mut tsv = format!("t\t{}", tr("rocof-total"));
+        for (node, _) in &self.components {
+            tsv.push('\t');
+            tsv.push_str(&graph[*node].label);
+        }
+        tsv.push('\n');
+        for (t, total) in self.total.iter().enumerate() {
+            tsv.push_str(&format!("{t}\t{total}"));
+            for (_, curve) in &self.components {
+                tsv.push_str(&format!("\t{}", curve[t]));
+            }
+            tsv.push('\n');
+        }
+        tsv
+    }
+}
+
+#[derive(Default)]
+pub struct Rocof {
+    per_component: bool,
+    /// Whether the plot shows N(t), the expected failures so far, rather than w(t).
+    cumulative: bool,
+    key: Option<(Revision, Option<NodeId>)>,
+    /// The intensities of the active node, solved for again only once the
+    /// model changes rather than every frame.
+    intensity: Option<Result<Intensity, String>>,
+}
+
+impl Rocof {
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("rocof-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let key = (Revision::of(graph, state), state.active_node);
+                if self.key.as_ref() != Some(&key) {
+                    let result = state
+                        .active_node
+                        .ok_or_else(|| anyhow::anyhow!(tr("goal-seek-no-system")))
+                        .and_then(|system| intensity(graph, &key.0.options, system));
+                    self.intensity = Some(result.map_err(|err| err.to_string()));
+                    self.key = Some(key);
+                }
+                let intensity = match &self.intensity {
+                    Some(Ok(intensity)) => intensity,
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                        return;
+                    }
+                    None => return,
+                };
+
+                let time = state.mission_time;
+                let symbol = state.settings.time_unit.symbol();
+                if let Some(rate) = intensity.total.get(time) {
+                    ui.label(tr_with(
+                        "rocof-at-mission-time",
+                        &[("rate", &format!("{rate:.3e}")), ("unit", &symbol)],
+                    ));
+                    ui.label(tr_with(
+                        "rocof-expected-failures",
+                        &[(
+                            "failures",
+                            &format!("{:.3}", expected_failures(&intensity.total, time)),
+                        )],
+                    ));
+                }
+                if let Some(trend) = trend(&intensity.total, time) {
+                    ui.label(tr(trend));
+                }
+                let cumulative;
+                let intensity = if self.cumulative {
+                    cumulative = intensity.cumulative();
+                    &cumulative
+                } else {
+                    intensity
+                };
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.per_component, tr("rocof-per-component"));
+                    ui.checkbox(&mut self.cumulative, tr("rocof-cumulative"));
+                    if ui.button(tr("plot-copy-data")).clicked() {
+                        ui.output_mut(|o| o.copied_text = intensity.to_tsv(graph));
+                    }
+                });
+
+                let theme = state.settings.theme.catppuccin();
+                let points = |curve: &[f64]| -> Vec<[f64; 2]> {
+                    curve
+                        .iter()
+                        .enumerate()
+                        .map(|(t, w)| [t as f64, *w])
+                        .collect()
+                };
+                Plot::new("rocof")
+                    .height(250.0)
+                    .include_y(0.0)
+                    .x_axis_label(format!("t [{symbol}]"))
+                    .y_axis_label(if self.cumulative {
+                        "N(t)".to_owned()
+                    } else {
+                        format!("w(t) [1/{symbol}]")
+                    })
+                    .legend(Legend::default())
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(
+                            Line::new(points(&intensity.total))
+                                .color(theme.blue)
+                                .width(2.0)
+                                .name(tr("rocof-total")),
+                        );
+                        if self.per_component {
+                            for (node, curve) in &intensity.components {
+                                plot_ui.line(Line::new(points(curve)).name(&graph[*node].label));
+                            }
+                        }
+                        plot_ui.vline(VLine::new(time as f64).name(tr("mission-time")));
+                    });
+                ui.label(tr("rocof-hint"));
+            });
+    }
+}
@@ -0,0 +1,259 @@
+//! Scaling every component's life, and optionally repair times, by one
+//! factor, for derating studies like "everything 20% worse" without editing
+//! each node. Each scaling can be undone.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::{evaluate_curve, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, NodeParameters};
+use crate::variables::{self, Bound};
+
+/// How many components the preview lists.
+const PREVIEW: usize = 200;
+
+/// The factors a preview was made for, and R(mission) of the system before
+/// and after scaling by them.
+type Preview = (f64, f64, Result<(f64, f64), String>);
+
+/// A scaling applied, with what it replaced.
+struct Step {
+    factor: f64,
+    mttr_factor: f64,
+    originals: Vec<(NodeId, NodeParameters)>,
+}
+
+pub struct Scaling {
+    /// What each scale, the primary one and those of further failure modes,
+    /// is multiplied by; below 1 makes components fail sooner.
+    factor: f64,
+    /// What each MTTR is multiplied by.
+    mttr_factor: f64,
+    selected_only: bool,
+    preview: Option<Preview>,
+    /// The scalings applied, latest last.
+    history: Vec<Step>,
+}
+
+impl Default for Scaling {
+    fn default() -> Self {
+        Self {
+            factor: 0.8,
+            mttr_factor: 1.0,
+            selected_only: false,
+            preview: None,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl Scaling {
+    fn scaled(&self, parameters: &NodeParameters) -> NodeParameters {
+        let mut scaled = parameters.clone();
+        scaled.scale *= self.factor;
+        for mode in &mut scaled.modes {
+            mode.scale *= self.factor;
+        }
+        scaled.mttr *= self.mttr_factor;
+        scaled
+    }
+
+    /// The components scaled, and how many are left out because they are
+    /// read-only, mirror another or have a scaled parameter given by an
+    /// expression.
+    fn targets(
+        &self,
+        graph: &Graph,
+        selected: &[NodeId],
+        state: &GraphState,
+    ) -> (Vec<(NodeId, NodeParameters)>, usize) {
+        let mut targets = Vec::new();
+        let mut skipped = 0;
+        for node in nodes::components(graph) {
+            if self.selected_only && !selected.contains(&node) {
+                continue;
+            }
+            let Some(parameters) = nodes::component_parameters(graph, node) else {
+                continue;
+            };
+            let bound = parameters.is_bound(Bound::Scale)
+                || (self.mttr_factor != 1.0 && parameters.is_bound(Bound::Mttr));
+            if bound
+                || state.read_only.contains_key(&node)
+                || graph[node].user_data.mirror.is_some()
+            {
+                skipped += 1;
+                continue;
+            }
+            targets.push((node, parameters));
+        }
+        (targets, skipped)
+    }
+
+    /// R(mission) of the system with `targets` scaled, restoring their
+    /// parameters afterwards.
+    fn evaluate(
+        &self,
+        graph: &mut Graph,
+        system: NodeId,
+        targets: &[(NodeId, NodeParameters)],
+        state: &GraphState,
+    ) -> Result<f64, String> {
+        for (node, original) in targets {
+            if let Some(parameters) = nodes::component_parameters_mut(graph, *node) {
+                *parameters = self.scaled(original);
+            }
+        }
+        let curve = evaluate_curve(graph, &Options::of(state), system, &mut OutputsCache::new());
+        for (node, original) in targets {
+            if let Some(parameters) = nodes::component_parameters_mut(graph, *node) {
+                *parameters = original.clone();
+            }
+        }
+        curve
+            .map(|curve| curve.get(state.mission_time).copied().unwrap_or(f64::NAN))
+            .map_err(|err| err.to_string())
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        selected: &[NodeId],
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("scaling-title"))
+            .open(open)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("scaling setup")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(tr("scaling-factor"));
+                        ui.add(
+                            variables::drag_value(&mut self.factor)
+                                .speed(0.01)
+                                .clamp_range(0.01..=100.0)
+                                .prefix("× "),
+                        )
+                        .on_hover_text(tr("scaling-factor-hint"));
+                        ui.end_row();
+                        ui.label(tr("scaling-mttr-factor"));
+                        ui.add(
+                            variables::drag_value(&mut self.mttr_factor)
+                                .speed(0.01)
+                                .clamp_range(0.01..=100.0)
+                                .prefix("× "),
+                        );
+                        ui.end_row();
+                    });
+                ui.checkbox(&mut self.selected_only, tr("rename-selected-only"));
+
+                let (targets, skipped) = self.targets(graph, selected, state);
+                ui.separator();
+                ui.label(tr_with("scaling-count", &[("count", &targets.len())]));
+                if skipped > 0 {
+                    ui.label(tr_with("scaling-skipped", &[("count", &skipped)]));
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("scaling preview")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (node, parameters) in targets.iter().take(PREVIEW) {
+                                    let scaled = self.scaled(parameters);
+                                    ui.label(&graph[*node].label);
+                                    ui.label(format!(
+                                        "η {:.4} → {:.4}",
+                                        parameters.scale, scaled.scale
+                                    ));
+                                    if parameters.mttr > 0.0 {
+                                        ui.label(format!(
+                                            "MTTR {:.4} → {:.4}",
+                                            parameters.mttr, scaled.mttr
+                                        ));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        if targets.len() > PREVIEW {
+                            ui.label(tr_with(
+                                "rename-more",
+                                &[("count", &(targets.len() - PREVIEW))],
+                            ));
+                        }
+                    });
+
+                if let Some(system) = state.active_node {
+                    if ui.button(tr("scaling-preview")).clicked() {
+                        let outcome = self.evaluate(graph, system, &[], state).and_then(|before| {
+                            self.evaluate(graph, system, &targets, state)
+                                .map(|after| (before, after))
+                        });
+                        self.preview = Some((self.factor, self.mttr_factor, outcome));
+                    }
+                    match &self.preview {
+                        Some((factor, mttr_factor, Ok((before, after))))
+                            if *factor == self.factor && *mttr_factor == self.mttr_factor =>
+                        {
+                            ui.label(tr_with(
+                                "scaling-system",
+                                &[
+                                    ("before", &format!("{before:.6}")),
+                                    ("after", &format!("{after:.6}")),
+                                ],
+                            ));
+                        }
+                        Some((_, _, Err(err))) => {
+                            ui.colored_label(ui.visuals().error_fg_color, err);
+                        }
+                        _ => {}
+                    }
+                } else {
+                    ui.label(tr("goal-seek-no-system"));
+                }
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!targets.is_empty(), egui::Button::new(tr("scaling-apply")))
+                        .clicked()
+                    {
+                        for (node, original) in &targets {
+                            if let Some(parameters) = nodes::component_parameters_mut(graph, *node)
+                            {
+                                *parameters = self.scaled(original);
+                            }
+                        }
+                        self.history.push(Step {
+                            factor: self.factor,
+                            mttr_factor: self.mttr_factor,
+                            originals: targets,
+                        });
+                        self.preview = None;
+                    }
+                    let last = self.history.last().map(|step| {
+                        tr_with(
+                            "scaling-undo",
+                            &[("factor", &step.factor), ("mttr", &step.mttr_factor)],
+                        )
+                    });
+                    if let Some(text) = last {
+                        if ui.button(text).clicked() {
+                            let step = self.history.pop().expect("checked above");
+                            for (node, original) in step.originals {
+                                if let Some(parameters) =
+                                    nodes::component_parameters_mut(graph, node)
+                                {
+                                    *parameters = original;
+                                }
+                            }
+                            self.preview = None;
+                        }
+                    }
+                });
+            });
+    }
+}
@@ -0,0 +1,511 @@
+//! User preferences, persisted through eframe storage.
+use std::time::Duration;
+
+use eframe::egui;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use egui_node_graph2::NodeTemplateIter;
+
+use crate::i18n::{tr, tr_with};
+use crate::locale;
+use crate::metrics::Metrics;
+use crate::nodes::{AllNodeTemplates, NodeParameters, NodeTemplate};
+
+/// How many recently inserted templates the node finder lists first.
+const RECENT_TEMPLATES: usize = 5;
+
+/// The generator every random study draws from. `StdRng` may change its
+/// algorithm in any release of `rand`, while ChaCha8 gives the same numbers
+/// for a seed everywhere, so a recorded seed reproduces a run.
+pub fn rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: Theme,
+    /// Zoom applied on top of the OS scale factor, for 4K monitors and projectors.
+    pub ui_scale: f32,
+    pub autosave_seconds: u64,
+    /// Whether the app starts with the diagram it was closed on, rather than
+    /// an empty one.
+    pub restore_session: bool,
+    /// The name sections are locked under, so collaborators know whom to ask.
+    pub author: String,
+    /// Parameters given to newly created component nodes.
+    pub default_parameters: NodeParameters,
+    /// How new nodes are labelled; see [`crate::nodes::auto_name`].
+    pub node_names: String,
+    /// The most trials a simulation runs.
+    pub monte_carlo_samples: usize,
+    /// Simulations stop early once the 95% confidence interval of
+    /// R(mission time) is no wider than ± this; zero runs every trial.
+    pub monte_carlo_half_width: f64,
+    /// Seeds every random study, so results reproduce across runs and machines.
+    pub seed: u64,
+    /// The unit of one time step.
+    pub time_unit: TimeUnit,
+    /// How numbers and dates are written to CSV files and reports.
+    pub number_format: NumberFormat,
+    /// Templates pinned to the top of the node finder.
+    pub favorite_templates: Vec<NodeTemplate>,
+    /// The last templates inserted, most recent first.
+    pub recent_templates: Vec<NodeTemplate>,
+    pub wire_style: WireStyle,
+    /// The failed percentages x whose Bx lives are reported, such as B10.
+    pub b_lives: Vec<f64>,
+    pub precision: Precision,
+    /// Whether large models are evaluated approximately while a value is
+    /// dragged, and precisely once it is let go.
+    pub fast_preview: bool,
+    pub disabled_nodes: DisabledNodes,
+    /// Whether frame and evaluation times are written to a local file, to
+    /// attach to reports of slow models.
+    pub record_metrics: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            ui_scale: 1.0,
+            autosave_seconds: 30,
+            restore_session: true,
+            author: String::new(),
+            default_parameters: NodeParameters::default(),
+            node_names: "{type}-{n}".to_owned(),
+            monte_carlo_samples: 10_000,
+            monte_carlo_half_width: 0.001,
+            seed: 0,
+            time_unit: TimeUnit::default(),
+            number_format: NumberFormat::default(),
+            favorite_templates: Vec::new(),
+            recent_templates: Vec::new(),
+            wire_style: WireStyle::default(),
+            b_lives: vec![10.0, 50.0],
+            precision: Precision::default(),
+            fast_preview: true,
+            disabled_nodes: DisabledNodes::default(),
+            record_metrics: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    Latte,
+    Frappe,
+    #[default]
+    Macchiato,
+    Mocha,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Latte, Theme::Frappe, Theme::Macchiato, Theme::Mocha];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Latte => "Latte",
+            Theme::Frappe => "Frappé",
+            Theme::Macchiato => "Macchiato",
+            Theme::Mocha => "Mocha",
+        }
+    }
+
+    pub fn catppuccin(self) -> catppuccin_egui::Theme {
+        match self {
+            Theme::Latte => catppuccin_egui::LATTE,
+            Theme::Frappe => catppuccin_egui::FRAPPE,
+            Theme::Macchiato => catppuccin_egui::MACCHIATO,
+            Theme::Mocha => catppuccin_egui::MOCHA,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeUnit {
+    #[default]
+    Hours,
+    Days,
+    Years,
+    Cycles,
+}
+
+impl TimeUnit {
+    pub const ALL: [TimeUnit; 4] = [
+        TimeUnit::Hours,
+        TimeUnit::Days,
+        TimeUnit::Years,
+        TimeUnit::Cycles,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            TimeUnit::Hours => tr("unit-hours"),
+            TimeUnit::Days => tr("unit-days"),
+            TimeUnit::Years => tr("unit-years"),
+            TimeUnit::Cycles => tr("unit-cycles"),
+        }
+    }
+
+    pub fn symbol(self) -> String {
+        match self {
+            TimeUnit::Hours => tr("unit-hours-symbol"),
+            TimeUnit::Days => tr("unit-days-symbol"),
+            TimeUnit::Years => tr("unit-years-symbol"),
+            TimeUnit::Cycles => tr("unit-cycles-symbol"),
+        }
+    }
+
+    /// The hours in one unit, or `None` for cycles, which aren't tied to time.
+    pub fn hours(self) -> Option<f64> {
+        match self {
+            TimeUnit::Hours => Some(1.0),
+            TimeUnit::Days => Some(24.0),
+            TimeUnit::Years => Some(8_760.0),
+            TimeUnit::Cycles => None,
+        }
+    }
+}
+
+/// The decimal separator and date order of exported files.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberFormat {
+    /// As is usual for the language of the UI.
+    #[default]
+    Language,
+    /// 1234.5 and 2024-03-31, with commas between CSV fields.
+    Point,
+    /// 1234,5 and 31.03.2024, with semicolons between CSV fields.
+    Comma,
+}
+
+impl NumberFormat {
+    pub const ALL: [NumberFormat; 3] = [
+        NumberFormat::Language,
+        NumberFormat::Point,
+        NumberFormat::Comma,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            NumberFormat::Language => tr("number-format-language"),
+            NumberFormat::Point => tr("number-format-point"),
+            NumberFormat::Comma => tr("number-format-comma"),
+        }
+    }
+}
+
+/// How connections between nodes are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireStyle {
+    #[default]
+    Curved,
+    /// Right-angled polylines routed around node bodies, as in printed diagrams.
+    Orthogonal,
+}
+
+impl WireStyle {
+    pub const ALL: [WireStyle; 2] = [WireStyle::Curved, WireStyle::Orthogonal];
+
+    pub fn name(self) -> String {
+        match self {
+            WireStyle::Curved => tr("wire-curved"),
+            WireStyle::Orthogonal => tr("wire-orthogonal"),
+        }
+    }
+}
+
+/// How precisely evaluated curves are kept between the nodes of a diagram.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Precision {
+    #[default]
+    Double,
+    /// Half the memory for models with thousands of nodes and long horizons,
+    /// at about seven significant digits.
+    Single,
+}
+
+impl Precision {
+    pub const ALL: [Precision; 2] = [Precision::Double, Precision::Single];
+
+    pub fn name(self) -> String {
+        match self {
+            Precision::Double => tr("precision-double"),
+            Precision::Single => tr("precision-single"),
+        }
+    }
+}
+
+/// How nodes switched off for a staged build count in the blocks they feed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisabledNodes {
+    /// As never failing.
+    #[default]
+    Perfect,
+    /// As if they weren't connected: a series block no longer depends on
+    /// them, and a parallel block loses the redundancy.
+    Absent,
+}
+
+impl DisabledNodes {
+    pub const ALL: [DisabledNodes; 2] = [DisabledNodes::Perfect, DisabledNodes::Absent];
+
+    pub fn name(self) -> String {
+        match self {
+            DisabledNodes::Perfect => tr("disabled-perfect"),
+            DisabledNodes::Absent => tr("disabled-absent"),
+        }
+    }
+}
+
+impl Settings {
+    pub const KEY: &'static str = "settings";
+
+    pub fn autosave_interval(&self) -> Duration {
+        Duration::from_secs(self.autosave_seconds)
+    }
+
+    pub fn rng(&self) -> ChaCha8Rng {
+        rng(self.seed)
+    }
+
+    pub fn remember_template(&mut self, template: NodeTemplate) {
+        self.recent_templates.retain(|recent| *recent != template);
+        self.recent_templates.insert(0, template);
+        self.recent_templates.truncate(RECENT_TEMPLATES);
+    }
+
+    /// Every template in node finder order: favorites, then recently
+    /// inserted ones, then the rest.
+    pub fn finder_order(&self) -> Vec<NodeTemplate> {
+        let all = AllNodeTemplates.all_kinds();
+        let mut order: Vec<NodeTemplate> = all
+            .iter()
+            .copied()
+            .filter(|template| self.favorite_templates.contains(template))
+            .collect();
+        for template in self.recent_templates.iter().chain(&all) {
+            if !order.contains(template) {
+                order.push(*template);
+            }
+        }
+        order
+    }
+
+    /// Applies the settings that live in egui's own state.
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_zoom_factor(self.ui_scale);
+        locale::set_format(self.number_format);
+    }
+
+    pub fn window(&mut self, ctx: &egui::Context, open: &mut bool) {
+        egui::Window::new(tr("settings-title"))
+            .open(open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("settings").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("settings-theme"));
+                    egui::ComboBox::from_id_source("theme")
+                        .selected_text(self.theme.name())
+                        .show_ui(ui, |ui| {
+                            for theme in Theme::ALL {
+                                ui.selectable_value(&mut self.theme, theme, theme.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("settings-ui-scale"));
+                    ui.horizontal(|ui| {
+                        let slider = egui::Slider::new(&mut self.ui_scale, 0.5..=3.0)
+                            .step_by(0.05)
+                            .suffix("×");
+                        if ui.add(slider).changed() {
+                            ctx.set_zoom_factor(self.ui_scale);
+                        }
+                        if ui.button(tr("settings-reset")).clicked() {
+                            self.ui_scale = Settings::default().ui_scale;
+                            ctx.set_zoom_factor(self.ui_scale);
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("settings-autosave"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.autosave_seconds)
+                            .clamp_range(5..=3600)
+                            .suffix(" s"),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("settings-restore-session"));
+                    ui.checkbox(&mut self.restore_session, "");
+                    ui.end_row();
+
+                    ui.label(tr("settings-author"));
+                    ui.add(egui::TextEdit::singleline(&mut self.author).desired_width(120.0))
+                        .on_hover_text(tr("settings-author-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("settings-node-names"));
+                    ui.add(egui::TextEdit::singleline(&mut self.node_names).desired_width(120.0))
+                        .on_hover_text(tr("settings-node-names-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("settings-time-unit"));
+                    egui::ComboBox::from_id_source("time unit")
+                        .selected_text(self.time_unit.name())
+                        .show_ui(ui, |ui| {
+                            for unit in TimeUnit::ALL {
+                                ui.selectable_value(&mut self.time_unit, unit, unit.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("settings-number-format"));
+                    egui::ComboBox::from_id_source("number format")
+                        .selected_text(self.number_format.name())
+                        .show_ui(ui, |ui| {
+                            for format in NumberFormat::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.number_format,
+                                        format,
+                                        format.name(),
+                                    )
+                                    .changed()
+                                {
+                                    locale::set_format(self.number_format);
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("settings-number-format-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("settings-wire-style"));
+                    egui::ComboBox::from_id_source("wire style")
+                        .selected_text(self.wire_style.name())
+                        .show_ui(ui, |ui| {
+                            for style in WireStyle::ALL {
+                                ui.selectable_value(&mut self.wire_style, style, style.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("settings-b-lives"));
+                    ui.horizontal(|ui| {
+                        let mut remove = None;
+                        for (i, percent) in self.b_lives.iter_mut().enumerate() {
+                            ui.add(
+                                egui::DragValue::new(percent)
+                                    .speed(0.1)
+                                    .clamp_range(0.01..=99.99)
+                                    .prefix("B"),
+                            );
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                        }
+                        if let Some(i) = remove {
+                            self.b_lives.remove(i);
+                        }
+                        if ui.small_button("+").clicked() {
+                            let next = self.b_lives.last().map_or(10.0, |x| (x + 10.0).min(99.0));
+                            self.b_lives.push(next);
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("settings-precision"));
+                    egui::ComboBox::from_id_source("precision")
+                        .selected_text(self.precision.name())
+                        .show_ui(ui, |ui| {
+                            for precision in Precision::ALL {
+                                ui.selectable_value(
+                                    &mut self.precision,
+                                    precision,
+                                    precision.name(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("settings-fast-preview"));
+                    ui.checkbox(&mut self.fast_preview, "")
+                        .on_hover_text(tr("settings-fast-preview-hint"));
+                    ui.end_row();
+
+                    ui.label(tr("settings-disabled-nodes"));
+                    egui::ComboBox::from_id_source("disabled nodes")
+                        .selected_text(self.disabled_nodes.name())
+                        .show_ui(ui, |ui| {
+                            for disabled in DisabledNodes::ALL {
+                                ui.selectable_value(
+                                    &mut self.disabled_nodes,
+                                    disabled,
+                                    disabled.name(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr("settings-monte-carlo-samples"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.monte_carlo_samples)
+                            .clamp_range(100..=10_000_000)
+                            .speed(100.0),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("settings-monte-carlo-half-width"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.monte_carlo_half_width)
+                            .clamp_range(0.0..=0.1)
+                            .speed(0.0001)
+                            .prefix("± "),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr("settings-seed"));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut self.seed));
+                        if ui.button(tr("settings-new-seed")).clicked() {
+                            self.seed = rand::random::<u32>().into();
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("settings-record-metrics"));
+                    let path = Metrics::path()
+                        .map_or_else(|| "—".to_owned(), |path| path.display().to_string());
+                    ui.checkbox(&mut self.record_metrics, "")
+                        .on_hover_text(tr_with("settings-record-metrics-hint", &[("path", &path)]));
+                    ui.end_row();
+                });
+
+                ui.separator();
+                ui.label(tr("settings-favorite-templates"));
+                ui.horizontal_wrapped(|ui| {
+                    for template in AllNodeTemplates.all_kinds() {
+                        let mut favorite = self.favorite_templates.contains(&template);
+                        if ui.checkbox(&mut favorite, template.name()).changed() {
+                            if favorite {
+                                self.favorite_templates.push(template);
+                            } else {
+                                self.favorite_templates.retain(|t| *t != template);
+                            }
+                        }
+                    }
+                });
+
+                ui.separator();
+                ui.label(tr("settings-default-parameters"));
+                self.default_parameters.ui(ui);
+            });
+    }
+}
@@ -0,0 +1,154 @@
+//! System-level shocks, like overloads or lightning strikes, that arrive at a
+//! given rate and take away part of the remaining life of the components
+//! they hit. They act in the Monte Carlo sampling of failure times only, as
+//! the analytic curves have no notion of events.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Shock {
+    pub name: String,
+    /// Events per time step, arriving as a Poisson process.
+    pub rate: f64,
+    /// The fraction of a hit component's remaining life each event takes.
+    pub damage: f64,
+    pub targets: Vec<NodeId>,
+}
+
+impl Default for Shock {
+    fn default() -> Self {
+        Self {
+            name: tr("shocks-default-name"),
+            rate: 1e-3,
+            damage: 0.2,
+            targets: Vec::new(),
+        }
+    }
+}
+
+/// The event times of each shock in one trial, drawn as far as needed so
+/// every component of the trial sees the same events.
+#[derive(Default)]
+pub struct Events {
+    times: Vec<Vec<f64>>,
+}
+
+impl Events {
+    pub fn clear(&mut self) {
+        self.times.clear();
+    }
+
+    /// The first event of shock `i` after `time`.
+    fn next(&mut self, shock: &Shock, i: usize, time: f64, rng: &mut impl Rng) -> f64 {
+        if self.times.len() <= i {
+            self.times.resize(i + 1, Vec::new());
+        }
+        let times = &mut self.times[i];
+        while times.last().is_none_or(|last| *last <= time) {
+            let last = times.last().copied().unwrap_or(0.0);
+            times.push(last - (1.0 - rng.gen::<f64>()).ln() / shock.rate);
+        }
+        times[times.partition_point(|t| *t <= time)]
+    }
+
+    /// When `node`, sampled to fail at `life`, fails once the shocks hitting
+    /// it have each shortened what was left of its life.
+    pub fn degrade(
+        &mut self,
+        shocks: &[Shock],
+        node: NodeId,
+        life: f64,
+        rng: &mut impl Rng,
+    ) -> f64 {
+        if !life.is_finite() {
+            return life;
+        }
+        let (mut life, mut now) = (life, 0.0);
+        loop {
+            let mut next: Option<(f64, f64)> = None;
+            for (i, shock) in shocks.iter().enumerate() {
+                if shock.rate <= 0.0 || !shock.targets.contains(&node) {
+                    continue;
+                }
+                let time = self.next(shock, i, now, rng);
+                if next.is_none_or(|(first, _)| time < first) {
+                    next = Some((time, shock.damage));
+                }
+            }
+            match next {
+                Some((time, damage)) if time < life => {
+                    life = time + (life - time) * (1.0 - damage);
+                    now = time;
+                }
+                _ => return life,
+            }
+        }
+    }
+}
+
+/// The editor of the shocks, in the simulation window.
+pub fn ui(ui: &mut egui::Ui, graph: &Graph, shocks: &mut Vec<Shock>) {
+    let components = nodes::components(graph);
+    let mut removed = None;
+    for (i, shock) in shocks.iter_mut().enumerate() {
+        shock.targets.retain(|node| graph.nodes.contains_key(*node));
+        ui.push_id(i, |ui| {
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut shock.name);
+                ui.label(tr("shocks-rate"));
+                ui.add(
+                    egui::DragValue::new(&mut shock.rate)
+                        .speed(1e-5)
+                        .clamp_range(0.0..=f64::MAX)
+                        .max_decimals(6),
+                );
+                ui.label(tr("shocks-damage"));
+                ui.add(
+                    egui::DragValue::new(&mut shock.damage)
+                        .speed(0.01)
+                        .clamp_range(0.0..=1.0),
+                )
+                .on_hover_text(tr("shocks-damage-hint"));
+                ui.menu_button(
+                    tr_with("shocks-targets", &[("count", &shock.targets.len())]),
+                    |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                for node in &components {
+                                    let mut hit = shock.targets.contains(node);
+                                    if ui.checkbox(&mut hit, &graph[*node].label).changed() {
+                                        if hit {
+                                            shock.targets.push(*node);
+                                        } else {
+                                            shock.targets.retain(|target| target != node);
+                                        }
+                                    }
+                                }
+                            });
+                    },
+                );
+                if ui
+                    .small_button("🗑")
+                    .on_hover_text(tr("shocks-remove"))
+                    .clicked()
+                {
+                    removed = Some(i);
+                }
+            });
+        });
+    }
+    if let Some(i) = removed {
+        shocks.remove(i);
+    }
+    if ui.button(tr("shocks-add")).clicked() {
+        shocks.push(Shock::default());
+    }
+    ui.label(tr("shocks-hint"));
+}
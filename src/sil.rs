@@ -0,0 +1,239 @@
+//! Safety integrity of protective subsystems: the average probability of
+//! failure on demand for low-demand mode and the average frequency of
+//! dangerous failures per hour for high-demand mode, mapped to the SIL bands
+//! of IEC 61508.
+//!
+//! A proof test every T renews the subsystem, except for the failures it
+//! doesn't cover, which stay hidden for the whole lifetime L:
+//! PFD_avg = c · F̄(T) + (1 − c) · F̄(L) and PFH = c · F(T)/T + (1 − c) · F(L)/L,
+//! where F̄ is the average unreliability over the interval.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval::{evaluate_curve, Options, OutputsCache};
+use crate::event_tree::{self, Demand};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, GraphState};
+use crate::settings::TimeUnit;
+
+/// The upper band edges of SIL 1 to 4 in low-demand mode, on PFD_avg.
+const LOW_DEMAND: [f64; 4] = [1e-1, 1e-2, 1e-3, 1e-4];
+/// The upper band edges of SIL 1 to 4 in high-demand mode, on PFH.
+const HIGH_DEMAND: [f64; 4] = [1e-5, 1e-6, 1e-7, 1e-8];
+
+/// The SIL whose band `value` falls in. Values below the SIL 4 band still
+/// count as SIL 4, the highest there is; `None` is short of SIL 1.
+fn band(value: f64, edges: &[f64; 4]) -> Option<u8> {
+    if value.is_nan() || value >= edges[0] {
+        return None;
+    }
+    let higher = edges[1..].iter().take_while(|edge| value < **edge).count();
+    Some(higher as u8 + 1)
+}
+
+pub fn low_demand_sil(pfd_avg: f64) -> Option<u8> {
+    band(pfd_avg, &LOW_DEMAND)
+}
+
+pub fn high_demand_sil(pfh: f64) -> Option<u8> {
+    band(pfh, &HIGH_DEMAND)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integrity {
+    pub pfd_avg: f64,
+    /// Per hour, or `None` when time steps are cycles.
+    pub pfh: Option<f64>,
+}
+
+/// PFD_avg and PFH of a subsystem with reliability `curve`, proof tested
+/// every `interval` steps with `coverage` over a `lifetime` in steps.
+pub fn integrity(
+    curve: &[f64],
+    interval: usize,
+    coverage: f64,
+    lifetime: usize,
+    time_unit: TimeUnit,
+) -> Integrity {
+    let lifetime = lifetime.min(curve.len().saturating_sub(1)).max(1);
+    let interval = interval.clamp(1, lifetime);
+    let pfd_avg = coverage * event_tree::pfd(curve, interval, Demand::Average)
+        + (1.0 - coverage) * event_tree::pfd(curve, lifetime, Demand::Average);
+    let rate = |time: usize| (1.0 - curve.get(time).copied().unwrap_or(1.0)) / time as f64;
+    let per_step = coverage * rate(interval) + (1.0 - coverage) * rate(lifetime);
+    Integrity {
+        pfd_avg,
+        pfh: time_unit.hours().map(|hours| per_step / hours),
+    }
+}
+
+fn sil_label(sil: Option<u8>) -> String {
+    match sil {
+        Some(sil) => format!("SIL {sil}"),
+        None => tr("sil-none"),
+    }
+}
+
+pub struct Sil {
+    subsystems: Vec<Option<NodeId>>,
+    /// In time steps.
+    interval: usize,
+    coverage: f64,
+    result: Option<Result<Vec<(NodeId, Integrity)>, String>>,
+}
+
+impl Default for Sil {
+    fn default() -> Self {
+        Self {
+            subsystems: vec![None],
+            interval: 8_760,
+            coverage: 1.0,
+            result: None,
+        }
+    }
+}
+
+impl Sil {
+    fn run(&self, graph: &Graph, state: &GraphState) -> anyhow::Result<Vec<(NodeId, Integrity)>> {
+        let options = Options::of(state);
+        let mut curves = OutputsCache::new();
+        self.subsystems
+            .iter()
+            .flatten()
+            .map(|node| {
+                let curve = evaluate_curve(graph, &options, *node, &mut curves)?;
+                let integrity = integrity(
+                    &curve,
+                    self.interval,
+                    self.coverage,
+                    state.mission_time,
+                    state.settings.time_unit,
+                );
+                Ok((*node, integrity))
+            })
+            .collect()
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("sil-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let unit = state.settings.time_unit.symbol();
+                egui::Grid::new("sil setup").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("sil-interval"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.interval)
+                            .speed(10.0)
+                            .clamp_range(1..=state.mission_time.max(1))
+                            .suffix(format!(" {unit}")),
+                    );
+                    ui.end_row();
+                    ui.label(tr("sil-coverage"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.coverage)
+                            .speed(0.01)
+                            .clamp_range(0.0..=1.0),
+                    )
+                    .on_hover_text(tr("sil-coverage-hint"));
+                    ui.end_row();
+                    ui.label(tr("sil-lifetime"));
+                    ui.label(format!("{} {unit}", state.mission_time));
+                    ui.end_row();
+                });
+
+                ui.strong(tr("sil-subsystems"));
+                let candidates: Vec<NodeId> = graph
+                    .nodes
+                    .iter()
+                    .filter(|(_, node)| node.user_data.template.evaluates())
+                    .map(|(node_id, _)| node_id)
+                    .collect();
+                let mut removed = None;
+                for (i, subsystem) in self.subsystems.iter_mut().enumerate() {
+                    if subsystem.is_some_and(|node| !candidates.contains(&node)) {
+                        *subsystem = None;
+                    }
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source(("sil subsystem", i))
+                            .selected_text(
+                                subsystem
+                                    .map(|node| graph[node].label.clone())
+                                    .unwrap_or_default(),
+                            )
+                            .show_ui(ui, |ui| {
+                                for node in &candidates {
+                                    ui.selectable_value(
+                                        subsystem,
+                                        Some(*node),
+                                        &graph[*node].label,
+                                    );
+                                }
+                            });
+                        if ui.small_button("🗑").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = removed {
+                    self.subsystems.remove(i);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(tr("sil-add")).clicked() {
+                        self.subsystems.push(None);
+                    }
+                    let ready = self.subsystems.iter().any(Option::is_some);
+                    if ui
+                        .add_enabled(ready, egui::Button::new(tr("sil-run")))
+                        .clicked()
+                    {
+                        self.result = Some(self.run(graph, state).map_err(|err| err.to_string()));
+                    }
+                });
+
+                let results = match &self.result {
+                    Some(Ok(results)) => results,
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                        return;
+                    }
+                    None => return,
+                };
+                egui::Grid::new("sil results")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("sil-subsystem"));
+                        ui.strong("PFD_avg");
+                        ui.strong(tr("sil-low-demand"));
+                        ui.strong(tr_with("sil-pfh", &[("unit", &tr("unit-hours-symbol"))]));
+                        ui.strong(tr("sil-high-demand"));
+                        ui.end_row();
+                        for (node, integrity) in results {
+                            let label = graph.nodes.get(*node).map_or("", |node| &node.label);
+                            ui.label(label);
+                            ui.label(format!("{:.3e}", integrity.pfd_avg));
+                            ui.label(sil_label(low_demand_sil(integrity.pfd_avg)));
+                            match integrity.pfh {
+                                Some(pfh) => {
+                                    ui.label(format!("{pfh:.3e}"));
+                                    ui.label(sil_label(high_demand_sil(pfh)));
+                                }
+                                None => {
+                                    ui.label("—").on_hover_text(tr("sil-cycles"));
+                                    ui.label("—");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.label(tr("sil-hint"));
+            });
+    }
+}
@@ -0,0 +1,1152 @@
+//! Monte Carlo simulation of system failure times.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints};
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::audit::Audit;
+use crate::eval::{self, evaluate_curve, evaluate_node, Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::locale;
+use crate::maintenance::Maintenance;
+use crate::nodes::{
+    self, Forced, Graph, GraphState, NodeParameters, NodeTemplate, Repair, ValueType,
+};
+use crate::rocof;
+use crate::settings::{self, Settings};
+use crate::shocks::{self, Events, Shock};
+use crate::toasts;
+
+/// The sampled failure times of one Monte Carlo run.
+pub struct Simulation {
+    pub seed: u64,
+    /// The components the system depends on, sorted by label.
+    pub components: Vec<NodeId>,
+    pub system_times: Vec<f64>,
+    /// One row per trial, with a column per entry of `components`.
+    pub component_times: Vec<Vec<f64>>,
+}
+
+/// Samples `trials` system failure times. A series block fails with its first
+/// input, a parallel block with its last. Components hit by `shocks` lose
+/// part of their remaining life at each event.
+pub fn simulate(
+    graph: &Graph,
+    options: &Options,
+    shocks: &[Shock],
+    system: NodeId,
+    trials: usize,
+    seed: u64,
+) -> anyhow::Result<Simulation> {
+    // The analytic evaluation rejects cycles, notes and unconnected inputs, so
+    // the sampler below doesn't have to.
+    evaluate_node(graph, options, system, &mut OutputsCache::new())?;
+
+    let mut rng = settings::rng(seed);
+    let mut sampler = Sampler {
+        graph,
+        options,
+        rng: &mut rng,
+        components: HashMap::new(),
+        times: HashMap::new(),
+        shocks,
+        events: Events::default(),
+    };
+    let mut simulation = Simulation {
+        seed,
+        components: Vec::new(),
+        system_times: Vec::with_capacity(trials),
+        component_times: Vec::with_capacity(trials),
+    };
+    for trial in 0..trials {
+        sampler.times.clear();
+        sampler.events.clear();
+        let time = sampler.failure_time(system)?;
+        if trial == 0 {
+            simulation.components = nodes::components(graph)
+                .into_iter()
+                .filter(|node| sampler.times.contains_key(node))
+                .collect();
+        }
+        simulation.system_times.push(time);
+        simulation.component_times.push(
+            simulation
+                .components
+                .iter()
+                .map(|node| sampler.times[node])
+                .collect(),
+        );
+    }
+    Ok(simulation)
+}
+
+/// Trials sampled by each batch of a background simulation.
+const BATCH_TRIALS: usize = 500;
+/// Batches sampled in parallel between two checks for convergence, fixed
+/// rather than one per thread so that where a run stops doesn't depend on
+/// the machine.
+const ROUND_BATCHES: u64 = 8;
+/// The normal quantile of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// The estimate of R(mission time) once `trials` trials were in.
+#[derive(Clone, Copy, Debug)]
+pub struct Convergence {
+    pub trials: usize,
+    pub estimate: f64,
+    /// Half the width of the estimate's 95% confidence interval.
+    pub half_width: f64,
+}
+
+impl Simulation {
+    /// The fraction of trials the system survived `time` in, and the 95%
+    /// interval around it. Sampling counts as converged only once both
+    /// outcomes have been seen, since until then the interval collapses.
+    fn convergence(&self, time: f64) -> (Convergence, bool) {
+        let trials = self.system_times.len();
+        let survived = self.system_times.iter().filter(|t| **t > time).count();
+        let estimate = survived as f64 / trials.max(1) as f64;
+        let half_width = Z_95 * (estimate * (1.0 - estimate) / trials.max(1) as f64).sqrt();
+        let mixed = survived > 0 && survived < trials;
+        (
+            Convergence {
+                trials,
+                estimate,
+                half_width,
+            },
+            mixed,
+        )
+    }
+}
+
+/// Samples rounds of [`ROUND_BATCHES`] batches in parallel until
+/// `max_trials`, until the estimate of R(mission time) is within
+/// `half_width`, or until canceled, reporting the estimate after each round.
+/// Batch `i` always gets seed + i and the rounds are the same whatever the
+/// number of threads, so a run reproduces however many share the work.
+#[allow(clippy::too_many_arguments)]
+fn sample_until(
+    graph: &Graph,
+    options: &Options,
+    shocks: &[Shock],
+    system: NodeId,
+    mission_time: usize,
+    max_trials: usize,
+    half_width: f64,
+    seed: u64,
+    cancel: &AtomicBool,
+    mut report: impl FnMut(Convergence),
+) -> anyhow::Result<Simulation> {
+    let mut simulation: Option<Simulation> = None;
+    let mut next = 0;
+    loop {
+        let round = (next..next + ROUND_BATCHES)
+            .into_par_iter()
+            .map(|batch| {
+                simulate(
+                    graph,
+                    options,
+                    shocks,
+                    system,
+                    BATCH_TRIALS,
+                    seed.wrapping_add(batch),
+                )
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        next += ROUND_BATCHES;
+        let all = simulation.get_or_insert_with(|| Simulation {
+            seed,
+            components: round[0].components.clone(),
+            system_times: Vec::new(),
+            component_times: Vec::new(),
+        });
+        for batch in round {
+            all.system_times.extend(batch.system_times);
+            all.component_times.extend(batch.component_times);
+        }
+        all.system_times.truncate(max_trials);
+        all.component_times.truncate(max_trials);
+
+        let (point, mixed) = all.convergence(mission_time as f64);
+        report(point);
+        if point.trials >= max_trials
+            || (mixed && point.half_width <= half_width)
+            || cancel.load(Ordering::Relaxed)
+        {
+            return Ok(simulation.take().expect("at least one round ran"));
+        }
+    }
+}
+
+/// What a background simulation has produced so far.
+#[derive(Default)]
+struct Progress {
+    convergence: Vec<Convergence>,
+    finished: Option<Result<Simulation, String>>,
+}
+
+/// A failure-time simulation sampled in parallel batches on a background
+/// thread. It stops after `max_trials`, when the estimate of R(mission time)
+/// is within `half_width`, or when canceled.
+pub struct Running {
+    progress: Arc<Mutex<Progress>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl Running {
+    #[allow(clippy::too_many_arguments)]
+    pub fn start(
+        ctx: &egui::Context,
+        graph: Graph,
+        options: Options,
+        shocks: Vec<Shock>,
+        system: NodeId,
+        mission_time: usize,
+        max_trials: usize,
+        half_width: f64,
+        seed: u64,
+    ) -> Self {
+        let progress = Arc::new(Mutex::new(Progress::default()));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let running = Self {
+            progress: progress.clone(),
+            cancel: cancel.clone(),
+        };
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let result = sample_until(
+                &graph,
+                &options,
+                &shocks,
+                system,
+                mission_time,
+                max_trials,
+                half_width,
+                seed,
+                &cancel,
+                |point| {
+                    progress.lock().unwrap().convergence.push(point);
+                    ctx.request_repaint();
+                },
+            );
+            progress.lock().unwrap().finished = Some(result.map_err(|err| err.to_string()));
+            ctx.request_repaint();
+        });
+        running
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// The estimates so far, and the simulation once it has finished.
+    fn poll(&self) -> (Vec<Convergence>, Option<Result<Simulation, String>>) {
+        let mut progress = self.progress.lock().unwrap();
+        (progress.convergence.clone(), progress.finished.take())
+    }
+}
+
+struct Sampler<'a, R> {
+    graph: &'a Graph,
+    rng: &'a mut R,
+    options: &'a Options,
+    components: HashMap<NodeId, NodeParameters>,
+    /// The failure times of the current trial, so a component feeding several
+    /// blocks fails at the same time in all of them.
+    times: HashMap<NodeId, f64>,
+    shocks: &'a [Shock],
+    /// The shock events of the current trial.
+    events: Events,
+}
+
+impl<R: Rng> Sampler<'_, R> {
+    fn failure_time(&mut self, node_id: NodeId) -> anyhow::Result<f64> {
+        if let Some(time) = self.times.get(&node_id) {
+            return Ok(*time);
+        }
+        let node = &self.graph[node_id].user_data;
+        let time = match node.template {
+            _ if node.forced == Some(Forced::Failed) => 0.0,
+            _ if node.forced == Some(Forced::Working) => f64::INFINITY,
+            _ if self.options.filtered_out(node) => f64::INFINITY,
+            NodeTemplate::Component => {
+                if !self.components.contains_key(&node_id) {
+                    let parameters = nodes::component_parameters(self.graph, node_id)
+                        .ok_or_else(|| anyhow::anyhow!(tr("error-not-component")))?;
+                    self.components.insert(node_id, parameters);
+                }
+                // Inverse transform sampling: the unit fails when its
+                // cumulative hazard reaches an Exp(1) draw.
+                let target = -(1.0 - self.rng.gen::<f64>()).ln();
+                let parameters = &self.components[&node_id];
+                let life = self.options.profile.time_at_hazard(
+                    0.0,
+                    |t| parameters.cumulative_hazard(t),
+                    |hazard| parameters.time_at_hazard(hazard),
+                    target,
+                );
+                self.events
+                    .degrade(self.shocks, node_id, life, &mut *self.rng)
+            }
+            NodeTemplate::Series => self
+                .input_time(node_id, "A")?
+                .min(self.input_time(node_id, "B")?),
+            NodeTemplate::Parallel => {
+                let a = self.input_time(node_id, "A")?;
+                let b = self.input_time(node_id, "B")?;
+                // An uncovered first failure takes the block down with it.
+                if self.covered(node_id) {
+                    a.max(b)
+                } else {
+                    a.min(b)
+                }
+            }
+            NodeTemplate::Standby => {
+                let input_id = self.graph[node_id].get_input("dormancy")?;
+                let ValueType::Dormancy { dormancy } = self.graph[input_id].value else {
+                    anyhow::bail!(tr("error-expected-dormancy"));
+                };
+                let primary = self.input_time(node_id, "A")?;
+                // The spare's draw is its life when active; while dormant
+                // it uses that life up at the aging rate.
+                let spare = self.input_time(node_id, "B")?;
+                let age = dormancy.aging() * primary;
+                if primary.is_infinite() || spare <= age || !self.covered(node_id) {
+                    primary
+                } else {
+                    primary + spare - age
+                }
+            }
+            NodeTemplate::Voting => self.voting_time(node_id)?,
+            NodeTemplate::Empirical => {
+                // Inverse transform sampling of the step curve. Units that
+                // outlive the data fail just past its horizon, where the
+                // analytic curve ends too; blocks with inputs of other
+                // horizons were rejected by the evaluation above.
+                let input_id = self.graph[node_id].get_input("curve")?;
+                let curve = self.graph[input_id].value.clone().try_to_reliability()?;
+                let u: f64 = self.rng.gen();
+                curve.partition_point(|r| *r > u) as f64
+            }
+            NodeTemplate::Human => {
+                // A failed action fails at once; one carried out never does.
+                let input_id = self.graph[node_id].get_input("action")?;
+                let ValueType::HumanAction { action } = &self.graph[input_id].value else {
+                    anyhow::bail!(tr("error-expected-human-action"));
+                };
+                if self.rng.gen::<f64>() < action.adjusted_hep() {
+                    0.0
+                } else {
+                    f64::INFINITY
+                }
+            }
+            NodeTemplate::Software => {
+                let input_id = self.graph[node_id].get_input("growth")?;
+                let ValueType::SoftwareGrowth { growth } = &self.graph[input_id].value else {
+                    anyhow::bail!(tr("error-expected-software-growth"));
+                };
+                let model = growth.model().map_err(anyhow::Error::msg)?;
+                model.sample(growth.test_time, self.rng.gen())
+            }
+            NodeTemplate::OneShot => {
+                // The device works or not when demanded, and never wears out.
+                let Some(device) = nodes::one_shot(self.graph, node_id) else {
+                    anyhow::bail!(tr("error-expected-one-shot"));
+                };
+                if self.rng.gen::<f64>() < device.success {
+                    f64::INFINITY
+                } else {
+                    0.0
+                }
+            }
+            NodeTemplate::PetriNet => {
+                let Some(net) = nodes::petri_net(self.graph, node_id) else {
+                    anyhow::bail!(tr("error-expected-petri-net"));
+                };
+                net.sample_failure(&mut *self.rng)
+                    .map_err(anyhow::Error::msg)?
+            }
+            NodeTemplate::Note => anyhow::bail!(tr("error-note")),
+        };
+        self.times.insert(node_id, time);
+        Ok(time)
+    }
+
+    /// When fewer than the votes needed are left working, or a failure goes
+    /// uncovered before that. Members are left out and taken as working as
+    /// in the analytic evaluation.
+    fn voting_time(&mut self, node_id: NodeId) -> anyhow::Result<f64> {
+        let node = &self.graph[node_id];
+        let votes = node.user_data.votes;
+        let names: Vec<String> = node.inputs.iter().map(|(name, _)| name.clone()).collect();
+        let mut times = Vec::with_capacity(names.len());
+        for name in &names {
+            let input_id = self.graph[node_id].get_input(name)?;
+            if let Some(output_id) = self.graph.connection(input_id) {
+                let source = &self.graph[self.graph[output_id].node].user_data;
+                if source.excluded || (!source.enabled && self.options.disabled_absent()) {
+                    continue;
+                }
+            }
+            times.push(self.input_time(node_id, name)?);
+        }
+        if votes > times.len() {
+            anyhow::bail!(tr_with(
+                "error-too-few-members",
+                &[("members", &times.len())]
+            ));
+        }
+        times.sort_by(f64::total_cmp);
+        // The block goes down with the failure that leaves votes − 1 working.
+        let last = times.len() - votes;
+        for time in &times[..last] {
+            if !self.covered(node_id) {
+                return Ok(*time);
+            }
+        }
+        Ok(times[last])
+    }
+
+    /// Whether the switchover in a redundant block catches this trial's
+    /// failure. Fully covered blocks draw nothing, so seeded runs of models
+    /// without coverage repeat.
+    fn covered(&mut self, node_id: NodeId) -> bool {
+        let coverage = eval::coverage(self.graph, node_id);
+        coverage >= 1.0 || self.rng.gen::<f64>() < coverage
+    }
+
+    fn input_time(&mut self, node_id: NodeId, name: &str) -> anyhow::Result<f64> {
+        let input_id = self.graph[node_id].get_input(name)?;
+        let output_id = self.graph.connection(input_id).ok_or_else(|| {
+            anyhow::anyhow!(tr_with("error-unconnected-input", &[("input", &name)]))
+        })?;
+        let source = self.graph[output_id].node;
+        // Disabled inputs aren't sampled, as in the analytic evaluation.
+        if !self.graph[source].user_data.enabled {
+            return Ok(
+                match (
+                    self.options.disabled_absent(),
+                    self.graph[node_id].user_data.template,
+                ) {
+                    (true, NodeTemplate::Parallel | NodeTemplate::Standby) => 0.0,
+                    _ => f64::INFINITY,
+                },
+            );
+        }
+        let time = self.failure_time(source)?;
+        // Excluded inputs are taken out of the block, as in the analytic evaluation.
+        if self.graph[source].user_data.excluded {
+            return Ok(match self.graph[node_id].user_data.template {
+                NodeTemplate::Parallel | NodeTemplate::Standby => 0.0,
+                _ => f64::INFINITY,
+            });
+        }
+        Ok(time)
+    }
+}
+
+/// Failure counts of each component over a fixed horizon.
+pub struct Renewals {
+    pub seed: u64,
+    pub horizon: f64,
+    /// The components the system depends on, sorted by label.
+    pub components: Vec<NodeId>,
+    /// The mean number of failures of each entry of `components`.
+    pub means: Vec<f64>,
+    /// The standard error of each mean.
+    pub standard_errors: Vec<f64>,
+}
+
+/// Simulates every component `system` depends on as a renewal process over
+/// `0..horizon`: each failed unit is repaired, as good as new or as bad as
+/// old, after an exponentially distributed time of mean MTTR. Components with
+/// no MTTR are never repaired, so they fail at most once.
+pub fn simulate_renewals(
+    graph: &Graph,
+    options: &Options,
+    system: NodeId,
+    horizon: f64,
+    trials: usize,
+    seed: u64,
+) -> anyhow::Result<Renewals> {
+    evaluate_node(graph, options, system, &mut OutputsCache::new())?;
+
+    let mut rng = settings::rng(seed);
+    let components = nodes::upstream_components(graph, system);
+    let mut means = Vec::with_capacity(components.len());
+    let mut standard_errors = Vec::with_capacity(components.len());
+    for node in &components {
+        let parameters = nodes::component_parameters(graph, *node)
+            .ok_or_else(|| anyhow::anyhow!(tr("error-not-component")))?;
+        let (mut sum, mut sum_of_squares) = (0.0, 0.0);
+        for _ in 0..trials {
+            // `age` is the calendar time the current unit has been up.
+            let (mut time, mut age, mut failures) = (0.0, 0.0, 0usize);
+            loop {
+                // The hazard from here on, of a unit that has already aged.
+                let aged = parameters.cumulative_hazard(age);
+                let target = -(1.0 - rng.gen::<f64>()).ln();
+                let up = options.profile.time_at_hazard(
+                    time,
+                    |t| parameters.cumulative_hazard(age + t) - aged,
+                    |hazard| parameters.time_at_hazard(hazard + aged) - age,
+                    target,
+                );
+                time += up;
+                if time > horizon {
+                    break;
+                }
+                failures += 1;
+                if parameters.mttr <= 0.0 {
+                    break;
+                }
+                age = match parameters.repair {
+                    Repair::Renewal => 0.0,
+                    Repair::Minimal => age + up,
+                };
+                time -= parameters.mttr * (1.0 - rng.gen::<f64>()).ln();
+            }
+            sum += failures as f64;
+            sum_of_squares += (failures * failures) as f64;
+        }
+        let n = trials.max(1) as f64;
+        let mean = sum / n;
+        let variance = (sum_of_squares / n - mean * mean).max(0.0);
+        means.push(mean);
+        standard_errors.push((variance / n).sqrt());
+    }
+    Ok(Renewals {
+        seed,
+        horizon,
+        components,
+        means,
+        standard_errors,
+    })
+}
+
+impl Simulation {
+    /// Formats the trials as CSV: the system failure time, then one column per component.
+    pub fn to_csv(&self, graph: &Graph) -> String {
+        let mut header = vec!["trial".to_owned(), "system".to_owned()];
+        header.extend(
+            self.components
+                .iter()
+                .map(|node| csv_field(&graph[*node].label)),
+        );
+        let mut csv = locale::row(&header);
+        for (trial, (system, components)) in self
+            .system_times
+            .iter()
+            .zip(&self.component_times)
+            .enumerate()
+        {
+            let mut fields = vec![trial.to_string(), locale::number(system)];
+            fields.extend(components.iter().map(locale::number));
+            csv.push_str(&locale::row(&fields));
+        }
+        csv
+    }
+}
+
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', ';', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Mode {
+    /// Samples the system's time to first failure.
+    #[default]
+    FailureTimes,
+    /// Counts failures of repaired components over the mission.
+    Renewals,
+    /// Compares the mission with and without opportunistic replacements.
+    Maintenance,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum View {
+    #[default]
+    Histogram,
+    Survival,
+    /// The estimate of R(mission time) as the trials came in.
+    Convergence,
+}
+
+pub struct MonteCarlo {
+    mode: Mode,
+    running: Option<Running>,
+    convergence: Vec<Convergence>,
+    result: Option<Result<Simulation, String>>,
+    /// The analytic system curve at the time of the run, for comparison.
+    analytic: Vec<f64>,
+    /// The maximum and mean absolute deviation between the curves.
+    deviation: Option<(f64, f64)>,
+    export_error: Option<String>,
+    view: View,
+    bins: usize,
+    renewals: Option<Result<Renewals, String>>,
+    /// The expected failures of each component from its failure intensity.
+    analytic_failures: Vec<f64>,
+    maintenance: Maintenance,
+}
+
+impl Default for MonteCarlo {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            running: None,
+            convergence: Vec::new(),
+            result: None,
+            analytic: Vec::new(),
+            deviation: None,
+            export_error: None,
+            view: View::default(),
+            bins: 50,
+            renewals: None,
+            analytic_failures: Vec::new(),
+            maintenance: Maintenance::default(),
+        }
+    }
+}
+
+impl MonteCarlo {
+    fn run_renewals(&mut self, graph: &Graph, state: &GraphState, system: NodeId) {
+        let settings = &state.settings;
+        let options = &Options::of(state);
+        let result = simulate_renewals(
+            graph,
+            options,
+            system,
+            state.mission_time as f64,
+            settings.monte_carlo_samples,
+            settings.seed,
+        );
+        self.analytic_failures = match &result {
+            Ok(renewals) => renewals
+                .components
+                .iter()
+                .map(|node| {
+                    nodes::component_parameters(graph, *node)
+                        .and_then(|parameters| {
+                            eval::component_failure_intensity(&parameters, &options.profile).ok()
+                        })
+                        .map(|intensity| rocof::expected_failures(&intensity, state.mission_time))
+                        .unwrap_or(f64::NAN)
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        self.renewals = Some(result.map_err(|err| err.to_string()));
+    }
+
+    fn renewals_ui(&self, ui: &mut egui::Ui, graph: &Graph, state: &GraphState) {
+        match &self.renewals {
+            Some(Ok(renewals)) => {
+                ui.label(tr_with(
+                    "simulation-renewals-horizon",
+                    &[
+                        ("horizon", &renewals.horizon),
+                        ("unit", &state.settings.time_unit.symbol()),
+                    ],
+                ));
+                egui::Grid::new("renewals")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(tr("goal-seek-component"));
+                        ui.strong(tr("simulation-simulated"));
+                        ui.strong(tr("simulation-analytic"));
+                        ui.end_row();
+                        for (i, node) in renewals.components.iter().enumerate() {
+                            ui.label(&graph[*node].label);
+                            ui.label(format!(
+                                "{:.3} ± {:.3}",
+                                renewals.means[i], renewals.standard_errors[i]
+                            ));
+                            match self.analytic_failures.get(i) {
+                                Some(analytic) if analytic.is_finite() => {
+                                    ui.label(format!("{analytic:.3}"))
+                                }
+                                _ => ui.label("—"),
+                            };
+                            ui.end_row();
+                        }
+                        ui.strong(tr("rocof-total"));
+                        ui.strong(format!("{:.3}", renewals.means.iter().sum::<f64>()));
+                        ui.strong(format!("{:.3}", self.analytic_failures.iter().sum::<f64>()));
+                        ui.end_row();
+                    });
+            }
+            Some(Err(err)) => {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            None => {}
+        }
+        ui.label(tr("rocof-hint"));
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &Graph,
+        state: &mut GraphState,
+    ) {
+        // Polled while the window is closed too, so a run finishing behind
+        // it is still announced.
+        if let Some(running) = &self.running {
+            let (convergence, finished) = running.poll();
+            self.convergence = convergence;
+            if let Some(result) = finished {
+                self.deviation = match &result {
+                    Ok(simulation) => {
+                        toasts::success(tr_with(
+                            "toast-simulation-done",
+                            &[("trials", &simulation.system_times.len())],
+                        ));
+                        deviation(&simulation.system_times, &self.analytic)
+                    }
+                    Err(err) => {
+                        toasts::error(tr_with("toast-simulation-failed", &[("error", err)]));
+                        None
+                    }
+                };
+                self.result = Some(result);
+                self.running = None;
+            }
+        }
+        egui::Window::new(tr("simulation-title"))
+            .open(open)
+            .show(ctx, |ui| {
+                let settings = &state.settings;
+                ui.label(tr_with(
+                    "simulation-settings",
+                    &[
+                        ("trials", &settings.monte_carlo_samples),
+                        ("seed", &settings.seed),
+                    ],
+                ));
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.mode,
+                        Mode::FailureTimes,
+                        tr("simulation-failure-times"),
+                    );
+                    ui.radio_value(&mut self.mode, Mode::Renewals, tr("simulation-renewals"));
+                    ui.radio_value(
+                        &mut self.mode,
+                        Mode::Maintenance,
+                        tr("simulation-maintenance"),
+                    );
+                });
+                if self.mode == Mode::FailureTimes {
+                    egui::CollapsingHeader::new(tr_with(
+                        "shocks-title",
+                        &[("count", &state.shocks.len())],
+                    ))
+                    .id_source("shocks")
+                    .show(ui, |ui| shocks::ui(ui, graph, &mut state.shocks));
+                }
+                if state.active_node.is_none() {
+                    ui.label(tr("goal-seek-no-system"));
+                }
+                if ui
+                    .add_enabled(
+                        state.active_node.is_some() && self.running.is_none(),
+                        egui::Button::new(tr("simulation-run")),
+                    )
+                    .clicked()
+                {
+                    if let Some(system) = state.active_node {
+                        if self.mode == Mode::Renewals {
+                            self.run_renewals(graph, state, system);
+                        } else if self.mode == Mode::Maintenance {
+                            self.maintenance.run(graph, state, system);
+                        } else {
+                            self.result = None;
+                            self.running = Some(Running::start(
+                                ctx,
+                                graph.clone(),
+                                Options::of(state),
+                                state.shocks.clone(),
+                                system,
+                                state.mission_time,
+                                settings.monte_carlo_samples,
+                                settings.monte_carlo_half_width,
+                                settings.seed,
+                            ));
+                            self.analytic = evaluate_curve(
+                                graph,
+                                &Options::of(state),
+                                system,
+                                &mut OutputsCache::new(),
+                            )
+                            .unwrap_or_default();
+                            self.export_error = None;
+                        }
+                    }
+                }
+
+                if let Some(running) = &self.running {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        if let Some(point) = self.convergence.last() {
+                            ui.label(convergence_label(point, settings.monte_carlo_samples));
+                        }
+                        if ui.button(tr("simulation-cancel")).clicked() {
+                            running.cancel();
+                        }
+                    });
+                    convergence_plot(ui, &self.convergence, settings);
+                    return;
+                }
+
+                if self.mode == Mode::Renewals {
+                    self.renewals_ui(ui, graph, state);
+                    return;
+                }
+                if self.mode == Mode::Maintenance {
+                    self.maintenance.ui(ui, settings.time_unit);
+                    return;
+                }
+                match &self.result {
+                    Some(Ok(simulation)) => {
+                        let trials = simulation.system_times.len() as f64;
+                        let mean = simulation.system_times.iter().sum::<f64>() / trials;
+                        ui.label(tr_with(
+                            "simulation-mean",
+                            &[
+                                ("mean", &format!("{mean:.1}")),
+                                ("unit", &settings.time_unit.symbol()),
+                            ],
+                        ));
+                        if let Some((max, mean)) = self.deviation {
+                            // The standard error of a proportion is at most 1 / (2√n).
+                            let standard_error = 0.5 / trials.sqrt();
+                            ui.label(tr_with(
+                                "simulation-deviation",
+                                &[
+                                    ("max", &format!("{max:.4}")),
+                                    ("mean", &format!("{mean:.4}")),
+                                    ("error", &format!("{standard_error:.4}")),
+                                ],
+                            ));
+                        }
+                        if ui.button(tr("simulation-export")).clicked() {
+                            self.export_error =
+                                export(simulation, graph, Audit::new(graph, state)).err();
+                        }
+
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut self.view,
+                                View::Histogram,
+                                tr("simulation-histogram"),
+                            );
+                            ui.radio_value(
+                                &mut self.view,
+                                View::Survival,
+                                tr("simulation-survival"),
+                            );
+                            ui.radio_value(
+                                &mut self.view,
+                                View::Convergence,
+                                tr("simulation-convergence"),
+                            );
+                            if self.view == View::Histogram {
+                                ui.separator();
+                                ui.label(tr("simulation-bins"));
+                                ui.add(egui::DragValue::new(&mut self.bins).clamp_range(5..=500));
+                            }
+                        });
+                        let theme = settings.theme.catppuccin();
+                        let x_label = format!("t [{}]", settings.time_unit.symbol());
+                        match self.view {
+                            View::Histogram => Plot::new("simulation histogram")
+                                .height(250.0)
+                                .x_axis_label(x_label)
+                                .y_axis_label("f(t)")
+                                .legend(Legend::default())
+                                .show(ui, |plot_ui| {
+                                    plot_ui.bar_chart(
+                                        histogram(&simulation.system_times, self.bins)
+                                            .color(theme.blue)
+                                            .name(tr("simulation-simulated")),
+                                    );
+                                    plot_ui.line(
+                                        Line::new(analytic_density(&self.analytic))
+                                            .color(theme.peach)
+                                            .name(tr("simulation-analytic")),
+                                    );
+                                }),
+                            View::Survival => Plot::new("simulation survival")
+                                .height(250.0)
+                                .include_y(0.0)
+                                .include_y(1.0)
+                                .x_axis_label(x_label)
+                                .y_axis_label("R(t)")
+                                .legend(Legend::default())
+                                .show(ui, |plot_ui| {
+                                    plot_ui.line(
+                                        Line::new(empirical_survival(&simulation.system_times))
+                                            .color(theme.blue)
+                                            .name(tr("simulation-simulated")),
+                                    );
+                                    plot_ui.line(
+                                        Line::new(curve_points(&self.analytic))
+                                            .color(theme.peach)
+                                            .name(tr("simulation-analytic")),
+                                    );
+                                }),
+                            View::Convergence => convergence_plot(ui, &self.convergence, settings),
+                        };
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                    None => {}
+                }
+                if let Some(err) = &self.export_error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+            });
+    }
+}
+
+fn convergence_label(point: &Convergence, max_trials: usize) -> String {
+    tr_with(
+        "simulation-progress",
+        &[
+            ("trials", &point.trials),
+            ("max", &max_trials),
+            ("estimate", &format!("{:.5}", point.estimate)),
+            ("half_width", &format!("{:.5}", point.half_width)),
+        ],
+    )
+}
+
+/// The running estimate of R(mission time) and its 95% interval by trials.
+fn convergence_plot(
+    ui: &mut egui::Ui,
+    convergence: &[Convergence],
+    settings: &Settings,
+) -> egui_plot::PlotResponse<()> {
+    let theme = settings.theme.catppuccin();
+    let line = |offset: f64| -> PlotPoints {
+        convergence
+            .iter()
+            .map(|point| {
+                [
+                    point.trials as f64,
+                    point.estimate + offset * point.half_width,
+                ]
+            })
+            .collect()
+    };
+    Plot::new("simulation convergence")
+        .height(250.0)
+        .x_axis_label(tr("simulation-trials"))
+        .y_axis_label("R(t_m)")
+        .legend(Legend::default())
+        .show(ui, |plot_ui| {
+            plot_ui.line(
+                Line::new(line(0.0))
+                    .color(theme.blue)
+                    .name(tr("simulation-simulated")),
+            );
+            for offset in [-1.0, 1.0] {
+                plot_ui.line(
+                    Line::new(line(offset))
+                        .color(theme.blue.gamma_multiply(0.4))
+                        .style(egui_plot::LineStyle::dashed_dense())
+                        .name(tr("simulation-interval")),
+                );
+            }
+        })
+}
+
+/// A density-normalised histogram of `times`, so it is comparable with f(t).
+fn histogram(times: &[f64], bins: usize) -> BarChart {
+    let max = times.iter().copied().fold(0.0, f64::max);
+    let width = if max > 0.0 { max / bins as f64 } else { 1.0 };
+    let mut counts = vec![0usize; bins];
+    for time in times {
+        counts[((time / width) as usize).min(bins - 1)] += 1;
+    }
+    let scale = 1.0 / (times.len() as f64 * width);
+    BarChart::new(
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| Bar::new((i as f64 + 0.5) * width, count as f64 * scale).width(width))
+            .collect(),
+    )
+}
+
+/// f(t) = -dR/dt of an analytic curve, by forward differences.
+fn analytic_density(curve: &[f64]) -> PlotPoints {
+    curve
+        .windows(2)
+        .enumerate()
+        .map(|(t, w)| [t as f64 + 0.5, w[0] - w[1]])
+        .collect()
+}
+
+fn curve_points(curve: &[f64]) -> PlotPoints {
+    curve
+        .iter()
+        .enumerate()
+        .map(|(t, r)| [t as f64, *r])
+        .collect()
+}
+
+/// The fraction of trials still running at each sampled failure time, as a
+/// step function.
+fn empirical_survival(times: &[f64]) -> PlotPoints {
+    let mut sorted = times.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let mut points = vec![[0.0, 1.0]];
+    for (i, time) in sorted.into_iter().enumerate() {
+        points.push([time, 1.0 - i as f64 / n]);
+        points.push([time, 1.0 - (i + 1) as f64 / n]);
+    }
+    points.into()
+}
+
+/// The maximum and mean absolute difference between the empirical survival
+/// of `times` and the analytic `curve`, over the curve's time steps.
+fn deviation(times: &[f64], curve: &[f64]) -> Option<(f64, f64)> {
+    if times.is_empty() || curve.is_empty() {
+        return None;
+    }
+    let mut sorted = times.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let deviations: Vec<f64> = curve
+        .iter()
+        .enumerate()
+        .map(|(t, r)| {
+            let failed = sorted.partition_point(|time| *time <= t as f64);
+            (1.0 - failed as f64 / n - r).abs()
+        })
+        .collect();
+    let max = deviations.iter().copied().fold(0.0, f64::max);
+    let mean = deviations.iter().sum::<f64>() / deviations.len() as f64;
+    Some((max, mean))
+}
+
+/// Asks for a file name and writes the raw samples there, headed by `audit`
+/// with the run's own seed and trials.
+fn export(simulation: &Simulation, graph: &Graph, audit: Audit) -> Result<(), String> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .set_file_name("samples.csv")
+        .save_file()
+    else {
+        return Ok(());
+    };
+    let audit = audit
+        .with("seed", simulation.seed)
+        .with("trials", simulation.system_times.len());
+    std::fs::write(path, audit.comments("# ") + &simulation.to_csv(graph))
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::Builder;
+    use crate::nodes::Dormancy;
+
+    #[test]
+    fn a_seed_reproduces_on_any_number_of_threads() {
+        let mut state = GraphState::default();
+        let mut model = Builder::new(&mut state);
+        let parameters = model.parameters(1.5, 2_000.0, 0.0);
+        let pump = model.component("Pump".to_owned(), parameters.clone(), 0, 0);
+        let spare = model.component("Spare".to_owned(), parameters, 0, 1);
+        let system = model.block(NodeTemplate::Parallel, [pump, spare], 1, 0);
+        let graph = model.finish().graph;
+
+        let run = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                let options = Options::default();
+                let cancel = AtomicBool::new(false);
+                sample_until(
+                    &graph,
+                    &options,
+                    &[],
+                    system,
+                    1_000,
+                    100_000,
+                    0.01,
+                    7,
+                    &cancel,
+                    |_| {},
+                )
+                .unwrap()
+                .system_times
+            })
+        };
+        // Converged well short of the cap, where the rounds decide the stop.
+        let one = run(1);
+        assert!(one.len() < 100_000);
+        assert_eq!(one, run(4));
+    }
+
+    #[test]
+    fn sampled_standby_agrees_with_the_analytic_curve() {
+        let mut state = GraphState::default();
+        let mut model = Builder::new(&mut state);
+        let parameters = NodeParameters {
+            time_steps: 2_000,
+            ..model.parameters(1.0, 1_000.0, 0.0)
+        };
+        let pump = model.component("Pump".to_owned(), parameters.clone(), 0, 0);
+        let spare = model.component("Spare".to_owned(), parameters, 0, 1);
+        let system = model.block(NodeTemplate::Standby, [pump, spare], 1, 0);
+        model.set_value(
+            system,
+            "dormancy",
+            ValueType::Dormancy {
+                dormancy: Dormancy::Cold,
+            },
+        );
+        model.data(system).coverage = 1.0;
+        let graph = model.finish().graph;
+        let options = Options::default();
+        let curve =
+            eval::evaluate_curve(&graph, &options, system, &mut OutputsCache::new()).unwrap();
+        let trials = 20_000;
+        let simulation = simulate(&graph, &options, &[], system, trials, 7).unwrap();
+        for t in [500, 1_000, 2_000] {
+            let survived = simulation
+                .system_times
+                .iter()
+                .filter(|time| **time > t as f64)
+                .count() as f64
+                / trials as f64;
+            // The 95% confidence interval of the sampled fraction.
+            let half_width = 1.96 * (curve[t] * (1.0 - curve[t]) / trials as f64).sqrt();
+            assert!(
+                (survived - curve[t]).abs() < half_width,
+                "sampled R({t}) = {survived}, analytic {} ± {half_width}",
+                curve[t]
+            );
+        }
+    }
+}
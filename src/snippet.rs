@@ -0,0 +1,166 @@
+//! Self-contained JSON snippets of single nodes, for sharing in chat or issue trackers.
+use eframe::egui;
+use egui_node_graph2::{InputParamKind, NodeId, NodeTemplateTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{
+    self, DataType, EditorState, Forced, Graph, GraphState, NodeTemplate, Severity, ValueType,
+};
+use crate::tags::ColorTag;
+
+/// Bumped whenever a change to the snippet layout breaks older snippets.
+pub const VERSION: u32 = 1;
+
+/// A node with everything needed to recreate it, but not its connections.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    pub version: u32,
+    pub template: NodeTemplate,
+    pub label: String,
+    pub tag: Option<ColorTag>,
+    pub feasibility: f64,
+    pub forced: Option<Forced>,
+    #[serde(default = "crate::nodes::default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default = "crate::nodes::default_coverage")]
+    pub coverage: f64,
+    #[serde(default = "crate::nodes::default_votes")]
+    pub votes: usize,
+    /// The values of the inputs edited on the node itself, by input name.
+    pub values: Vec<(String, ValueType)>,
+}
+
+impl Snippet {
+    pub fn from_node(graph: &Graph, node_id: NodeId) -> Self {
+        let node = &graph[node_id];
+        let values = node
+            .inputs
+            .iter()
+            .filter(|(_, input)| !matches!(graph[*input].kind, InputParamKind::ConnectionOnly))
+            .map(|(name, input)| (name.clone(), graph[*input].value.clone()))
+            .collect();
+        Self {
+            version: VERSION,
+            template: node.user_data.template,
+            label: node.label.clone(),
+            tag: node.user_data.tag.clone(),
+            feasibility: node.user_data.feasibility,
+            forced: node.user_data.forced,
+            enabled: node.user_data.enabled,
+            severity: node.user_data.severity,
+            coverage: node.user_data.coverage,
+            votes: node.user_data.votes,
+            values,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("snippets always serialize")
+    }
+
+    /// Parses a snippet, checking its version before its layout so that
+    /// snippets from newer releases get a clearer message than a parse error.
+    pub fn from_json(text: &str) -> anyhow::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(text.trim())
+            .map_err(|_| anyhow::anyhow!(tr("snippet-not-json")))?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| anyhow::anyhow!(tr("snippet-no-version")))?;
+        if version != u64::from(VERSION) {
+            anyhow::bail!(tr_with(
+                "snippet-unsupported-version",
+                &[("version", &version), ("supported", &VERSION)]
+            ));
+        }
+        serde_json::from_value(value)
+            .map_err(|err| anyhow::anyhow!(tr_with("snippet-invalid", &[("error", &err)])))
+    }
+}
+
+pub fn value_type(value: &ValueType) -> DataType {
+    match value {
+        ValueType::Reliability { .. } | ValueType::CompactReliability { .. } => {
+            DataType::Reliability
+        }
+        ValueType::Parameters { .. } => DataType::Parameters,
+        ValueType::Text { .. } => DataType::Text,
+        ValueType::Dormancy { .. } => DataType::Dormancy,
+        ValueType::HumanAction { .. } => DataType::HumanAction,
+        ValueType::SoftwareGrowth { .. } => DataType::SoftwareGrowth,
+        ValueType::OneShot { .. } => DataType::OneShot,
+        ValueType::PetriNet { .. } => DataType::PetriNet,
+    }
+}
+
+/// The node to add for `snippet`, or why its values don't fit the template.
+fn build(
+    snippet: Snippet,
+    graph: &mut Graph,
+    user_state: &mut GraphState,
+) -> anyhow::Result<NodeId> {
+    let template = snippet.template;
+    let node_id = graph.add_node(
+        snippet.label,
+        template.user_data(user_state),
+        |graph, node_id| template.build_node(graph, user_state, node_id),
+    );
+    for (name, value) in snippet.values {
+        let input = graph[node_id].get_input(&name).ok().filter(|input| {
+            let param = &graph[*input];
+            param.typ == value_type(&value) && !matches!(param.kind, InputParamKind::ConnectionOnly)
+        });
+        let Some(input) = input else {
+            graph.remove_node(node_id);
+            anyhow::bail!(tr_with("snippet-unknown-input", &[("input", &name)]));
+        };
+        graph[input].value = value;
+    }
+    let data = &mut graph[node_id].user_data;
+    data.tag = snippet.tag;
+    data.feasibility = snippet.feasibility;
+    data.forced = snippet.forced;
+    data.enabled = snippet.enabled;
+    data.severity = snippet.severity;
+    data.coverage = snippet.coverage;
+    data.votes = snippet.votes;
+    // Members are connections, which snippets leave out, so a voting block
+    // gets back at least as many as it needs.
+    while graph[node_id].inputs.len() < snippet.votes {
+        nodes::add_member(graph, node_id);
+    }
+    Ok(node_id)
+}
+
+/// Recreates the node in `text` with its top left corner at `position` on screen.
+pub fn paste(
+    editor: &mut EditorState,
+    user_state: &mut GraphState,
+    text: &str,
+    position: egui::Pos2,
+    viewport: egui::Rect,
+) -> anyhow::Result<NodeId> {
+    insert(
+        editor,
+        user_state,
+        Snippet::from_json(text)?,
+        position,
+        viewport,
+    )
+}
+
+/// Recreates the node of `snippet` with its top left corner at `position` on screen.
+pub fn insert(
+    editor: &mut EditorState,
+    user_state: &mut GraphState,
+    snippet: Snippet,
+    position: egui::Pos2,
+    viewport: egui::Rect,
+) -> anyhow::Result<NodeId> {
+    let node_id = build(snippet, &mut editor.graph, user_state)?;
+    nodes::place_node(editor, node_id, position, viewport);
+    Ok(node_id)
+}
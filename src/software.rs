@@ -0,0 +1,179 @@
+//! Software reliability growth after the Goel-Okumoto model: defects are
+//! found as a Poisson process with mean m(t) = a(1 − e^(−bt)), where a is the
+//! number of defects there are to find and b the rate at which each is found.
+//!
+//! Software released after testing for T fails in the field as the process
+//! goes on, so R(t) = exp(−(m(T + t) − m(T))).
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::NodeParameters;
+
+/// A fitted Goel-Okumoto model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoelOkumoto {
+    /// The expected number of defects in total.
+    pub a: f64,
+    /// The detection rate per defect and time step.
+    pub b: f64,
+}
+
+impl GoelOkumoto {
+    /// The expected number of defects found by `time`.
+    pub fn mean_value(&self, time: f64) -> f64 {
+        self.a * (1.0 - (-self.b * time).exp())
+    }
+
+    /// The chance of no failure within `time` of a release at `release`.
+    pub fn reliability(&self, release: f64, time: f64) -> f64 {
+        (-(self.mean_value(release + time) - self.mean_value(release))).exp()
+    }
+
+    /// The defects expected to be left at `release`.
+    pub fn remaining(&self, release: f64) -> f64 {
+        self.a - self.mean_value(release)
+    }
+
+    /// The time to the first field failure of a release at `release`, by
+    /// inverse transform of `u`. Infinite when no defect is left to show.
+    pub fn sample(&self, release: f64, u: f64) -> f64 {
+        let left = (-self.b * release).exp() + u.ln() / self.a;
+        if left <= 0.0 {
+            f64::INFINITY
+        } else {
+            -left.ln() / self.b - release
+        }
+    }
+}
+
+/// Parses one discovery time per line, or separated by commas, semicolons
+/// or spaces. `#` comments are skipped.
+pub fn parse(text: &str) -> Result<Vec<f64>, String> {
+    let mut times = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        for field in line
+            .split([',', ';', '\t', ' '])
+            .filter(|field| !field.is_empty())
+        {
+            match field.parse::<f64>() {
+                Ok(time) if time >= 0.0 => times.push(time),
+                _ => return Err(tr_with("software-error-line", &[("line", &(i + 1))])),
+            }
+        }
+    }
+    Ok(times)
+}
+
+/// The maximum likelihood fit to defects found at `times` during testing that
+/// ran for `test_time`. b solves n/b − Σtᵢ = nT·e^(−bT)/(1 − e^(−bT)), which
+/// has a root only while discoveries thin out, with a mean time below T/2.
+pub fn fit(times: &[f64], test_time: f64) -> Result<GoelOkumoto, String> {
+    if times.is_empty() {
+        return Err(tr("software-error-no-data"));
+    }
+    if test_time <= 0.0 || times.iter().any(|time| *time > test_time) {
+        return Err(tr("software-error-beyond-test"));
+    }
+    let n = times.len() as f64;
+    let sum: f64 = times.iter().sum();
+    if sum / n >= test_time / 2.0 {
+        return Err(tr("software-error-no-growth"));
+    }
+    let score = |b: f64| {
+        let decay = (-b * test_time).exp();
+        n / b - sum - n * test_time * decay / (1.0 - decay)
+    };
+    // The score falls from nT/2 − Σtᵢ > 0 towards −Σtᵢ, so bisect on a log scale.
+    let (mut low, mut high) = (1e-12 / test_time, 1e3 / test_time);
+    for _ in 0..200 {
+        let mid = (low * high).sqrt();
+        if score(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let b = (low * high).sqrt();
+    Ok(GoelOkumoto {
+        a: n / (1.0 - (-b * test_time).exp()),
+        b,
+    })
+}
+
+/// Defect discovery data of a piece of software and how long it is run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoftwareGrowth {
+    /// When each defect was found, in time steps since testing began.
+    pub discoveries: String,
+    /// How long testing ran; the software is released at its end.
+    pub test_time: f64,
+    pub time_steps: usize,
+}
+
+impl Default for SoftwareGrowth {
+    fn default() -> Self {
+        Self {
+            discoveries: String::new(),
+            test_time: 1_000.0,
+            time_steps: NodeParameters::default().time_steps,
+        }
+    }
+}
+
+impl SoftwareGrowth {
+    pub fn model(&self) -> Result<GoelOkumoto, String> {
+        fit(&parse(&self.discoveries)?, self.test_time)
+    }
+
+    /// R at each time step after release.
+    pub fn curve(&self) -> Result<Vec<f64>, String> {
+        let model = self.model()?;
+        Ok((0..=self.time_steps)
+            .map(|t| model.reliability(self.test_time, t as f64))
+            .collect())
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("software growth")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr("software-test-time"));
+                ui.add(
+                    egui::DragValue::new(&mut self.test_time)
+                        .speed(10.0)
+                        .clamp_range(1.0..=f64::MAX),
+                )
+                .on_hover_text(tr("software-test-time-hint"));
+                ui.end_row();
+                ui.label(tr("param-time-steps"));
+                ui.add(egui::DragValue::new(&mut self.time_steps).clamp_range(1..=1_000_000));
+                ui.end_row();
+            });
+        ui.label(tr("software-discoveries"));
+        ui.add(
+            egui::TextEdit::multiline(&mut self.discoveries)
+                .hint_text(tr("software-discoveries-hint"))
+                .desired_rows(3),
+        );
+        match self.model() {
+            Ok(model) => {
+                ui.label(tr_with(
+                    "software-fit",
+                    &[
+                        ("a", &format!("{:.1}", model.a)),
+                        ("b", &format!("{:.3e}", model.b)),
+                        (
+                            "remaining",
+                            &format!("{:.2}", model.remaining(self.test_time)),
+                        ),
+                    ],
+                ));
+            }
+            Err(err) => {
+                ui.colored_label(ui.visuals().warn_fg_color, err);
+            }
+        }
+    }
+}
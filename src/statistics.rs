@@ -0,0 +1,243 @@
+//! Size and shape figures of the diagram, for getting a feel for a model
+//! someone else assembled.
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::eval;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, NodeTemplate};
+
+const TEMPLATES: [NodeTemplate; 11] = [
+    NodeTemplate::Component,
+    NodeTemplate::Human,
+    NodeTemplate::Software,
+    NodeTemplate::OneShot,
+    NodeTemplate::PetriNet,
+    NodeTemplate::Series,
+    NodeTemplate::Parallel,
+    NodeTemplate::Standby,
+    NodeTemplate::Voting,
+    NodeTemplate::Empirical,
+    NodeTemplate::Note,
+];
+
+pub struct Statistics {
+    /// How many nodes there are of each kind, in the order of `TEMPLATES`.
+    pub counts: Vec<(NodeTemplate, usize)>,
+    pub connections: usize,
+    /// The most nodes on a path through the connections; `None` if the
+    /// diagram has a cycle.
+    pub depth: Option<usize>,
+    /// Groups of nodes connected to each other but not to the rest.
+    pub subsystems: usize,
+    /// The group with the most nodes, by the node everything else in it
+    /// feeds, and its size.
+    pub largest: Option<(NodeId, usize)>,
+    /// Roughly how many curve values evaluating the active node computes.
+    pub cost: Option<usize>,
+}
+
+pub fn compute(graph: &Graph, active: Option<NodeId>) -> Statistics {
+    let counts = TEMPLATES
+        .iter()
+        .map(|template| {
+            let count = graph
+                .nodes
+                .values()
+                .filter(|node| node.user_data.template == *template)
+                .count();
+            (*template, count)
+        })
+        .collect();
+
+    let mut neighbours: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    let mut connections = 0;
+    for (input, output) in graph.iter_connections() {
+        let (to, from) = (graph[input].node, graph[output].node);
+        neighbours.entry(to).or_default().push(from);
+        neighbours.entry(from).or_default().push(to);
+        connections += 1;
+    }
+    let mut seen = HashSet::new();
+    let mut subsystems = 0;
+    let mut largest: Option<Vec<NodeId>> = None;
+    for start in graph.nodes.keys() {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut group = vec![start];
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for next in neighbours.get(&node).into_iter().flatten() {
+                if seen.insert(*next) {
+                    group.push(*next);
+                    stack.push(*next);
+                }
+            }
+        }
+        subsystems += 1;
+        if largest.as_ref().map_or(0, Vec::len) < group.len() {
+            largest = Some(group);
+        }
+    }
+    let largest = largest.and_then(|group| {
+        let size = group.len();
+        let root = group
+            .into_iter()
+            .max_by_key(|node| nodes::upstream(graph, *node).len())?;
+        Some((root, size))
+    });
+
+    let mut depths = HashMap::new();
+    let depth = graph
+        .nodes
+        .keys()
+        .map(|node| depth(graph, node, &mut depths, &mut HashSet::new()))
+        .try_fold(0, |deepest, depth| Some(deepest.max(depth?)));
+
+    Statistics {
+        counts,
+        connections,
+        depth,
+        subsystems,
+        largest,
+        cost: active.map(|node| eval::cost(graph, node)),
+    }
+}
+
+/// The most nodes on a path ending at `node_id`, or `None` on a cycle.
+fn depth(
+    graph: &Graph,
+    node_id: NodeId,
+    depths: &mut HashMap<NodeId, usize>,
+    visiting: &mut HashSet<NodeId>,
+) -> Option<usize> {
+    if let Some(depth) = depths.get(&node_id) {
+        return Some(*depth);
+    }
+    if !visiting.insert(node_id) {
+        return None;
+    }
+    let mut deepest = 0;
+    for output in graph[node_id]
+        .input_ids()
+        .filter_map(|input| graph.connection(input))
+    {
+        deepest = deepest.max(depth(graph, graph[output].node, depths, visiting)?);
+    }
+    visiting.remove(&node_id);
+    depths.insert(node_id, deepest + 1);
+    Some(deepest + 1)
+}
+
+impl Statistics {
+    /// The figures as (name, value) rows.
+    fn rows(&self, graph: &Graph) -> Vec<(String, String)> {
+        let mut rows: Vec<(String, String)> = self
+            .counts
+            .iter()
+            .map(|(template, count)| (template.name(), count.to_string()))
+            .collect();
+        let or_dash = |value: Option<String>| value.unwrap_or_else(|| "—".to_owned());
+        rows.extend([
+            (tr("statistics-connections"), self.connections.to_string()),
+            (
+                tr("statistics-depth"),
+                or_dash(self.depth.map(|depth| depth.to_string())),
+            ),
+            (tr("statistics-subsystems"), self.subsystems.to_string()),
+            (
+                tr("statistics-largest"),
+                or_dash(self.largest.map(|(root, size)| {
+                    tr_with(
+                        "statistics-largest-value",
+                        &[("node", &graph[root].label), ("count", &size)],
+                    )
+                })),
+            ),
+            (
+                tr("statistics-cost"),
+                or_dash(self.cost.map(|cost| cost.to_string())),
+            ),
+        ]);
+        rows
+    }
+}
+
+pub fn window(ctx: &egui::Context, open: &mut bool, graph: &Graph, state: &GraphState) {
+    egui::Window::new(tr("statistics-title"))
+        .open(open)
+        .show(ctx, |ui| {
+            let rows = compute(graph, state.active_node).rows(graph);
+            egui::Grid::new("statistics")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, value) in &rows {
+                        ui.label(name);
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+            if ui.button(tr("plot-copy-data")).clicked() {
+                let tsv: String = rows
+                    .iter()
+                    .map(|(name, value)| format!("{name}\t{value}\n"))
+                    .collect();
+                ui.output_mut(|o| o.copied_text = tsv);
+            }
+            ui.label(tr("statistics-hint"));
+        });
+}
+
+/// What the selected components would come to at the mission time if they
+/// were joined in series or in parallel.
+pub struct Selection {
+    pub components: usize,
+    pub series: f64,
+    pub parallel: f64,
+    /// The sum of their hazard rates, which is the series hazard rate.
+    pub rate: f64,
+}
+
+/// The readout of `selected`, or `None` unless at least two components are
+/// among them.
+pub fn selection(graph: &Graph, state: &GraphState, selected: &[NodeId]) -> Option<Selection> {
+    let mut readout = Selection {
+        components: 0,
+        series: 1.0,
+        parallel: 1.0,
+        rate: 0.0,
+    };
+    for node in selected {
+        if graph[*node].user_data.template != NodeTemplate::Component {
+            continue;
+        }
+        let parameters = nodes::component_parameters(graph, *node)?;
+        let curve = eval::component_reliability(&parameters, &state.mission_profile).ok()?;
+        let time = state.mission_time.min(curve.len().checked_sub(1)?);
+        readout.components += 1;
+        readout.series *= curve[time];
+        readout.parallel *= 1.0 - curve[time];
+        readout.rate += eval::hazard_rate(&curve)[time];
+    }
+    readout.parallel = 1.0 - readout.parallel;
+    (readout.components >= 2).then_some(readout)
+}
+
+impl Selection {
+    pub fn label(&self, unit: &str) -> String {
+        tr_with(
+            "statistics-selection",
+            &[
+                ("count", &self.components),
+                ("series", &format!("{:.6}", self.series)),
+                ("parallel", &format!("{:.6}", self.parallel)),
+                ("rate", &format!("{:.3e}", self.rate)),
+                ("unit", &unit),
+            ],
+        )
+    }
+}
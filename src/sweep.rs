@@ -0,0 +1,175 @@
+//! Sweeps a component parameter over a range and charts the system response.
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use egui_plot::{Line, Plot, PlotPoints};
+
+use crate::eval::{evaluate_curve, mttf, Options, OutputsCache};
+use crate::i18n::tr;
+use crate::nodes::{self, Graph, GraphState, Parameter};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Metric {
+    #[default]
+    MissionReliability,
+    Mttf,
+}
+
+pub struct Sweep {
+    component: Option<NodeId>,
+    parameter: Parameter,
+    from: f64,
+    to: f64,
+    steps: usize,
+    metric: Metric,
+    /// (parameter value, R(mission), MTTF) for each swept value.
+    results: Vec<[f64; 3]>,
+    error: Option<String>,
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            component: None,
+            parameter: Parameter::default(),
+            from: 1_000.0,
+            to: 20_000.0,
+            steps: 50,
+            metric: Metric::default(),
+            results: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl Sweep {
+    /// Evaluates the system once per swept value, restoring the component's
+    /// parameters afterwards.
+    fn run(
+        &mut self,
+        graph: &mut Graph,
+        options: &Options,
+        system: NodeId,
+        component: NodeId,
+        mission_time: usize,
+    ) {
+        self.results.clear();
+        self.error = None;
+        let Some(original) = nodes::component_parameters(graph, component) else {
+            self.error = Some(tr("error-not-component"));
+            return;
+        };
+
+        for i in 0..self.steps {
+            let fraction = if self.steps > 1 {
+                i as f64 / (self.steps - 1) as f64
+            } else {
+                0.0
+            };
+            let value = self.from + fraction * (self.to - self.from);
+            if let Some(parameters) = nodes::component_parameters_mut(graph, component) {
+                parameters.set(self.parameter, value);
+            }
+            match evaluate_curve(graph, options, system, &mut OutputsCache::new()) {
+                Ok(curve) => {
+                    let r = curve.get(mission_time).copied().unwrap_or(f64::NAN);
+                    self.results.push([value, r, mttf(&curve)]);
+                }
+                Err(err) => {
+                    self.error = Some(err.to_string());
+                    break;
+                }
+            }
+        }
+
+        if let Some(parameters) = nodes::component_parameters_mut(graph, component) {
+            *parameters = original;
+        }
+    }
+
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        graph: &mut Graph,
+        state: &GraphState,
+    ) {
+        egui::Window::new(tr("sweep-title"))
+            .open(open)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                egui::Grid::new("sweep").num_columns(2).show(ui, |ui| {
+                    ui.label(tr("goal-seek-component"));
+                    nodes::component_combo(ui, "sweep component", graph, &mut self.component);
+                    ui.end_row();
+
+                    ui.label(tr("goal-seek-parameter"));
+                    self.parameter.combo(ui, "sweep parameter");
+                    ui.end_row();
+
+                    ui.label(tr("sweep-range"));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut self.from).speed(1.0));
+                        ui.label("–");
+                        ui.add(egui::DragValue::new(&mut self.to).speed(1.0));
+                    });
+                    ui.end_row();
+
+                    ui.label(tr("sweep-steps"));
+                    ui.add(egui::DragValue::new(&mut self.steps).clamp_range(2..=1_000));
+                    ui.end_row();
+                });
+
+                let ready = state.active_node.is_some() && self.component.is_some();
+                if state.active_node.is_none() {
+                    ui.label(tr("goal-seek-no-system"));
+                }
+                if ui
+                    .add_enabled(ready, egui::Button::new(tr("sweep-run")))
+                    .clicked()
+                {
+                    if let (Some(system), Some(component)) = (state.active_node, self.component) {
+                        self.run(
+                            graph,
+                            &Options::of(state),
+                            system,
+                            component,
+                            state.mission_time,
+                        );
+                    }
+                }
+                if let Some(err) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                if self.results.is_empty() {
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.metric,
+                        Metric::MissionReliability,
+                        tr("sweep-metric-reliability"),
+                    );
+                    ui.radio_value(&mut self.metric, Metric::Mttf, tr("sweep-metric-mttf"));
+                });
+                let column = match self.metric {
+                    Metric::MissionReliability => 1,
+                    Metric::Mttf => 2,
+                };
+                let points: PlotPoints = self
+                    .results
+                    .iter()
+                    .map(|row| [row[0], row[column]])
+                    .collect();
+                let y_label = match self.metric {
+                    Metric::MissionReliability => "R(t_m)".to_owned(),
+                    Metric::Mttf => format!("MTTF [{}]", state.settings.time_unit.symbol()),
+                };
+                Plot::new("sweep plot")
+                    .height(250.0)
+                    .x_axis_label(self.parameter.name())
+                    .y_axis_label(y_label)
+                    .show(ui, |plot_ui| plot_ui.line(Line::new(points)));
+            });
+    }
+}
@@ -0,0 +1,66 @@
+//! Color tags grouping related nodes, e.g. by subsystem.
+use eframe::egui::{self, Color32};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::nodes::Graph;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorTag {
+    pub name: String,
+    pub color: Color32,
+}
+
+/// The distinct tags used in the graph with their node counts, sorted by name.
+pub fn graph_tags(graph: &Graph) -> Vec<(ColorTag, usize)> {
+    let mut tags: Vec<(ColorTag, usize)> = Vec::new();
+    for tag in graph.nodes.values().filter_map(|node| node.user_data.tag.as_ref()) {
+        match tags.iter_mut().find(|(known, _)| known.name == tag.name) {
+            Some((_, count)) => *count += 1,
+            None => tags.push((tag.clone(), 1)),
+        }
+    }
+    tags.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    tags
+}
+
+/// A color for the next new tag, cycling through the theme's accents.
+pub fn next_color(theme: &catppuccin_egui::Theme, existing: usize) -> Color32 {
+    let accents = [
+        theme.blue,
+        theme.green,
+        theme.peach,
+        theme.mauve,
+        theme.teal,
+        theme.red,
+        theme.yellow,
+        theme.pink,
+        theme.sapphire,
+        theme.maroon,
+    ];
+    accents[existing % accents.len()]
+}
+
+/// Lists the tags in use, letting the user recolor them.
+pub fn legend(ui: &mut egui::Ui, graph: &mut Graph) {
+    ui.heading(tr("legend-title"));
+    let tags = graph_tags(graph);
+    if tags.is_empty() {
+        ui.label(tr("legend-empty"));
+    }
+    egui::Grid::new("legend").num_columns(2).show(ui, |ui| {
+        for (mut tag, count) in tags {
+            if ui.color_edit_button_srgba(&mut tag.color).changed() {
+                for node in graph.nodes.values_mut() {
+                    if let Some(node_tag) = &mut node.user_data.tag {
+                        if node_tag.name == tag.name {
+                            node_tag.color = tag.color;
+                        }
+                    }
+                }
+            }
+            ui.label(format!("{} ({count})", tag.name));
+            ui.end_row();
+        }
+    });
+}
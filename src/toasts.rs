@@ -0,0 +1,155 @@
+//! Toasts: short notices stacked in the corner of the window for errors,
+//! imports and finished runs. Past ones are kept for the history window.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use eframe::egui;
+
+use crate::i18n::tr;
+
+/// How long a toast stays up, in seconds. Errors stay until dismissed.
+const DURATION: f64 = 5.0;
+
+/// The most toasts kept in the history; older ones are dropped first.
+const HISTORY: usize = 200;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Info,
+    Success,
+    Error,
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    pub kind: Kind,
+    pub text: String,
+    pub time: SystemTime,
+}
+
+/// Toasts pushed since the last frame, from anywhere in the app.
+static QUEUE: Mutex<Vec<Toast>> = Mutex::new(Vec::new());
+
+pub fn push(kind: Kind, text: impl Into<String>) {
+    let toast = Toast {
+        kind,
+        text: text.into(),
+        time: SystemTime::now(),
+    };
+    QUEUE
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(toast);
+}
+
+pub fn success(text: impl Into<String>) {
+    push(Kind::Success, text);
+}
+
+pub fn error(text: impl Into<String>) {
+    push(Kind::Error, text);
+}
+
+#[derive(Default)]
+pub struct Toasts {
+    /// The toasts on screen, with the time they go away at.
+    shown: Vec<(Toast, Option<f64>)>,
+    history: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Draws the toasts on screen, taking in the ones pushed since the last
+    /// frame.
+    pub fn show(&mut self, ctx: &egui::Context, theme: catppuccin_egui::Theme) {
+        let now = ctx.input(|i| i.time);
+        let queued = std::mem::take(&mut *QUEUE.lock().unwrap_or_else(|err| err.into_inner()));
+        for toast in queued {
+            if self.history.len() == HISTORY {
+                self.history.pop_front();
+            }
+            self.history.push_back(toast.clone());
+            let until = (toast.kind != Kind::Error).then_some(now + DURATION);
+            self.shown.push((toast, until));
+        }
+        self.shown
+            .retain(|(_, until)| until.is_none_or(|until| until > now));
+        if let Some(next) = self
+            .shown
+            .iter()
+            .filter_map(|(_, until)| *until)
+            .reduce(f64::min)
+        {
+            ctx.request_repaint_after(std::time::Duration::from_secs_f64(next - now));
+        }
+        if self.shown.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -36.0))
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                for (index, (toast, _)) in self.shown.iter().enumerate().rev() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let (icon, color) = style(theme, toast.kind);
+                            ui.colored_label(color, icon);
+                            ui.add(egui::Label::new(&toast.text).wrap(true));
+                            if ui.small_button("×").clicked() {
+                                dismissed = Some(index);
+                            }
+                        });
+                    });
+                }
+            });
+        if let Some(index) = dismissed {
+            self.shown.remove(index);
+        }
+    }
+
+    /// The toasts shown so far this session, newest first.
+    pub fn history_window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        theme: catppuccin_egui::Theme,
+    ) {
+        egui::Window::new(tr("toasts-title"))
+            .open(open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                if self.history.is_empty() {
+                    ui.label(tr("toasts-none"));
+                    return;
+                }
+                if ui.button(tr("toasts-clear")).clicked() {
+                    self.history.clear();
+                }
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .auto_shrink(false)
+                    .show(ui, |ui| {
+                        for toast in self.history.iter().rev() {
+                            ui.horizontal_wrapped(|ui| {
+                                let (icon, color) = style(theme, toast.kind);
+                                ui.colored_label(color, icon);
+                                ui.monospace(crate::log::clock(toast.time));
+                                ui.label(&toast.text);
+                            });
+                        }
+                    });
+            });
+    }
+}
+
+fn style(theme: catppuccin_egui::Theme, kind: Kind) -> (&'static str, egui::Color32) {
+    match kind {
+        Kind::Info => ("ℹ", theme.blue),
+        Kind::Success => ("✔", theme.green),
+        Kind::Error => ("⚠", theme.red),
+    }
+}
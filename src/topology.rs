@@ -0,0 +1,149 @@
+//! The diagram's structure on its own, without parameters or layout, in
+//! formats other graph tools read.
+use std::collections::HashMap;
+
+use egui_node_graph2::NodeId;
+
+use crate::audit::Audit;
+use crate::i18n::tr;
+use crate::locale;
+use crate::nodes::Graph;
+use crate::simulate::csv_field;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StructureFormat {
+    /// One row and column per node, counting the connections from the row's
+    /// node into the column's.
+    AdjacencyMatrix,
+    /// One `from,to,input` row per connection.
+    EdgeList,
+}
+
+impl StructureFormat {
+    pub const ALL: [StructureFormat; 2] =
+        [StructureFormat::AdjacencyMatrix, StructureFormat::EdgeList];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            StructureFormat::AdjacencyMatrix => "menu-file-export-adjacency",
+            StructureFormat::EdgeList => "menu-file-export-edge-list",
+        })
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            StructureFormat::AdjacencyMatrix => "adjacency.csv",
+            StructureFormat::EdgeList => "edges.csv",
+        }
+    }
+
+    pub fn encode(self, graph: &Graph) -> String {
+        match self {
+            StructureFormat::AdjacencyMatrix => adjacency_matrix(graph),
+            StructureFormat::EdgeList => edge_list(graph),
+        }
+    }
+}
+
+/// The nodes that take part in the structure, sorted by label, each with a
+/// name no other node has: repeated labels get a `#2`, `#3`, … suffix.
+pub fn names(graph: &Graph) -> Vec<(NodeId, String)> {
+    let mut nodes: Vec<(NodeId, &str)> = graph
+        .nodes
+        .iter()
+        .filter(|(_, node)| node.user_data.template.evaluates())
+        .map(|(id, node)| (id, node.label.as_str()))
+        .collect();
+    nodes.sort_by_key(|(_, label)| *label);
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    nodes
+        .into_iter()
+        .map(|(id, label)| {
+            let count = seen.entry(label).or_default();
+            *count += 1;
+            let name = match *count {
+                1 => label.to_owned(),
+                n => format!("{label} #{n}"),
+            };
+            (id, name)
+        })
+        .collect()
+}
+
+/// The connections between the nodes of `names` as (from, to, input name),
+/// in their order.
+pub fn edges(graph: &Graph) -> Vec<(NodeId, NodeId, String)> {
+    let mut edges: Vec<(NodeId, NodeId, String)> = graph
+        .iter_connections()
+        .filter_map(|(input, output)| {
+            let to = graph[input].node;
+            let name = graph[to]
+                .inputs
+                .iter()
+                .find(|(_, id)| *id == input)
+                .map(|(name, _)| name.clone())?;
+            Some((graph[output].node, to, name))
+        })
+        .collect();
+    let order: HashMap<NodeId, usize> = names(graph)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (id, _))| (id, i))
+        .collect();
+    edges.sort_by_key(|(from, to, name)| (order.get(from), order.get(to), name.clone()));
+    edges
+}
+
+fn adjacency_matrix(graph: &Graph) -> String {
+    let names = names(graph);
+    let index: HashMap<NodeId, usize> = names
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _))| (*id, i))
+        .collect();
+    let mut matrix = vec![vec![0; names.len()]; names.len()];
+    for (from, to, _) in edges(graph) {
+        if let (Some(from), Some(to)) = (index.get(&from), index.get(&to)) {
+            matrix[*from][*to] += 1;
+        }
+    }
+    let mut header = vec![String::new()];
+    header.extend(names.iter().map(|(_, name)| csv_field(name)));
+    let mut csv = locale::row(&header);
+    for ((_, name), row) in names.iter().zip(&matrix) {
+        let mut fields = vec![csv_field(name)];
+        fields.extend(row.iter().map(ToString::to_string));
+        csv.push_str(&locale::row(&fields));
+    }
+    csv
+}
+
+fn edge_list(graph: &Graph) -> String {
+    let names: HashMap<NodeId, String> = names(graph).into_iter().collect();
+    let header = ["from", "to", "input"];
+    let mut csv = locale::row(&header.map(str::to_owned));
+    for (from, to, input) in edges(graph) {
+        if let (Some(from), Some(to)) = (names.get(&from), names.get(&to)) {
+            csv.push_str(&locale::row(&[
+                csv_field(from),
+                csv_field(to),
+                csv_field(&input),
+            ]));
+        }
+    }
+    csv
+}
+
+/// Asks for a file name and writes the structure there, headed by `audit`.
+/// Returns whether it was written.
+pub fn export(graph: &Graph, format: StructureFormat, audit: &Audit) -> anyhow::Result<bool> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("CSV", &["csv"])
+        .set_file_name(format.file_name())
+        .save_file()
+    else {
+        return Ok(false);
+    };
+    std::fs::write(path, audit.comments("# ") + &format.encode(graph))?;
+    Ok(true)
+}
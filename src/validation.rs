@@ -0,0 +1,69 @@
+//! Checks of the diagram for mistakes that evaluate without an error but
+//! leave the system results wrong, like a component that was added and
+//! never wired into the structure.
+use std::collections::HashSet;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{Graph, NodeCategory, NodeTemplate};
+
+/// Components, and empirical curves, whose output feeds no structural
+/// block, so they count towards no system result. The active node is left
+/// out, as a system of a single component is fine. Sorted by label.
+pub fn unused_outputs(graph: &Graph, active: Option<NodeId>) -> Vec<NodeId> {
+    let consumed: HashSet<NodeId> = graph
+        .iter_connections()
+        .filter(|(input, _)| {
+            graph[graph[*input].node].user_data.template.category() == NodeCategory::Structures
+        })
+        .map(|(_, output)| graph[output].node)
+        .collect();
+    let mut unused: Vec<NodeId> = graph
+        .nodes
+        .iter()
+        .filter(|(node, data)| {
+            let template = data.user_data.template;
+            (template.category() == NodeCategory::Components || template == NodeTemplate::Empirical)
+                && !consumed.contains(node)
+                && Some(*node) != active
+        })
+        .map(|(node, _)| node)
+        .collect();
+    unused.sort_by(|a, b| graph[*a].label.cmp(&graph[*b].label));
+    unused
+}
+
+/// The header of the validation section, warning when there is something
+/// to look at.
+pub fn title(ui: &egui::Ui, problems: usize) -> egui::RichText {
+    let text = egui::RichText::new(tr_with("validation-title", &[("count", &problems)]));
+    if problems > 0 {
+        text.color(ui.visuals().warn_fg_color)
+    } else {
+        text
+    }
+}
+
+/// Lists the problems found, returning the node the user asked to be shown.
+pub fn ui(ui: &mut egui::Ui, graph: &Graph, unused: &[NodeId]) -> Option<NodeId> {
+    if unused.is_empty() {
+        ui.label(tr("validation-none"));
+        return None;
+    }
+    let mut shown = None;
+    ui.label(tr("validation-unused-hint"));
+    egui::ScrollArea::vertical()
+        .id_source("validation")
+        .max_height(150.0)
+        .show(ui, |ui| {
+            for node in unused {
+                let text = tr_with("validation-unused", &[("node", &graph[*node].label)]);
+                if ui.link(text).on_hover_text(tr("validation-show")).clicked() {
+                    shown = Some(*node);
+                }
+            }
+        });
+    shown
+}
@@ -0,0 +1,415 @@
+//! Named project variables, such as `mission_hours` or `ambient_temp`, that
+//! component parameters can be given as expressions of. The expressions are
+//! worked out again every frame, so changing a variable updates every
+//! component that depends on it at once.
+//!
+//! Expressions take numbers, variables, `+ - * / ^` and parentheses. The
+//! same arithmetic can be typed into the number fields of a node.
+use std::collections::HashMap;
+
+use eframe::egui;
+use egui_node_graph2::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph, GraphState, NodeParameters};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Variable {
+    pub name: String,
+    pub value: f64,
+}
+
+/// A component parameter that can be given as an expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Bound {
+    Shape,
+    Scale,
+    TimeSteps,
+    DutyCycle,
+    Mttr,
+    EnvironmentFactor,
+    Temperature,
+}
+
+impl Bound {
+    pub const ALL: [Bound; 7] = [
+        Bound::Shape,
+        Bound::Scale,
+        Bound::TimeSteps,
+        Bound::DutyCycle,
+        Bound::Mttr,
+        Bound::EnvironmentFactor,
+        Bound::Temperature,
+    ];
+
+    pub fn name(self) -> String {
+        tr(match self {
+            Bound::Shape => "param-shape",
+            Bound::Scale => "param-scale",
+            Bound::TimeSteps => "param-time-steps",
+            Bound::DutyCycle => "param-duty-cycle",
+            Bound::Mttr => "param-mttr",
+            Bound::EnvironmentFactor => "param-environment-factor",
+            Bound::Temperature => "param-temperature",
+        })
+    }
+
+    /// The values the parameter accepts, as its editor clamps them.
+    fn range(self) -> (f64, f64) {
+        match self {
+            Bound::Shape | Bound::Scale => (0.01, f64::MAX),
+            Bound::TimeSteps => (1.0, 1_000_000.0),
+            Bound::DutyCycle => (0.01, 1.0),
+            Bound::Mttr => (0.0, f64::MAX),
+            Bound::EnvironmentFactor => (0.01, 100.0),
+            Bound::Temperature => (-273.15, f64::MAX),
+        }
+    }
+
+    /// Sets the parameter, unless `value` is out of its range.
+    fn set(self, parameters: &mut NodeParameters, value: f64) -> Result<(), String> {
+        let (min, max) = self.range();
+        if !(min..=max).contains(&value) {
+            return Err(tr_with(
+                "variables-out-of-range",
+                &[("parameter", &self.name()), ("value", &value)],
+            ));
+        }
+        match self {
+            Bound::Shape => parameters.shape = value,
+            Bound::Scale => parameters.scale = value,
+            Bound::TimeSteps => parameters.time_steps = value.round() as usize,
+            Bound::DutyCycle => parameters.duty_cycle = value,
+            Bound::Mttr => parameters.mttr = value,
+            Bound::EnvironmentFactor => parameters.environment_factor = value,
+            Bound::Temperature => parameters.temperature = value,
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` can be used in expressions: a letter or underscore, then
+/// letters, digits and underscores.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Symbol(char),
+}
+
+fn tokens(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                // An exponent's sign belongs to the number.
+                let sign = matches!(c, '+' | '-') && text[..i].ends_with(['e', 'E']);
+                if !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E') || sign) {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let number = &text[start..end];
+            tokens.push(Token::Number(
+                number
+                    .parse()
+                    .map_err(|_| tr_with("variables-syntax", &[("text", &number)]))?,
+            ));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            tokens.push(Token::Name(text[start..end].to_owned()));
+        } else if "+-*/^()".contains(c) {
+            tokens.push(Token::Symbol(c));
+            chars.next();
+        } else {
+            return Err(tr_with("variables-syntax", &[("text", &c)]));
+        }
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent over the tokens, with the usual precedence and `^`
+/// binding tightest and to the right.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    next: usize,
+    variables: &'a HashMap<&'a str, f64>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        let found = self.peek() == Some(&Token::Symbol(symbol));
+        self.next += usize::from(found);
+        found
+    }
+
+    fn sum(&mut self) -> Result<f64, String> {
+        let mut value = self.product()?;
+        loop {
+            if self.eat('+') {
+                value += self.product()?;
+            } else if self.eat('-') {
+                value -= self.product()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<f64, String> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                value /= self.unary()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// A sign binds looser than `^`, so `-2^2` is −4.
+    fn unary(&mut self) -> Result<f64, String> {
+        if self.eat('-') {
+            return Ok(-self.unary()?);
+        }
+        if self.eat('+') {
+            return self.unary();
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<f64, String> {
+        let base = self.atom()?;
+        if self.eat('^') {
+            return Ok(base.powf(self.unary()?));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<f64, String> {
+        let token = self.peek().cloned();
+        self.next += 1;
+        match token {
+            Some(Token::Number(value)) => Ok(value),
+            Some(Token::Name(name)) => self
+                .variables
+                .get(name.as_str())
+                .copied()
+                .ok_or_else(|| tr_with("variables-unknown", &[("name", &name)])),
+            Some(Token::Symbol('(')) => {
+                let value = self.sum()?;
+                if self.eat(')') {
+                    Ok(value)
+                } else {
+                    Err(tr("variables-unclosed"))
+                }
+            }
+            Some(Token::Symbol(c)) => Err(tr_with("variables-syntax", &[("text", &c)])),
+            None => Err(tr("variables-incomplete")),
+        }
+    }
+}
+
+/// The value of `expression` with the given variables.
+pub fn evaluate(expression: &str, variables: &[Variable]) -> Result<f64, String> {
+    let values: HashMap<&str, f64> = variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable.value))
+        .collect();
+    let mut parser = Parser {
+        tokens: tokens(expression)?,
+        next: 0,
+        variables: &values,
+    };
+    let value = parser.sum()?;
+    if let Some(token) = parser.peek() {
+        let text = match token {
+            Token::Number(value) => value.to_string(),
+            Token::Name(name) => name.clone(),
+            Token::Symbol(c) => c.to_string(),
+        };
+        return Err(tr_with("variables-syntax", &[("text", &text)]));
+    }
+    if !value.is_finite() {
+        return Err(tr("variables-not-finite"));
+    }
+    Ok(value)
+}
+
+/// A number typed as arithmetic, such as `2*8760` or `1/0.0003`.
+pub fn parse(text: &str) -> Option<f64> {
+    evaluate(text, &[]).ok()
+}
+
+/// A drag value that also takes arithmetic typed into it, worked out when
+/// the edit is committed.
+pub fn drag_value<Num: egui::emath::Numeric>(value: &mut Num) -> egui::DragValue<'_> {
+    egui::DragValue::new(value).custom_parser(parse)
+}
+
+/// Sets every bound component parameter to the value of its expression,
+/// returning what went wrong with each node whose expressions didn't all
+/// work out. Those parameters keep their last value.
+pub fn apply(graph: &mut Graph, variables: &[Variable]) -> HashMap<NodeId, String> {
+    let mut errors = HashMap::new();
+    for node in nodes::components(graph) {
+        let Some(parameters) = nodes::component_parameters_mut(graph, node) else {
+            continue;
+        };
+        if parameters.expressions.is_empty() {
+            continue;
+        }
+        let mut updated = parameters.clone();
+        for (bound, expression) in &parameters.expressions {
+            let set =
+                evaluate(expression, variables).and_then(|value| bound.set(&mut updated, value));
+            if let Err(err) = set {
+                errors.insert(node, format!("{}: {err}", bound.name()));
+            }
+        }
+        *parameters = updated;
+    }
+    errors
+}
+
+/// The expressions of a component's parameters. A parameter given by one
+/// can't be edited directly.
+pub fn expressions_ui(
+    ui: &mut egui::Ui,
+    parameters: &mut NodeParameters,
+    node: NodeId,
+    state: &GraphState,
+) {
+    egui::Grid::new("expressions")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for bound in Bound::ALL {
+                ui.label(bound.name());
+                let mut expression = parameters
+                    .expressions
+                    .get(&bound)
+                    .cloned()
+                    .unwrap_or_default();
+                let edited = ui.add(
+                    egui::TextEdit::singleline(&mut expression)
+                        .hint_text(tr("variables-expression-hint"))
+                        .desired_width(120.0),
+                );
+                if edited.changed() {
+                    if expression.trim().is_empty() {
+                        parameters.expressions.remove(&bound);
+                    } else {
+                        parameters.expressions.insert(bound, expression);
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(err) = state.variable_errors.get(&node) {
+        ui.colored_label(ui.visuals().error_fg_color, err);
+    }
+}
+
+/// Lists the project's variables for editing, with how many components use
+/// each.
+pub fn window(ctx: &egui::Context, open: &mut bool, graph: &Graph, state: &mut GraphState) {
+    egui::Window::new(tr("variables-title"))
+        .open(open)
+        .show(ctx, |ui| {
+            ui.label(tr("variables-hint"));
+            let expressions: Vec<String> = nodes::components(graph)
+                .into_iter()
+                .filter_map(|node| nodes::component_parameters(graph, node))
+                .flat_map(|parameters| parameters.expressions.into_values())
+                .collect();
+            let names: Vec<String> = state
+                .variables
+                .iter()
+                .map(|variable| variable.name.clone())
+                .collect();
+            let mut remove = None;
+            egui::Grid::new("variables")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong(tr("variables-name"));
+                    ui.strong(tr("variables-value"));
+                    ui.strong(tr("variables-uses"));
+                    ui.end_row();
+                    for (i, variable) in state.variables.iter_mut().enumerate() {
+                        let name = ui.add(
+                            egui::TextEdit::singleline(&mut variable.name).desired_width(120.0),
+                        );
+                        if !is_valid_name(&variable.name) {
+                            name.on_hover_text(tr("variables-invalid-name"));
+                        } else if names.iter().filter(|n| **n == variable.name).count() > 1 {
+                            name.on_hover_text(tr("variables-duplicate-name"));
+                        }
+                        ui.add(drag_value(&mut variable.value).speed(0.1));
+                        let uses = expressions
+                            .iter()
+                            .filter(|expression| {
+                                tokens(expression).is_ok_and(|tokens| {
+                                    tokens.contains(&Token::Name(variable.name.clone()))
+                                })
+                            })
+                            .count();
+                        ui.label(uses.to_string());
+                        if ui.small_button("🗑").clicked() {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                state.variables.remove(i);
+            }
+            if ui.button(tr("variables-add")).clicked() {
+                let mut n = state.variables.len() + 1;
+                while names.contains(&format!("var{n}")) {
+                    n += 1;
+                }
+                state.variables.push(Variable {
+                    name: format!("var{n}"),
+                    value: 1.0,
+                });
+            }
+            if !state.variable_errors.is_empty() {
+                ui.separator();
+                for (node, err) in &state.variable_errors {
+                    let label = graph
+                        .nodes
+                        .get(*node)
+                        .map_or("", |node| node.label.as_str());
+                    ui.colored_label(ui.visuals().error_fg_color, format!("{label}: {err}"));
+                }
+            }
+        });
+}
@@ -0,0 +1,32 @@
+//! Windows that pop out of the main one into an OS window of their own, so
+//! the diagram can fill one monitor while results live on another.
+use eframe::egui;
+
+/// Shows `contents` in an OS window titled `title`, clearing `open` when it
+/// is closed. Backends without multiple viewports show it as an ordinary
+/// window instead.
+pub fn show(
+    ctx: &egui::Context,
+    id: &str,
+    title: String,
+    size: [f32; 2],
+    open: &mut bool,
+    contents: impl FnOnce(&mut egui::Ui),
+) {
+    ctx.show_viewport_immediate(
+        egui::ViewportId::from_hash_of(id),
+        egui::ViewportBuilder::default()
+            .with_title(&title)
+            .with_inner_size(size),
+        |ctx, class| {
+            if class == egui::ViewportClass::Embedded {
+                egui::Window::new(title).open(open).show(ctx, contents);
+            } else {
+                egui::CentralPanel::default().show(ctx, contents);
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    *open = false;
+                }
+            }
+        },
+    );
+}
@@ -0,0 +1,33 @@
+//! `--watch` for the command-line tools: running them again whenever one of
+//! the files they read is saved, by this app or by a script.
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// How often the files are looked at.
+const INTERVAL: Duration = Duration::from_millis(500);
+
+/// Calls `run` now and again each time the files `files` lists change, are
+/// added or are removed, until the process is stopped. `files` is asked
+/// again on every look, so a glob can pick up new projects.
+pub fn watch(files: impl Fn() -> Vec<PathBuf>, run: impl Fn() -> i32) -> ! {
+    let mut last = None;
+    loop {
+        let stamps: Vec<(PathBuf, Option<SystemTime>)> = files()
+            .into_iter()
+            .map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|meta| meta.modified());
+                (path, modified.ok())
+            })
+            .collect();
+        if last.as_ref() != Some(&stamps) {
+            let code = run();
+            eprintln!(
+                "[{}] {} file(s), exit code {code}; watching for changes (Ctrl+C to stop)",
+                crate::log::clock(SystemTime::now()),
+                stamps.len()
+            );
+            last = Some(stamps);
+        }
+        std::thread::sleep(INTERVAL);
+    }
+}
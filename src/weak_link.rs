@@ -0,0 +1,232 @@
+//! The weak link of a system: its dominant cut set, the components whose
+//! joint failure is likeliest to take it down at a given time, and the
+//! blocks that failure passes through on its way to the system.
+//!
+//! Each block keeps the likeliest cut set of its inputs: the likeliest one of
+//! any input for a series block, one of every input for a parallel or standby
+//! pair, and of the n − k + 1 likeliest members for a k-out-of-n block. This
+//! finds the dominant cut set exactly when no component is shared.
+use std::collections::HashSet;
+
+use catppuccin_egui::Theme;
+use eframe::egui::{self, Stroke};
+use egui_node_graph2::NodeId;
+
+use crate::eval::{Options, OutputsCache};
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, Forced, Graph, NodeTemplate, ValueType};
+use crate::wires::Layout;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeakLink {
+    pub time: usize,
+    /// The components of the dominant cut set.
+    pub cut_set: Vec<NodeId>,
+    /// The blocks between the cut set and the system, the system included.
+    pub path: HashSet<NodeId>,
+    /// The probability that every component of the cut set has failed.
+    pub probability: f64,
+}
+
+/// A block's likeliest cut set, and the blocks it was chosen through.
+#[derive(Clone, Default)]
+struct Cut {
+    members: Vec<NodeId>,
+    path: Vec<NodeId>,
+}
+
+impl Cut {
+    fn join(cuts: impl IntoIterator<Item = Cut>) -> Cut {
+        let mut joined = Cut::default();
+        for cut in cuts {
+            joined.members.extend(cut.members);
+            joined.path.extend(cut.path);
+        }
+        joined.members.sort();
+        joined.members.dedup();
+        joined
+    }
+}
+
+struct Tracer<'a> {
+    graph: &'a Graph,
+    options: &'a Options,
+    curves: &'a OutputsCache,
+    time: usize,
+    in_progress: HashSet<NodeId>,
+}
+
+impl Tracer<'_> {
+    /// 1 − R of `node_id` at the time, from its evaluated curve.
+    fn unreliability(&self, node_id: NodeId) -> Option<f64> {
+        let output = self.graph[node_id].get_output("reliability").ok()?;
+        let r = match self.curves.get(&output)? {
+            ValueType::Reliability { curve } => *curve.get(self.time)?,
+            ValueType::CompactReliability { curve } => *curve.get(self.time)? as f64,
+            _ => return None,
+        };
+        Some(1.0 - r)
+    }
+
+    fn probability(&self, cut: &Cut) -> f64 {
+        cut.members
+            .iter()
+            .map(|member| self.unreliability(*member).unwrap_or(0.0))
+            .product()
+    }
+
+    /// The cut sets of the inputs that can fail, or `None` if one can't,
+    /// which a block needing all of them to fail then can't either.
+    /// Excluded inputs, and disabled ones counted as absent, are left out.
+    fn inputs(&mut self, node_id: NodeId) -> Vec<Option<Cut>> {
+        let graph = self.graph;
+        graph[node_id]
+            .input_ids()
+            .filter_map(|input| graph.connection(input))
+            .map(|output| graph[output].node)
+            .filter(|source| {
+                let data = &graph[*source].user_data;
+                !data.excluded && (data.enabled || !self.options.disabled_absent())
+            })
+            .map(|source| {
+                if graph[source].user_data.enabled {
+                    self.cut(source)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn cut(&mut self, node_id: NodeId) -> Option<Cut> {
+        if !self.in_progress.insert(node_id) {
+            return None;
+        }
+        let cut = self.cut_inner(node_id);
+        self.in_progress.remove(&node_id);
+        cut
+    }
+
+    fn cut_inner(&mut self, node_id: NodeId) -> Option<Cut> {
+        let graph = self.graph;
+        let data = &graph[node_id].user_data;
+        let leaf = Cut {
+            members: vec![node_id],
+            path: Vec::new(),
+        };
+        match data.forced {
+            Some(Forced::Working) => return None,
+            Some(Forced::Failed) => return Some(leaf),
+            None if self.options.filtered_out(data) => return None,
+            None => {}
+        }
+        let mut cut = match data.template {
+            NodeTemplate::Series => {
+                let inputs: Vec<Cut> = self.inputs(node_id).into_iter().flatten().collect();
+                inputs
+                    .into_iter()
+                    .max_by(|a, b| self.probability(a).total_cmp(&self.probability(b)))?
+            }
+            NodeTemplate::Parallel | NodeTemplate::Standby => Cut::join(
+                self.inputs(node_id)
+                    .into_iter()
+                    .collect::<Option<Vec<Cut>>>()?,
+            ),
+            NodeTemplate::Voting => {
+                let inputs = self.inputs(node_id);
+                let needed = (inputs.len() + 1).checked_sub(data.votes)?;
+                let mut inputs: Vec<Cut> = inputs.into_iter().flatten().collect();
+                inputs.sort_by(|a, b| self.probability(b).total_cmp(&self.probability(a)));
+                if inputs.len() < needed || needed == 0 {
+                    return None;
+                }
+                Cut::join(inputs.into_iter().take(needed))
+            }
+            NodeTemplate::Note => return None,
+            _ => {
+                return (self.unreliability(node_id)? > 0.0).then_some(leaf);
+            }
+        };
+        cut.path.push(node_id);
+        Some(cut)
+    }
+}
+
+/// The weak link of `system` at `time`, read from the evaluated `curves`, or
+/// `None` if nothing can take the system down.
+pub fn trace(
+    graph: &Graph,
+    options: &Options,
+    curves: &OutputsCache,
+    system: NodeId,
+    time: usize,
+) -> Option<WeakLink> {
+    let mut tracer = Tracer {
+        graph,
+        options,
+        curves,
+        time,
+        in_progress: HashSet::new(),
+    };
+    let cut = tracer.cut(system)?;
+    let probability = tracer.probability(&cut);
+    Some(WeakLink {
+        time,
+        path: cut.path.into_iter().collect(),
+        cut_set: cut.members,
+        probability,
+    })
+}
+
+/// Outlines the cut set and the blocks on its way to the system, and says
+/// what the cut set is in the corner of the canvas.
+pub fn paint(
+    ui: &egui::Ui,
+    editor: &EditorState,
+    layout: &Layout,
+    weak_link: Option<&WeakLink>,
+    theme: &Theme,
+) {
+    let painter = ui.painter();
+    let corner = ui.max_rect().left_top() + egui::vec2(8.0, 8.0);
+    let font = egui::FontId::proportional(14.0);
+    let Some(weak_link) = weak_link else {
+        painter.text(
+            corner,
+            egui::Align2::LEFT_TOP,
+            tr("weak-link-none"),
+            font,
+            theme.subtext0,
+        );
+        return;
+    };
+    for node in &weak_link.path {
+        if let Some(rect) = layout.node_rect(*node) {
+            painter.rect_stroke(rect.expand(4.0), 6.0, Stroke::new(2.0, theme.peach));
+        }
+    }
+    for node in &weak_link.cut_set {
+        if let Some(rect) = layout.node_rect(*node) {
+            painter.rect_stroke(rect.expand(4.0), 6.0, Stroke::new(3.5, theme.red));
+        }
+    }
+    let members: Vec<&str> = weak_link
+        .cut_set
+        .iter()
+        .filter_map(|node| Some(editor.graph.nodes.get(*node)?.label.as_str()))
+        .collect();
+    painter.text(
+        corner,
+        egui::Align2::LEFT_TOP,
+        tr_with(
+            "weak-link-summary",
+            &[
+                ("time", &weak_link.time),
+                ("members", &members.join(", ")),
+                ("probability", &format!("{:.3e}", weak_link.probability)),
+            ],
+        ),
+        font,
+        theme.red,
+    );
+}
@@ -0,0 +1,41 @@
+//! Structural what-if analysis by taking components out of the diagram.
+use eframe::egui;
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, Graph};
+
+/// Lists the components with a checkbox each to exclude them from evaluation.
+pub fn panel(ui: &mut egui::Ui, graph: &mut Graph) {
+    ui.heading(tr("what-if-title"));
+    ui.label(tr("what-if-hint"));
+    let components = nodes::components(graph);
+    if components.is_empty() {
+        ui.label(tr("what-if-empty"));
+        return;
+    }
+    let excluded = components
+        .iter()
+        .filter(|node| graph[**node].user_data.excluded)
+        .count();
+    if ui
+        .add_enabled(
+            excluded > 0,
+            egui::Button::new(tr_with("what-if-restore-all", &[("count", &excluded)])),
+        )
+        .clicked()
+    {
+        for node in &components {
+            graph[*node].user_data.excluded = false;
+        }
+    }
+    ui.separator();
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for node in components {
+            let node = &mut graph[node];
+            let mut included = !node.user_data.excluded;
+            if ui.checkbox(&mut included, &node.label).changed() {
+                node.user_data.excluded = !included;
+            }
+        }
+    });
+}
@@ -0,0 +1,266 @@
+//! Right-angled connections, painted by the app in place of the graph
+//! editor's curves when the orthogonal wire style is chosen.
+//!
+//! The editor doesn't expose where it put its ports, so the node callbacks
+//! record the same rows it measures into a [`Layout`] while each node is drawn.
+use std::collections::HashMap;
+
+use eframe::egui::{self, pos2, Pos2, Rect, Shape, Stroke};
+use egui_node_graph2::{AnyParameterId, DataTypeTrait, NodeId};
+
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{self, EditorState, Graph, GraphState};
+
+/// The padding the graph editor puts around a node's contents, at zoom 1.
+const NODE_MARGIN: egui::Vec2 = egui::vec2(15.0, 5.0);
+/// How far a wire runs straight out of a port before it turns, at zoom 1.
+const STUB: f32 = 20.0;
+/// How close the pointer has to be for a port to light up, as in the editor.
+const DISTANCE_TO_CONNECT: f32 = 10.0;
+/// How many evenly spaced columns are tried for the bend of a forward wire.
+const BEND_CANDIDATES: usize = 8;
+
+/// Where the graph editor drew nodes and ports in the current frame.
+#[derive(Default)]
+pub struct Layout {
+    zoom: f32,
+    /// The top of the input or output row being drawn.
+    row_top: f32,
+    /// The ports of the node being drawn and the height of their rows, until
+    /// the node's edges are known.
+    rows: Vec<(AnyParameterId, f32)>,
+    ports: HashMap<AnyParameterId, Pos2>,
+    nodes: HashMap<NodeId, Rect>,
+}
+
+impl Layout {
+    pub fn new(zoom: f32) -> Self {
+        Self {
+            zoom,
+            ..Default::default()
+        }
+    }
+
+    /// Where `node_id` was drawn on screen, if it was drawn this frame.
+    pub fn node_rect(&self, node_id: NodeId) -> Option<Rect> {
+        self.nodes.get(&node_id).copied()
+    }
+
+    /// Where `port` was drawn on screen, if it was drawn this frame.
+    pub fn port_position(&self, port: AnyParameterId) -> Option<Pos2> {
+        self.ports.get(&port).copied()
+    }
+
+    /// Called as the widget of an input or output row starts.
+    pub fn begin_row(&mut self, ui: &egui::Ui) {
+        self.row_top = ui.min_rect().bottom();
+    }
+
+    /// Called once the row of `port` is drawn; its port sits halfway down it.
+    pub fn end_row(&mut self, ui: &egui::Ui, port: AnyParameterId) {
+        let bottom = ui.min_rect().bottom();
+        self.rows.push((port, (self.row_top + bottom) / 2.0));
+    }
+
+    /// Called after the last widget of `node_id`, when its size is known.
+    pub fn end_node(&mut self, ui: &egui::Ui, node_id: NodeId) {
+        let rect = ui.min_rect().expand2(NODE_MARGIN * self.zoom);
+        for (port, y) in self.rows.drain(..) {
+            let x = match port {
+                AnyParameterId::Input(_) => rect.left(),
+                AnyParameterId::Output(_) => rect.right(),
+            };
+            self.ports.insert(port, pos2(x, y));
+        }
+        self.nodes.insert(node_id, rect);
+    }
+}
+
+/// Paints every connection, the one being dragged and the ports on top of
+/// the graph editor, which drew its own transparent.
+pub fn paint(ui: &egui::Ui, editor: &EditorState, layout: &Layout) {
+    let graph = &editor.graph;
+    let painter = ui.painter();
+    let stroke = |port: AnyParameterId| {
+        let color = graph
+            .any_param_type(port)
+            .map_or(egui::Color32::GRAY, |typ| typ.color());
+        Stroke::new(5.0 * layout.zoom, color)
+    };
+    let obstacles: Vec<Rect> = layout.nodes.values().copied().collect();
+
+    for (input, output) in graph.iter_connections() {
+        let (input, output) = (AnyParameterId::Input(input), AnyParameterId::Output(output));
+        if let (Some(src), Some(dst)) = (layout.ports.get(&output), layout.ports.get(&input)) {
+            let points = route(*src, *dst, &obstacles, layout.zoom);
+            painter.add(Shape::line(points, stroke(output)));
+        }
+    }
+
+    let pointer = ui.ctx().pointer_hover_pos();
+    if let (Some((_, from)), Some(pointer)) = (editor.connection_in_progress, pointer) {
+        if let Some(start) = layout.ports.get(&from) {
+            // Like the editor, snap to a compatible port near the pointer.
+            let end = layout
+                .ports
+                .iter()
+                .find(|(port, pos)| {
+                    matches!(
+                        (from, port),
+                        (AnyParameterId::Input(_), AnyParameterId::Output(_))
+                            | (AnyParameterId::Output(_), AnyParameterId::Input(_))
+                    ) && graph.any_param_type(**port).ok() == graph.any_param_type(from).ok()
+                        && pos.distance(pointer) < DISTANCE_TO_CONNECT
+                })
+                .map_or(pointer, |(_, pos)| *pos);
+            let (src, dst) = match from {
+                AnyParameterId::Output(_) => (*start, end),
+                AnyParameterId::Input(_) => (end, *start),
+            };
+            painter.add(Shape::line(
+                route(src, dst, &obstacles, layout.zoom),
+                stroke(from),
+            ));
+        }
+    }
+
+    for (port, pos) in &layout.ports {
+        let color = match pointer {
+            Some(pointer) if pos.distance(pointer) < DISTANCE_TO_CONNECT * layout.zoom => {
+                egui::Color32::WHITE
+            }
+            _ => stroke(*port).color,
+        };
+        painter.circle_filled(*pos, 5.0 * layout.zoom, color);
+    }
+}
+
+/// While a wire is dragged, rings the ports it could end at and dims those
+/// it can't, saying why when the pointer is over one.
+pub fn paint_drag_preview(ui: &egui::Ui, editor: &EditorState, state: &GraphState) {
+    let Some((_, from)) = editor.connection_in_progress else {
+        return;
+    };
+    let layout = &state.layout;
+    let theme = state.settings.theme.catppuccin();
+    let veil = ui.visuals().extreme_bg_color.gamma_multiply(0.8);
+    let painter = ui.painter();
+    let pointer = ui.ctx().pointer_hover_pos();
+    for (port, pos) in &layout.ports {
+        if *port == from {
+            continue;
+        }
+        match refusal(&editor.graph, from, *port, state) {
+            None => {
+                painter.circle_stroke(*pos, 9.0 * layout.zoom, Stroke::new(2.0, theme.green));
+            }
+            Some(reason) => {
+                painter.circle_filled(*pos, 6.0 * layout.zoom, veil);
+                if pointer.is_some_and(|pointer| {
+                    pos.distance(pointer) < DISTANCE_TO_CONNECT * layout.zoom
+                }) {
+                    egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("port refusal"), |ui| {
+                        ui.label(reason);
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Why a wire from `from` can't end at `port`, or `None` if it can.
+fn refusal(
+    graph: &Graph,
+    from: AnyParameterId,
+    port: AnyParameterId,
+    state: &GraphState,
+) -> Option<String> {
+    let (output, input) = match (from, port) {
+        (AnyParameterId::Output(output), AnyParameterId::Input(input))
+        | (AnyParameterId::Input(input), AnyParameterId::Output(output)) => (output, input),
+        _ => return Some(tr("port-same-direction")),
+    };
+    let (source, sink) = (graph[output].node, graph[input].node);
+    if source == sink {
+        return Some(tr("port-same-node"));
+    }
+    if state.read_only.contains_key(&sink) || state.read_only.contains_key(&source) {
+        return Some(tr("port-locked"));
+    }
+    let (carried, expected) = (graph[output].typ, graph[input].typ);
+    if carried != expected {
+        return Some(tr_with(
+            "port-wrong-type",
+            &[("expected", &expected.name()), ("carried", &carried.name())],
+        ));
+    }
+    if nodes::upstream(graph, source).contains(&sink) {
+        return Some(tr("port-cycle"));
+    }
+    None
+}
+
+/// A right-angled path from an output at `src` to an input at `dst`, bent
+/// where it crosses the fewest of `obstacles`, and the shortest such path.
+fn route(src: Pos2, dst: Pos2, obstacles: &[Rect], zoom: f32) -> Vec<Pos2> {
+    let stub = STUB * zoom;
+    let (top, bottom) = (src.y.min(dst.y), src.y.max(dst.y));
+    let candidates: Vec<Vec<Pos2>> = if dst.x - src.x >= 2.0 * stub {
+        // Forward: out, one vertical run somewhere in between, and in.
+        let (low, high) = (src.x + stub, dst.x - stub);
+        let mut columns: Vec<f32> = (0..=BEND_CANDIDATES)
+            .map(|i| low + (high - low) * i as f32 / BEND_CANDIDATES as f32)
+            .collect();
+        for rect in obstacles {
+            if rect.bottom() >= top && rect.top() <= bottom {
+                columns.extend([rect.left() - stub, rect.right() + stub]);
+            }
+        }
+        columns
+            .into_iter()
+            .filter(|x| (low..=high).contains(x))
+            .map(|x| vec![src, pos2(x, src.y), pos2(x, dst.y), dst])
+            .collect()
+    } else {
+        // Backward: out, around above or below, and back in from the left.
+        let (out, back) = (src.x + stub, dst.x - stub);
+        let mut rows = vec![(src.y + dst.y) / 2.0];
+        for rect in obstacles {
+            if rect.right() >= back && rect.left() <= out {
+                rows.extend([rect.top() - stub, rect.bottom() + stub]);
+            }
+        }
+        rows.into_iter()
+            .map(|y| {
+                vec![
+                    src,
+                    pos2(out, src.y),
+                    pos2(out, y),
+                    pos2(back, y),
+                    pos2(back, dst.y),
+                    dst,
+                ]
+            })
+            .collect()
+    };
+
+    let cost = |points: &Vec<Pos2>| {
+        let crossings = points
+            .windows(2)
+            .map(|segment| {
+                let span = Rect::from_two_pos(segment[0], segment[1]);
+                obstacles
+                    .iter()
+                    .filter(|rect| rect.shrink(1.0).intersects(span))
+                    .count()
+            })
+            .sum::<usize>();
+        let length: f32 = points.windows(2).map(|s| s[0].distance(s[1])).sum();
+        (crossings, length)
+    };
+    candidates
+        .into_iter()
+        .map(|points| (cost(&points), points))
+        .min_by(|(a, _), (b, _)| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+        .map_or_else(|| vec![src, dst], |(_, points)| points)
+}
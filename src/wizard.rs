@@ -0,0 +1,193 @@
+//! A step-by-step dialog that starts a new project from a few key figures.
+use eframe::egui;
+
+use crate::config;
+use crate::examples::Builder;
+use crate::i18n::{tr, tr_with};
+use crate::nodes::{EditorState, GraphState, NodeTemplate};
+use crate::requirement::Requirement;
+use crate::settings::TimeUnit;
+
+const MAX_COMPONENTS: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Step {
+    Mission,
+    Target,
+    Components,
+}
+
+pub struct Wizard {
+    step: Step,
+    time_unit: TimeUnit,
+    mission_time: usize,
+    target: f64,
+    components: usize,
+}
+
+impl Default for Wizard {
+    fn default() -> Self {
+        Self {
+            step: Step::Mission,
+            time_unit: config::get().time_unit.unwrap_or_default(),
+            mission_time: config::get()
+                .mission_time
+                .unwrap_or(Requirement::default().time),
+            target: Requirement::default().reliability,
+            components: 3,
+        }
+    }
+}
+
+impl Wizard {
+    /// Shows the wizard, replacing the project when the user finishes it.
+    pub fn window(
+        &mut self,
+        ctx: &egui::Context,
+        open: &mut bool,
+        editor: &mut EditorState,
+        user_state: &mut GraphState,
+    ) {
+        let mut finished = false;
+        egui::Window::new(tr("wizard-title"))
+            .open(open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                match self.step {
+                    Step::Mission => {
+                        ui.label(tr("wizard-mission-hint"));
+                        egui::Grid::new("wizard mission")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                ui.label(tr("settings-time-unit"));
+                                egui::ComboBox::from_id_source("wizard time unit")
+                                    .selected_text(self.time_unit.name())
+                                    .show_ui(ui, |ui| {
+                                        for unit in TimeUnit::ALL {
+                                            ui.selectable_value(
+                                                &mut self.time_unit,
+                                                unit,
+                                                unit.name(),
+                                            );
+                                        }
+                                    });
+                                ui.end_row();
+                                ui.label(tr("mission-time"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.mission_time)
+                                        .clamp_range(1..=usize::MAX)
+                                        .suffix(format!(" {}", self.time_unit.symbol())),
+                                );
+                                ui.end_row();
+                            });
+                    }
+                    Step::Target => {
+                        ui.label(tr("wizard-target-hint"));
+                        ui.horizontal(|ui| {
+                            ui.label("R ≥");
+                            ui.add(
+                                egui::DragValue::new(&mut self.target)
+                                    .speed(0.001)
+                                    .clamp_range(0.0..=1.0)
+                                    .max_decimals(6),
+                            );
+                            ui.label(tr_with(
+                                "wizard-target-at",
+                                &[
+                                    ("time", &self.mission_time),
+                                    ("unit", &self.time_unit.symbol()),
+                                ],
+                            ));
+                        });
+                    }
+                    Step::Components => {
+                        ui.label(tr("wizard-components-hint"));
+                        ui.horizontal(|ui| {
+                            ui.label(tr("wizard-components"));
+                            ui.add(
+                                egui::DragValue::new(&mut self.components)
+                                    .clamp_range(1..=MAX_COMPONENTS),
+                            );
+                        });
+                        ui.colored_label(ui.visuals().warn_fg_color, tr("wizard-replaces"));
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let back = match self.step {
+                        Step::Mission => None,
+                        Step::Target => Some(Step::Mission),
+                        Step::Components => Some(Step::Target),
+                    };
+                    if ui
+                        .add_enabled(back.is_some(), egui::Button::new(tr("wizard-back")))
+                        .clicked()
+                    {
+                        self.step = back.unwrap_or(self.step);
+                    }
+                    match self.step {
+                        Step::Mission if ui.button(tr("wizard-next")).clicked() => {
+                            self.step = Step::Target
+                        }
+                        Step::Target if ui.button(tr("wizard-next")).clicked() => {
+                            self.step = Step::Components
+                        }
+                        Step::Components if ui.button(tr("wizard-create")).clicked() => {
+                            finished = true
+                        }
+                        _ => {}
+                    }
+                });
+            });
+
+        if finished {
+            *user_state = GraphState {
+                settings: std::mem::take(&mut user_state.settings),
+                ..config::get().graph_state()
+            };
+            *editor = self.seed(user_state);
+            *open = false;
+            *self = Self::default();
+        }
+    }
+
+    /// A chain of equal components in series, each given the scale that lets
+    /// the chain just meet the target, and the project settings chosen.
+    fn seed(&self, user_state: &mut GraphState) -> EditorState {
+        user_state.settings.time_unit = self.time_unit;
+        user_state.mission_time = self.mission_time;
+        user_state.requirement = Requirement {
+            enabled: true,
+            reliability: self.target,
+            time: self.mission_time,
+        };
+
+        // Equal apportionment: each of n components needs R_i = R^(1/n), and
+        // R_i = exp(-(t/η)^β) solves to η = t / (-ln R_i)^(1/β).
+        let mut parameters = user_state.settings.default_parameters.clone();
+        parameters.time_steps = parameters.time_steps.max(self.mission_time);
+        let share = self.target.powf(1.0 / self.components as f64);
+        if share > 0.0 && share < 1.0 {
+            parameters.scale =
+                self.mission_time as f64 / (-share.ln()).powf(1.0 / parameters.shape);
+        }
+
+        let mut model = Builder::new(user_state);
+        let mut system = None;
+        for i in 0..self.components {
+            let label = tr_with("wizard-component-label", &[("number", &(i + 1))]);
+            let component = model.component(label, parameters.clone(), i as i32, 0);
+            system = Some(match system {
+                None => component,
+                Some(chain) => model.block(NodeTemplate::Series, [chain, component], i as i32, 1),
+            });
+        }
+        if let Some(system) = system {
+            model.rename(system, tr("wizard-system-label"));
+        }
+        model.user_state.active_node = system;
+        model.finish()
+    }
+}
@@ -0,0 +1,128 @@
+//! The Rust code [`block::fixture::encode`] writes rebuilds a model that
+//! evaluates to the same curves. `fixtures/model.rs` is that code for the
+//! model below, checked in so that it is compiled too.
+use eframe::egui::Color32;
+
+use block::eval::{evaluate_curve, Options, OutputsCache};
+use block::examples::Builder;
+use block::fixture;
+use block::nodes::{EditorState, GraphState, NodeParameters, NodeTemplate, Severity};
+use block::profile::{MissionProfile, Segment};
+use block::shocks::Shock;
+use block::tags::ColorTag;
+
+mod generated {
+    include!("fixtures/model.rs");
+}
+
+fn original(user_state: &mut GraphState) -> EditorState {
+    let mut model = Builder::new(user_state);
+    let pump = model.component(
+        "Pump".to_owned(),
+        model.parameters(1.5, 3_000.0, 12.0),
+        0,
+        0,
+    );
+    let spare = model.component(
+        "Spare pump".to_owned(),
+        model.parameters(1.5, 3_000.0, 12.0),
+        0,
+        1,
+    );
+    let valve = model.component(
+        "Valve".to_owned(),
+        NodeParameters {
+            duty_cycle: 0.5,
+            ..model.parameters(0.8, 20_000.0, 0.0)
+        },
+        0,
+        2,
+    );
+    let pumps = model.block(NodeTemplate::Standby, [pump, spare], 1, 0);
+    let system = model.block(NodeTemplate::Series, [pumps, valve], 2, 1);
+    model.data(pump).library = Some("pumps/centrifugal".to_owned());
+    model.data(spare).mirror = Some(pump);
+    model.data(valve).severity = Severity::SafetyCritical;
+    model.data(valve).tag = Some(ColorTag {
+        name: "Hydraulics".to_owned(),
+        color: Color32::from_rgb(30, 102, 245),
+    });
+    model.user_state.active_node = Some(system);
+    model.user_state.mission_time = 4_380;
+    model.user_state.mission_profile = MissionProfile {
+        enabled: true,
+        segments: vec![
+            Segment {
+                name: "Duty".to_owned(),
+                duration: 16.0,
+                stress: 1.5,
+            },
+            Segment {
+                name: "Idle".to_owned(),
+                duration: 8.0,
+                stress: 0.2,
+            },
+        ],
+    };
+    model.user_state.shocks.push(Shock {
+        name: "Water hammer".to_owned(),
+        rate: 1e-3,
+        damage: 0.2,
+        targets: vec![pump, valve],
+    });
+    model.finish()
+}
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let mut state = GraphState::default();
+    let editor = original(&mut state);
+    assert_eq!(
+        fixture::encode(&editor, &state),
+        include_str!("fixtures/model.rs")
+    );
+}
+
+#[test]
+fn generated_code_rebuilds_the_same_curves() {
+    let mut state = GraphState::default();
+    let editor = original(&mut state);
+    let mut rebuilt_state = GraphState::default();
+    let rebuilt = generated::model(&mut rebuilt_state);
+    assert_eq!(rebuilt_state.mission_profile, state.mission_profile);
+    assert_eq!(rebuilt_state.shocks.len(), 1);
+
+    let (graph, rebuilt) = (&editor.graph, &rebuilt.graph);
+    assert_eq!(rebuilt.nodes.len(), graph.nodes.len());
+    for (node_id, node) in &graph.nodes {
+        let (twin, twin_node) = rebuilt
+            .nodes
+            .iter()
+            .find(|(_, twin)| twin.label == node.label)
+            .expect("every node is rebuilt");
+        assert_eq!(twin_node.user_data.tag, node.user_data.tag);
+        assert_eq!(twin_node.user_data.library, node.user_data.library);
+        assert_eq!(
+            twin_node
+                .user_data
+                .mirror
+                .map(|mirror| &rebuilt[mirror].label),
+            node.user_data.mirror.map(|mirror| &graph[mirror].label)
+        );
+        let curve = |graph, node_id, state| {
+            evaluate_curve(
+                graph,
+                &Options::of(state),
+                node_id,
+                &mut OutputsCache::new(),
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            curve(rebuilt, twin, &rebuilt_state),
+            curve(graph, node_id, &state),
+            "{}",
+            node.label
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use block::examples::Builder;
+use block::nodes::{EditorState, GraphState, NodeParameters, NodeTemplate, Severity};
+use block::shocks::Shock;
+
+/// Rebuilds the model, with its system made active in `user_state`.
+pub fn model(user_state: &mut GraphState) -> EditorState {
+    let mut model = Builder::new(user_state);
+    let pump = model.component("Pump".to_owned(), NodeParameters { shape: 1.5, scale: 3000.0, mttr: 12.0, ..NodeParameters::default() }, 0, 0);
+    model.data(pump).library = Some("pumps/centrifugal".to_owned());
+    let spare_pump = model.component("Spare pump".to_owned(), NodeParameters { shape: 1.5, scale: 3000.0, mttr: 12.0, ..NodeParameters::default() }, 0, 1);
+    let valve = model.component("Valve".to_owned(), serde_json::from_str(r##"{"shape":0.8,"scale":20000.0,"scale_entry":"Scale","modes":[],"time_steps":8760,"duty_cycle":0.5,"schedule":null,"environment_factor":1.0,"mttr":0.0,"repair":"Renewal","inspection":null,"temperature":25.0,"derating":"None","uncertainty":null,"expressions":{}}"##).expect("valid parameters"), 0, 2);
+    model.data(valve).severity = Severity::SafetyCritical;
+    model.data(valve).tag = Some(serde_json::from_str(r##"{"name":"Hydraulics","color":[30,102,245,255]}"##).expect("valid tag"));
+    let standby = model.node(NodeTemplate::Standby, "Standby".to_owned(), 1, 0);
+    let series = model.node(NodeTemplate::Series, "Series".to_owned(), 2, 1);
+    model.connect(pump, standby, "A");
+    model.connect(spare_pump, standby, "B");
+    model.connect(standby, series, "A");
+    model.connect(valve, series, "B");
+    model.data(spare_pump).mirror = Some(pump);
+    model.user_state.active_node = Some(series);
+    model.user_state.mission_time = 4380;
+    model.user_state.mission_profile = serde_json::from_str(r##"{"enabled":true,"segments":[{"name":"Duty","duration":16.0,"stress":1.5},{"name":"Idle","duration":8.0,"stress":0.2}]}"##).expect("valid mission profile");
+    model.user_state.shocks.push(Shock { name: "Water hammer".to_owned(), rate: 0.001, damage: 0.2, targets: vec![pump, valve] });
+    model.finish()
+}